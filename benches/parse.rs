@@ -0,0 +1,120 @@
+//! Response envelope parsing: serde_json against simd-json.
+//!
+//! Bodies are shaped like the largest endpoints: all-variety warehouse
+//! receipts and a full option quote list. Run with
+//! `cargo bench --features simd-json`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dceapi_rs::{ApiResponse, Quote, WarehouseReceipt};
+use serde_json::{json, Value};
+
+/// Response body with `data` set to the given payload.
+fn envelope(data: Value) -> String {
+    json!({ "code": 200, "msg": "success", "data": data }).to_string()
+}
+
+/// All-variety warehouse receipts: every warehouse of 20 varieties.
+fn receipts_body() -> String {
+    let rows: Vec<Value> = (0..20)
+        .flat_map(|variety| {
+            (0..150).map(move |wh| {
+                json!({
+                    "varietyOrder": format!("{:02}", variety),
+                    "groupCodeOrder": "1",
+                    "whCodeOrder": format!("{:04}", wh),
+                    "whType": "仓库",
+                    "variety": format!("品种{}", variety),
+                    "genDate": "20250930",
+                    "whAbbr": format!("交割库{}", wh),
+                    "deliveryAbbr": "",
+                    "lastWbillQty": 1200,
+                    "regWbillQty": 30,
+                    "logoutWbillQty": 10,
+                    "wbillQty": 1220,
+                    "diff": 20,
+                })
+            })
+        })
+        .collect();
+    envelope(json!({
+        "entityList": rows,
+        "ifAgioFlag": "0",
+        "agioDeliType": "",
+        "ifAgioBrandFlag": "0",
+    }))
+}
+
+/// Full option quote list: 12 series of 60 strikes, calls and puts.
+fn option_quotes_body() -> String {
+    let rows: Vec<Value> = (0..12)
+        .flat_map(|series| {
+            (0..60).flat_map(move |strike| {
+                ["C", "P"].into_iter().map(move |kind| {
+                    let contract_id =
+                        format!("m25{:02}-{}-{}", series + 1, kind, 2500 + strike * 50);
+                    json!({
+                        "variety": "豆粕",
+                        "contractId": contract_id,
+                        "delivMonth": format!("25{:02}", series + 1),
+                        "open": "120",
+                        "high": "131.5",
+                        "low": "118",
+                        "close": "127",
+                        "lastClear": "121",
+                        "lastPrice": "127",
+                        "clearPrice": "126.5",
+                        "diff": "6",
+                        "volumn": "1,520",
+                        "openInterest": "8,310",
+                        "diffI": "-40",
+                        "turnover": "19.2",
+                        "delta": "0.4512",
+                        "impliedVolatility": "18.25",
+                        "seriesId": format!("m25{:02}", series + 1),
+                    })
+                })
+            })
+        })
+        .collect();
+    envelope(Value::Array(rows))
+}
+
+fn parse_envelope(c: &mut Criterion) {
+    let mut group = c.benchmark_group("envelope");
+    for (name, body) in [
+        ("receipts", receipts_body()),
+        ("option_quotes", option_quotes_body()),
+    ] {
+        group.throughput(Throughput::Bytes(body.len() as u64));
+        group.bench_with_input(BenchmarkId::new("serde_json", name), &body, |b, body| {
+            b.iter(|| serde_json::from_str::<ApiResponse>(body).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("simd_json", name), &body, |b, body| {
+            b.iter(|| {
+                let mut bytes = body.as_bytes().to_vec();
+                simd_json::serde::from_slice::<ApiResponse>(&mut bytes).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn parse_models(c: &mut Criterion) {
+    let mut group = c.benchmark_group("models");
+    let receipts = serde_json::from_str::<ApiResponse>(&receipts_body())
+        .unwrap()
+        .data;
+    let quotes = serde_json::from_str::<ApiResponse>(&option_quotes_body())
+        .unwrap()
+        .data;
+    group.bench_function("receipts", |b| {
+        b.iter(|| serde_json::from_value::<WarehouseReceipt>(receipts.clone()).unwrap())
+    });
+    group.bench_function("option_quotes", |b| {
+        b.iter(|| serde_json::from_value::<Vec<Quote>>(quotes.clone()).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse_envelope, parse_models);
+criterion_main!(benches);
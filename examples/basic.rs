@@ -1,6 +1,9 @@
 //! Basic usage example for the DCE API client.
 
-use dceapi_rs::{Client, Config, GetArticleByPageRequest, QuotesRequest, RequestOptions};
+use dceapi_rs::{
+    Client, ColumnId, Config, GetArticleByPageRequest, Lang, QuotesRequest, RequestOptions,
+    TradeType,
+};
 
 #[tokio::main]
 async fn main() -> dceapi_rs::Result<()> {
@@ -41,7 +44,7 @@ async fn main() -> dceapi_rs::Result<()> {
     // Example 3: Get articles
     println!("\n--- Getting exchange announcements ---");
     let article_req = GetArticleByPageRequest {
-        column_id: "244".to_string(), // Exchange announcements
+        column_id: ColumnId::BusinessNotices,
         page_no: 1,
         page_size: 5,
         site_id: 5,
@@ -58,23 +61,21 @@ async fn main() -> dceapi_rs::Result<()> {
 
     // Example 4: Get day quotes (with options)
     println!("\n--- Getting day quotes ---");
-    let quotes_req = QuotesRequest {
-        variety_id: Some("a".to_string()), // Soybean No.1
-        variety: None,
-        trade_date: "20240115".to_string(),
-        trade_type: "1".to_string(), // Futures
-        lang: Some("zh".to_string()),
-        statistics_type: None,
-    };
+    let quotes_req = QuotesRequest::builder()
+        .variety_id("a") // Soybean No.1
+        .trade_date("20240115")
+        .futures()
+        .lang(Lang::Zh)
+        .build()?;
 
-    let opts = RequestOptions::new().with_trade_type(1);
+    let opts = RequestOptions::new().with_trade_type(TradeType::Futures);
 
     match client.market.get_day_quotes(&quotes_req, Some(opts)).await {
         Ok(quotes) => {
             println!("Found {} quotes:", quotes.len());
             for quote in quotes.iter().take(3) {
                 println!(
-                    "  - {} | Open: {} | High: {} | Low: {} | Close: {}",
+                    "  - {} | Open: {:?} | High: {:?} | Low: {:?} | Close: {:?}",
                     quote.contract_id, quote.open, quote.high, quote.low, quote.close
                 );
             }
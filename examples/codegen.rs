@@ -0,0 +1,31 @@
+//! Generate draft endpoint wrappers from recorded request/response fixtures.
+//!
+//! Each fixture is a JSON file with `path`, `request` and `response` keys.
+//!
+//! ```bash
+//! cargo run --example codegen --features codegen -- SpotPrice fixtures/spot_*.json
+//! ```
+
+use dceapi_rs::codegen::{generate, Fixture};
+
+fn main() -> dceapi_rs::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let Some(name) = args.next() else {
+        eprintln!("usage: codegen <Name> <fixture.json>...");
+        std::process::exit(2);
+    };
+
+    let mut fixtures = Vec::new();
+    for path in args {
+        let json = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {}", path, e));
+        fixtures.push(Fixture::from_json(&json)?);
+    }
+
+    let code = generate(&name, &fixtures)?;
+    println!("// ---- models.rs ----\n");
+    println!("{}", code.models);
+    println!("// ---- service ----\n");
+    println!("{}", code.service);
+    Ok(())
+}
@@ -20,7 +20,10 @@
 //! 运行命令:
 //!   cargo run --example complete
 
-use dceapi_rs::{Client, Config};
+use dceapi_rs::{
+    Client, ColumnId, Config, DeliveryMonth, DeliveryWay, Lang, StatContent, StatisticsType,
+    TradeType, VarietyType,
+};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -30,6 +33,11 @@ fn print_separator(title: &str) {
     println!("{}", "=".repeat(80));
 }
 
+/// 格式化价格, 缺失时显示 "-"
+fn price(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
 #[tokio::main]
 async fn main() -> dceapi_rs::Result<()> {
     println!("\nDCE API Rust SDK - 完整功能演示 (基于 dceapiv1.0.md)");
@@ -44,32 +52,33 @@ async fn main() -> dceapi_rs::Result<()> {
     let trade_date_res = client.common.get_curr_trade_date(None).await?;
     let trade_date = trade_date_res.date.clone();
     let trade_month = trade_date[0..6].to_string(); // YYYYMM
+    let delivery_month: DeliveryMonth = trade_month.parse()?;
 
     println!("✓ 当前交易日期: {}", trade_date);
     println!("✓ 交易月份: {}\n", trade_month);
 
-    // sleep(Duration::from_secs(1)).await;
+    sleep(Duration::from_secs(1)).await;
 
     // 运行各服务示例 (可单独注释掉某个服务进行测试)
-    // run_common_service_examples(&client, &trade_date, &trade_month).await;
-    // sleep(Duration::from_secs(1)).await;
+    run_common_service_examples(&client, &trade_date, &trade_month).await;
+    sleep(Duration::from_secs(1)).await;
 
-    // run_news_service_examples(&client).await;
-    // sleep(Duration::from_secs(1)).await;
+    run_news_service_examples(&client).await;
+    sleep(Duration::from_secs(1)).await;
 
-    run_market_service_examples(&client, &trade_date, &trade_month).await;
+    run_market_service_examples(&client, &trade_date, &trade_month, delivery_month).await;
     sleep(Duration::from_secs(1)).await;
 
-    // run_member_service_examples(&client, &trade_date, &trade_month).await;
-    // sleep(Duration::from_secs(1)).await;
+    run_member_service_examples(&client, &trade_date, &trade_month).await;
+    sleep(Duration::from_secs(1)).await;
 
-    // run_trade_service_examples(&client, &trade_date).await;
-    // sleep(Duration::from_secs(1)).await;
+    run_trade_service_examples(&client, &trade_date).await;
+    sleep(Duration::from_secs(1)).await;
 
-    // run_settle_service_examples(&client, &trade_date).await;
-    // sleep(Duration::from_secs(1)).await;
+    run_settle_service_examples(&client, &trade_date).await;
+    sleep(Duration::from_secs(1)).await;
 
-    // run_delivery_service_examples(&client, &trade_date, &trade_month).await;
+    run_delivery_service_examples(&client, &trade_date, delivery_month).await;
 
     // 完成总结
     print_separator("完成总结");
@@ -129,8 +138,8 @@ async fn run_common_service_examples(client: &Client, trade_date: &str, trade_mo
         .get_variety_month_year_stat(
             &dceapi_rs::VarietyMonthYearStatRequest {
                 trade_month: trade_month.to_string(),
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -165,20 +174,11 @@ async fn run_news_service_examples(client: &Client) {
     print_separator("NewsService - 资讯服务");
 
     // columnId 列表及说明
-    let column_configs = [
-        ("244", "业务公告与通知"),
-        ("245", "活动公告与通知"),
-        ("246", "交易所新闻-文媒"),
-        ("248", "媒体看大商所-文媒"),
-        ("1076", "今日提示"),
-        ("242", "新闻发布"),
-    ];
-
-    for (i, (column_id, name)) in column_configs.iter().enumerate() {
+    for (i, column_id) in ColumnId::ALL.iter().enumerate() {
         println!(
             "\n[{}/6] GetArticleByPage - {} (columnId={})",
             i + 1,
-            name,
+            column_id.name(Lang::Zh),
             column_id
         );
 
@@ -187,7 +187,7 @@ async fn run_news_service_examples(client: &Client) {
             .news
             .get_article_by_page(
                 dceapi_rs::GetArticleByPageRequest {
-                    column_id: column_id.to_string(),
+                    column_id: column_id.clone(),
                     page_no: 1,
                     page_size: 3,
                     site_id: 5,
@@ -213,7 +213,12 @@ async fn run_news_service_examples(client: &Client) {
 // 文档参考: 数据 > 行情统计
 // ============================================================================
 
-async fn run_market_service_examples(client: &Client, trade_date: &str, trade_month: &str) {
+async fn run_market_service_examples(
+    client: &Client,
+    trade_date: &str,
+    trade_month: &str,
+    delivery_month: DeliveryMonth,
+) {
     print_separator("MarketService - 行情服务");
     println!("\n使用交易日期: {}", trade_date);
 
@@ -228,8 +233,8 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 trade_date: trade_date.to_string(),
                 variety: Some("a".to_string()),
                 variety_id: None,
-                trade_type: "1".to_string(),
-                lang: Some("zh".to_string()),
+                trade_type: TradeType::Futures,
+                lang: Some(Lang::Zh),
                 statistics_type: None,
             },
             None,
@@ -240,7 +245,7 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
             println!("✓ 豆一夜盘行情, 合约数: {}", quotes.len());
             let mut count = 0;
             for q in quotes.iter() {
-                if q.deliv_month.is_empty() || q.variety == "总计" {
+                if q.deliv_month.is_empty() || q.is_subtotal() {
                     continue;
                 }
                 if count >= 3 {
@@ -248,7 +253,9 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 }
                 println!(
                     "   {} | 最新价: {} | 持仓量: {}",
-                    q.deliv_month, q.last_price, q.open_interest
+                    q.deliv_month,
+                    price(q.last_price),
+                    q.open_interest
                 );
                 count += 1;
             }
@@ -268,8 +275,8 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 trade_date: trade_date.to_string(),
                 variety: None,
                 variety_id: Some("a".to_string()),
-                trade_type: "1".to_string(),
-                lang: Some("zh".to_string()),
+                trade_type: TradeType::Futures,
+                lang: Some(Lang::Zh),
                 statistics_type: None,
             },
             None,
@@ -280,7 +287,7 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
             println!("✓ 豆一日行情, 合约数: {}", quotes.len());
             let mut count = 0;
             for q in quotes.iter() {
-                if q.contract_id.is_empty() || q.variety == "总计" {
+                if q.contract_id.is_empty() || q.is_subtotal() {
                     continue;
                 }
                 if count >= 3 {
@@ -288,7 +295,11 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 }
                 println!(
                     "   {} | 开: {} 高: {} 低: {} 收: {}",
-                    q.contract_id, q.open, q.high, q.low, q.close
+                    q.contract_id,
+                    price(q.open),
+                    price(q.high),
+                    price(q.low),
+                    price(q.close)
                 );
                 count += 1;
             }
@@ -307,9 +318,9 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 trade_date: trade_date.to_string(),
                 variety: None,
                 variety_id: Some("a".to_string()),
-                trade_type: "2".to_string(),
-                lang: Some("zh".to_string()),
-                statistics_type: Some(0), // 0=合约, 1=系列, 2=品种
+                trade_type: TradeType::Options,
+                lang: Some(Lang::Zh),
+                statistics_type: Some(StatisticsType::Contract),
             },
             None,
         )
@@ -332,8 +343,8 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 trade_date: trade_date.to_string(),
                 variety: None,
                 variety_id: Some("m".to_string()),
-                trade_type: "1".to_string(),
-                lang: Some("zh".to_string()),
+                trade_type: TradeType::Futures,
+                lang: Some(Lang::Zh),
                 statistics_type: None,
             },
             None,
@@ -357,8 +368,8 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 trade_date: trade_date.to_string(),
                 variety: Some("c".to_string()),
                 variety_id: None,
-                trade_type: "1".to_string(),
-                lang: Some("zh".to_string()),
+                trade_type: TradeType::Futures,
+                lang: Some(Lang::Zh),
                 statistics_type: None,
             },
             None,
@@ -382,11 +393,11 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
         .market
         .get_contract_month_max_volume(
             &dceapi_rs::ContractMonthMaxRequest {
-                start_month: trade_month.to_string(),
-                end_month: trade_month.to_string(),
-                stat_content: "0".to_string(), // 0=成交量
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                start_month: delivery_month,
+                end_month: delivery_month,
+                stat_content: StatContent::Volume,
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -412,11 +423,11 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
         .market
         .get_contract_month_max_turnover(
             &dceapi_rs::ContractMonthMaxRequest {
-                start_month: trade_month.to_string(),
-                end_month: trade_month.to_string(),
-                stat_content: "1".to_string(), // 1=成交额
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                start_month: delivery_month,
+                end_month: delivery_month,
+                stat_content: StatContent::Turnover,
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -442,11 +453,11 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
         .market
         .get_contract_month_max_openi(
             &dceapi_rs::ContractMonthMaxRequest {
-                start_month: trade_month.to_string(),
-                end_month: trade_month.to_string(),
-                stat_content: "2".to_string(), // 2=持仓量
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                start_month: delivery_month,
+                end_month: delivery_month,
+                stat_content: StatContent::OpenInterest,
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -472,11 +483,11 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
         .market
         .get_contract_month_max_price(
             &dceapi_rs::ContractMonthMaxRequest {
-                start_month: trade_month.to_string(),
-                end_month: trade_month.to_string(),
-                stat_content: "3".to_string(), // 3=价格统计
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                start_month: delivery_month,
+                end_month: delivery_month,
+                stat_content: StatContent::Price,
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -506,7 +517,7 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
                 start_date: start_date.clone(),
                 end_date: trade_date.to_string(),
                 variety_id: "all".to_string(),
-                lang: "zh".to_string(),
+                lang: Lang::Zh,
             },
             None,
         )
@@ -534,7 +545,7 @@ async fn run_market_service_examples(client: &Client, trade_date: &str, trade_mo
             &dceapi_rs::DivisionPriceInfoRequest {
                 variety_id: "m".to_string(),
                 trade_date: trade_date.to_string(),
-                trade_type: "1".to_string(),
+                trade_type: TradeType::Futures,
             },
             None,
         )
@@ -572,7 +583,7 @@ async fn run_member_service_examples(client: &Client, trade_date: &str, trade_mo
                 variety_id: "a".to_string(),
                 contract_id: "a2505".to_string(),
                 trade_date: trade_date.to_string(),
-                trade_type: "1".to_string(),
+                trade_type: TradeType::Futures,
             },
             None,
         )
@@ -623,7 +634,7 @@ async fn run_member_service_examples(client: &Client, trade_date: &str, trade_mo
                 variety: "a".to_string(),
                 start_month: trade_month.to_string(),
                 end_month: trade_month.to_string(),
-                trade_type: "1".to_string(),
+                trade_type: TradeType::Futures,
             },
             None,
         )
@@ -659,8 +670,8 @@ async fn run_trade_service_examples(client: &Client, trade_date: &str) {
         .get_day_trade_param(
             &dceapi_rs::DayTradeParamRequest {
                 variety_id: "m".to_string(),
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -670,7 +681,7 @@ async fn run_trade_service_examples(client: &Client, trade_date: &str) {
             println!("✓ 交易参数数量: {}", params.len());
             if let Some(p) = params.first() {
                 println!(
-                    "   {} | 投机买保证金率: {} | 涨停价: {}",
+                    "   {} | 投机买保证金率: {:?} | 涨停价: {:?}",
                     p.contract_id, p.spec_buy_rate, p.rise_limit
                 );
             }
@@ -699,8 +710,8 @@ async fn run_trade_service_examples(client: &Client, trade_date: &str) {
         .get_contract_info(
             &dceapi_rs::ContractInfoRequest {
                 variety_id: "m".to_string(),
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -722,7 +733,11 @@ async fn run_trade_service_examples(client: &Client, trade_date: &str) {
     // [4/8] GetArbitrageContract - 获取套利合约
     // 文档: POST /dceapi/forward/publicweb/tradeparam/arbitrageContract
     println!("\n[4/8] GetArbitrageContract - 获取套利合约");
-    match client.trade.get_arbitrage_contract(Some("zh"), None).await {
+    match client
+        .trade
+        .get_arbitrage_contract(Some(Lang::Zh), None)
+        .await
+    {
         Ok(contracts) => {
             println!("✓ 套利合约数量: {}", contracts.len());
             for c in contracts.iter().take(3) {
@@ -739,7 +754,7 @@ async fn run_trade_service_examples(client: &Client, trade_date: &str) {
     // [5/8] GetTradingParam - 获取交易参数(按品种)
     // 文档: POST /dceapi/forward/publicweb/tradeparam/tradingParam
     println!("\n[5/8] GetTradingParam - 获取交易参数(按品种)");
-    match client.trade.get_trading_param(Some("zh"), None).await {
+    match client.trade.get_trading_param(Some(Lang::Zh), None).await {
         Ok(params) => {
             println!("✓ 交易参数数量: {}", params.len());
             for p in params.iter().take(3) {
@@ -789,8 +804,8 @@ async fn run_trade_service_examples(client: &Client, trade_date: &str) {
         .get_new_contract_info(
             &dceapi_rs::NewContractInfoRequest {
                 trade_date: trade_date.to_string(),
-                trade_type: "1".to_string(),
-                lang: Some("zh".to_string()),
+                trade_type: TradeType::Futures,
+                lang: Some(Lang::Zh),
             },
             None,
         )
@@ -855,8 +870,8 @@ async fn run_settle_service_examples(client: &Client, trade_date: &str) {
             &dceapi_rs::SettleParamRequest {
                 variety_id: "m".to_string(),
                 trade_date: trade_date.to_string(),
-                trade_type: "1".to_string(),
-                lang: "zh".to_string(),
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
             },
             None,
         )
@@ -880,7 +895,11 @@ async fn run_settle_service_examples(client: &Client, trade_date: &str) {
 // 文档参考: 数据 > 交割统计
 // ============================================================================
 
-async fn run_delivery_service_examples(client: &Client, trade_date: &str, trade_month: &str) {
+async fn run_delivery_service_examples(
+    client: &Client,
+    trade_date: &str,
+    delivery_month: DeliveryMonth,
+) {
     print_separator("DeliveryService - 交割统计服务");
 
     // [1/10] GetDeliveryData - 交割情况统计
@@ -892,9 +911,9 @@ async fn run_delivery_service_examples(client: &Client, trade_date: &str, trade_
         .get_delivery_data(
             &dceapi_rs::DeliveryDataRequest {
                 variety_id: "a".to_string(),
-                start_month: trade_month.to_string(),
-                end_month: trade_month.to_string(),
-                variety_type: "0".to_string(),
+                start_month: delivery_month,
+                end_month: delivery_month,
+                variety_type: VarietyType::Physical,
             },
             None,
         )
@@ -916,7 +935,8 @@ async fn run_delivery_service_examples(client: &Client, trade_date: &str, trade_
     // [2/10] GetDeliveryMatch - 配对交割明细
     // 文档: POST /dceapi/forward/publicweb/deliverystat/deliveryMatch
     // 请求示例: {"varietyId": "a", "contractId": "a2501", "startMonth": "202501", "endMonth": "202510"}
-    let year_start_month = format!("{}01", &trade_date[0..4]);
+    let year_start_month =
+        DeliveryMonth::new(delivery_month.year(), 1).expect("January is a valid month");
     println!("\n[2/10] GetDeliveryMatch - 配对交割明细");
     match client
         .delivery
@@ -924,8 +944,8 @@ async fn run_delivery_service_examples(client: &Client, trade_date: &str, trade_
             &dceapi_rs::DeliveryMatchRequest {
                 variety_id: "a".to_string(),
                 contract_id: "a2501".to_string(),
-                start_month: year_start_month.clone(),
-                end_month: trade_month.to_string(),
+                start_month: year_start_month,
+                end_month: delivery_month,
             },
             None,
         )
@@ -948,7 +968,11 @@ async fn run_delivery_service_examples(client: &Client, trade_date: &str, trade_
     // 文档: POST /dceapi/forward/publicweb/deliverystat/deliveryCost
     // 请求示例: {"varietyId": "a", "varietyType": "0"}
     println!("\n[3/10] GetDeliveryCost - 交割费用");
-    match client.delivery.get_delivery_cost("a", "0", None).await {
+    match client
+        .delivery
+        .get_delivery_cost("a", VarietyType::Physical, None)
+        .await
+    {
         Ok(costs) => {
             println!("✓ 交割费用数量: {}", costs.len());
             for cost in costs.iter().take(3) {
@@ -1032,7 +1056,10 @@ async fn run_delivery_service_examples(client: &Client, trade_date: &str, trade_
             for i in intentions.iter().take(3) {
                 println!(
                     "   {} | 合约: {} | 数量: {} | 交割方式: {}",
-                    i.variety_name, i.contract, i.quantity, i.delivery_way
+                    i.variety_name,
+                    i.contract,
+                    i.quantity,
+                    i.delivery_way.as_ref().map_or("-", DeliveryWay::label)
                 );
             }
         }
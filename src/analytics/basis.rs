@@ -0,0 +1,97 @@
+//! Spot-futures basis per factory.
+//!
+//! Factory warehouses publish a spot agio: the premium (or, negative, the
+//! discount) of goods delivered at the factory over the futures price.
+//! [`basis_table`] joins these with the same day's futures quote to give
+//! each factory's implied spot price and its basis against the contract.
+
+use serde::Serialize;
+
+use crate::continuous::RollBy;
+use crate::models::{parse_optional_f64, FactorySpotAgio, Quote};
+use crate::quote_list::QuoteListExt;
+
+/// Basis of one factory against a futures contract on one trade date.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BasisRow {
+    /// Factory (warehouse) abbreviation.
+    pub wh_abbr: String,
+    /// Warehouse code.
+    pub wh_code: String,
+    /// Futures contract the basis is taken against.
+    pub contract_id: String,
+    /// Futures close (settlement price if the contract has no close).
+    pub futures_price: f64,
+    /// Factory agio over the futures price.
+    pub agio: f64,
+    /// Implied spot price at the factory (`futures_price + agio`).
+    pub spot_price: f64,
+}
+
+impl BasisRow {
+    /// Basis (`spot_price - futures_price`).
+    pub fn basis(&self) -> f64 {
+        self.spot_price - self.futures_price
+    }
+
+    /// Basis in percent of the futures price.
+    pub fn basis_pct(&self) -> Option<f64> {
+        (self.futures_price != 0.0).then(|| self.basis() / self.futures_price * 100.0)
+    }
+}
+
+/// Join factory agios with the futures quotes of the same trade date.
+///
+/// The basis is taken against `contract_id`, or against the main contract
+/// of `quotes` when `None`. Returns an empty table when the contract has no
+/// price; factories whose agio does not parse are left out.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::analytics::basis_table;
+/// use dceapi_rs::{FactorySpotAgio, Quote};
+///
+/// let quote: Quote = serde_json::from_value(serde_json::json!({
+///     "contractId": "fb2509", "close": "1,200", "openInterest": 100,
+/// }))
+/// .unwrap();
+/// let agio: FactorySpotAgio = serde_json::from_value(serde_json::json!({
+///     "whAbbr": "丰林", "agio": "-15",
+/// }))
+/// .unwrap();
+///
+/// let table = basis_table(&[agio], &[quote], None);
+/// assert_eq!(table[0].spot_price, 1185.0);
+/// assert_eq!(table[0].basis(), -15.0);
+/// ```
+pub fn basis_table(
+    agios: &[FactorySpotAgio],
+    quotes: &[Quote],
+    contract_id: Option<&str>,
+) -> Vec<BasisRow> {
+    let quote = match contract_id {
+        Some(id) => quotes
+            .iter()
+            .find(|q| q.contract_id.eq_ignore_ascii_case(id.trim())),
+        None => quotes.main_contract(RollBy::default()),
+    };
+    let Some((quote, futures_price)) = quote.and_then(|q| Some((q, q.close.or(q.clear_price)?)))
+    else {
+        return Vec::new();
+    };
+    agios
+        .iter()
+        .filter_map(|row| {
+            let agio = parse_optional_f64(&row.agio)?;
+            Some(BasisRow {
+                wh_abbr: row.wh_abbr.clone(),
+                wh_code: row.wh_code.clone(),
+                contract_id: quote.contract_id.clone(),
+                futures_price,
+                agio,
+                spot_price: futures_price + agio,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,204 @@
+//! Delivery flows between buy and sell members.
+//!
+//! [`delivery_flows`] aggregates delivery match records (as returned by
+//! `DeliveryService::get_delivery_match`) by buy/sell member pair and by
+//! member, and measures how concentrated the delivered quantity is on each
+//! side.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::models::{parse_optional_f64, DeliveryMatch};
+
+/// Quantity delivered from one sell member to one buy member.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeliveryFlow {
+    /// Buy (receiving) member ID.
+    pub buy_member_id: String,
+    /// Sell (delivering) member ID.
+    pub sell_member_id: String,
+    /// Matched quantity in lots.
+    pub qty: i64,
+    /// Number of match records.
+    pub matches: usize,
+    /// Share of the report's total quantity (0 to 1).
+    pub share: f64,
+    /// Quantity-weighted average delivery price, or `None` if no record
+    /// has a price.
+    pub avg_price: Option<f64>,
+}
+
+/// Quantity delivered or received by one member.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemberDelivery {
+    /// Member ID.
+    pub member_id: String,
+    /// Matched quantity in lots.
+    pub qty: i64,
+    /// Share of the report's total quantity (0 to 1).
+    pub share: f64,
+    /// Number of distinct counterparties.
+    pub counterparties: usize,
+}
+
+/// Concentration of delivered quantity among members.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub struct Concentration {
+    /// Herfindahl-Hirschman index: the sum of squared shares, from near 0
+    /// (evenly spread) to 1 (a single member).
+    pub hhi: f64,
+    /// Combined share of the four largest members.
+    pub cr4: f64,
+}
+
+/// Delivery flows over a set of match records.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct DeliveryFlowReport {
+    /// Total matched quantity in lots.
+    pub total_qty: i64,
+    /// Member pairs, largest quantity first.
+    pub pairs: Vec<DeliveryFlow>,
+    /// Buy members, largest quantity first.
+    pub buyers: Vec<MemberDelivery>,
+    /// Sell members, largest quantity first.
+    pub sellers: Vec<MemberDelivery>,
+    /// Concentration of received quantity among buy members.
+    pub buy_concentration: Concentration,
+    /// Concentration of delivered quantity among sell members.
+    pub sell_concentration: Concentration,
+}
+
+impl DeliveryFlowReport {
+    /// Flows received by a buy member, largest first.
+    pub fn received_by<'a>(&'a self, member_id: &'a str) -> impl Iterator<Item = &'a DeliveryFlow> {
+        self.pairs
+            .iter()
+            .filter(move |f| f.buy_member_id == member_id)
+    }
+
+    /// Flows delivered by a sell member, largest first.
+    pub fn delivered_by<'a>(
+        &'a self,
+        member_id: &'a str,
+    ) -> impl Iterator<Item = &'a DeliveryFlow> {
+        self.pairs
+            .iter()
+            .filter(move |f| f.sell_member_id == member_id)
+    }
+}
+
+/// Aggregate delivery matches by member pair and member.
+///
+/// Records without quantity are ignored. Ties in quantity are ordered by
+/// member ID. Filter the records first to report on a single contract or
+/// month.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::analytics::delivery_flows;
+/// use dceapi_rs::DeliveryMatch;
+///
+/// let record = |buy: &str, sell: &str, qty: i64| -> DeliveryMatch {
+///     serde_json::from_value(serde_json::json!({
+///         "contractId": "m2509", "buyMemberId": buy, "sellMemberId": sell,
+///         "deliveryQty": qty, "deliveryPrice": "3000",
+///     }))
+///     .unwrap()
+/// };
+/// let report = delivery_flows(&[record("0101", "0202", 30), record("0103", "0202", 10)]);
+/// assert_eq!(report.total_qty, 40);
+/// assert_eq!(report.pairs[0].buy_member_id, "0101");
+/// assert_eq!(report.sellers[0].counterparties, 2);
+/// assert_eq!(report.sell_concentration.hhi, 1.0);
+/// ```
+pub fn delivery_flows(matches: &[DeliveryMatch]) -> DeliveryFlowReport {
+    #[derive(Default)]
+    struct Pair {
+        qty: i64,
+        matches: usize,
+        priced_qty: f64,
+        price_sum: f64,
+    }
+
+    let mut pairs: BTreeMap<(&str, &str), Pair> = BTreeMap::new();
+    for record in matches.iter().filter(|m| m.delivery_qty != 0) {
+        let pair = pairs
+            .entry((record.buy_member_id.trim(), record.sell_member_id.trim()))
+            .or_default();
+        pair.qty += record.delivery_qty;
+        pair.matches += 1;
+        if let Some(price) = parse_optional_f64(&record.delivery_price) {
+            pair.priced_qty += record.delivery_qty as f64;
+            pair.price_sum += price * record.delivery_qty as f64;
+        }
+    }
+
+    let total_qty: i64 = pairs.values().map(|p| p.qty).sum();
+    let share = |qty: i64| {
+        if total_qty == 0 {
+            0.0
+        } else {
+            qty as f64 / total_qty as f64
+        }
+    };
+
+    let mut flows: Vec<DeliveryFlow> = pairs
+        .iter()
+        .map(|(&(buy, sell), pair)| DeliveryFlow {
+            buy_member_id: buy.to_string(),
+            sell_member_id: sell.to_string(),
+            qty: pair.qty,
+            matches: pair.matches,
+            share: share(pair.qty),
+            avg_price: (pair.priced_qty != 0.0).then(|| pair.price_sum / pair.priced_qty),
+        })
+        .collect();
+    flows.sort_by_key(|f| Reverse(f.qty));
+
+    let buyers = members(&flows, |f| &f.buy_member_id, share);
+    let sellers = members(&flows, |f| &f.sell_member_id, share);
+    DeliveryFlowReport {
+        total_qty,
+        buy_concentration: concentration(&buyers),
+        sell_concentration: concentration(&sellers),
+        pairs: flows,
+        buyers,
+        sellers,
+    }
+}
+
+/// Per-member totals for one side, largest first.
+fn members(
+    flows: &[DeliveryFlow],
+    member: impl Fn(&DeliveryFlow) -> &String,
+    share: impl Fn(i64) -> f64,
+) -> Vec<MemberDelivery> {
+    let mut totals: BTreeMap<&str, (i64, usize)> = BTreeMap::new();
+    for flow in flows {
+        let total = totals.entry(member(flow).as_str()).or_default();
+        total.0 += flow.qty;
+        total.1 += 1;
+    }
+    let mut members: Vec<MemberDelivery> = totals
+        .into_iter()
+        .map(|(member_id, (qty, counterparties))| MemberDelivery {
+            member_id: member_id.to_string(),
+            qty,
+            share: share(qty),
+            counterparties,
+        })
+        .collect();
+    members.sort_by_key(|m| Reverse(m.qty));
+    members
+}
+
+/// Concentration of members sorted by quantity, largest first.
+fn concentration(members: &[MemberDelivery]) -> Concentration {
+    Concentration {
+        hhi: members.iter().map(|m| m.share * m.share).sum(),
+        cr4: members.iter().take(4).map(|m| m.share).sum(),
+    }
+}
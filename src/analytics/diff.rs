@@ -0,0 +1,247 @@
+//! Record-level and field-level differences between dataset snapshots.
+//!
+//! [`diff`] compares two snapshots of the same dataset (two settlement
+//! parameter lists, two receipt reports, two trading parameter lists) by
+//! key and reports the records that were added, removed or changed. Fields
+//! are compared as serialized, so every field of a model, including
+//! unrecognized ones kept in `extra`, takes part without per-type code.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::models::{
+    parse_optional_f64, PhaseRanking, Quote, RollDeliverySellerIntention, SettleParam,
+    TradingParam, WarehouseReceiptDetail,
+};
+
+/// A record with a key identifying it across snapshots.
+pub trait Keyed {
+    /// Key type, such as a contract ID.
+    type Key: Ord + Clone + Debug;
+
+    /// Key of this record.
+    fn key(&self) -> Self::Key;
+}
+
+impl Keyed for Quote {
+    type Key = String;
+
+    /// Contract ID.
+    fn key(&self) -> String {
+        self.contract_id.clone()
+    }
+}
+
+impl Keyed for SettleParam {
+    type Key = String;
+
+    /// Contract ID.
+    fn key(&self) -> String {
+        self.contract_id.clone()
+    }
+}
+
+impl Keyed for TradingParam {
+    type Key = String;
+
+    /// Variety ID.
+    fn key(&self) -> String {
+        self.variety_id.clone()
+    }
+}
+
+impl Keyed for WarehouseReceiptDetail {
+    type Key = (String, String);
+
+    /// Variety name and warehouse abbreviation.
+    fn key(&self) -> (String, String) {
+        (self.variety.clone(), self.wh_abbr.clone())
+    }
+}
+
+impl Keyed for RollDeliverySellerIntention {
+    type Key = (String, String, String);
+
+    /// Contract, warehouse code and delivery type.
+    fn key(&self) -> (String, String, String) {
+        (
+            self.contract.clone(),
+            self.warehouse_code.clone(),
+            self.type_.clone(),
+        )
+    }
+}
+
+impl Keyed for PhaseRanking {
+    type Key = String;
+
+    /// Member ID.
+    fn key(&self) -> String {
+        self.member_id.clone()
+    }
+}
+
+/// Change of one field between snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// Field name as serialized (the API's field name, e.g. `clearPrice`).
+    pub field: String,
+    /// Value in the older snapshot (`null` if absent).
+    pub old: Value,
+    /// Value in the newer snapshot (`null` if absent).
+    pub new: Value,
+}
+
+impl FieldChange {
+    /// Numeric change (`new - old`) when both values are numbers or
+    /// numeric strings such as "1,234.5".
+    pub fn delta(&self) -> Option<f64> {
+        Some(number(&self.new)? - number(&self.old)?)
+    }
+}
+
+/// A record present in both snapshots with different field values.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordChange<K> {
+    /// Record key.
+    pub key: K,
+    /// Changed fields, in field name order.
+    pub fields: Vec<FieldChange>,
+}
+
+impl<K> RecordChange<K> {
+    /// The change of a field, if it changed.
+    pub fn field(&self, name: &str) -> Option<&FieldChange> {
+        self.fields.iter().find(|f| f.field == name)
+    }
+}
+
+/// Differences between two snapshots, each list ordered by key.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnapshotDiff<K, T> {
+    /// Records only in the newer snapshot.
+    pub added: Vec<T>,
+    /// Records only in the older snapshot.
+    pub removed: Vec<T>,
+    /// Records in both snapshots whose fields differ.
+    pub changed: Vec<RecordChange<K>>,
+}
+
+impl<K, T> SnapshotDiff<K, T> {
+    /// Whether the snapshots are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two snapshots of a [`Keyed`] dataset.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::analytics::diff;
+/// use dceapi_rs::SettleParam;
+///
+/// let param = |contract: &str, price: &str| -> SettleParam {
+///     serde_json::from_value(serde_json::json!({
+///         "variety": "豆粕", "contractId": contract, "clearPrice": price,
+///     }))
+///     .unwrap()
+/// };
+/// let old = [param("m2601", "2,950"), param("m2603", "2900")];
+/// let new = [param("m2601", "3,010"), param("m2605", "2880")];
+///
+/// let changes = diff(&old, &new);
+/// assert_eq!(changes.added[0].contract_id, "m2605");
+/// assert_eq!(changes.removed[0].contract_id, "m2603");
+/// let price = changes.changed[0].field("clearPrice").unwrap();
+/// assert_eq!(price.delta(), Some(60.0));
+/// ```
+pub fn diff<T>(old: &[T], new: &[T]) -> SnapshotDiff<T::Key, T>
+where
+    T: Keyed + Serialize + Clone,
+{
+    diff_by(old, new, T::key)
+}
+
+/// Compare two snapshots, identifying records with `key`.
+///
+/// If a snapshot has several records with the same key, the last one is
+/// used. Fields are compared by their serialized values, so "2950" and
+/// "2,950" count as different.
+pub fn diff_by<T, K, F>(old: &[T], new: &[T], key: F) -> SnapshotDiff<K, T>
+where
+    T: Serialize + Clone,
+    K: Ord + Clone,
+    F: Fn(&T) -> K,
+{
+    let previous: BTreeMap<K, &T> = old.iter().map(|r| (key(r), r)).collect();
+    let current: BTreeMap<K, &T> = new.iter().map(|r| (key(r), r)).collect();
+
+    let mut result = SnapshotDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+    for (k, before) in &previous {
+        match current.get(k) {
+            None => result.removed.push((*before).clone()),
+            Some(after) => {
+                let fields = field_changes(&fields(*before), &fields(*after));
+                if !fields.is_empty() {
+                    result.changed.push(RecordChange {
+                        key: k.clone(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    result.added = current
+        .iter()
+        .filter(|(k, _)| !previous.contains_key(*k))
+        .map(|(_, r)| (*r).clone())
+        .collect();
+    result
+}
+
+/// Serialized fields of a record. Records that do not serialize to an
+/// object are treated as a single field named `value`.
+fn fields<T: Serialize>(record: &T) -> Map<String, Value> {
+    match serde_json::to_value(record) {
+        Ok(Value::Object(map)) => map,
+        Ok(value) => Map::from_iter([("value".to_string(), value)]),
+        Err(_) => Map::new(),
+    }
+}
+
+/// Fields whose values differ, in field name order.
+fn field_changes(old: &Map<String, Value>, new: &Map<String, Value>) -> Vec<FieldChange> {
+    let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let before = old.get(name).unwrap_or(&Value::Null);
+            let after = new.get(name).unwrap_or(&Value::Null);
+            (before != after).then(|| FieldChange {
+                field: name.clone(),
+                old: before.clone(),
+                new: after.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Numeric value of a JSON number or numeric string.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => parse_optional_f64(s),
+        _ => None,
+    }
+}
@@ -0,0 +1,371 @@
+//! Black-76 option greeks.
+//!
+//! Option day quotes carry the exchange's delta and implied volatility but
+//! no other sensitivities. DCE options are options on futures, so the
+//! helpers here price them with the Black-76 model from the underlying
+//! futures price, the days to expiry (see [`days_to_expiry`], or
+//! [`ContractCatalog::days_to_expiry`](crate::ContractCatalog::days_to_expiry)
+//! to look the contract up in the catalog) and the published implied
+//! volatility.
+
+use crate::contract_id::{ContractId, OptionKind};
+use crate::days::day_number;
+use crate::models::{parse_optional_f64, ContractInfo, Quote};
+
+/// Days per year used to convert days to expiry into years.
+pub const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Highest volatility [`implied_volatility`] searches (500%).
+const MAX_VOLATILITY: f64 = 5.0;
+
+/// Black-76 price and sensitivities of one option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    /// Theoretical option price.
+    pub price: f64,
+    /// Change in price per unit change of the futures price.
+    pub delta: f64,
+    /// Change in delta per unit change of the futures price.
+    pub gamma: f64,
+    /// Change in price per percentage point of volatility.
+    pub vega: f64,
+    /// Change in price per calendar day.
+    pub theta: f64,
+    /// Change in price per percentage point of the interest rate.
+    pub rho: f64,
+}
+
+/// Compute Black-76 greeks.
+///
+/// `volatility` and `rate` are annualized decimals (0.2 for 20%); `years`
+/// is the time to expiry. Returns `None` if the futures price, strike,
+/// volatility or time to expiry is not positive.
+pub fn black76(
+    kind: OptionKind,
+    futures_price: f64,
+    strike: f64,
+    years: f64,
+    volatility: f64,
+    rate: f64,
+) -> Option<Greeks> {
+    if !(futures_price > 0.0 && strike > 0.0 && years > 0.0 && volatility > 0.0) {
+        return None;
+    }
+
+    let sqrt_t = years.sqrt();
+    let vol_sqrt_t = volatility * sqrt_t;
+    let d1 = ((futures_price / strike).ln() + 0.5 * volatility * volatility * years) / vol_sqrt_t;
+    let d2 = d1 - vol_sqrt_t;
+    let discount = (-rate * years).exp();
+    let pdf_d1 = normal_pdf(d1);
+
+    let (price, delta) = match kind {
+        OptionKind::Call => (
+            discount * (futures_price * normal_cdf(d1) - strike * normal_cdf(d2)),
+            discount * normal_cdf(d1),
+        ),
+        OptionKind::Put => (
+            discount * (strike * normal_cdf(-d2) - futures_price * normal_cdf(-d1)),
+            -discount * normal_cdf(-d1),
+        ),
+    };
+    let gamma = discount * pdf_d1 / (futures_price * vol_sqrt_t);
+    let vega = futures_price * discount * pdf_d1 * sqrt_t;
+    let theta = -futures_price * discount * pdf_d1 * volatility / (2.0 * sqrt_t) + rate * price;
+    let rho = -years * price;
+
+    Some(Greeks {
+        price,
+        delta,
+        gamma,
+        vega: vega / 100.0,
+        theta: theta / DAYS_PER_YEAR,
+        rho: rho / 100.0,
+    })
+}
+
+/// Black-76 implied volatility of an option price, as an annualized
+/// decimal.
+///
+/// Solved by bisection over volatilities up to 500%. Returns `None` if the
+/// futures price, strike or time to expiry is not positive, or if no
+/// volatility in that range gives `price`: at or below the discounted
+/// intrinsic value, or above the price at 500%.
+pub fn implied_volatility(
+    kind: OptionKind,
+    futures_price: f64,
+    strike: f64,
+    years: f64,
+    rate: f64,
+    price: f64,
+) -> Option<f64> {
+    let price_at =
+        |volatility| black76(kind, futures_price, strike, years, volatility, rate).map(|g| g.price);
+    let (mut low, mut high) = (1e-9, MAX_VOLATILITY);
+    if !(price.is_finite() && price > price_at(low)? && price <= price_at(high)?) {
+        return None;
+    }
+    while high - low > 1e-12 {
+        let mid = 0.5 * (low + high);
+        if price_at(mid)? < price {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(0.5 * (low + high))
+}
+
+/// An option quote with its Black-76 greeks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionQuoteGreeks {
+    /// The option quote.
+    pub quote: Quote,
+    /// Parsed option contract ID.
+    pub contract: ContractId,
+    /// Underlying futures price used.
+    pub futures_price: f64,
+    /// Calendar days to expiry used.
+    pub days_to_expiry: f64,
+    /// Implied volatility used, as a decimal.
+    pub volatility: f64,
+    /// Computed greeks.
+    pub greeks: Greeks,
+}
+
+/// Compute greeks for an option day quote.
+///
+/// Uses the quote's published implied volatility, which the exchange gives
+/// in percent ("18.5" or "18.5%"). `rate` is the annualized risk-free rate
+/// as a decimal. Returns `None` if the quote is not an option contract, has
+/// no implied volatility, or `days_to_expiry` is not positive.
+pub fn option_greeks(
+    quote: &Quote,
+    futures_price: f64,
+    days_to_expiry: f64,
+    rate: f64,
+) -> Option<OptionQuoteGreeks> {
+    let contract = quote.parsed_contract_id()?;
+    let option = contract.option?;
+    let volatility =
+        parse_optional_f64(quote.implied_volatility.trim().trim_end_matches('%'))? / 100.0;
+    let greeks = black76(
+        option.kind,
+        futures_price,
+        option.strike,
+        days_to_expiry / DAYS_PER_YEAR,
+        volatility,
+        rate,
+    )?;
+    Some(OptionQuoteGreeks {
+        quote: quote.clone(),
+        contract,
+        futures_price,
+        days_to_expiry,
+        volatility,
+        greeks,
+    })
+}
+
+/// Calendar days from `trade_date` to the contract's last trade date.
+///
+/// Returns `None` if either date does not parse.
+pub fn days_to_expiry(info: &ContractInfo, trade_date: &str) -> Option<i64> {
+    Some(day_number(&info.end_trade_date)? - day_number(trade_date)?)
+}
+
+/// Standard normal density.
+fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Standard normal cumulative distribution (Hart's double-precision
+/// algorithm 5666, as given by West, "Better approximations to cumulative
+/// normal functions", 2005; absolute error around 1e-15).
+fn normal_cdf(x: f64) -> f64 {
+    const NUMERATOR: [f64; 7] = [
+        3.526_249_659_989_11e-2,
+        0.700_383_064_443_688,
+        6.373_962_203_531_65,
+        33.912_866_078_383,
+        112.079_291_497_871,
+        221.213_596_169_931,
+        220.206_867_912_376,
+    ];
+    const DENOMINATOR: [f64; 8] = [
+        8.838_834_764_831_84e-2,
+        1.755_667_163_182_64,
+        16.064_177_579_207,
+        86.780_732_202_946_1,
+        296.564_248_779_674,
+        637.333_633_378_831,
+        793.826_512_519_948,
+        440.413_735_824_752,
+    ];
+    let z = x.abs();
+    let tail = if z > 37.0 {
+        0.0
+    } else if z < 7.071_067_811_865_47 {
+        let polynomial = |coefficients: &[f64]| coefficients.iter().fold(0.0, |acc, c| acc * z + c);
+        (-0.5 * z * z).exp() * polynomial(&NUMERATOR) / polynomial(&DENOMINATOR)
+    } else {
+        // Continued fraction for the far tail.
+        let fraction = [4.0, 3.0, 2.0, 1.0]
+            .iter()
+            .fold(z + 0.65, |acc, k| z + k / acc);
+        normal_pdf(z) / fraction
+    };
+    if x > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn matches_textbook_black76_values() {
+        // Hull, Options, Futures, and Other Derivatives: a put on futures
+        // with F = K = 20, r = 9%, four months to expiry and 25%
+        // volatility is worth 1.12.
+        let years = 4.0 / 12.0;
+        let put = black76(OptionKind::Put, 20.0, 20.0, years, 0.25, 0.09).unwrap();
+        assert_close(put.price, 1.116_641_456_6);
+        assert_close(put.delta, -0.457_306_730_4);
+        assert_close(put.gamma, 0.133_764_502_7);
+        assert_close(put.vega, 0.044_588_167_6);
+        assert_close(put.theta, -0.004_305_639_9);
+        assert_close(put.rho, -0.003_722_138_2);
+
+        let call = black76(OptionKind::Call, 3000.0, 3100.0, 0.25, 0.2, 0.02).unwrap();
+        assert_close(call.price, 77.711_587_112_0);
+        assert_close(call.delta, 0.388_597_389_5);
+        assert_close(call.gamma, 0.001_273_056_3);
+        assert_close(call.vega, 5.728_753_428_3);
+        assert_close(call.theta, -0.623_550_425_7);
+        assert_close(call.rho, -0.194_278_967_8);
+    }
+
+    #[test]
+    fn normal_cdf_is_accurate_into_the_tails() {
+        for (x, expected) in [
+            (0.0, 0.5),
+            (1.96, 0.975_002_104_851_779_5),
+            (-3.0, 0.001_349_898_031_630_095_7),
+            (-8.0, 6.220_960_574_271_819e-16),
+        ] {
+            let cdf = normal_cdf(x);
+            assert!((cdf - expected).abs() < 1e-14, "{x}: {cdf}");
+            assert!((cdf + normal_cdf(-x) - 1.0).abs() < 1e-14, "{x}");
+        }
+        assert_eq!(normal_cdf(40.0), 1.0);
+    }
+
+    #[test]
+    fn calls_and_puts_satisfy_parity() {
+        let (futures_price, years, volatility, rate): (f64, f64, f64, f64) =
+            (3000.0, 0.5, 0.3, 0.03);
+        let discount = (-rate * years).exp();
+        for strike in [2000.0, 2900.0, 3000.0, 3100.0, 4500.0] {
+            let greeks = |kind| black76(kind, futures_price, strike, years, volatility, rate);
+            let (call, put) = (
+                greeks(OptionKind::Call).unwrap(),
+                greeks(OptionKind::Put).unwrap(),
+            );
+            assert_close(call.price - put.price, discount * (futures_price - strike));
+            assert_close(call.delta - put.delta, discount);
+            assert_close(call.gamma, put.gamma);
+            assert_close(call.vega, put.vega);
+        }
+    }
+
+    #[test]
+    fn non_positive_inputs_have_no_greeks() {
+        for (futures_price, strike, years, volatility) in [
+            (0.0, 3000.0, 0.5, 0.2),
+            (3000.0, -1.0, 0.5, 0.2),
+            (3000.0, 3000.0, 0.0, 0.2),
+            (3000.0, 3000.0, 0.5, 0.0),
+            (f64::NAN, 3000.0, 0.5, 0.2),
+        ] {
+            let greeks = black76(
+                OptionKind::Call,
+                futures_price,
+                strike,
+                years,
+                volatility,
+                0.0,
+            );
+            assert_eq!(greeks, None);
+        }
+    }
+
+    #[test]
+    fn implied_volatility_round_trips() {
+        for kind in [OptionKind::Call, OptionKind::Put] {
+            for strike in [2700.0, 3000.0, 3300.0] {
+                for volatility in [0.1, 0.2, 0.8, 2.0] {
+                    let price = black76(kind, 3000.0, strike, 0.25, volatility, 0.02)
+                        .unwrap()
+                        .price;
+                    let solved =
+                        implied_volatility(kind, 3000.0, strike, 0.25, 0.02, price).unwrap();
+                    assert_close(solved, volatility);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn implied_volatility_fails_outside_the_price_range() {
+        let solve = |kind, price| implied_volatility(kind, 3000.0, 2800.0, 0.25, 0.0, price);
+        // At or below intrinsic value.
+        assert_eq!(solve(OptionKind::Call, 200.0), None);
+        assert_eq!(solve(OptionKind::Call, 150.0), None);
+        assert_eq!(solve(OptionKind::Put, 0.0), None);
+        // Above the futures price, which no volatility reaches.
+        assert_eq!(solve(OptionKind::Call, 3000.0), None);
+        assert_eq!(solve(OptionKind::Call, f64::NAN), None);
+        assert_eq!(
+            implied_volatility(OptionKind::Call, 3000.0, 2800.0, 0.0, 0.0, 250.0),
+            None
+        );
+    }
+
+    #[test]
+    fn quote_greeks_use_the_published_volatility() {
+        let quote: Quote = serde_json::from_value(serde_json::json!({
+            "contractId": "m2509-C-3100", "impliedVolatility": "20%",
+        }))
+        .unwrap();
+        let enriched = option_greeks(&quote, 3000.0, 91.25, 0.02).unwrap();
+        assert_eq!(enriched.volatility, 0.2);
+        assert_close(enriched.greeks.price, 77.711_587_112_0);
+
+        let future: Quote =
+            serde_json::from_value(serde_json::json!({ "contractId": "m2509" })).unwrap();
+        assert_eq!(option_greeks(&future, 3000.0, 91.25, 0.02), None);
+        assert_eq!(option_greeks(&quote, 3000.0, 0.0, 0.02), None);
+    }
+
+    #[test]
+    fn days_to_expiry_counts_calendar_days() {
+        let info: ContractInfo = serde_json::from_value(serde_json::json!({
+            "contractId": "m2509-C-3100", "endTradeDate": "20250807",
+        }))
+        .unwrap();
+        assert_eq!(days_to_expiry(&info, "20250801"), Some(6));
+        assert_eq!(days_to_expiry(&info, "20250901"), Some(-25));
+        assert_eq!(days_to_expiry(&info, "bad"), None);
+    }
+}
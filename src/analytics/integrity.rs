@@ -0,0 +1,214 @@
+//! Cross-checks of returned data against the exchange's own totals.
+//!
+//! Quote and receipt reports carry subtotal rows next to the detail rows
+//! they summarize. The checks here recompute those totals and report any
+//! mismatch in an [`IntegrityReport`], so glitches on the exchange side are
+//! caught before the data is stored or used.
+
+use std::fmt;
+
+use crate::models::{is_subtotal_label, parse_optional_f64, Quote, WarehouseReceipt};
+
+use super::variety_stat::TURNOVER_TOLERANCE;
+
+/// A discrepancy found by an integrity check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityIssue {
+    /// A subtotal or total row does not match the sum of the rows it covers.
+    SubtotalMismatch {
+        /// Label of the subtotal row (e.g. "豆一小计").
+        label: String,
+        /// Field that differs (e.g. "volume").
+        field: &'static str,
+        /// Sum of the covered rows.
+        computed: f64,
+        /// Value in the subtotal row.
+        reported: f64,
+    },
+    /// A warehouse's receipts do not balance: previous quantity plus
+    /// registrations minus cancellations differs from the current quantity.
+    ReceiptImbalance {
+        /// Variety name.
+        variety: String,
+        /// Warehouse abbreviation.
+        warehouse: String,
+        /// Quantity implied by the previous quantity and the day's flows.
+        expected: i64,
+        /// Reported current quantity.
+        reported: i64,
+    },
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegrityIssue::SubtotalMismatch {
+                label,
+                field,
+                computed,
+                reported,
+            } => write!(
+                f,
+                "{} {}: rows sum to {}, subtotal reports {}",
+                label, field, computed, reported
+            ),
+            IntegrityIssue::ReceiptImbalance {
+                variety,
+                warehouse,
+                expected,
+                reported,
+            } => write!(
+                f,
+                "{} {}: flows imply {} receipts, report shows {}",
+                variety, warehouse, expected, reported
+            ),
+        }
+    }
+}
+
+/// Result of integrity checks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntegrityReport {
+    /// Discrepancies found, in row order.
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// Whether no discrepancies were found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Append the issues of another report.
+    pub fn merge(&mut self, other: IntegrityReport) {
+        self.issues.extend(other.issues);
+    }
+}
+
+/// Check quote subtotal rows against the contract rows above them.
+///
+/// Each variety subtotal ("豆一小计") is compared with the contract rows
+/// since the previous subtotal, and a grand total ("总计") with all contract
+/// rows. Volume and open interest must match exactly; turnover within
+/// [`TURNOVER_TOLERANCE`] to allow for rounding.
+pub fn check_quotes(quotes: &[Quote]) -> IntegrityReport {
+    let mut report = IntegrityReport::default();
+    let mut group = QuoteSums::default();
+    let mut all = QuoteSums::default();
+    for quote in quotes {
+        if !quote.is_subtotal() {
+            group.add(quote);
+            all.add(quote);
+            continue;
+        }
+        let label = quote.variety_label().trim();
+        let is_grand_total =
+            ["总计", "合计"].contains(&label) || label.eq_ignore_ascii_case("total");
+        let sums = if is_grand_total { &all } else { &group };
+        sums.compare(quote, &mut report);
+        group = QuoteSums::default();
+    }
+    report
+}
+
+/// Check a warehouse receipt report.
+///
+/// Each warehouse row must balance (previous quantity plus registrations
+/// minus cancellations equals the current quantity), and each subtotal row
+/// must match the warehouse rows since the previous subtotal.
+pub fn check_warehouse_receipt(receipt: &WarehouseReceipt) -> IntegrityReport {
+    let mut report = IntegrityReport::default();
+    let mut sums = [0i64; 4];
+    for detail in &receipt.entity_list {
+        let values = [
+            detail.last_wbill_qty,
+            detail.reg_wbill_qty,
+            detail.logout_wbill_qty,
+            detail.wbill_qty,
+        ];
+        if !is_subtotal_label(&detail.variety) && !is_subtotal_label(&detail.wh_abbr) {
+            let expected = detail.last_wbill_qty + detail.reg_wbill_qty - detail.logout_wbill_qty;
+            if expected != detail.wbill_qty {
+                report.issues.push(IntegrityIssue::ReceiptImbalance {
+                    variety: detail.variety.clone(),
+                    warehouse: detail.wh_abbr.clone(),
+                    expected,
+                    reported: detail.wbill_qty,
+                });
+            }
+            for (sum, value) in sums.iter_mut().zip(values) {
+                *sum += value;
+            }
+            continue;
+        }
+
+        let label = if is_subtotal_label(&detail.wh_abbr) {
+            format!("{} {}", detail.variety, detail.wh_abbr)
+        } else {
+            detail.variety.clone()
+        };
+        let fields = [
+            "last_wbill_qty",
+            "reg_wbill_qty",
+            "logout_wbill_qty",
+            "wbill_qty",
+        ];
+        for ((field, computed), reported) in fields.into_iter().zip(sums).zip(values) {
+            if computed != reported {
+                report.issues.push(IntegrityIssue::SubtotalMismatch {
+                    label: label.trim().to_string(),
+                    field,
+                    computed: computed as f64,
+                    reported: reported as f64,
+                });
+            }
+        }
+        sums = [0; 4];
+    }
+    report
+}
+
+/// Running sums of quote rows.
+#[derive(Default)]
+struct QuoteSums {
+    volume: i64,
+    open_interest: i64,
+    turnover: f64,
+}
+
+impl QuoteSums {
+    /// Add a contract row.
+    fn add(&mut self, quote: &Quote) {
+        self.volume += quote.volume;
+        self.open_interest += quote.open_interest;
+        self.turnover += parse_optional_f64(quote.turnover_text()).unwrap_or(0.0);
+    }
+
+    /// Compare the sums with a subtotal row.
+    fn compare(&self, subtotal: &Quote, report: &mut IntegrityReport) {
+        let mut mismatch = |field, computed: f64, reported: f64| {
+            report.issues.push(IntegrityIssue::SubtotalMismatch {
+                label: subtotal.variety_label().trim().to_string(),
+                field,
+                computed,
+                reported,
+            });
+        };
+        if self.volume != subtotal.volume {
+            mismatch("volume", self.volume as f64, subtotal.volume as f64);
+        }
+        if self.open_interest != subtotal.open_interest {
+            mismatch(
+                "open_interest",
+                self.open_interest as f64,
+                subtotal.open_interest as f64,
+            );
+        }
+        if let Some(reported) = parse_optional_f64(subtotal.turnover_text()) {
+            let tolerance = TURNOVER_TOLERANCE * reported.abs().max(1.0);
+            if (self.turnover - reported).abs() > tolerance {
+                mismatch("turnover", self.turnover, reported);
+            }
+        }
+    }
+}
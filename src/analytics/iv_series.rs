@@ -0,0 +1,134 @@
+//! Implied volatility time series per option series.
+//!
+//! Option day quotes requested per series
+//! ([`StatisticsType::Series`](crate::StatisticsType::Series)) carry the
+//! exchange's implied volatility for each series (e.g. "m2505"). These helpers
+//! extract one series' values from quotes collected over several trade dates;
+//! [`MarketService::get_iv_series`](crate::MarketService::get_iv_series)
+//! fetches the quotes and builds the series in one call.
+
+use std::collections::BTreeMap;
+
+use crate::models::{parse_optional_f64, Quote};
+
+/// Implied volatility of a series on one trade date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IvPoint {
+    /// Trade date (YYYYMMDD).
+    pub date: String,
+    /// Implied volatility as published, or `None` if the series had no value that day.
+    pub iv: Option<f64>,
+}
+
+/// Daily implied volatility series for one option series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IvSeries {
+    /// Option series ID (e.g. "m2505").
+    pub series_id: String,
+    /// One point per trade date, in ascending date order.
+    pub points: Vec<IvPoint>,
+}
+
+impl IvSeries {
+    /// Dates for which no implied volatility was available.
+    pub fn missing_dates(&self) -> Vec<&str> {
+        self.points
+            .iter()
+            .filter(|p| p.iv.is_none())
+            .map(|p| p.date.as_str())
+            .collect()
+    }
+
+    /// Only the dates with a value, as (date, iv) pairs.
+    pub fn values(&self) -> Vec<(&str, f64)> {
+        self.points
+            .iter()
+            .filter_map(|p| p.iv.map(|iv| (p.date.as_str(), iv)))
+            .collect()
+    }
+
+    /// Copy of the series with gaps filled by the last known value.
+    ///
+    /// Leading gaps (before the first value) stay empty.
+    pub fn forward_filled(&self) -> IvSeries {
+        let mut last = None;
+        let points = self
+            .points
+            .iter()
+            .map(|p| {
+                if p.iv.is_some() {
+                    last = p.iv;
+                }
+                IvPoint {
+                    date: p.date.clone(),
+                    iv: last,
+                }
+            })
+            .collect();
+        IvSeries {
+            series_id: self.series_id.clone(),
+            points,
+        }
+    }
+}
+
+/// Build the daily implied volatility series for an option series.
+///
+/// `quotes` maps each trade date (YYYYMMDD) to the option day quotes of the
+/// series' variety on that date. Every date in `dates` yields a point, in
+/// ascending order, with `iv: None` when the date has no quotes, the series
+/// is absent or its value does not parse. If several rows match the series
+/// (contract-level quotes), their implied volatilities are averaged.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use dceapi_rs::analytics::iv_series;
+/// use dceapi_rs::Quote;
+///
+/// let quote: Quote = serde_json::from_value(serde_json::json!({
+///     "seriesId": "m2505", "impliedVolatility": "18.5%",
+/// }))
+/// .unwrap();
+/// let quotes = BTreeMap::from([("20250102".to_string(), vec![quote])]);
+/// let dates = ["20250102".to_string(), "20250103".to_string()];
+///
+/// let series = iv_series(&quotes, "m2505", &dates);
+/// assert_eq!(series.values(), [("20250102", 18.5)]);
+/// assert_eq!(series.missing_dates(), ["20250103"]);
+/// ```
+pub fn iv_series(
+    quotes: &BTreeMap<String, Vec<Quote>>,
+    series_id: &str,
+    dates: &[String],
+) -> IvSeries {
+    let mut dates = dates.to_vec();
+    dates.sort();
+    dates.dedup();
+    let points = dates
+        .into_iter()
+        .map(|date| {
+            let values: Vec<f64> = quotes
+                .get(&date)
+                .into_iter()
+                .flatten()
+                .filter(|q| q.series_id.eq_ignore_ascii_case(series_id))
+                .filter_map(|q| parse_iv(&q.implied_volatility))
+                .collect();
+            let iv = (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64);
+            IvPoint { date, iv }
+        })
+        .collect();
+
+    IvSeries {
+        series_id: series_id.to_string(),
+        points,
+    }
+}
+
+/// Parse an implied volatility value, accepting a trailing percent sign.
+fn parse_iv(raw: &str) -> Option<f64> {
+    parse_optional_f64(raw.trim().trim_end_matches('%'))
+}
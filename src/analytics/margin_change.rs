@@ -0,0 +1,97 @@
+//! Margin rate changes between trading parameter snapshots.
+//!
+//! [`margin_changes`] compares two [`TradingParam`] lists variety by
+//! variety, for example the lists fetched before and after the exchange
+//! announces a margin adjustment. It narrows the record-level [`diff`] of
+//! the two lists down to the margin rates.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::analytics::diff::{diff, Keyed};
+use crate::models::TradingParam;
+
+/// Change in a variety's trading margin rates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MarginChange {
+    /// Variety ID.
+    pub variety_id: String,
+    /// Variety name.
+    pub variety_name: String,
+    /// Speculation margin rate in the older list.
+    pub old_speculation: String,
+    /// Speculation margin rate in the newer list.
+    pub speculation: String,
+    /// Hedging margin rate in the older list.
+    pub old_hedging: String,
+    /// Hedging margin rate in the newer list.
+    pub hedging: String,
+}
+
+/// Compare two trading parameter lists variety by variety.
+///
+/// Returns the varieties whose speculation or hedging margin rate changed,
+/// ordered by variety ID. Varieties listed in only one of the lists are
+/// skipped; a variety listed twice uses its last entry. Rates are compared
+/// as published, ignoring surrounding whitespace.
+pub fn margin_changes(old: &[TradingParam], new: &[TradingParam]) -> Vec<MarginChange> {
+    let previous: BTreeMap<String, &TradingParam> = old.iter().map(|p| (p.key(), p)).collect();
+    let current: BTreeMap<String, &TradingParam> = new.iter().map(|p| (p.key(), p)).collect();
+    diff(old, new)
+        .changed
+        .iter()
+        .filter_map(|record| {
+            let (before, after) = (previous.get(&record.key)?, current.get(&record.key)?);
+            let change = MarginChange {
+                variety_id: after.variety_id.clone(),
+                variety_name: after.variety_name.clone(),
+                old_speculation: before.trading_margin_rate_speculation.trim().to_string(),
+                speculation: after.trading_margin_rate_speculation.trim().to_string(),
+                old_hedging: before.trading_margin_rate_hedging.trim().to_string(),
+                hedging: after.trading_margin_rate_hedging.trim().to_string(),
+            };
+            (change.old_speculation != change.speculation || change.old_hedging != change.hedging)
+                .then_some(change)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(variety: &str, speculation: &str, hedging: &str) -> TradingParam {
+        serde_json::from_value(serde_json::json!({
+            "varietyId": variety,
+            "tradingMarginRateSpeculation": speculation,
+            "tradingMarginRateHedging": hedging,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reports_changed_rates_only() {
+        let old = vec![
+            param("m", "7%", "6%"),
+            param("i", "11%", "10%"),
+            param("a", "8%", "7%"),
+        ];
+        let new = vec![
+            param("i", " 11% ", "10%"),
+            param("m", "8%", "6%"),
+            param("c", "7%", "6%"),
+        ];
+
+        let changes = margin_changes(&old, &new);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].variety_id, "m");
+        assert_eq!(
+            (
+                changes[0].old_speculation.as_str(),
+                changes[0].speculation.as_str()
+            ),
+            ("7%", "8%")
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Analytics helpers built on top of API responses.
+//!
+//! These helpers operate on data already returned by the services and never
+//! perform network requests on their own.
+
+mod basis;
+mod delivery_flow;
+mod diff;
+mod greeks;
+mod integrity;
+mod iv_series;
+mod margin_change;
+mod options_summary;
+mod premium_compare;
+mod price_limit;
+mod quote_change;
+mod receipt_change;
+#[cfg(feature = "stats")]
+mod stats;
+mod variety_stat;
+
+pub use basis::{basis_table, BasisRow};
+pub use delivery_flow::{
+    delivery_flows, Concentration, DeliveryFlow, DeliveryFlowReport, MemberDelivery,
+};
+pub use diff::{diff, diff_by, FieldChange, Keyed, RecordChange, SnapshotDiff};
+pub use greeks::{
+    black76, days_to_expiry, implied_volatility, option_greeks, Greeks, OptionQuoteGreeks,
+    DAYS_PER_YEAR,
+};
+pub use integrity::{check_quotes, check_warehouse_receipt, IntegrityIssue, IntegrityReport};
+pub use iv_series::{iv_series, IvPoint, IvSeries};
+pub use margin_change::{margin_changes, MarginChange};
+pub use options_summary::{options_summary, OptionsSummary};
+pub use premium_compare::{premium_groups, PremiumGroup};
+pub use price_limit::{limit_prices, next_price_limits, PriceLimit};
+pub use quote_change::{
+    quote_changes, ChangeTolerance, QuoteChange, QuoteChangeTracker, QuoteDiff, QuoteField,
+};
+pub use receipt_change::{receipt_alerts, receipt_changes, ReceiptAlert, ReceiptChange};
+#[cfg(feature = "stats")]
+pub use stats::{
+    historical_volatility, log_returns, open_interest_zscore, returns, rolling_mean, rolling_std,
+    rolling_zscore, volume_zscore, TRADING_DAYS_PER_YEAR,
+};
+pub use variety_stat::{
+    rolling_12m_summary, ytd_summary, StatDiscrepancy, VarietyPeriodStat, TURNOVER_TOLERANCE,
+};
@@ -0,0 +1,183 @@
+//! Put/call ratios, premium turnover and max pain for option quotes.
+//!
+//! [`options_summary`] condenses the contract-level option day quotes of a
+//! variety and trade date into the figures usually quoted in market
+//! commentary.
+
+use std::collections::BTreeMap;
+
+use crate::models::Quote;
+use crate::option_chain::{OptionChain, OptionSeries};
+
+/// Summary of a variety's option market on one trade date.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OptionsSummary {
+    /// Call volume in lots.
+    pub call_volume: i64,
+    /// Put volume in lots.
+    pub put_volume: i64,
+    /// Call open interest in lots.
+    pub call_open_interest: i64,
+    /// Put open interest in lots.
+    pub put_open_interest: i64,
+    /// Premium turnover of calls, in yuan.
+    pub call_turnover: f64,
+    /// Premium turnover of puts, in yuan.
+    pub put_turnover: f64,
+    /// Max pain strike per series, keyed by series ID (e.g. "m2505").
+    pub max_pain: BTreeMap<String, f64>,
+}
+
+impl OptionsSummary {
+    /// Put volume divided by call volume, or `None` without call volume.
+    pub fn put_call_volume_ratio(&self) -> Option<f64> {
+        ratio(self.put_volume, self.call_volume)
+    }
+
+    /// Put open interest divided by call open interest, or `None` without
+    /// call open interest.
+    pub fn put_call_oi_ratio(&self) -> Option<f64> {
+        ratio(self.put_open_interest, self.call_open_interest)
+    }
+
+    /// Total premium turnover (calls and puts), in yuan.
+    pub fn total_turnover(&self) -> f64 {
+        self.call_turnover + self.put_turnover
+    }
+}
+
+/// Summarize contract-level option quotes of one variety and trade date.
+///
+/// Rows that are not option contracts (summary or series rows) are ignored,
+/// as are turnover values that do not parse. Max pain is the strike at
+/// which option holders' total intrinsic value at expiry, weighted by open
+/// interest, is smallest; series without open interest have no entry.
+pub fn options_summary(quotes: &[Quote]) -> OptionsSummary {
+    let chain = OptionChain::from_quotes("", "", quotes);
+    let mut summary = OptionsSummary::default();
+
+    for series in chain.series.values() {
+        for quote in series.calls() {
+            summary.call_volume += quote.volume;
+            summary.call_open_interest += quote.open_interest;
+            summary.call_turnover += quote.turnover_yuan().unwrap_or_default();
+        }
+        for quote in series.puts() {
+            summary.put_volume += quote.volume;
+            summary.put_open_interest += quote.open_interest;
+            summary.put_turnover += quote.turnover_yuan().unwrap_or_default();
+        }
+        if let Some(strike) = max_pain(series) {
+            summary.max_pain.insert(series.series_id.clone(), strike);
+        }
+    }
+    summary
+}
+
+/// Strike minimizing the open-interest-weighted payout to option holders.
+fn max_pain(series: &OptionSeries) -> Option<f64> {
+    let open_interest =
+        |quote: &Option<Quote>| quote.as_ref().map_or(0.0, |q| q.open_interest as f64);
+    if series
+        .strikes
+        .iter()
+        .all(|row| open_interest(&row.call) == 0.0 && open_interest(&row.put) == 0.0)
+    {
+        return None;
+    }
+
+    series
+        .strikes
+        .iter()
+        .map(|settle| {
+            let payout: f64 = series
+                .strikes
+                .iter()
+                .map(|row| {
+                    open_interest(&row.call) * (settle.strike - row.strike).max(0.0)
+                        + open_interest(&row.put) * (row.strike - settle.strike).max(0.0)
+                })
+                .sum();
+            (settle.strike, payout)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(strike, _)| strike)
+}
+
+/// `numerator / denominator`, or `None` if the denominator is zero.
+fn ratio(numerator: i64, denominator: i64) -> Option<f64> {
+    (denominator != 0).then(|| numerator as f64 / denominator as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn quote(contract_id: &str, volume: i64, open_interest: i64, turnover: &str) -> Quote {
+        serde_json::from_value(json!({
+            "contractId": contract_id,
+            "volumn": volume,
+            "openInterest": open_interest,
+            "turnover": turnover,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn summary_adds_up_calls_and_puts() {
+        let quotes = [
+            quote("m2509-C-2900", 10, 500, "1.0"),
+            quote("m2509-C-3000", 20, 200, "0.5"),
+            quote("m2509-C-3100", 30, 300, "-"),
+            quote("m2509-P-2900", 15, 300, "0.25"),
+            quote("m2509-P-3000", 15, 200, "0.25"),
+            quote("m2509-P-3100", 0, 100, ""),
+            quote("m2601-C-3000", 5, 0, "0.1"),
+            quote("m2509", 1000, 9000, "99"),
+        ];
+        let summary = options_summary(&quotes);
+
+        assert_eq!((summary.call_volume, summary.put_volume), (65, 30));
+        assert_eq!(
+            (summary.call_open_interest, summary.put_open_interest),
+            (1000, 600)
+        );
+        assert!((summary.call_turnover - 16_000.0).abs() < 1e-6);
+        assert!((summary.put_turnover - 5_000.0).abs() < 1e-6);
+        assert!((summary.total_turnover() - 21_000.0).abs() < 1e-6);
+        assert!((summary.put_call_volume_ratio().unwrap() - 30.0 / 65.0).abs() < 1e-12);
+        assert!((summary.put_call_oi_ratio().unwrap() - 0.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn max_pain_is_the_cheapest_settlement_strike() {
+        // Holder payout at each settlement price:
+        //   2900: puts 200 * 100 + 100 * 200            =  40,000
+        //   3000: calls 500 * 100, puts 100 * 100       =  60,000
+        //   3100: calls 500 * 200 + 200 * 100           = 120,000
+        let quotes = [
+            quote("m2509-C-2900", 0, 500, ""),
+            quote("m2509-C-3000", 0, 200, ""),
+            quote("m2509-C-3100", 0, 300, ""),
+            quote("m2509-P-2900", 0, 300, ""),
+            quote("m2509-P-3000", 0, 200, ""),
+            quote("m2509-P-3100", 0, 100, ""),
+            quote("m2601-C-3000", 5, 0, ""),
+        ];
+        let summary = options_summary(&quotes);
+        assert_eq!(summary.max_pain.get("m2509"), Some(&2900.0));
+        // Series without open interest have no max pain.
+        assert_eq!(summary.max_pain.len(), 1);
+    }
+
+    #[test]
+    fn empty_input_has_no_ratios() {
+        let summary = options_summary(&[]);
+        assert_eq!(summary, OptionsSummary::default());
+        assert_eq!(summary.put_call_volume_ratio(), None);
+        assert_eq!(summary.put_call_oi_ratio(), None);
+        assert_eq!(summary.total_turnover(), 0.0);
+    }
+}
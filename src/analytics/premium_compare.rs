@@ -0,0 +1,94 @@
+//! Warehouse premiums grouped by warehouse group.
+//!
+//! [`premium_groups`] turns a variety's warehouse premium list into one
+//! entry per warehouse group with the range and average of its premiums,
+//! ranked from the cheapest to take delivery at to the most expensive.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::models::{parse_optional_f64, WarehousePremium};
+
+/// Premiums of one warehouse group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PremiumGroup {
+    /// Warehouse group abbreviation (empty for warehouses outside a group).
+    pub wh_group_abbr: String,
+    /// Lowest premium (yuan per ton; negative for a discount).
+    pub min_agio: f64,
+    /// Highest premium.
+    pub max_agio: f64,
+    /// Average premium over the group's rows.
+    pub avg_agio: f64,
+    /// Earliest valid date of the group's rows.
+    pub valid_from: String,
+    /// Latest valid date of the group's rows.
+    pub valid_to: String,
+    /// The group's rows, lowest premium first.
+    pub warehouses: Vec<WarehousePremium>,
+}
+
+impl PremiumGroup {
+    /// The warehouse row with the lowest premium.
+    pub fn cheapest(&self) -> Option<&WarehousePremium> {
+        self.warehouses.first()
+    }
+}
+
+/// Group warehouse premiums by warehouse group, cheapest group (lowest
+/// average premium) first.
+///
+/// Rows whose premium does not parse are left out. Groups with equal
+/// averages are ordered by abbreviation.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::analytics::premium_groups;
+/// use dceapi_rs::WarehousePremium;
+///
+/// let row = |group: &str, wh: &str, agio: &str| -> WarehousePremium {
+///     serde_json::from_value(serde_json::json!({
+///         "whGroupAbbr": group, "whName": wh, "avgAgio": agio, "validDate": "20250515",
+///     }))
+///     .unwrap()
+/// };
+/// let groups = premium_groups(&[row("A", "a1", "40"), row("A", "a2", "0"), row("B", "b1", "-20")]);
+/// assert_eq!(groups[0].wh_group_abbr, "B");
+/// assert_eq!(groups[1].avg_agio, 20.0);
+/// assert_eq!(groups[1].cheapest().unwrap().wh_name, "a2");
+/// ```
+pub fn premium_groups(premiums: &[WarehousePremium]) -> Vec<PremiumGroup> {
+    let mut by_group: BTreeMap<&str, Vec<(f64, &WarehousePremium)>> = BTreeMap::new();
+    for premium in premiums {
+        if let Some(agio) = parse_optional_f64(&premium.avg_agio) {
+            by_group
+                .entry(premium.wh_group_abbr.trim())
+                .or_default()
+                .push((agio, premium));
+        }
+    }
+
+    let mut groups: Vec<PremiumGroup> = by_group
+        .into_iter()
+        .map(|(abbr, mut rows)| {
+            rows.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let dates = rows
+                .iter()
+                .map(|(_, p)| p.valid_date.trim())
+                .filter(|d| !d.is_empty());
+            PremiumGroup {
+                wh_group_abbr: abbr.to_string(),
+                min_agio: rows[0].0,
+                max_agio: rows[rows.len() - 1].0,
+                avg_agio: rows.iter().map(|(agio, _)| agio).sum::<f64>() / rows.len() as f64,
+                valid_from: dates.clone().min().unwrap_or_default().to_string(),
+                valid_to: dates.max().unwrap_or_default().to_string(),
+                warehouses: rows.into_iter().map(|(_, p)| p.clone()).collect(),
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| a.avg_agio.total_cmp(&b.avg_agio));
+    groups
+}
@@ -0,0 +1,225 @@
+//! Price limits of the next trading session.
+//!
+//! A contract's limit-up and limit-down prices are its last settlement
+//! price plus and minus the price limit rate, rounded to the tick inside
+//! the band: the limit-up price down and the limit-down price up.
+//! [`next_price_limits`] applies this to a day's settlement parameters.
+
+use serde::Serialize;
+
+use crate::contract_id::ContractId;
+use crate::margin::MarginPhase;
+use crate::models::{parse_rate, rate_fraction, SettleParam, TradeParam, TradingParam};
+
+/// Price limits of one contract.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceLimit {
+    /// Contract ID.
+    pub contract_id: String,
+    /// Settlement price the limits are taken from.
+    pub settle_price: f64,
+    /// Price limit rate, as a fraction.
+    pub rate: f64,
+    /// Highest price allowed.
+    pub limit_up: f64,
+    /// Lowest price allowed.
+    pub limit_down: f64,
+}
+
+impl TradingParam {
+    /// Price limit rate of the variety's contracts in a margin phase, as a
+    /// fraction.
+    ///
+    /// [`MarginPhase::Normal`] uses the rate for existing contracts. Phases
+    /// without a published rate keep the rate of the phase before them.
+    /// Newly listed contracts use
+    /// [`price_limit_new_contract`](Self::price_limit_new_contract) instead.
+    pub fn price_limit_rate(&self, phase: MarginPhase) -> Option<f64> {
+        let steps = [
+            &self.price_limit_existing_contract,
+            &self.price_limit_n,
+            &self.price_limit_n1,
+            &self.price_limit_n2,
+            &self.price_limit_delivery_month,
+        ];
+        let last = match phase {
+            MarginPhase::Normal => 0,
+            MarginPhase::N => 1,
+            MarginPhase::N1 => 2,
+            MarginPhase::N2 => 3,
+            MarginPhase::DeliveryMonth => 4,
+        };
+        steps[..=last].iter().rev().find_map(|raw| parse_rate(raw))
+    }
+}
+
+/// Limit-up and limit-down prices for a settlement price, price limit rate
+/// (as a fraction) and tick size.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::analytics::limit_prices;
+///
+/// // 3,017 ± 4% is 3,137.68 / 2,896.32; rounded into the band.
+/// assert_eq!(limit_prices(3017.0, 0.04, 1.0), (3137.0, 2897.0));
+/// ```
+pub fn limit_prices(settle_price: f64, rate: f64, tick: f64) -> (f64, f64) {
+    let up = settle_price * (1.0 + rate);
+    let down = settle_price * (1.0 - rate);
+    if !(tick.is_finite() && tick > 0.0) {
+        return (up, down);
+    }
+    // The epsilon keeps exact multiples of the tick from being pushed a
+    // tick inwards by floating-point error.
+    let up_ticks = (up / tick + 1e-9).floor();
+    let down_ticks = (down / tick - 1e-9).ceil();
+    (round_price(up_ticks * tick), round_price(down_ticks * tick))
+}
+
+/// Next session's price limits of the contracts in a day's settlement
+/// parameters.
+///
+/// Each contract's rate is the `rise_limit_rate` of its trade parameters;
+/// contracts without one, options, and contracts of varieties whose tick
+/// size is not known are left out.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::analytics::next_price_limits;
+/// use dceapi_rs::{SettleParam, TradeParam};
+///
+/// let settle: SettleParam = serde_json::from_value(serde_json::json!({
+///     "contractId": "m2509", "clearPrice": "3,017",
+/// }))
+/// .unwrap();
+/// let param: TradeParam = serde_json::from_value(serde_json::json!({
+///     "contractId": "m2509", "riseLimitRate": 0.04,
+/// }))
+/// .unwrap();
+///
+/// let limits = next_price_limits(&[settle], &[param]);
+/// assert_eq!(limits[0].limit_up, 3137.0);
+/// assert_eq!(limits[0].limit_down, 2897.0);
+/// ```
+pub fn next_price_limits(
+    settle_params: &[SettleParam],
+    trade_params: &[TradeParam],
+) -> Vec<PriceLimit> {
+    settle_params
+        .iter()
+        .filter_map(|settle| {
+            let contract_id = settle.contract_id.trim();
+            let contract = ContractId::parse(contract_id)
+                .ok()
+                .filter(|c| !c.is_option())?;
+            let tick = contract.variety_code().tick_size()?;
+            let settle_price = settle.clear_price_f64()?;
+            let rate = trade_params
+                .iter()
+                .find(|p| p.contract_id.trim().eq_ignore_ascii_case(contract_id))
+                .and_then(|p| p.rise_limit_rate)
+                .map(rate_fraction)?;
+            let (limit_up, limit_down) = limit_prices(settle_price, rate, tick);
+            Some(PriceLimit {
+                contract_id: contract_id.to_string(),
+                settle_price,
+                rate,
+                limit_up,
+                limit_down,
+            })
+        })
+        .collect()
+}
+
+/// Round away floating-point noise left by multiplying with the tick.
+fn round_price(price: f64) -> f64 {
+    (price * 1e8).round() / 1e8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_round_into_the_band() {
+        // 2,951.5 ± 4% is 3,069.56 / 2,833.44.
+        assert_eq!(limit_prices(2951.5, 0.04, 0.5), (3069.5, 2833.5));
+        assert_eq!(limit_prices(2951.5, 0.04, 2.0), (3068.0, 2834.0));
+        // 812.5 ± 7% is 869.375 / 755.625.
+        assert_eq!(limit_prices(812.5, 0.07, 0.5), (869.0, 756.0));
+    }
+
+    #[test]
+    fn exact_multiples_of_the_tick_are_kept() {
+        assert_eq!(limit_prices(3000.0, 0.05, 1.0), (3150.0, 2850.0));
+        assert_eq!(limit_prices(3000.0, 0.07, 1.0), (3210.0, 2790.0));
+        assert_eq!(limit_prices(8.1, 0.1, 0.01), (8.91, 7.29));
+    }
+
+    #[test]
+    fn invalid_ticks_leave_limits_unrounded() {
+        for tick in [0.0, -1.0, f64::NAN] {
+            let (up, down) = limit_prices(3017.0, 0.04, tick);
+            assert!((up - 3137.68).abs() < 1e-9 && (down - 2896.32).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn unpublished_phase_rates_keep_the_rate_before() {
+        let param: TradingParam = serde_json::from_value(serde_json::json!({
+            "varietyId": "m",
+            "priceLimitExistingContract": "4%",
+            "priceLimitN": "-",
+            "priceLimitN1": "6",
+            "priceLimitDeliveryMonth": "0.1",
+        }))
+        .unwrap();
+        assert_eq!(param.price_limit_rate(MarginPhase::Normal), Some(0.04));
+        assert_eq!(param.price_limit_rate(MarginPhase::N), Some(0.04));
+        assert_eq!(param.price_limit_rate(MarginPhase::N1), Some(0.06));
+        assert_eq!(param.price_limit_rate(MarginPhase::N2), Some(0.06));
+        assert_eq!(
+            param.price_limit_rate(MarginPhase::DeliveryMonth),
+            Some(0.1)
+        );
+
+        let empty: TradingParam =
+            serde_json::from_value(serde_json::json!({ "varietyId": "m" })).unwrap();
+        assert_eq!(empty.price_limit_rate(MarginPhase::N2), None);
+    }
+
+    #[test]
+    fn next_limits_skip_options_and_contracts_without_rates() {
+        let settle = |contract_id: &str, price: &str| -> SettleParam {
+            serde_json::from_value(serde_json::json!({
+                "contractId": contract_id, "clearPrice": price,
+            }))
+            .unwrap()
+        };
+        let param = |contract_id: &str, rate: f64| -> TradeParam {
+            serde_json::from_value(serde_json::json!({
+                "contractId": contract_id, "riseLimitRate": rate,
+            }))
+            .unwrap()
+        };
+        let limits = next_price_limits(
+            &[
+                settle("m2509", "3,017"),
+                settle("m2601", "3,100"),
+                settle("m2509-C-3000", "120"),
+                settle("c2509", "-"),
+            ],
+            &[
+                param("M2509", 4.0),
+                param("m2509-C-3000", 0.04),
+                param("c2509", 0.05),
+            ],
+        );
+        assert_eq!(limits.len(), 1);
+        assert_eq!(limits[0].contract_id, "m2509");
+        assert_eq!(limits[0].rate, 0.04);
+        assert_eq!((limits[0].limit_up, limits[0].limit_down), (3137.0, 2897.0));
+    }
+}
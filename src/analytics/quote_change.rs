@@ -0,0 +1,429 @@
+//! Change detection between successive quote snapshots.
+//!
+//! Polling the quote endpoints returns full snapshots. [`QuoteChangeTracker`]
+//! remembers the previous snapshot per contract and reports only the fields
+//! that moved by more than the configured [`ChangeTolerance`], so alerting
+//! code is not flooded with one-tick price flickers or small position changes.
+//! [`QuoteDiff`] compares any two snapshots in full, including contracts that
+//! were added or removed.
+
+use std::collections::HashMap;
+
+use crate::contract_id::ContractId;
+use crate::models::Quote;
+
+/// Quote field reported in a [`QuoteChange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteField {
+    /// Latest price (`lastPrice`).
+    LastPrice,
+    /// Close price.
+    Close,
+    /// Settlement price (`clearPrice`).
+    ClearPrice,
+    /// Volume.
+    Volume,
+    /// Open interest.
+    OpenInterest,
+}
+
+impl QuoteField {
+    /// Whether the field is a price (compared in ticks) rather than a quantity (in lots).
+    pub fn is_price(&self) -> bool {
+        matches!(
+            self,
+            QuoteField::LastPrice | QuoteField::Close | QuoteField::ClearPrice
+        )
+    }
+}
+
+/// A single field change for a contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteChange {
+    /// Contract ID.
+    pub contract: String,
+    /// Field that changed.
+    pub field: QuoteField,
+    /// Value in the previous snapshot.
+    pub old: f64,
+    /// Value in the current snapshot.
+    pub new: f64,
+}
+
+impl QuoteChange {
+    /// Signed difference (`new - old`).
+    pub fn delta(&self) -> f64 {
+        self.new - self.old
+    }
+}
+
+/// Thresholds below which changes are ignored.
+///
+/// Price changes are measured in ticks of the contract's variety; quantities
+/// in lots. A threshold of zero reports every change. Tick sizes default to
+/// the exchange's ([`VarietyCode::tick_size`](crate::VarietyCode::tick_size)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeTolerance {
+    /// Minimum price move, in ticks, that is reported.
+    pub price_ticks: u32,
+    /// Minimum open interest change, in lots, that is reported.
+    pub open_interest_lots: i64,
+    /// Minimum volume change, in lots, that is reported.
+    pub volume_lots: i64,
+    /// Tick size used for contracts whose variety has no entry in
+    /// `tick_sizes` and no known exchange tick size.
+    pub default_tick: f64,
+    /// Tick size per variety ID (e.g. "m" -> 1.0), overriding the exchange
+    /// tick size.
+    pub tick_sizes: HashMap<String, f64>,
+}
+
+impl Default for ChangeTolerance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChangeTolerance {
+    /// Create a tolerance that reports every change.
+    pub fn new() -> Self {
+        ChangeTolerance {
+            price_ticks: 0,
+            open_interest_lots: 0,
+            volume_lots: 0,
+            default_tick: 1.0,
+            tick_sizes: HashMap::new(),
+        }
+    }
+
+    /// Ignore price moves smaller than the given number of ticks.
+    pub fn with_price_ticks(mut self, ticks: u32) -> Self {
+        self.price_ticks = ticks;
+        self
+    }
+
+    /// Ignore open interest changes smaller than the given number of lots.
+    pub fn with_open_interest_lots(mut self, lots: i64) -> Self {
+        self.open_interest_lots = lots;
+        self
+    }
+
+    /// Ignore volume changes smaller than the given number of lots.
+    pub fn with_volume_lots(mut self, lots: i64) -> Self {
+        self.volume_lots = lots;
+        self
+    }
+
+    /// Set the tick size for a variety (e.g. from `ContractInfo::tick`),
+    /// overriding the exchange tick size.
+    pub fn with_tick_size(mut self, variety_id: impl Into<String>, tick: f64) -> Self {
+        self.tick_sizes
+            .insert(variety_id.into().to_lowercase(), tick);
+        self
+    }
+
+    /// Get the tick size for a contract: the variety's entry in
+    /// `tick_sizes`, else the exchange tick size of the variety, else
+    /// `default_tick`.
+    pub fn tick_for(&self, contract: &str) -> f64 {
+        let Ok(contract) = ContractId::parse(contract) else {
+            return self.default_tick;
+        };
+        self.tick_sizes
+            .get(&contract.variety)
+            .copied()
+            .or_else(|| contract.variety_code().tick_size())
+            .unwrap_or(self.default_tick)
+    }
+
+    /// Check whether a change in the given field exceeds the tolerance.
+    pub fn exceeds(&self, contract: &str, field: QuoteField, old: f64, new: f64) -> bool {
+        let delta = (new - old).abs();
+        if delta == 0.0 {
+            return false;
+        }
+        let threshold = match field {
+            QuoteField::Volume => self.volume_lots as f64,
+            QuoteField::OpenInterest => self.open_interest_lots as f64,
+            _ => self.price_ticks as f64 * self.tick_for(contract),
+        };
+        // Tolerate float noise when comparing a move of exactly N ticks
+        delta + f64::EPSILON * delta.max(1.0) >= threshold
+    }
+}
+
+/// Compare two snapshots and return the changes that exceed the tolerance.
+///
+/// Contracts are matched by contract ID (or delivery month for night quotes).
+/// Summary rows and contracts present in only one snapshot are skipped, as
+/// are price fields that do not parse as numbers.
+pub fn quote_changes(
+    old: &[Quote],
+    new: &[Quote],
+    tolerance: &ChangeTolerance,
+) -> Vec<QuoteChange> {
+    let previous: HashMap<String, &Quote> = old
+        .iter()
+        .filter_map(|q| contract_key(q).map(|key| (key, q)))
+        .collect();
+
+    let mut changes = Vec::new();
+    for quote in new {
+        let Some(contract) = contract_key(quote) else {
+            continue;
+        };
+        if let Some(before) = previous.get(&contract) {
+            diff_quote(&contract, before, quote, tolerance, &mut changes);
+        }
+    }
+    changes
+}
+
+/// Full comparison of two snapshots, for change reports.
+///
+/// Unlike [`quote_changes`], also lists contracts that appeared in or
+/// disappeared from the newer snapshot. The snapshots may be from the same
+/// trade date (successive polls) or from different dates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteDiff {
+    /// Field changes of contracts present in both snapshots, in the order
+    /// of the newer snapshot.
+    pub changes: Vec<QuoteChange>,
+    /// Rows of contracts only in the newer snapshot.
+    pub added: Vec<Quote>,
+    /// Rows of contracts only in the older snapshot.
+    pub removed: Vec<Quote>,
+}
+
+impl QuoteDiff {
+    /// Compare two snapshots, reporting every change.
+    pub fn new(old: &[Quote], new: &[Quote]) -> QuoteDiff {
+        QuoteDiff::with_tolerance(old, new, &ChangeTolerance::new())
+    }
+
+    /// Compare two snapshots, reporting changes that exceed the tolerance.
+    ///
+    /// Summary rows without a contract ID or delivery month are skipped.
+    pub fn with_tolerance(old: &[Quote], new: &[Quote], tolerance: &ChangeTolerance) -> QuoteDiff {
+        let previous: HashMap<String, &Quote> = old
+            .iter()
+            .filter_map(|q| contract_key(q).map(|key| (key, q)))
+            .collect();
+        let current: HashMap<String, &Quote> = new
+            .iter()
+            .filter_map(|q| contract_key(q).map(|key| (key, q)))
+            .collect();
+
+        let mut diff = QuoteDiff::default();
+        for quote in new {
+            let Some(contract) = contract_key(quote) else {
+                continue;
+            };
+            match previous.get(&contract) {
+                Some(before) => diff_quote(&contract, before, quote, tolerance, &mut diff.changes),
+                None => diff.added.push(quote.clone()),
+            }
+        }
+        diff.removed = old
+            .iter()
+            .filter(|q| contract_key(q).is_some_and(|key| !current.contains_key(&key)))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Contracts with at least one field change, in first-change order.
+    pub fn changed_contracts(&self) -> Vec<&str> {
+        let mut contracts: Vec<&str> = Vec::new();
+        for change in &self.changes {
+            if !contracts.contains(&change.contract.as_str()) {
+                contracts.push(&change.contract);
+            }
+        }
+        contracts
+    }
+
+    /// Field changes of one contract.
+    pub fn changes_for<'a>(&'a self, contract: &'a str) -> impl Iterator<Item = &'a QuoteChange> {
+        self.changes.iter().filter(move |c| c.contract == contract)
+    }
+}
+
+/// Stateful tracker that diffs each new snapshot against the last one seen.
+///
+/// The first snapshot only primes the tracker. Baselines advance only when a
+/// change is reported, so slow drifts below the tolerance accumulate until
+/// they cross it instead of being lost.
+/// [`MarketService::watch_night_quotes`](crate::MarketService::watch_night_quotes)
+/// feeds one with polled night quotes.
+#[derive(Debug, Clone, Default)]
+pub struct QuoteChangeTracker {
+    tolerance: ChangeTolerance,
+    baseline: HashMap<String, Quote>,
+}
+
+impl QuoteChangeTracker {
+    /// Create a tracker with the given tolerance.
+    pub fn new(tolerance: ChangeTolerance) -> Self {
+        QuoteChangeTracker {
+            tolerance,
+            baseline: HashMap::new(),
+        }
+    }
+
+    /// Get the tolerance.
+    pub fn tolerance(&self) -> &ChangeTolerance {
+        &self.tolerance
+    }
+
+    /// Feed a new snapshot and return the changes since the baseline.
+    pub fn update(&mut self, quotes: &[Quote]) -> Vec<QuoteChange> {
+        let mut changes = Vec::new();
+        for quote in quotes {
+            let Some(contract) = contract_key(quote) else {
+                continue;
+            };
+            match self.baseline.get_mut(&contract) {
+                Some(before) => {
+                    let start = changes.len();
+                    diff_quote(&contract, before, quote, &self.tolerance, &mut changes);
+                    for change in &changes[start..] {
+                        advance_baseline(before, quote, change.field);
+                    }
+                }
+                None => {
+                    self.baseline.insert(contract, quote.clone());
+                }
+            }
+        }
+        changes
+    }
+
+    /// Forget all baselines.
+    pub fn reset(&mut self) {
+        self.baseline.clear();
+    }
+}
+
+/// Key used to match a contract across snapshots.
+fn contract_key(quote: &Quote) -> Option<String> {
+    if !quote.contract_id.is_empty() {
+        Some(quote.contract_id.clone())
+    } else if !quote.deliv_month.is_empty() {
+        Some(quote.deliv_month.clone())
+    } else {
+        None
+    }
+}
+
+/// Append the changes between two rows of the same contract.
+fn diff_quote(
+    contract: &str,
+    old: &Quote,
+    new: &Quote,
+    tolerance: &ChangeTolerance,
+    changes: &mut Vec<QuoteChange>,
+) {
+    let fields = [
+        QuoteField::LastPrice,
+        QuoteField::Close,
+        QuoteField::ClearPrice,
+        QuoteField::Volume,
+        QuoteField::OpenInterest,
+    ];
+    for field in fields {
+        let (Some(before), Some(after)) = (field_value(old, field), field_value(new, field)) else {
+            continue;
+        };
+        if tolerance.exceeds(contract, field, before, after) {
+            changes.push(QuoteChange {
+                contract: contract.to_string(),
+                field,
+                old: before,
+                new: after,
+            });
+        }
+    }
+}
+
+/// Read a field as a number.
+fn field_value(quote: &Quote, field: QuoteField) -> Option<f64> {
+    match field {
+        QuoteField::LastPrice => quote.last_price,
+        QuoteField::Close => quote.close,
+        QuoteField::ClearPrice => quote.clear_price,
+        QuoteField::Volume => Some(quote.volume as f64),
+        QuoteField::OpenInterest => Some(quote.open_interest as f64),
+    }
+}
+
+/// Copy a reported field from the current row into the baseline.
+fn advance_baseline(baseline: &mut Quote, current: &Quote, field: QuoteField) {
+    match field {
+        QuoteField::LastPrice => baseline.last_price = current.last_price,
+        QuoteField::Close => baseline.close = current.close,
+        QuoteField::ClearPrice => baseline.clear_price = current.clear_price,
+        QuoteField::Volume => baseline.volume = current.volume,
+        QuoteField::OpenInterest => baseline.open_interest = current.open_interest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(contract_id: &str, last_price: f64, open_interest: i64) -> Quote {
+        serde_json::from_value(serde_json::json!({
+            "contractId": contract_id,
+            "lastPrice": last_price,
+            "openInterest": open_interest,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn tick_sizes_default_to_the_exchange() {
+        let tolerance = ChangeTolerance::new().with_tick_size("m", 2.0);
+        assert_eq!(tolerance.tick_for("m2509"), 2.0);
+        assert_eq!(tolerance.tick_for("i2509"), 0.5);
+        assert_eq!(tolerance.tick_for("zz2509"), 1.0);
+        assert_eq!(tolerance.tick_for("2509"), 1.0);
+    }
+
+    #[test]
+    fn tracker_reports_moves_beyond_the_tolerance() {
+        let tolerance = ChangeTolerance::new()
+            .with_price_ticks(2)
+            .with_open_interest_lots(100);
+        let mut tracker = QuoteChangeTracker::new(tolerance);
+
+        assert!(tracker.update(&[quote("i2509", 800.0, 1000)]).is_empty());
+        // Half a tick on i is below two ticks, and 50 lots below 100.
+        assert!(tracker.update(&[quote("i2509", 800.5, 1050)]).is_empty());
+        // The drift accumulates against the baseline until it crosses.
+        let changes = tracker.update(&[quote("i2509", 801.0, 1100)]);
+        assert_eq!(
+            changes,
+            vec![
+                QuoteChange {
+                    contract: "i2509".to_string(),
+                    field: QuoteField::LastPrice,
+                    old: 800.0,
+                    new: 801.0,
+                },
+                QuoteChange {
+                    contract: "i2509".to_string(),
+                    field: QuoteField::OpenInterest,
+                    old: 1000.0,
+                    new: 1100.0,
+                },
+            ]
+        );
+        assert!(tracker.update(&[quote("i2509", 801.0, 1100)]).is_empty());
+    }
+}
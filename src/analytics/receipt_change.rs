@@ -0,0 +1,171 @@
+//! Warehouse receipt changes between reports.
+//!
+//! Each daily report lists receipts per warehouse with the previous day's
+//! quantity alongside. [`receipt_changes`] compares two reports warehouse by
+//! warehouse on top of the record-level [`diff`], and [`receipt_alerts`]
+//! flags varieties whose total receipts moved by at least a threshold
+//! within one report.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::analytics::diff::{diff, Keyed};
+use crate::models::{is_subtotal_label, WarehouseReceipt, WarehouseReceiptDetail};
+
+/// Change in one warehouse's receipts between two reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptChange {
+    /// Variety name.
+    pub variety: String,
+    /// Warehouse abbreviation.
+    pub warehouse: String,
+    /// Receipts in the older report (0 if the warehouse was not listed).
+    pub old_qty: i64,
+    /// Receipts in the newer report (0 if the warehouse is no longer listed).
+    pub new_qty: i64,
+    /// Receipts registered on the newer report's trade date.
+    pub registered: i64,
+    /// Receipts cancelled on the newer report's trade date.
+    pub cancelled: i64,
+}
+
+impl ReceiptChange {
+    /// Net change (`new_qty - old_qty`).
+    pub fn delta(&self) -> i64 {
+        self.new_qty - self.old_qty
+    }
+}
+
+/// Compare two receipt reports warehouse by warehouse.
+///
+/// Returns the warehouses whose quantity changed or that registered or
+/// cancelled receipts, ordered by variety and warehouse. Subtotal rows are
+/// skipped. For reports of consecutive trade dates, `registered` and
+/// `cancelled` account for the whole change.
+pub fn receipt_changes(old: &WarehouseReceipt, new: &WarehouseReceipt) -> Vec<ReceiptChange> {
+    let old: Vec<WarehouseReceiptDetail> = details(old).cloned().collect();
+    let new: Vec<WarehouseReceiptDetail> = details(new).cloned().collect();
+    let previous = by_warehouse(&old);
+    let current = by_warehouse(&new);
+
+    let records = diff(&old, &new);
+    let mut keys: Vec<(String, String)> = records
+        .added
+        .iter()
+        .chain(&records.removed)
+        .map(Keyed::key)
+        .chain(records.changed.into_iter().map(|record| record.key))
+        .collect();
+    keys.sort();
+    keys.into_iter()
+        .filter_map(|key| {
+            let before = previous.get(&key);
+            let after = current.get(&key);
+            let (variety, warehouse) = key;
+            let change = ReceiptChange {
+                variety,
+                warehouse,
+                old_qty: before.map_or(0, |d| d.wbill_qty),
+                new_qty: after.map_or(0, |d| d.wbill_qty),
+                registered: after.map_or(0, |d| d.reg_wbill_qty),
+                cancelled: after.map_or(0, |d| d.logout_wbill_qty),
+            };
+            (change.delta() != 0 || change.registered != 0 || change.cancelled != 0)
+                .then_some(change)
+        })
+        .collect()
+}
+
+/// Day-over-day move of a variety's total receipts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReceiptAlert {
+    /// Trade date of the report (from the rows' generation date).
+    pub trade_date: String,
+    /// Variety name.
+    pub variety: String,
+    /// Total receipts on the previous trade date.
+    pub previous_total: i64,
+    /// Total receipts on the report's trade date.
+    pub total: i64,
+}
+
+impl ReceiptAlert {
+    /// Net change (`total - previous_total`).
+    pub fn delta(&self) -> i64 {
+        self.total - self.previous_total
+    }
+}
+
+/// Varieties whose total receipts moved by at least `threshold` lots since
+/// the previous trade date, using the previous quantities carried in the
+/// report. Unchanged varieties are never reported, even with a threshold
+/// of zero. Subtotal rows are skipped.
+pub fn receipt_alerts(receipt: &WarehouseReceipt, threshold: i64) -> Vec<ReceiptAlert> {
+    let mut totals: BTreeMap<&str, ReceiptAlert> = BTreeMap::new();
+    for detail in details(receipt) {
+        let alert = totals
+            .entry(detail.variety.as_str())
+            .or_insert_with(|| ReceiptAlert {
+                trade_date: detail.gen_date.clone(),
+                variety: detail.variety.clone(),
+                previous_total: 0,
+                total: 0,
+            });
+        alert.previous_total += detail.last_wbill_qty;
+        alert.total += detail.wbill_qty;
+    }
+    totals
+        .into_values()
+        .filter(|alert| alert.delta().abs() >= threshold.max(1))
+        .collect()
+}
+
+/// Warehouse rows, without subtotal rows.
+fn details(receipt: &WarehouseReceipt) -> impl Iterator<Item = &WarehouseReceiptDetail> {
+    receipt.entity_list.iter().filter(|d| {
+        !d.variety.is_empty()
+            && !d.wh_abbr.is_empty()
+            && !is_subtotal_label(&d.variety)
+            && !is_subtotal_label(&d.wh_abbr)
+    })
+}
+
+/// Warehouse rows keyed by variety and warehouse.
+fn by_warehouse(
+    details: &[WarehouseReceiptDetail],
+) -> BTreeMap<(String, String), &WarehouseReceiptDetail> {
+    details.iter().map(|d| (d.key(), d)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(rows: serde_json::Value) -> WarehouseReceipt {
+        serde_json::from_value(serde_json::json!({ "entityList": rows })).unwrap()
+    }
+
+    #[test]
+    fn compares_warehouses_and_skips_subtotals() {
+        let old = report(serde_json::json!([
+            { "variety": "豆一", "whAbbr": "A", "wbillQty": 100 },
+            { "variety": "豆一", "whAbbr": "B", "wbillQty": 50 },
+            { "variety": "小计", "whAbbr": "", "wbillQty": 150 },
+        ]));
+        let new = report(serde_json::json!([
+            { "variety": "豆一", "whAbbr": "A", "wbillQty": 100 },
+            { "variety": "豆一", "whAbbr": "C", "wbillQty": 30, "regWbillQty": 30 },
+            { "variety": "小计", "whAbbr": "", "wbillQty": 130 },
+        ]));
+
+        let changes = receipt_changes(&old, &new);
+        let summary: Vec<(&str, i64, i64)> = changes
+            .iter()
+            .map(|c| (c.warehouse.as_str(), c.old_qty, c.new_qty))
+            .collect();
+        assert_eq!(summary, vec![("B", 50, 0), ("C", 0, 30)]);
+        assert_eq!(changes[1].registered, 30);
+        assert!(receipt_changes(&new, &new).is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Rolling statistics over candle series.
+//!
+//! Every function returns one value per input element, so results line up
+//! with the candles they came from. Positions without enough history are
+//! `None`.
+
+use crate::candle::Candle;
+
+/// Trading days per year used to annualize daily volatility.
+pub const TRADING_DAYS_PER_YEAR: f64 = 244.0;
+
+/// Mean over a trailing window of `window` values.
+pub fn rolling_mean(values: &[f64], window: usize) -> Vec<Option<f64>> {
+    rolling(values, window, mean)
+}
+
+/// Sample standard deviation over a trailing window of `window` values.
+///
+/// Windows shorter than 2 give `None` everywhere.
+pub fn rolling_std(values: &[f64], window: usize) -> Vec<Option<f64>> {
+    rolling(values, window, std_dev)
+}
+
+/// Simple close-to-close returns; the first candle has none.
+pub fn returns(candles: &[Candle]) -> Vec<Option<f64>> {
+    pairwise(candles, |prev, close| close / prev - 1.0)
+}
+
+/// Logarithmic close-to-close returns; the first candle has none.
+pub fn log_returns(candles: &[Candle]) -> Vec<Option<f64>> {
+    pairwise(candles, |prev, close| (close / prev).ln())
+}
+
+/// Annualized historical volatility: the standard deviation of the last
+/// `window` log returns times the square root of `periods_per_year`
+/// ([`TRADING_DAYS_PER_YEAR`] for daily candles).
+pub fn historical_volatility(
+    candles: &[Candle],
+    window: usize,
+    periods_per_year: f64,
+) -> Vec<Option<f64>> {
+    let returns = log_returns(candles);
+    (0..returns.len())
+        .map(|i| {
+            let start = (i + 1).checked_sub(window)?;
+            let window: Option<Vec<f64>> = returns[start..=i].iter().copied().collect();
+            Some(std_dev(&window?)? * periods_per_year.sqrt())
+        })
+        .collect()
+}
+
+/// Z-score of each value against the trailing window of `window` values
+/// ending at it.
+///
+/// Flat windows (zero standard deviation) give `None`.
+pub fn rolling_zscore(values: &[f64], window: usize) -> Vec<Option<f64>> {
+    rolling(values, window, |w| {
+        let sd = std_dev(w).filter(|sd| *sd > 0.0)?;
+        Some((w[w.len() - 1] - mean(w)?) / sd)
+    })
+}
+
+/// Z-scores of open interest; see [`rolling_zscore`].
+pub fn open_interest_zscore(candles: &[Candle], window: usize) -> Vec<Option<f64>> {
+    let values: Vec<f64> = candles.iter().map(|c| c.open_interest as f64).collect();
+    rolling_zscore(&values, window)
+}
+
+/// Z-scores of volume; see [`rolling_zscore`].
+pub fn volume_zscore(candles: &[Candle], window: usize) -> Vec<Option<f64>> {
+    let values: Vec<f64> = candles.iter().map(|c| c.volume as f64).collect();
+    rolling_zscore(&values, window)
+}
+
+/// Apply `f` to each full trailing window.
+fn rolling(values: &[f64], window: usize, f: impl Fn(&[f64]) -> Option<f64>) -> Vec<Option<f64>> {
+    (0..values.len())
+        .map(|i| {
+            let start = (i + 1).checked_sub(window.max(1))?;
+            f(&values[start..=i])
+        })
+        .collect()
+}
+
+/// Apply `f(previous close, close)` to consecutive candles.
+fn pairwise(candles: &[Candle], f: impl Fn(f64, f64) -> f64) -> Vec<Option<f64>> {
+    let mut out = Vec::with_capacity(candles.len());
+    let mut prev: Option<f64> = None;
+    for candle in candles {
+        out.push(prev.filter(|p| *p > 0.0).map(|p| f(p, candle.close)));
+        prev = Some(candle.close);
+    }
+    out
+}
+
+/// Arithmetic mean, `None` if empty.
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Sample standard deviation, `None` for fewer than two values.
+fn std_dev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = mean(values)?;
+    let sum_sq: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+    Some((sum_sq / (values.len() - 1) as f64).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .map(|&close| Candle {
+                date: String::new(),
+                contract_id: "m2509".to_string(),
+                open: close,
+                high: close,
+                low: close,
+                close,
+                settle: None,
+                volume: 0,
+                open_interest: 0,
+                turnover: None,
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: &[Option<f64>], expected: &[Option<f64>]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            match (a, e) {
+                (Some(a), Some(e)) => assert!((a - e).abs() < 1e-9, "{actual:?}"),
+                _ => assert_eq!(a, e, "{actual:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn rolling_windows_line_up_with_their_input() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        assert_close(
+            &rolling_mean(&values, 2),
+            &[None, Some(1.5), Some(2.5), Some(3.5)],
+        );
+        assert_close(&rolling_mean(&values, 0), &values.map(Some));
+
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let std = rolling_std(&values, 8);
+        assert_close(&std[7..], &[Some((32.0_f64 / 7.0).sqrt())]);
+        assert!(std[..7].iter().all(Option::is_none));
+        assert!(rolling_std(&values, 1).iter().all(Option::is_none));
+
+        assert_close(
+            &rolling_zscore(&[1.0, 2.0, 3.0, 3.0, 3.0], 3),
+            &[None, None, Some(1.0), Some(1.0 / 3.0_f64.sqrt()), None],
+        );
+    }
+
+    #[test]
+    fn returns_and_volatility_use_closes() {
+        let series = candles(&[100.0, 110.0, 99.0]);
+        assert_close(&returns(&series), &[None, Some(0.1), Some(-0.1)]);
+        assert_close(
+            &log_returns(&series),
+            &[None, Some(1.1_f64.ln()), Some(0.9_f64.ln())],
+        );
+
+        let spread = 1.1_f64.ln() - 0.9_f64.ln();
+        let expected = spread / 2.0_f64.sqrt() * TRADING_DAYS_PER_YEAR.sqrt();
+        assert_close(
+            &historical_volatility(&series, 2, TRADING_DAYS_PER_YEAR),
+            &[None, None, Some(expected)],
+        );
+
+        // A zero close has no return after it.
+        assert_close(&returns(&candles(&[0.0, 5.0])), &[None, None]);
+    }
+
+    #[test]
+    fn empty_and_short_inputs_give_no_values() {
+        assert!(rolling_mean(&[], 3).is_empty());
+        assert!(rolling_zscore(&[], 3).is_empty());
+        assert!(returns(&[]).is_empty());
+        assert!(historical_volatility(&[], 20, TRADING_DAYS_PER_YEAR).is_empty());
+        assert_close(&rolling_mean(&[1.0, 2.0], 3), &[None, None]);
+        assert_close(&volume_zscore(&candles(&[1.0; 3]), 3), &[None, None, None]);
+        assert_close(&open_interest_zscore(&candles(&[1.0]), 1), &[None]);
+    }
+}
@@ -0,0 +1,224 @@
+//! Year-to-date and rolling aggregates over variety monthly statistics.
+//!
+//! `get_variety_month_year_stat` returns one snapshot per trade month. These
+//! helpers combine several snapshots (keyed by `YYYYMM`) into per-variety
+//! period totals and cross-check them against the exchange's own `this_year_*`
+//! figures.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::delivery_month::DeliveryMonth;
+use crate::models::{parse_optional_f64, VarietyMonthYearStat};
+
+/// Relative tolerance used when comparing summed turnover to the exchange's totals.
+///
+/// Turnover is published with rounding, so small relative differences are expected.
+pub const TURNOVER_TOLERANCE: f64 = 1e-3;
+
+/// A data-quality issue found while aggregating monthly statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatDiscrepancy {
+    /// No statistics were supplied for a month inside the period.
+    MissingMonth {
+        /// The missing month (YYYYMM).
+        month: String,
+    },
+    /// A turnover value could not be parsed as a number.
+    InvalidTurnover {
+        /// The month (YYYYMM) the value belongs to.
+        month: String,
+        /// The raw value returned by the API.
+        raw: String,
+    },
+    /// Summed monthly volume does not match the exchange's year-to-date volume.
+    VolumeMismatch {
+        /// Volume computed from the monthly figures.
+        computed: i64,
+        /// Volume reported in `this_year_volumn`.
+        reported: i64,
+    },
+    /// Summed monthly turnover does not match the exchange's year-to-date turnover.
+    TurnoverMismatch {
+        /// Turnover computed from the monthly figures.
+        computed: f64,
+        /// Turnover reported in `this_year_turnover`.
+        reported: f64,
+    },
+}
+
+/// Aggregated statistics for a variety over a span of months.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarietyPeriodStat {
+    /// Variety name.
+    pub variety: String,
+    /// First month of the period (YYYYMM).
+    pub start_month: String,
+    /// Last month of the period (YYYYMM).
+    pub end_month: String,
+    /// Months that contributed data, in ascending order.
+    pub months: Vec<String>,
+    /// Total volume over the period.
+    pub volume: i64,
+    /// Total turnover over the period, in the unit used by the API.
+    pub turnover: f64,
+    /// Average month-end open interest over the contributing months.
+    pub avg_open_interest: f64,
+    /// Data-quality issues detected while aggregating.
+    pub discrepancies: Vec<StatDiscrepancy>,
+}
+
+impl VarietyPeriodStat {
+    /// Check whether the aggregate passed all consistency checks.
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// Compute year-to-date aggregates per variety, ending at `month` (YYYYMM).
+///
+/// `stats` maps each trade month (YYYYMM) to the response of
+/// `get_variety_month_year_stat` for that month. The summed volume and turnover
+/// are compared to the `this_year_*` fields of the `month` snapshot; mismatches
+/// and missing months are reported in [`VarietyPeriodStat::discrepancies`].
+///
+/// Returns an empty list if `month` is not a valid YYYYMM string.
+pub fn ytd_summary(
+    stats: &BTreeMap<String, Vec<VarietyMonthYearStat>>,
+    month: &str,
+) -> Vec<VarietyPeriodStat> {
+    let Ok(end) = DeliveryMonth::parse(month) else {
+        return Vec::new();
+    };
+    let Ok(january) = DeliveryMonth::new(end.year(), 1) else {
+        return Vec::new();
+    };
+    let months: Vec<String> = january
+        .range_to(end)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let month = end.to_string();
+    let month = month.as_str();
+
+    let mut summaries = summarize(stats, &months);
+    for summary in &mut summaries {
+        let reported = stats
+            .get(month)
+            .and_then(|rows| rows.iter().find(|s| s.variety == summary.variety));
+        if let Some(reported) = reported {
+            check_against_reported(summary, reported, month);
+        }
+    }
+    summaries
+}
+
+/// Compute trailing twelve-month aggregates per variety, ending at `month` (YYYYMM).
+///
+/// `stats` has the same layout as for [`ytd_summary`]. The exchange does not
+/// publish rolling totals, so only missing months and unparseable values are
+/// reported as discrepancies.
+///
+/// Returns an empty list if `month` is not a valid YYYYMM string.
+pub fn rolling_12m_summary(
+    stats: &BTreeMap<String, Vec<VarietyMonthYearStat>>,
+    month: &str,
+) -> Vec<VarietyPeriodStat> {
+    let Ok(end) = DeliveryMonth::parse(month) else {
+        return Vec::new();
+    };
+    let start = end.add_months(-11).unwrap_or(end);
+    let months: Vec<String> = start
+        .range_to(end)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    summarize(stats, &months)
+}
+
+/// Sum the given months per variety.
+fn summarize(
+    stats: &BTreeMap<String, Vec<VarietyMonthYearStat>>,
+    months: &[String],
+) -> Vec<VarietyPeriodStat> {
+    let varieties: BTreeSet<&str> = months
+        .iter()
+        .filter_map(|m| stats.get(m))
+        .flatten()
+        .map(|s| s.variety.as_str())
+        .collect();
+
+    let mut summaries = Vec::with_capacity(varieties.len());
+    for variety in varieties {
+        let mut summary = VarietyPeriodStat {
+            variety: variety.to_string(),
+            start_month: months.first().cloned().unwrap_or_default(),
+            end_month: months.last().cloned().unwrap_or_default(),
+            months: Vec::new(),
+            volume: 0,
+            turnover: 0.0,
+            avg_open_interest: 0.0,
+            discrepancies: Vec::new(),
+        };
+        let mut open_interest = 0i64;
+
+        for month in months {
+            let row = stats
+                .get(month)
+                .and_then(|rows| rows.iter().find(|s| s.variety == variety));
+            let Some(row) = row else {
+                summary.discrepancies.push(StatDiscrepancy::MissingMonth {
+                    month: month.clone(),
+                });
+                continue;
+            };
+
+            summary.months.push(month.clone());
+            summary.volume += row.this_month_volumn;
+            open_interest += row.this_month_openi;
+            match parse_optional_f64(&row.this_month_turnover) {
+                Some(turnover) => summary.turnover += turnover,
+                None => summary.discrepancies.push(StatDiscrepancy::InvalidTurnover {
+                    month: month.clone(),
+                    raw: row.this_month_turnover.clone(),
+                }),
+            }
+        }
+
+        if !summary.months.is_empty() {
+            summary.avg_open_interest = open_interest as f64 / summary.months.len() as f64;
+        }
+        summaries.push(summary);
+    }
+    summaries
+}
+
+/// Compare a year-to-date aggregate to the exchange's own year totals.
+fn check_against_reported(
+    summary: &mut VarietyPeriodStat,
+    reported: &VarietyMonthYearStat,
+    month: &str,
+) {
+    if summary.volume != reported.this_year_volumn {
+        summary.discrepancies.push(StatDiscrepancy::VolumeMismatch {
+            computed: summary.volume,
+            reported: reported.this_year_volumn,
+        });
+    }
+
+    match parse_optional_f64(&reported.this_year_turnover) {
+        Some(year_turnover) => {
+            let scale = year_turnover.abs().max(1.0);
+            if (summary.turnover - year_turnover).abs() / scale > TURNOVER_TOLERANCE {
+                summary.discrepancies.push(StatDiscrepancy::TurnoverMismatch {
+                    computed: summary.turnover,
+                    reported: year_turnover,
+                });
+            }
+        }
+        None => summary.discrepancies.push(StatDiscrepancy::InvalidTurnover {
+            month: month.to_string(),
+            raw: reported.this_year_turnover.clone(),
+        }),
+    }
+}
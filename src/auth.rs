@@ -0,0 +1,328 @@
+//! Pluggable authentication schemes.
+//!
+//! The DCE API authenticates with an `apikey` header plus a bearer token
+//! obtained from the auth endpoint ([`ApiKeyAuth`]). Deployments that front
+//! the API with an internal gateway can add client certificates
+//! ([`MtlsAuth`]) or signed headers (`HmacHeaderAuth`, feature `hmac-auth`)
+//! by implementing [`AuthScheme`].
+
+use std::fmt::Debug;
+
+use reqwest::{ClientBuilder, Method, RequestBuilder};
+
+use crate::error::{Error, Result};
+
+/// Details of an outgoing request made available to an [`AuthScheme`].
+#[derive(Debug, Clone, Copy)]
+pub struct AuthContext<'a> {
+    /// API key from the configuration.
+    pub api_key: &'a str,
+    /// Bearer token, or `None` for the token request itself.
+    pub token: Option<&'a str>,
+    /// HTTP method.
+    pub method: &'a Method,
+    /// Full request URL.
+    pub url: &'a str,
+    /// Serialized request body (empty for requests without a body).
+    pub body: &'a [u8],
+}
+
+/// Authentication scheme applied to every request.
+///
+/// Implementations are shared between the token manager and all services, so
+/// they must be thread-safe.
+pub trait AuthScheme: Debug + Send + Sync {
+    /// Configure the underlying HTTP client (e.g. install client certificates).
+    ///
+    /// Called once when the [`Client`](crate::Client) is created.
+    fn configure_client(&self, builder: ClientBuilder) -> Result<ClientBuilder> {
+        Ok(builder)
+    }
+
+    /// Add authentication headers to an outgoing request.
+    fn apply(&self, request: RequestBuilder, ctx: &AuthContext<'_>) -> Result<RequestBuilder>;
+}
+
+/// Default DCE authentication: `apikey` header plus bearer token.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiKeyAuth;
+
+impl AuthScheme for ApiKeyAuth {
+    fn apply(&self, request: RequestBuilder, ctx: &AuthContext<'_>) -> Result<RequestBuilder> {
+        let mut request = request.header("apikey", ctx.api_key);
+        if let Some(token) = ctx.token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        Ok(request)
+    }
+}
+
+/// Mutual TLS: presents a client certificate in addition to [`ApiKeyAuth`].
+#[derive(Debug, Clone)]
+pub struct MtlsAuth {
+    identity_pem: Vec<u8>,
+    root_ca_pem: Option<Vec<u8>>,
+}
+
+impl MtlsAuth {
+    /// Create from a PEM bundle containing the client certificate chain and private key.
+    pub fn new(identity_pem: impl Into<Vec<u8>>) -> Self {
+        MtlsAuth {
+            identity_pem: identity_pem.into(),
+            root_ca_pem: None,
+        }
+    }
+
+    /// Trust an additional root CA (PEM), e.g. the gateway's private CA.
+    pub fn with_root_ca(mut self, root_ca_pem: impl Into<Vec<u8>>) -> Self {
+        self.root_ca_pem = Some(root_ca_pem.into());
+        self
+    }
+}
+
+impl AuthScheme for MtlsAuth {
+    fn configure_client(&self, builder: ClientBuilder) -> Result<ClientBuilder> {
+        let identity = reqwest::Identity::from_pem(&self.identity_pem)
+            .map_err(|e| Error::config(format!("invalid client identity: {}", e)))?;
+        let mut builder = builder.identity(identity);
+
+        if let Some(root_ca_pem) = &self.root_ca_pem {
+            let cert = reqwest::Certificate::from_pem(root_ca_pem)
+                .map_err(|e| Error::config(format!("invalid root certificate: {}", e)))?;
+            builder = builder.add_root_certificate(cert);
+        }
+        Ok(builder)
+    }
+
+    fn apply(&self, request: RequestBuilder, ctx: &AuthContext<'_>) -> Result<RequestBuilder> {
+        ApiKeyAuth.apply(request, ctx)
+    }
+}
+
+#[cfg(feature = "hmac-auth")]
+pub use hmac_auth::HmacHeaderAuth;
+
+#[cfg(feature = "hmac-auth")]
+mod hmac_auth {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use hmac::{Hmac, Mac};
+    use reqwest::RequestBuilder;
+    use sha2::Sha256;
+
+    use super::{ApiKeyAuth, AuthContext, AuthScheme};
+    use crate::error::{Error, Result};
+
+    /// Default header carrying the gateway key ID.
+    const DEFAULT_KEY_ID_HEADER: &str = "X-Auth-Key";
+
+    /// Default header carrying the Unix timestamp (seconds).
+    const DEFAULT_TIMESTAMP_HEADER: &str = "X-Auth-Timestamp";
+
+    /// Default header carrying the hex-encoded signature.
+    const DEFAULT_SIGNATURE_HEADER: &str = "X-Auth-Signature";
+
+    /// HMAC-SHA256 signed headers in addition to [`ApiKeyAuth`].
+    ///
+    /// The signature covers `METHOD\nURL\nTIMESTAMP\nBODY` and is sent
+    /// hex-encoded together with the key ID and timestamp.
+    #[derive(Clone)]
+    pub struct HmacHeaderAuth {
+        key_id: String,
+        secret: Vec<u8>,
+        key_id_header: String,
+        timestamp_header: String,
+        signature_header: String,
+    }
+
+    impl std::fmt::Debug for HmacHeaderAuth {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("HmacHeaderAuth")
+                .field("key_id", &self.key_id)
+                .field("secret", &"***")
+                .finish()
+        }
+    }
+
+    impl HmacHeaderAuth {
+        /// Create a signer with the default header names.
+        pub fn new(key_id: impl Into<String>, secret: impl Into<Vec<u8>>) -> Self {
+            HmacHeaderAuth {
+                key_id: key_id.into(),
+                secret: secret.into(),
+                key_id_header: DEFAULT_KEY_ID_HEADER.to_string(),
+                timestamp_header: DEFAULT_TIMESTAMP_HEADER.to_string(),
+                signature_header: DEFAULT_SIGNATURE_HEADER.to_string(),
+            }
+        }
+
+        /// Override the header names used by the gateway.
+        pub fn with_headers(
+            mut self,
+            key_id: impl Into<String>,
+            timestamp: impl Into<String>,
+            signature: impl Into<String>,
+        ) -> Self {
+            self.key_id_header = key_id.into();
+            self.timestamp_header = timestamp.into();
+            self.signature_header = signature.into();
+            self
+        }
+
+        /// Compute the hex-encoded signature for a request.
+        pub fn sign(&self, ctx: &AuthContext<'_>, timestamp: u64) -> Result<String> {
+            let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+                .map_err(|e| Error::auth(format!("invalid HMAC key: {}", e)))?;
+            mac.update(ctx.method.as_str().as_bytes());
+            mac.update(b"\n");
+            mac.update(ctx.url.as_bytes());
+            mac.update(b"\n");
+            mac.update(timestamp.to_string().as_bytes());
+            mac.update(b"\n");
+            mac.update(ctx.body);
+
+            let digest = mac.finalize().into_bytes();
+            Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+        }
+    }
+
+    impl HmacHeaderAuth {
+        /// Add the headers signed with a given timestamp.
+        pub(super) fn apply_at(
+            &self,
+            request: RequestBuilder,
+            ctx: &AuthContext<'_>,
+            timestamp: u64,
+        ) -> Result<RequestBuilder> {
+            let signature = self.sign(ctx, timestamp)?;
+
+            Ok(ApiKeyAuth
+                .apply(request, ctx)?
+                .header(self.key_id_header.as_str(), self.key_id.as_str())
+                .header(self.timestamp_header.as_str(), timestamp.to_string())
+                .header(self.signature_header.as_str(), signature))
+        }
+    }
+
+    impl AuthScheme for HmacHeaderAuth {
+        fn apply(&self, request: RequestBuilder, ctx: &AuthContext<'_>) -> Result<RequestBuilder> {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            self.apply_at(request, ctx, timestamp)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "http://www.dce.com.cn/dceapi/forward/publicweb/tradepara/dayTradPara";
+    const BODY: &[u8] = br#"{"varietyId":"m"}"#;
+
+    fn context<'a>(token: Option<&'a str>, method: &'a Method, body: &'a [u8]) -> AuthContext<'a> {
+        AuthContext {
+            api_key: "key",
+            token,
+            method,
+            url: URL,
+            body,
+        }
+    }
+
+    /// Headers of a request after `apply`.
+    fn headers(
+        apply: impl FnOnce(RequestBuilder) -> Result<RequestBuilder>,
+    ) -> Vec<(String, String)> {
+        let request = apply(reqwest::Client::new().post(URL))
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut headers: Vec<_> = request
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_str().unwrap().to_string()))
+            .collect();
+        headers.sort();
+        headers
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn api_key_sends_the_key_and_bearer_token() {
+        let method = Method::POST;
+        let ctx = context(Some("tok"), &method, BODY);
+        assert_eq!(
+            headers(|r| ApiKeyAuth.apply(r, &ctx)),
+            pairs(&[("apikey", "key"), ("authorization", "Bearer tok")])
+        );
+        let ctx = context(None, &method, BODY);
+        assert_eq!(
+            headers(|r| ApiKeyAuth.apply(r, &ctx)),
+            pairs(&[("apikey", "key")])
+        );
+    }
+
+    #[test]
+    fn mtls_sends_the_api_key_headers_and_rejects_bad_pem() {
+        let method = Method::POST;
+        let ctx = context(Some("tok"), &method, BODY);
+        let mtls = MtlsAuth::new("not a pem");
+        assert_eq!(
+            headers(|r| mtls.apply(r, &ctx)),
+            pairs(&[("apikey", "key"), ("authorization", "Bearer tok")])
+        );
+        let err = mtls
+            .configure_client(reqwest::Client::builder())
+            .unwrap_err();
+        assert!(matches!(err, Error::Config { .. }), "{err}");
+    }
+
+    #[cfg(feature = "hmac-auth")]
+    #[test]
+    fn hmac_signs_method_url_timestamp_and_body() {
+        let auth = HmacHeaderAuth::new("gw-1", "gateway-secret");
+        let post = Method::POST;
+        let ctx = context(Some("tok"), &post, BODY);
+        let signature = "03c609f5851a7e8286af5f904b35654bf67220fa4f8ffb76c7052ef0936eebcc";
+        assert_eq!(auth.sign(&ctx, 1_700_000_000).unwrap(), signature);
+        assert_eq!(
+            headers(|r| auth.apply_at(r, &ctx, 1_700_000_000)),
+            pairs(&[
+                ("apikey", "key"),
+                ("authorization", "Bearer tok"),
+                ("x-auth-key", "gw-1"),
+                ("x-auth-signature", signature),
+                ("x-auth-timestamp", "1700000000"),
+            ])
+        );
+
+        // Requests without a body sign an empty last line.
+        let get = Method::GET;
+        let ctx = AuthContext {
+            url: "http://gw.example/auth",
+            ..context(None, &get, b"")
+        };
+        let auth = auth.with_headers("X-Key", "X-Time", "X-Sig");
+        assert_eq!(
+            headers(|r| auth.apply_at(r, &ctx, 1_700_000_000)),
+            pairs(&[
+                ("apikey", "key"),
+                ("x-key", "gw-1"),
+                (
+                    "x-sig",
+                    "f6e78992155654f044f684eeb359fb951478e09b34923d8ad21bb26a34f31cf7"
+                ),
+                ("x-time", "1700000000"),
+            ])
+        );
+    }
+}
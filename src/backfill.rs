@@ -0,0 +1,399 @@
+//! Resumable bulk history downloads.
+//!
+//! A [`Backfill`] downloads a dataset one trading day at a time over a date
+//! range and records the last date completed per dataset in a checkpoint
+//! file. A run that fails or is interrupted resumes after that date the next
+//! time, so long historical pulls never start over. An idempotency key
+//! scopes the checkpoint to one job and gives every stored date a
+//! [`BackfillPartition`] key, so sinks can skip dates they already hold.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::download::{fetch_each, DownloadOptions};
+use crate::error::{Error, Result};
+
+/// Progress of a backfill run, reported after every completed date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillProgress {
+    /// Dataset name.
+    pub dataset: String,
+    /// Date just completed (YYYYMMDD).
+    pub date: String,
+    /// Dates completed in this run, including `date`.
+    pub done: usize,
+    /// Dates this run had left to fetch when it started.
+    pub total: usize,
+}
+
+/// One date of a dataset, as handed to the `store` callback of
+/// [`Backfill::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillPartition {
+    /// Dataset name.
+    pub dataset: String,
+    /// Trade date (YYYYMMDD).
+    pub date: String,
+    /// Key identifying the date within the job: the job's idempotency key
+    /// (if any), dataset and date. Re-running the job gives the same key.
+    pub key: String,
+}
+
+/// Callback receiving backfill progress.
+type ProgressFn = Box<dyn FnMut(&BackfillProgress) + Send>;
+
+/// Downloads date ranges with a persistent checkpoint per dataset.
+///
+/// Requests are paced, retried and run concurrently according to the
+/// [`DownloadOptions`], and results are handed to the caller in date order.
+/// Only the trading days of the options' calendar are requested (see
+/// [`DownloadOptions::with_calendar`]). The checkpoint is written after each
+/// group of concurrent requests, so an interrupted run repeats at most one
+/// group; sinks that record each [`BackfillPartition::key`] (such as
+/// `SqliteStore::record_partition` with the `sqlite` feature) can skip
+/// those dates.
+///
+/// # Example
+///
+/// ```no_run
+/// use dceapi_rs::{Backfill, Client, QuotesRequest, TradeType};
+///
+/// # async fn run(client: Client) -> dceapi_rs::Result<()> {
+/// let market = client.market.clone();
+/// let mut backfill = Backfill::open("backfill.json")?
+///     .with_progress(|p| println!("{} {} ({}/{})", p.dataset, p.date, p.done, p.total));
+/// backfill
+///     .run(
+///         "m day quotes",
+///         "20200101",
+///         "20241231",
+///         move |date| {
+///             let market = market.clone();
+///             async move {
+///                 let req = QuotesRequest {
+///                     variety_id: None,
+///                     variety: Some("m".to_string()),
+///                     trade_date: date,
+///                     trade_type: TradeType::Futures,
+///                     lang: None,
+///                     statistics_type: None,
+///                 };
+///                 market.get_day_quotes(&req, None).await
+///             }
+///         },
+///         |partition, quotes| {
+///             println!("{}: {} rows", partition.date, quotes.len());
+///             Ok(())
+///         },
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Backfill {
+    path: PathBuf,
+    completed: BTreeMap<String, String>,
+    download: DownloadOptions,
+    idempotency_key: Option<String>,
+    progress: Option<ProgressFn>,
+}
+
+impl fmt::Debug for Backfill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Backfill")
+            .field("path", &self.path)
+            .field("completed", &self.completed)
+            .field("download", &self.download)
+            .field("idempotency_key", &self.idempotency_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Backfill {
+    /// Open a backfill with its checkpoint file. A missing file means no
+    /// dataset has been started.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Backfill> {
+        let path = path.into();
+        let completed = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| Error::parse(json.as_str(), e.to_string()))?,
+            Err(err) if err.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(Error::io(&path, err)),
+        };
+        Ok(Backfill {
+            path,
+            completed,
+            download: DownloadOptions::new(),
+            idempotency_key: None,
+            progress: None,
+        })
+    }
+
+    /// Set the concurrency, rate, retry and calendar settings.
+    pub fn with_download_options(mut self, download: DownloadOptions) -> Self {
+        self.download = download;
+        self
+    }
+
+    /// Identify the job, so that running it again skips the dates it has
+    /// already stored.
+    ///
+    /// Checkpoints are kept per key and dataset: a job re-run with the same
+    /// key resumes after its last stored date, while a job with another key
+    /// starts from the beginning of its range. The key is also part of each
+    /// [`BackfillPartition::key`] handed to `store`.
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Call `progress` after every completed date.
+    pub fn with_progress(
+        mut self,
+        progress: impl FnMut(&BackfillProgress) + Send + 'static,
+    ) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Last date completed for a dataset (YYYYMMDD) under the current
+    /// idempotency key.
+    pub fn last_completed(&self, dataset: &str) -> Option<&str> {
+        self.completed
+            .get(&self.checkpoint_name(dataset))
+            .map(String::as_str)
+    }
+
+    /// Forget a dataset's progress under the current idempotency key so the
+    /// next run starts from the beginning of its range.
+    pub fn reset(&mut self, dataset: &str) -> Result<()> {
+        self.completed.remove(&self.checkpoint_name(dataset));
+        self.save()
+    }
+
+    /// Name a dataset's checkpoint is kept under.
+    fn checkpoint_name(&self, dataset: &str) -> String {
+        match &self.idempotency_key {
+            Some(key) => format!("{}/{}", key, dataset),
+            None => dataset.to_string(),
+        }
+    }
+
+    /// Download a dataset for every trading day from `start_date` to
+    /// `end_date` (inclusive), skipping dates up to the dataset's checkpoint,
+    /// and return the number of dates fetched.
+    ///
+    /// Trading days come from the calendar in the download options, or are
+    /// every weekday without one. `fetch` requests one date; `store`
+    /// receives each result in date order with its [`BackfillPartition`] and
+    /// should persist it before returning, since the date counts as
+    /// completed once it returns. The
+    /// first error from `fetch` (after retries) or `store` ends the run;
+    /// dates of the failed group before the failing date are still stored,
+    /// and progress up to the last stored date is kept.
+    ///
+    /// # Arguments
+    /// * `dataset` - Name the checkpoint is kept under
+    /// * `start_date` - First date (YYYYMMDD format)
+    /// * `end_date` - Last date (YYYYMMDD format)
+    /// * `fetch` - Request for one date
+    /// * `store` - Receives each date's partition and result
+    pub async fn run<T, F, Fut, S>(
+        &mut self,
+        dataset: &str,
+        start_date: &str,
+        end_date: &str,
+        fetch: F,
+        mut store: S,
+    ) -> Result<usize>
+    where
+        T: Send + 'static,
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        S: FnMut(&BackfillPartition, T) -> Result<()>,
+    {
+        let checkpoint = self.checkpoint_name(dataset);
+        let mut dates = self.download.trading_days(start_date, end_date)?;
+        if let Some(last) = self.completed.get(&checkpoint) {
+            dates.retain(|date| date > last);
+        }
+        let total = dates.len();
+        let fetch = Arc::new(fetch);
+        let mut done = 0;
+        for group in dates.chunks(self.download.concurrency.max(1)) {
+            let fetch = Arc::clone(&fetch);
+            let mut batch =
+                fetch_each(group.to_vec(), &self.download, move |date| fetch(date)).await;
+            for date in group {
+                let stored = match batch.values.remove(date) {
+                    Some(value) => {
+                        let partition = BackfillPartition {
+                            dataset: dataset.to_string(),
+                            date: date.clone(),
+                            key: format!("{}/{}", checkpoint, date),
+                        };
+                        store(&partition, value)
+                    }
+                    None => Err(batch
+                        .errors
+                        .remove(date)
+                        .unwrap_or_else(|| Error::missing_data(format!("no result for {}", date)))),
+                };
+                if let Err(err) = stored {
+                    self.save()?;
+                    return Err(err);
+                }
+                done += 1;
+                self.completed.insert(checkpoint.clone(), date.clone());
+                if let Some(progress) = self.progress.as_mut() {
+                    progress(&BackfillProgress {
+                        dataset: dataset.to_string(),
+                        date: date.clone(),
+                        done,
+                        total,
+                    });
+                }
+            }
+            self.save()?;
+        }
+        Ok(done)
+    }
+
+    /// Write the checkpoint file, replacing it atomically.
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.completed)
+            .map_err(|e| Error::io(&self.path, e.into()))?;
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, json)
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .map_err(|e| Error::io(&self.path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn failed_group_keeps_earlier_dates() {
+        let path = std::env::temp_dir().join(format!("backfill-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut backfill = Backfill::open(&path)
+            .unwrap()
+            .with_download_options(DownloadOptions::new().with_concurrency(3));
+
+        let mut stored = Vec::new();
+        // 20250103 is a Friday; the group is Wednesday to Friday.
+        let result = backfill
+            .run(
+                "quotes",
+                "20250101",
+                "20250103",
+                |date| async move {
+                    if date == "20250102" {
+                        Err(Error::validation("trade_date", "no data"))
+                    } else {
+                        Ok(date)
+                    }
+                },
+                |partition, value: String| {
+                    stored.push((partition.date.clone(), value));
+                    Ok(())
+                },
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            stored,
+            vec![("20250101".to_string(), "20250101".to_string())]
+        );
+        assert_eq!(backfill.last_completed("quotes"), Some("20250101"));
+        let reopened = Backfill::open(&path).unwrap();
+        assert_eq!(reopened.last_completed("quotes"), Some("20250101"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn calendar_holidays_are_not_requested() {
+        let path = std::env::temp_dir().join(format!("backfill-cal-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut calendar = crate::Calendar::new();
+        calendar.add_holiday("20250101").unwrap();
+        let mut backfill = Backfill::open(&path)
+            .unwrap()
+            .with_download_options(DownloadOptions::new().with_calendar(calendar));
+
+        let mut stored = Vec::new();
+        let done = backfill
+            .run(
+                "quotes",
+                "20250101",
+                "20250103",
+                |date| async move { Ok(date) },
+                |partition, _: String| {
+                    stored.push(partition.date.clone());
+                    Ok(())
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(done, 2);
+        assert_eq!(stored, vec!["20250102", "20250103"]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_scope_checkpoints_and_partitions() {
+        let path = std::env::temp_dir().join(format!("backfill-key-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut keys = Vec::new();
+        let mut backfill = Backfill::open(&path).unwrap().with_idempotency_key("job-1");
+        backfill
+            .run(
+                "quotes",
+                "20250102",
+                "20250103",
+                |date| async move { Ok(date) },
+                |partition, _: String| {
+                    keys.push(partition.key.clone());
+                    Ok(())
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(keys, vec!["job-1/quotes/20250102", "job-1/quotes/20250103"]);
+
+        // The same job skips what it stored; another job starts over.
+        let rerun = |key: &'static str| {
+            let path = path.clone();
+            async move {
+                Backfill::open(&path)
+                    .unwrap()
+                    .with_idempotency_key(key)
+                    .run(
+                        "quotes",
+                        "20250102",
+                        "20250103",
+                        |date| async move { Ok(date) },
+                        |_, _: String| Ok(()),
+                    )
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(rerun("job-1").await, 0);
+        assert_eq!(rerun("job-2").await, 2);
+        assert_eq!(
+            Backfill::open(&path).unwrap().last_completed("quotes"),
+            None
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
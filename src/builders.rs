@@ -0,0 +1,576 @@
+//! Builders for request models.
+//!
+//! Every request model has a `builder()` constructor. Required fields must be
+//! set (and non-blank) and the request must pass [`Validate`] before
+//! [`build`](QuotesRequestBuilder::build) succeeds; trade type and language
+//! default to futures and Chinese. All problems are reported together through
+//! [`Error::ValidationErrors`](crate::Error::ValidationErrors).
+
+use crate::delivery_month::DeliveryMonth;
+use crate::error::{Error, Result, ValidationErrors};
+use crate::models::*;
+use crate::validation::Validate;
+
+/// Values that count as missing when left empty.
+trait Blank {
+    fn is_blank(&self) -> bool;
+}
+
+impl Blank for String {
+    fn is_blank(&self) -> bool {
+        self.trim().is_empty()
+    }
+}
+
+impl Blank for i32 {
+    fn is_blank(&self) -> bool {
+        false
+    }
+}
+
+impl Blank for ColumnId {
+    fn is_blank(&self) -> bool {
+        self.id().trim().is_empty()
+    }
+}
+
+impl Blank for StatContent {
+    fn is_blank(&self) -> bool {
+        false
+    }
+}
+
+/// Stand-in for a missing or unparsable field, so the rest of the request
+/// can still be validated in the same pass.
+trait Placeholder {
+    fn placeholder() -> Self;
+}
+
+impl Placeholder for String {
+    fn placeholder() -> Self {
+        String::new()
+    }
+}
+
+impl Placeholder for i32 {
+    fn placeholder() -> Self {
+        0
+    }
+}
+
+impl Placeholder for ColumnId {
+    fn placeholder() -> Self {
+        ColumnId::Custom(String::new())
+    }
+}
+
+impl Placeholder for StatContent {
+    fn placeholder() -> Self {
+        StatContent::default()
+    }
+}
+
+impl Placeholder for DeliveryMonth {
+    // The earliest month, so it never reverses a range on its own.
+    fn placeholder() -> Self {
+        DeliveryMonth::new(1, 1).expect("January of year 1 is a valid month")
+    }
+}
+
+/// Message of a parse error, without the field prefix of validation errors.
+fn parse_error_message(err: Error) -> String {
+    match err {
+        Error::Validation { message, .. } => message,
+        other => other.to_string(),
+    }
+}
+
+/// Define a builder for a request model.
+///
+/// Fields are listed as `required` (must be set), `parsed` (required, set
+/// as text and parsed with `FromStr` on build), `default` (initialized with
+/// a value) or `optional` (`Option` fields in the model). The request is
+/// checked with [`Validate`] even when fields are missing, so missing and
+/// invalid fields come back in one [`ValidationErrors`].
+macro_rules! request_builder {
+    (
+        $request:ident => $builder:ident {
+            $(required $req:ident: $req_ty:ty;)*
+            $(parsed $par:ident: $par_ty:ty;)*
+            $(default $def:ident: $def_ty:ty = $def_val:expr;)*
+            $(optional $opt:ident: $opt_ty:ty;)*
+        }
+    ) => {
+        #[doc = concat!("Builder for [`", stringify!($request), "`].")]
+        #[derive(Debug, Clone)]
+        pub struct $builder {
+            $($req: Option<$req_ty>,)*
+            $($par: Option<String>,)*
+            $($def: $def_ty,)*
+            $($opt: Option<$opt_ty>,)*
+        }
+
+        impl $request {
+            #[doc = concat!("Create a builder for [`", stringify!($request), "`].")]
+            pub fn builder() -> $builder {
+                $builder::new()
+            }
+        }
+
+        impl Default for $builder {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $builder {
+            /// Create a new builder with default values.
+            pub fn new() -> Self {
+                $builder {
+                    $($req: None,)*
+                    $($par: None,)*
+                    $($def: $def_val,)*
+                    $($opt: None,)*
+                }
+            }
+
+            $(
+                #[doc = concat!("Set `", stringify!($req), "` (required).")]
+                pub fn $req(mut self, value: impl Into<$req_ty>) -> Self {
+                    self.$req = Some(value.into());
+                    self
+                }
+            )*
+
+            $(
+                #[doc = concat!("Set `", stringify!($par), "` (required).")]
+                pub fn $par(mut self, value: impl ToString) -> Self {
+                    self.$par = Some(value.to_string());
+                    self
+                }
+            )*
+
+            $(
+                #[doc = concat!("Set `", stringify!($def), "`.")]
+                pub fn $def(mut self, value: impl Into<$def_ty>) -> Self {
+                    self.$def = value.into();
+                    self
+                }
+            )*
+
+            $(
+                #[doc = concat!("Set `", stringify!($opt), "`.")]
+                pub fn $opt(mut self, value: impl Into<$opt_ty>) -> Self {
+                    self.$opt = Some(value.into());
+                    self
+                }
+            )*
+
+            /// Build the request, reporting every missing or invalid field.
+            pub fn build(self) -> Result<$request> {
+                let mut errors = ValidationErrors::new();
+                $(
+                    if self.$req.as_ref().map_or(true, Blank::is_blank) {
+                        errors.add(stringify!($req), concat!(stringify!($req), " is required"));
+                    }
+                )*
+                $(
+                    let $par = match self.$par.as_deref().map(str::trim) {
+                        None | Some("") => {
+                            errors.add(stringify!($par), concat!(stringify!($par), " is required"));
+                            None
+                        }
+                        Some(raw) => match raw.parse::<$par_ty>() {
+                            Ok(value) => Some(value),
+                            Err(err) => {
+                                errors.add(stringify!($par), parse_error_message(err));
+                                None
+                            }
+                        },
+                    };
+                )*
+                let reported: Vec<String> =
+                    errors.errors().iter().map(|error| error.field.clone()).collect();
+                let request = $request {
+                    $($req: self.$req.unwrap_or_else(Placeholder::placeholder),)*
+                    $($par: $par.unwrap_or_else(Placeholder::placeholder),)*
+                    $($def: self.$def,)*
+                    $($opt: self.$opt,)*
+                };
+
+                if let Err(err) = request.validate() {
+                    let field_errors = err.field_errors();
+                    if field_errors.is_empty() {
+                        return Err(err);
+                    }
+                    // Fields already reported as missing or unparsable hold
+                    // placeholders, so their validation errors are noise.
+                    for error in field_errors {
+                        if !reported.contains(&error.field) {
+                            errors.add(error.field, error.message);
+                        }
+                    }
+                }
+                errors.into_result()?;
+                Ok(request)
+            }
+        }
+    };
+}
+
+/// Add `futures()` / `options()` shortcuts to builders with a `trade_type` field.
+macro_rules! trade_type_shortcuts {
+    ($($builder:ident),* $(,)?) => {
+        $(
+            impl $builder {
+                /// Request futures data (`trade_type` = "1").
+                pub fn futures(self) -> Self {
+                    self.trade_type(TradeType::Futures)
+                }
+
+                /// Request options data (`trade_type` = "2").
+                pub fn options(self) -> Self {
+                    self.trade_type(TradeType::Options)
+                }
+            }
+        )*
+    };
+}
+
+// ============================================================================
+// News Builders (资讯)
+// ============================================================================
+
+request_builder! {
+    GetArticleByPageRequest => GetArticleByPageRequestBuilder {
+        required column_id: ColumnId;
+        default page_no: i32 = 1;
+        default page_size: i32 = 10;
+        default site_id: i32 = 5;
+    }
+}
+
+// ============================================================================
+// Market Builders (行情)
+// ============================================================================
+
+request_builder! {
+    QuotesRequest => QuotesRequestBuilder {
+        required trade_date: String;
+        default trade_type: TradeType = TradeType::Futures;
+        optional variety_id: String;
+        optional variety: String;
+        optional lang: Lang;
+        optional statistics_type: StatisticsType;
+    }
+}
+
+request_builder! {
+    WeekQuotesRequest => WeekQuotesRequestBuilder {
+        required variety_code: String;
+        required year: i32;
+        required week: i32;
+    }
+}
+
+request_builder! {
+    MonthQuotesRequest => MonthQuotesRequestBuilder {
+        required variety_code: String;
+        required year: i32;
+        required month: i32;
+    }
+}
+
+request_builder! {
+    WarehouseReceiptRequest => WarehouseReceiptRequestBuilder {
+        required variety_id: String;
+        required trade_date: String;
+    }
+}
+
+request_builder! {
+    VarietyMonthYearStatRequest => VarietyMonthYearStatRequestBuilder {
+        required trade_month: String;
+        default trade_type: TradeType = TradeType::Futures;
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+request_builder! {
+    ContractMonthMaxRequest => ContractMonthMaxRequestBuilder {
+        required stat_content: StatContent;
+        parsed start_month: DeliveryMonth;
+        parsed end_month: DeliveryMonth;
+        default trade_type: TradeType = TradeType::Futures;
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+request_builder! {
+    RiseFallEventRequest => RiseFallEventRequestBuilder {
+        required start_date: String;
+        required end_date: String;
+        required variety_id: String;
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+request_builder! {
+    DivisionPriceInfoRequest => DivisionPriceInfoRequestBuilder {
+        required variety_id: String;
+        required trade_date: String;
+        default trade_type: TradeType = TradeType::Futures;
+    }
+}
+
+// ============================================================================
+// Member Builders (会员)
+// ============================================================================
+
+request_builder! {
+    DailyRankingRequest => DailyRankingRequestBuilder {
+        required variety_id: String;
+        required contract_id: String;
+        required trade_date: String;
+        default trade_type: TradeType = TradeType::Futures;
+    }
+}
+
+request_builder! {
+    PhaseRankingRequest => PhaseRankingRequestBuilder {
+        required variety: String;
+        required start_month: String;
+        required end_month: String;
+        default trade_type: TradeType = TradeType::Futures;
+    }
+}
+
+// ============================================================================
+// Trade Parameter Builders (交易参数)
+// ============================================================================
+
+request_builder! {
+    DayTradeParamRequest => DayTradeParamRequestBuilder {
+        required variety_id: String;
+        default trade_type: TradeType = TradeType::Futures;
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+request_builder! {
+    ContractInfoRequest => ContractInfoRequestBuilder {
+        required variety_id: String;
+        default trade_type: TradeType = TradeType::Futures;
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+request_builder! {
+    ArbitrageContractRequest => ArbitrageContractRequestBuilder {
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+request_builder! {
+    TradingParamRequest => TradingParamRequestBuilder {
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+request_builder! {
+    MarginArbiPerfParaRequest => MarginArbiPerfParaRequestBuilder {
+        required variety_id: String;
+    }
+}
+
+request_builder! {
+    NewContractInfoRequest => NewContractInfoRequestBuilder {
+        required trade_date: String;
+        default trade_type: TradeType = TradeType::Futures;
+        optional lang: Lang;
+    }
+}
+
+request_builder! {
+    MainSeriesInfoRequest => MainSeriesInfoRequestBuilder {
+        required variety_id: String;
+        required trade_date: String;
+    }
+}
+
+// ============================================================================
+// Settlement Builders (结算参数)
+// ============================================================================
+
+request_builder! {
+    SettleParamRequest => SettleParamRequestBuilder {
+        required variety_id: String;
+        required trade_date: String;
+        default trade_type: TradeType = TradeType::Futures;
+        default lang: Lang = Lang::Zh;
+    }
+}
+
+// ============================================================================
+// Delivery Builders (交割)
+// ============================================================================
+
+request_builder! {
+    DeliveryDataRequest => DeliveryDataRequestBuilder {
+        required variety_id: String;
+        parsed start_month: DeliveryMonth;
+        parsed end_month: DeliveryMonth;
+        default variety_type: VarietyType = VarietyType::Physical;
+    }
+}
+
+request_builder! {
+    DeliveryMatchRequest => DeliveryMatchRequestBuilder {
+        required variety_id: String;
+        required contract_id: String;
+        parsed start_month: DeliveryMonth;
+        parsed end_month: DeliveryMonth;
+    }
+}
+
+request_builder! {
+    TcCongregateDeliveryRequest => TcCongregateDeliveryRequestBuilder {
+        required variety: String;
+        required contract_month: String;
+    }
+}
+
+request_builder! {
+    RollDeliverySellerIntentionRequest => RollDeliverySellerIntentionRequestBuilder {
+        required variety: String;
+        required date: String;
+    }
+}
+
+request_builder! {
+    BondedDeliveryRequest => BondedDeliveryRequestBuilder {
+        required start_date: String;
+        required end_date: String;
+    }
+}
+
+request_builder! {
+    TdBondedDeliveryRequest => TdBondedDeliveryRequestBuilder {
+        required start_date: String;
+        required end_date: String;
+    }
+}
+
+request_builder! {
+    FactorySpotAgioRequest => FactorySpotAgioRequestBuilder {
+        required variety_id: String;
+        required trade_date: String;
+    }
+}
+
+request_builder! {
+    PlywoodDeliveryCommodityRequest => PlywoodDeliveryCommodityRequestBuilder {
+        required variety_id: String;
+    }
+}
+
+trade_type_shortcuts!(
+    QuotesRequestBuilder,
+    VarietyMonthYearStatRequestBuilder,
+    ContractMonthMaxRequestBuilder,
+    DivisionPriceInfoRequestBuilder,
+    DailyRankingRequestBuilder,
+    PhaseRankingRequestBuilder,
+    DayTradeParamRequestBuilder,
+    ContractInfoRequestBuilder,
+    NewContractInfoRequestBuilder,
+    SettleParamRequestBuilder,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(result: Result<impl std::fmt::Debug>) -> Vec<String> {
+        let err = result.unwrap_err();
+        err.field_errors().into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn complete_builders_fill_defaults() {
+        let request = QuotesRequest::builder()
+            .trade_date("20250102")
+            .variety_id("m")
+            .options()
+            .build()
+            .unwrap();
+        assert_eq!(request.trade_date, "20250102");
+        assert_eq!(request.variety_id.as_deref(), Some("m"));
+        assert_eq!(request.trade_type, TradeType::Options);
+
+        let request = GetArticleByPageRequest::builder()
+            .column_id(ColumnId::BusinessNotices)
+            .build()
+            .unwrap();
+        assert_eq!((request.page_no, request.page_size), (1, 10));
+    }
+
+    #[test]
+    fn missing_and_invalid_fields_are_reported_together() {
+        let result = WeekQuotesRequest::builder()
+            .variety_code("m")
+            .week(54)
+            .build();
+        assert_eq!(fields(result), ["year", "week"]);
+
+        let result = RiseFallEventRequest::builder()
+            .start_date("20250230")
+            .end_date("  ")
+            .build();
+        assert_eq!(fields(result), ["end_date", "variety_id", "start_date"]);
+    }
+
+    #[test]
+    fn parsed_fields_report_one_error_each() {
+        let result = DeliveryDataRequest::builder()
+            .variety_id(" ")
+            .start_month("2025-13")
+            .build();
+        let err = result.unwrap_err();
+        assert!(matches!(err, Error::ValidationErrors(_)), "{err}");
+        let errors = err.field_errors();
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["variety_id", "start_month", "end_month"]);
+        assert!(
+            errors[1].message.contains("2025-13"),
+            "{}",
+            errors[1].message
+        );
+    }
+
+    #[test]
+    fn invalid_values_fail_the_build() {
+        let err = DeliveryMatchRequest::builder()
+            .variety_id("m")
+            .contract_id("m2601")
+            .start_month("202512")
+            .end_month("202501")
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::Validation { field, .. } if field == "end_month"),
+            "{err}"
+        );
+
+        let err = RiseFallEventRequest::builder()
+            .start_date("20250301")
+            .end_date("20250230")
+            .variety_id("m")
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::Validation { field, .. } if field == "end_date"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! DCE trading calendar.
+//!
+//! The API publishes no calendar, so trading days are derived: weekends
+//! never trade, and a weekday is a holiday if the exchange published no
+//! quotes for it. [`MarketService::update_calendar`] confirms past weekdays
+//! that way; weekdays not confirmed yet, such as future dates, are assumed
+//! to be trading days. A calendar can be saved to and loaded from a JSON
+//! file so each day only has to be confirmed once. Range downloads request
+//! only the calendar's trading days when it is set with
+//! [`DownloadOptions::with_calendar`](crate::DownloadOptions::with_calendar).
+//!
+//! [`MarketService::update_calendar`]: crate::MarketService::update_calendar
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::days::{day_number, format_day, is_weekday};
+use crate::error::{Error, Result};
+
+/// Trading calendar built from confirmed trading days and holidays.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Calendar {
+    /// Weekdays confirmed as trading days (YYYYMMDD).
+    #[serde(default)]
+    trading_days: BTreeSet<String>,
+    /// Weekdays confirmed as holidays (YYYYMMDD).
+    #[serde(default)]
+    holidays: BTreeSet<String>,
+}
+
+impl Calendar {
+    /// Create a calendar with no confirmed days, treating every weekday as
+    /// a trading day.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a calendar saved with [`save`](Self::save). A missing file gives
+    /// an empty calendar.
+    pub fn load(path: impl AsRef<Path>) -> Result<Calendar> {
+        let path = path.as_ref();
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Calendar::new()),
+            Err(err) => return Err(Error::io(path, err)),
+        };
+        serde_json::from_str(&json).map_err(|e| Error::parse(json.as_str(), e.to_string()))
+    }
+
+    /// Save the calendar as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).map_err(|e| Error::io(path, e.into()))?;
+        fs::write(path, json).map_err(|e| Error::io(path, e))
+    }
+
+    /// Mark a weekday as a trading day.
+    pub fn add_trading_day(&mut self, date: &str) -> Result<()> {
+        let date = parse_weekday(date)?;
+        self.holidays.remove(&date);
+        self.trading_days.insert(date);
+        Ok(())
+    }
+
+    /// Mark a weekday as a holiday.
+    pub fn add_holiday(&mut self, date: &str) -> Result<()> {
+        let date = parse_weekday(date)?;
+        self.trading_days.remove(&date);
+        self.holidays.insert(date);
+        Ok(())
+    }
+
+    /// Confirmed holidays (YYYYMMDD), in date order.
+    pub fn holidays(&self) -> impl Iterator<Item = &str> {
+        self.holidays.iter().map(String::as_str)
+    }
+
+    /// Add the confirmed days of another calendar; its entries win on
+    /// conflict.
+    pub fn merge(&mut self, other: Calendar) {
+        for date in &other.trading_days {
+            self.holidays.remove(date);
+        }
+        for date in &other.holidays {
+            self.trading_days.remove(date);
+        }
+        self.trading_days.extend(other.trading_days);
+        self.holidays.extend(other.holidays);
+    }
+
+    /// Whether a date is a trading day.
+    pub fn is_trading_day(&self, date: &str) -> Result<bool> {
+        Ok(self.is_trading(parse_day("date", date)?))
+    }
+
+    /// First trading day after a date.
+    pub fn next_trading_day(&self, date: &str) -> Result<String> {
+        let mut day = parse_day("date", date)? + 1;
+        while !self.is_trading(day) {
+            day += 1;
+        }
+        Ok(format_day(day))
+    }
+
+    /// Last trading day before a date.
+    pub fn prev_trading_day(&self, date: &str) -> Result<String> {
+        let mut day = parse_day("date", date)? - 1;
+        while !self.is_trading(day) {
+            day -= 1;
+        }
+        Ok(format_day(day))
+    }
+
+    /// Trading days from `start_date` to `end_date` (inclusive).
+    pub fn trading_days_between(&self, start_date: &str, end_date: &str) -> Result<Vec<String>> {
+        let (start, end) = parse_range(start_date, end_date)?;
+        Ok((start..=end)
+            .filter(|&day| self.is_trading(day))
+            .map(format_day)
+            .collect())
+    }
+
+    /// Weekdays from `start_date` to `end_date` (inclusive) that are not yet
+    /// confirmed as trading days or holidays.
+    pub fn unconfirmed_days(&self, start_date: &str, end_date: &str) -> Result<Vec<String>> {
+        let (start, end) = parse_range(start_date, end_date)?;
+        Ok((start..=end)
+            .filter(|&day| is_weekday(day))
+            .map(format_day)
+            .filter(|date| !self.trading_days.contains(date) && !self.holidays.contains(date))
+            .collect())
+    }
+
+    /// Whether a day number is a trading day.
+    pub(crate) fn is_trading(&self, day: i64) -> bool {
+        is_weekday(day) && !self.holidays.contains(&format_day(day))
+    }
+}
+
+/// Parse a date argument into a day number.
+fn parse_day(field: &str, raw: &str) -> Result<i64> {
+    day_number(raw).ok_or_else(|| Error::validation(field, format!("invalid date {:?}", raw)))
+}
+
+/// Parse a date that must fall on a weekday, normalized to YYYYMMDD.
+fn parse_weekday(raw: &str) -> Result<String> {
+    let day = parse_day("date", raw)?;
+    if !is_weekday(day) {
+        return Err(Error::validation(
+            "date",
+            format!("{} is not a weekday", raw),
+        ));
+    }
+    Ok(format_day(day))
+}
+
+/// Parse an inclusive date range.
+fn parse_range(start_date: &str, end_date: &str) -> Result<(i64, i64)> {
+    let start = parse_day("start_date", start_date)?;
+    let end = parse_day("end_date", end_date)?;
+    if start > end {
+        return Err(Error::validation(
+            "end_date",
+            format!("end date {} is before start date {}", end_date, start_date),
+        ));
+    }
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2025 National Day: Wednesday 1 October to Wednesday 8 October.
+    fn national_day() -> Calendar {
+        let mut calendar = Calendar::new();
+        for date in [
+            "20251001", "20251002", "20251003", "20251006", "20251007", "20251008",
+        ] {
+            calendar.add_holiday(date).unwrap();
+        }
+        calendar.add_trading_day("20250930").unwrap();
+        calendar.add_trading_day("2025-10-09").unwrap();
+        calendar
+    }
+
+    #[test]
+    fn weekends_and_confirmed_holidays_do_not_trade() {
+        let calendar = national_day();
+        assert!(calendar.is_trading_day("20250930").unwrap());
+        assert!(!calendar.is_trading_day("20251001").unwrap());
+        assert!(!calendar.is_trading_day("20251004").unwrap());
+        // Unconfirmed weekdays are assumed to trade.
+        assert!(calendar.is_trading_day("20251010").unwrap());
+        assert!(calendar.is_trading_day("20251013").unwrap());
+
+        let mut calendar = calendar;
+        let err = calendar.add_holiday("20251011").unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }), "{err}");
+        assert!(calendar.is_trading_day("20251301").is_err());
+
+        calendar.add_trading_day("20251001").unwrap();
+        assert!(calendar.is_trading_day("20251001").unwrap());
+        assert!(!calendar.holidays().any(|date| date == "20251001"));
+    }
+
+    #[test]
+    fn next_and_previous_days_skip_a_holiday_block() {
+        let calendar = national_day();
+        assert_eq!(calendar.next_trading_day("20250930").unwrap(), "20251009");
+        assert_eq!(calendar.prev_trading_day("20251009").unwrap(), "20250930");
+        assert_eq!(calendar.next_trading_day("20251003").unwrap(), "20251009");
+        assert_eq!(calendar.next_trading_day("20251010").unwrap(), "20251013");
+        assert_eq!(calendar.prev_trading_day("20251013").unwrap(), "20251010");
+
+        assert_eq!(
+            calendar
+                .trading_days_between("20250929", "20251010")
+                .unwrap(),
+            ["20250929", "20250930", "20251009", "20251010"]
+        );
+        assert_eq!(
+            calendar.unconfirmed_days("20250929", "20251010").unwrap(),
+            ["20250929", "20251010"]
+        );
+        assert!(calendar
+            .trading_days_between("20251010", "20250929")
+            .is_err());
+    }
+
+    #[test]
+    fn merge_prefers_the_other_calendar() {
+        let mut calendar = national_day();
+        let mut other = Calendar::new();
+        other.add_trading_day("20251008").unwrap();
+        other.add_holiday("20251010").unwrap();
+        calendar.merge(other);
+        assert!(calendar.is_trading_day("20251008").unwrap());
+        assert!(!calendar.is_trading_day("20251010").unwrap());
+        assert_eq!(calendar.holidays().count(), 6);
+    }
+
+    #[test]
+    fn calendars_round_trip_through_json_files() {
+        let path = std::env::temp_dir().join(format!("calendar-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(Calendar::load(&path).unwrap(), Calendar::new());
+
+        let calendar = national_day();
+        calendar.save(&path).unwrap();
+        let loaded = Calendar::load(&path).unwrap();
+        assert_eq!(loaded, calendar);
+        assert_eq!(loaded.next_trading_day("20250930").unwrap(), "20251009");
+
+        fs::write(&path, "not json").unwrap();
+        assert!(Calendar::load(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,309 @@
+//! Normalized OHLCV candles.
+//!
+//! [`Candle`] holds one contract's prices for one period as numbers, so
+//! quotes from any of the quote endpoints can be passed to charting or
+//! backtesting code without per-endpoint mapping.
+//!
+//! Daily candles can be rolled up locally into weekly, monthly or quarterly
+//! candles with [`Candle::aggregate`], for buckets other than the
+//! exchange's predefined week and month quotes.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::days::{day_number, format_day, iso_week};
+use crate::error::{Error, Result};
+use crate::models::Quote;
+
+/// Period that daily candles are aggregated into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CandlePeriod {
+    /// ISO week (Monday to Sunday), labelled "2025W03".
+    Week,
+    /// Calendar month, labelled "202501".
+    Month,
+    /// Calendar quarter, labelled "2025Q1".
+    Quarter,
+}
+
+impl CandlePeriod {
+    /// Label of the period containing a trade date (YYYYMMDD), or `None` if
+    /// the date does not parse.
+    pub fn label(&self, date: &str) -> Option<String> {
+        let day = day_number(date)?;
+        let date = format_day(day);
+        let month: u32 = date[4..6].parse().ok()?;
+        Some(match self {
+            CandlePeriod::Week => {
+                let (year, week) = iso_week(day);
+                format!("{}W{:02}", year, week)
+            }
+            CandlePeriod::Month => date[..6].to_string(),
+            CandlePeriod::Quarter => format!("{}Q{}", &date[..4], (month - 1) / 3 + 1),
+        })
+    }
+}
+
+/// Open/high/low/close/volume bar for one contract and period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// Period label: a trade date (YYYYMMDD) for day quotes, or the week or
+    /// month the quote covers. Empty if not known.
+    pub date: String,
+    /// Contract ID.
+    pub contract_id: String,
+    /// Open price.
+    pub open: f64,
+    /// High price.
+    pub high: f64,
+    /// Low price.
+    pub low: f64,
+    /// Close price (last price for night quotes).
+    pub close: f64,
+    /// Settlement price, if published.
+    pub settle: Option<f64>,
+    /// Volume in lots.
+    pub volume: i64,
+    /// Open interest in lots.
+    pub open_interest: i64,
+    /// Turnover in yuan, if published.
+    pub turnover: Option<f64>,
+}
+
+impl Candle {
+    /// Convert a quote for the given period (trade date, week or month).
+    ///
+    /// Works for day, night, weekly and monthly quotes alike.
+    ///
+    /// # Errors
+    /// Returns a missing data error if the row has no contract ID (summary
+    /// rows) or is missing an open, high, low or close price.
+    pub fn from_quote(quote: &Quote, date: impl Into<String>) -> Result<Candle> {
+        let mut candle = Candle::try_from(quote)?;
+        candle.date = date.into();
+        Ok(candle)
+    }
+
+    /// Convert every contract row of a quote list, skipping rows that
+    /// cannot form a candle (summary rows, contracts without trades).
+    pub fn from_quotes(quotes: &[Quote], date: &str) -> Vec<Candle> {
+        quotes
+            .iter()
+            .filter_map(|q| Candle::from_quote(q, date).ok())
+            .collect()
+    }
+
+    /// Set the period label.
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = date.into();
+        self
+    }
+
+    /// High minus low.
+    pub fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    /// Aggregate daily candles into one candle per contract and period.
+    ///
+    /// Each result takes the open of the first day, the close, settlement
+    /// and open interest of the last day, the highest high and lowest low,
+    /// and the summed volume and turnover. Its date is the period label
+    /// (see [`CandlePeriod::label`]). Candles whose date does not parse are
+    /// skipped. Results are ordered by contract, then period.
+    pub fn aggregate(candles: &[Candle], period: CandlePeriod) -> Vec<Candle> {
+        let mut groups: BTreeMap<(&str, String), Vec<&Candle>> = BTreeMap::new();
+        for candle in candles {
+            if let Some(label) = period.label(&candle.date) {
+                groups
+                    .entry((candle.contract_id.as_str(), label))
+                    .or_default()
+                    .push(candle);
+            }
+        }
+        groups
+            .into_iter()
+            .map(|((_, label), group)| Candle::merge(label, group))
+            .collect()
+    }
+
+    /// Aggregate a single series, such as a continuous main-contract
+    /// series, into one candle per period regardless of contract.
+    ///
+    /// Like [`aggregate`](Self::aggregate), but each result keeps the
+    /// contract ID of the period's last day.
+    pub fn aggregate_series(candles: &[Candle], period: CandlePeriod) -> Vec<Candle> {
+        let mut groups: BTreeMap<String, Vec<&Candle>> = BTreeMap::new();
+        for candle in candles {
+            if let Some(label) = period.label(&candle.date) {
+                groups.entry(label).or_default().push(candle);
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(label, group)| Candle::merge(label, group))
+            .collect()
+    }
+
+    /// Merge a non-empty group of daily candles into one period candle.
+    fn merge(label: String, mut group: Vec<&Candle>) -> Candle {
+        group.sort_by_key(|c| day_number(&c.date));
+        let first = group[0];
+        let last = group[group.len() - 1];
+        let turnover = group
+            .iter()
+            .filter_map(|c| c.turnover)
+            .fold(None, |sum: Option<f64>, t| Some(sum.unwrap_or(0.0) + t));
+        Candle {
+            date: label,
+            contract_id: last.contract_id.clone(),
+            open: first.open,
+            high: group.iter().map(|c| c.high).fold(f64::MIN, f64::max),
+            low: group.iter().map(|c| c.low).fold(f64::MAX, f64::min),
+            close: last.close,
+            settle: last.settle,
+            volume: group.iter().map(|c| c.volume).sum(),
+            open_interest: last.open_interest,
+            turnover,
+        }
+    }
+}
+
+impl TryFrom<&Quote> for Candle {
+    type Error = Error;
+
+    /// Convert a quote; the date is left empty since quotes do not carry it.
+    fn try_from(quote: &Quote) -> Result<Candle> {
+        let contract_id = if quote.contract_id.is_empty() {
+            quote.deliv_month.clone()
+        } else {
+            quote.contract_id.clone()
+        };
+        if contract_id.is_empty() {
+            return Err(Error::missing_data("quote has no contract ID"));
+        }
+
+        let price = |field: &str, value: Option<f64>| {
+            value.ok_or_else(|| {
+                Error::missing_data(format!("{} has no {} price", contract_id, field))
+            })
+        };
+        let open = price("open", quote.open)?;
+        let high = price("high", quote.high)?;
+        let low = price("low", quote.low)?;
+        let close = price("close", quote.close.or(quote.last_price))?;
+
+        Ok(Candle {
+            date: String::new(),
+            contract_id,
+            open,
+            high,
+            low,
+            close,
+            settle: quote.clear_price,
+            volume: quote.volume,
+            open_interest: quote.open_interest,
+            turnover: quote.turnover_yuan(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str, contract_id: &str, open: f64, close: f64, volume: i64) -> Candle {
+        Candle {
+            date: date.to_string(),
+            contract_id: contract_id.to_string(),
+            open,
+            high: open.max(close) + 10.0,
+            low: open.min(close) - 10.0,
+            close,
+            settle: Some(close),
+            volume,
+            open_interest: volume * 10,
+            turnover: None,
+        }
+    }
+
+    #[test]
+    fn periods_are_labelled_by_iso_week_month_and_quarter() {
+        assert_eq!(
+            CandlePeriod::Week.label("20241230").as_deref(),
+            Some("2025W01")
+        );
+        assert_eq!(
+            CandlePeriod::Week.label("2025-01-05").as_deref(),
+            Some("2025W01")
+        );
+        assert_eq!(
+            CandlePeriod::Month.label("20250131").as_deref(),
+            Some("202501")
+        );
+        assert_eq!(
+            CandlePeriod::Quarter.label("20250401").as_deref(),
+            Some("2025Q2")
+        );
+        assert_eq!(
+            CandlePeriod::Quarter.label("20251231").as_deref(),
+            Some("2025Q4")
+        );
+        assert_eq!(CandlePeriod::Month.label("20250230"), None);
+    }
+
+    #[test]
+    fn daily_candles_roll_up_per_contract_and_week() {
+        let mut candles = vec![
+            day("20250107", "m2505", 2710.0, 2730.0, 20),
+            day("20250106", "m2505", 2700.0, 2720.0, 10),
+            day("20250110", "m2505", 2730.0, 2690.0, 30),
+            day("20250113", "m2505", 2690.0, 2750.0, 40),
+            day("20250106", "m2509", 2800.0, 2810.0, 5),
+            day("not a date", "m2505", 1.0, 1.0, 1_000),
+        ];
+        candles[0].turnover = Some(100.0);
+        candles[2].turnover = Some(50.0);
+
+        let weeks = Candle::aggregate(&candles, CandlePeriod::Week);
+        let labels: Vec<_> = weeks
+            .iter()
+            .map(|c| (c.contract_id.as_str(), c.date.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                ("m2505", "2025W02"),
+                ("m2505", "2025W03"),
+                ("m2509", "2025W02")
+            ]
+        );
+
+        let week = &weeks[0];
+        assert_eq!((week.open, week.close), (2700.0, 2690.0));
+        assert_eq!((week.high, week.low), (2740.0, 2680.0));
+        assert_eq!(week.settle, Some(2690.0));
+        assert_eq!((week.volume, week.open_interest), (60, 300));
+        assert_eq!(week.turnover, Some(150.0));
+        assert_eq!(weeks[1].turnover, None);
+    }
+
+    #[test]
+    fn series_roll_up_keeps_the_last_contract() {
+        let candles = [
+            day("20250328", "m2505", 2700.0, 2710.0, 10),
+            day("20250401", "m2509", 2800.0, 2820.0, 20),
+            day("20250331", "m2505", 2710.0, 2705.0, 15),
+        ];
+        let quarters = Candle::aggregate_series(&candles, CandlePeriod::Quarter);
+        assert_eq!(quarters.len(), 2);
+        assert_eq!(quarters[0].date, "2025Q1");
+        assert_eq!(quarters[0].contract_id, "m2505");
+        assert_eq!((quarters[0].open, quarters[0].close), (2700.0, 2705.0));
+        assert_eq!(quarters[1].contract_id, "m2509");
+
+        assert!(Candle::aggregate(&[], CandlePeriod::Month).is_empty());
+        assert!(Candle::aggregate_series(&[], CandlePeriod::Month).is_empty());
+    }
+}
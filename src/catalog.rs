@@ -0,0 +1,385 @@
+//! Contract catalog with read-through caching.
+//!
+//! Contract metadata (trading unit, tick, listing and expiry dates) changes
+//! rarely but is needed by many helpers. The catalog caches
+//! `get_contract_info` results per variety and trade type so that lookups do
+//! not repeat network requests. A [`Client`](crate::Client) shares one
+//! catalog between contract validation, option chains, the delivery window
+//! check of the roll delivery watcher, and its margin and fee calculators.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Mutex, RwLock};
+
+use crate::analytics::days_to_expiry;
+use crate::contract_id::ContractId;
+use crate::error::{Error, Result};
+use crate::models::{ContractInfo, ContractInfoRequest, Lang, TradeType};
+use crate::services::TradeService;
+
+/// Default maximum age of cached contract data (12 hours).
+pub const DEFAULT_CATALOG_TTL_SECS: u64 = 12 * 3600;
+
+/// Staleness policy for the contract catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogPolicy {
+    /// Fetch on first use and whenever cached data is older than the given age.
+    ReadThrough(Duration),
+    /// Serve only data loaded by an explicit refresh; lookups never hit the network.
+    Manual,
+}
+
+impl Default for CatalogPolicy {
+    fn default() -> Self {
+        CatalogPolicy::ReadThrough(Duration::from_secs(DEFAULT_CATALOG_TTL_SECS))
+    }
+}
+
+/// Result of checking whether a contract can be queried on a trade date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractStatus {
+    /// The contract is listed and trading on the date.
+    Active,
+    /// The contract starts trading after the date.
+    NotYetListed {
+        /// First trade date (YYYYMMDD), if known.
+        start_trade_date: Option<String>,
+    },
+    /// The contract stopped trading before the date.
+    Expired {
+        /// Last trade date (YYYYMMDD), if known.
+        end_trade_date: Option<String>,
+    },
+    /// The variety exists but has no contract with this ID on the date.
+    NotFound,
+    /// No contracts are listed for the variety derived from the contract ID.
+    UnknownVariety {
+        /// Variety ID derived from the contract ID.
+        variety_id: String,
+    },
+}
+
+impl ContractStatus {
+    /// Check whether the contract is active.
+    pub fn is_active(&self) -> bool {
+        matches!(self, ContractStatus::Active)
+    }
+}
+
+impl std::fmt::Display for ContractStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContractStatus::Active => write!(f, "active"),
+            ContractStatus::NotYetListed {
+                start_trade_date: Some(date),
+            } => write!(f, "not yet listed (starts {})", date),
+            ContractStatus::NotYetListed { .. } => write!(f, "not yet listed"),
+            ContractStatus::Expired {
+                end_trade_date: Some(date),
+            } => write!(f, "expired (last traded {})", date),
+            ContractStatus::Expired { .. } => write!(f, "expired"),
+            ContractStatus::NotFound => write!(f, "contract not found"),
+            ContractStatus::UnknownVariety { variety_id } => {
+                write!(f, "unknown variety '{}'", variety_id)
+            }
+        }
+    }
+}
+
+/// Cache key: (variety ID, trade type).
+type CatalogKey = (String, TradeType);
+
+/// Cached contract list for one key.
+#[derive(Debug)]
+struct CatalogEntry {
+    contracts: Arc<Vec<ContractInfo>>,
+    fetched_at: Instant,
+}
+
+/// Shared, read-through cache of contract information.
+///
+/// Cloning the catalog is cheap; clones share the same cache. Concurrent
+/// misses for the same variety and trade type share one request; the cache
+/// is not locked while it runs, so other lookups are not held up.
+#[derive(Debug, Clone)]
+pub struct ContractCatalog {
+    trade: TradeService,
+    policy: CatalogPolicy,
+    entries: Arc<RwLock<HashMap<CatalogKey, CatalogEntry>>>,
+    fetches: Arc<Mutex<HashMap<CatalogKey, Arc<Mutex<()>>>>>,
+}
+
+impl ContractCatalog {
+    /// Create a new catalog backed by the given trade service.
+    pub fn new(trade: TradeService, policy: CatalogPolicy) -> Self {
+        ContractCatalog {
+            trade,
+            policy,
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            fetches: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Get the staleness policy.
+    pub fn policy(&self) -> CatalogPolicy {
+        self.policy
+    }
+
+    /// Get contracts for a variety and trade type.
+    ///
+    /// With [`CatalogPolicy::ReadThrough`] missing or stale data is fetched.
+    /// With [`CatalogPolicy::Manual`] an [`Error::CatalogNotLoaded`] error is
+    /// returned if the data has not been loaded with
+    /// [`refresh`](Self::refresh).
+    pub async fn contracts(
+        &self,
+        variety_id: &str,
+        trade_type: TradeType,
+    ) -> Result<Arc<Vec<ContractInfo>>> {
+        let key = (variety_id.to_string(), trade_type);
+
+        if let Some(contracts) = self.fresh(&key).await {
+            return Ok(contracts);
+        }
+        if self.policy == CatalogPolicy::Manual {
+            return Err(Error::catalog_not_loaded(variety_id, trade_type));
+        }
+
+        // One fetch per key at a time; the cache itself stays unlocked
+        let fetch_lock = self
+            .fetches
+            .lock()
+            .await
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        let _fetching = fetch_lock.lock().await;
+
+        // Another task may have fetched while this one waited
+        if let Some(contracts) = self.fresh(&key).await {
+            return Ok(contracts);
+        }
+
+        let contracts = self.fetch(variety_id, trade_type).await?;
+        self.entries.write().await.insert(
+            key,
+            CatalogEntry {
+                contracts: contracts.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(contracts)
+    }
+
+    /// Cached contracts for a key, unless missing or stale.
+    async fn fresh(&self, key: &CatalogKey) -> Option<Arc<Vec<ContractInfo>>> {
+        let entries = self.entries.read().await;
+        entries
+            .get(key)
+            .filter(|entry| !self.is_stale(entry))
+            .map(|entry| entry.contracts.clone())
+    }
+
+    /// Look up a single contract by ID (e.g. "m2505" or "m2505-C-3000").
+    ///
+    /// The variety and trade type are derived from the contract ID.
+    pub async fn contract(&self, contract_id: &str) -> Result<Option<ContractInfo>> {
+        let id = ContractId::parse(contract_id)?;
+        let contracts = self.contracts(&id.variety, id.trade_type()).await?;
+        Ok(contracts
+            .iter()
+            .find(|c| c.contract_id.eq_ignore_ascii_case(contract_id))
+            .cloned())
+    }
+
+    /// Calendar days from `trade_date` to a contract's last trade date, for
+    /// pricing options with [`option_greeks`](crate::analytics::option_greeks).
+    ///
+    /// Returns `None` if the contract is not listed or a date does not
+    /// parse.
+    pub async fn days_to_expiry(&self, contract_id: &str, trade_date: &str) -> Result<Option<i64>> {
+        Ok(self
+            .contract(contract_id)
+            .await?
+            .and_then(|info| days_to_expiry(&info, trade_date)))
+    }
+
+    /// Get cached contracts without touching the network, even if stale.
+    pub async fn cached(
+        &self,
+        variety_id: &str,
+        trade_type: TradeType,
+    ) -> Option<Arc<Vec<ContractInfo>>> {
+        let entries = self.entries.read().await;
+        entries
+            .get(&(variety_id.to_string(), trade_type))
+            .map(|entry| entry.contracts.clone())
+    }
+
+    /// Fetch and cache contracts for a variety and trade type.
+    pub async fn refresh(&self, variety_id: &str, trade_type: TradeType) -> Result<()> {
+        let contracts = self.fetch(variety_id, trade_type).await?;
+        let mut entries = self.entries.write().await;
+        entries.insert(
+            (variety_id.to_string(), trade_type),
+            CatalogEntry {
+                contracts,
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Re-fetch every variety and trade type currently in the cache.
+    pub async fn refresh_all(&self) -> Result<()> {
+        let keys: Vec<CatalogKey> = self.entries.read().await.keys().cloned().collect();
+        for (variety_id, trade_type) in keys {
+            self.refresh(&variety_id, trade_type).await?;
+        }
+        Ok(())
+    }
+
+    /// Drop all cached data.
+    pub async fn clear(&self) {
+        self.entries.write().await.clear();
+    }
+
+    /// Check whether an entry is older than the read-through TTL.
+    fn is_stale(&self, entry: &CatalogEntry) -> bool {
+        match self.policy {
+            CatalogPolicy::ReadThrough(ttl) => entry.fetched_at.elapsed() >= ttl,
+            CatalogPolicy::Manual => false,
+        }
+    }
+
+    /// Fetch contract information from the API.
+    async fn fetch(
+        &self,
+        variety_id: &str,
+        trade_type: TradeType,
+    ) -> Result<Arc<Vec<ContractInfo>>> {
+        let req = ContractInfoRequest {
+            variety_id: variety_id.to_string(),
+            trade_type,
+            lang: Lang::Zh,
+        };
+        let contracts = self.trade.get_contract_info(&req, None).await?;
+        Ok(Arc::new(contracts))
+    }
+}
+
+/// Derive the delivery month (YYYYMM) from a contract ID such as "m2505".
+pub(crate) fn contract_month(contract_id: &str) -> Option<String> {
+    ContractId::parse(contract_id)
+        .ok()
+        .map(|id| id.delivery_month())
+}
+
+/// Contract size of a contract: the trading unit listed in `contracts` (as
+/// fetched from the catalog), or the variety's standard size when the
+/// contract is not listed there.
+pub(crate) fn contract_size(contracts: &[ContractInfo], contract: &ContractId) -> Result<f64> {
+    let underlying = contract.underlying().to_string();
+    contracts
+        .iter()
+        .find(|c| c.contract_id.trim().eq_ignore_ascii_case(&underlying))
+        .filter(|c| c.unit > 0)
+        .map(|c| f64::from(c.unit))
+        .or_else(|| contract.variety_code().contract_size())
+        .ok_or_else(|| {
+            Error::missing_data(format!(
+                "contract size of {} is not known",
+                contract.variety
+            ))
+        })
+}
+
+/// Normalize a date such as "2025-09-30" or "20250930" to YYYYMMDD.
+pub(crate) fn normalize_date(raw: &str) -> Option<String> {
+    let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).take(8).collect();
+    (digits.len() == 8).then_some(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, Config};
+
+    #[tokio::test]
+    async fn manual_policy_miss_is_not_loaded() {
+        let config = Config::new()
+            .with_api_key("key")
+            .with_secret("secret")
+            .with_catalog_policy(CatalogPolicy::Manual);
+        let client = Client::new(config).unwrap();
+
+        let err = client
+            .catalog()
+            .contracts("m", TradeType::Futures)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CatalogNotLoaded { ref variety_id, .. } if variety_id == "m"
+        ));
+        assert!(client
+            .catalog()
+            .cached("m", TradeType::Futures)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn calculators_and_option_chains_use_the_catalog() {
+        // A server that never answers, so the API requests made next to the
+        // catalog lookups cannot fail first.
+        let silent = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = Config::new()
+            .with_api_key("key")
+            .with_secret("secret")
+            .with_base_url(format!("http://{}", silent.local_addr().unwrap()))
+            .with_catalog_policy(CatalogPolicy::Manual);
+        let client = Client::new(config).unwrap();
+
+        let not_loaded = |err: Error| matches!(err, Error::CatalogNotLoaded { .. });
+        assert!(not_loaded(client.margin_calculator("m").await.unwrap_err()));
+        assert!(not_loaded(
+            client.fee_calculator("m", "20250930").await.unwrap_err()
+        ));
+        assert!(not_loaded(
+            client
+                .market
+                .get_option_chain("m", "20250930", None)
+                .await
+                .unwrap_err()
+        ));
+        assert!(not_loaded(
+            client
+                .catalog()
+                .days_to_expiry("m2509-C-3000", "20250801")
+                .await
+                .unwrap_err()
+        ));
+    }
+
+    #[test]
+    fn contract_size_prefers_listed_units() {
+        let info = |contract_id: &str, unit: i32| -> ContractInfo {
+            serde_json::from_value(serde_json::json!({
+                "contractId": contract_id, "unit": unit,
+            }))
+            .unwrap()
+        };
+        let contracts = [info("m2509", 20), info("m2511", 0)];
+        let size =
+            |contract_id: &str| contract_size(&contracts, &ContractId::parse(contract_id).unwrap());
+        assert_eq!(size("M2509").unwrap(), 20.0);
+        assert_eq!(size("m2509-C-3000").unwrap(), 20.0);
+        assert_eq!(size("m2511").unwrap(), 10.0);
+        assert_eq!(size("m2601").unwrap(), 10.0);
+        let err = size("zz2509").unwrap_err();
+        assert!(matches!(err, Error::MissingData { .. }), "{err}");
+    }
+}
@@ -2,17 +2,32 @@
 //!
 //! The main entry point for using the DCE API.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use reqwest::Client as HttpClient;
 
+use crate::analytics::{basis_table, BasisRow};
+use crate::catalog::{contract_month, normalize_date, ContractCatalog, ContractStatus};
 use crate::config::Config;
+use crate::contract_id::ContractId;
+use crate::days::{day_number, format_day};
+use crate::delivery_cost::DeliveryCostEstimator;
+use crate::download::{fetch_dates, DownloadOptions};
 use crate::error::{Error, Result};
-use crate::http::BaseClient;
+use crate::fee::FeeCalculator;
+use crate::http::{BaseClient, RequestOptions};
+use crate::margin::MarginCalculator;
+use crate::models::{
+    DayTradeParamRequest, FactorySpotAgio, FactorySpotAgioRequest, Lang, Quote, QuotesRequest,
+    SettleParamRequest, StatisticsType, TradeType, VarietyType, WarehouseReceiptRequest,
+};
 use crate::services::{
     CommonService, DeliveryService, MarketService, MemberService, NewsService, SettleService,
     TradeService,
 };
+use crate::shutdown::Shutdown;
+use crate::snapshot::VarietySnapshot;
 use crate::token::TokenManager;
 
 /// DCE API client.
@@ -23,10 +38,10 @@ use crate::token::TokenManager;
 /// # Example
 ///
 /// ```no_run
-/// use dceapi::{Client, Config};
+/// use dceapi_rs::{Client, Config};
 ///
 /// #[tokio::main]
-/// async fn main() -> dceapi::Result<()> {
+/// async fn main() -> dceapi_rs::Result<()> {
 ///     let config = Config::new()
 ///         .with_api_key("your-api-key")
 ///         .with_secret("your-secret");
@@ -44,6 +59,8 @@ use crate::token::TokenManager;
 pub struct Client {
     config: Arc<Config>,
     token_manager: Arc<TokenManager>,
+    catalog: ContractCatalog,
+    shutdown: Shutdown,
 
     /// News service for articles and announcements.
     pub news: NewsService,
@@ -79,7 +96,7 @@ impl Client {
     /// # Example
     ///
     /// ```no_run
-    /// use dceapi::{Client, Config};
+    /// use dceapi_rs::{Client, Config};
     ///
     /// let config = Config::new()
     ///     .with_api_key("your-api-key")
@@ -95,41 +112,50 @@ impl Client {
         config.validate()?;
 
         // Create HTTP client
-        let http_client = HttpClient::builder()
+        let builder = HttpClient::builder()
             .timeout(config.timeout)
             .gzip(true)
             .brotli(true)
-            .deflate(true)
+            .deflate(true);
+        let http_client = config
+            .auth_scheme
+            .configure_client(builder)?
             .build()
-            .map_err(|e| {
-                Error::validation(
-                    "http_client",
-                    format!("failed to create HTTP client: {}", e),
-                )
-            })?;
+            .map_err(|e| Error::config(format!("failed to create HTTP client: {}", e)))?;
 
         // Create token manager
-        let token_manager = Arc::new(TokenManager::new(
-            &config.api_key,
-            &config.secret,
-            &config.base_url,
-            http_client.clone(),
-        ));
+        let token_manager = Arc::new(
+            TokenManager::new(
+                &config.api_key,
+                &config.secret,
+                &config.base_url,
+                http_client.clone(),
+            )
+            .with_auth_scheme(config.auth_scheme.clone()),
+        );
 
         // Create base client
         let base_client = BaseClient::new(config.clone(), http_client, token_manager.clone());
 
+        // Create shared contract catalog
+        let catalog = ContractCatalog::new(
+            TradeService::new(base_client.clone()),
+            config.catalog_policy,
+        );
+
         // Create client with all services
         Ok(Client {
             config: Arc::new(config),
             token_manager,
+            shutdown: base_client.shutdown_token().clone(),
             news: NewsService::new(base_client.clone()),
             common: CommonService::new(base_client.clone()),
-            market: MarketService::new(base_client.clone()),
-            delivery: DeliveryService::new(base_client.clone()),
+            market: MarketService::new(base_client.clone()).with_catalog(catalog.clone()),
+            delivery: DeliveryService::new(base_client.clone()).with_catalog(catalog.clone()),
             member: MemberService::new(base_client.clone()),
             trade: TradeService::new(base_client.clone()),
             settle: SettleService::new(base_client),
+            catalog,
         })
     }
 
@@ -155,4 +181,342 @@ impl Client {
     pub fn token_manager(&self) -> &TokenManager {
         &self.token_manager
     }
+
+    /// Get the shared contract catalog.
+    ///
+    /// The catalog caches contract information so that metadata lookups do not
+    /// repeat network requests.
+    pub fn catalog(&self) -> &ContractCatalog {
+        &self.catalog
+    }
+
+    /// Shutdown token observed by watchers started from this client.
+    ///
+    /// Pass it to [`Supervisor::with_shutdown`](crate::Supervisor::with_shutdown)
+    /// so application tasks stop with the client.
+    pub fn shutdown_token(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
+    /// Stop every watcher started from this client and wait for them to
+    /// exit. Watchers started afterwards stop immediately.
+    pub async fn shutdown(&self) {
+        self.shutdown.shutdown().await
+    }
+
+    /// Re-fetch all contract information currently held in the catalog.
+    pub async fn refresh_catalog(&self) -> Result<()> {
+        self.catalog.refresh_all().await
+    }
+
+    /// Fetch the day quotes, trading parameters, settlement parameters and
+    /// warehouse receipts of a variety concurrently.
+    ///
+    /// Fails if any of the requests fails.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    pub async fn snapshot(&self, variety: &str, trade_date: &str) -> Result<VarietySnapshot> {
+        let quotes_req = QuotesRequest {
+            variety_id: Some(variety.to_string()),
+            variety: None,
+            trade_date: trade_date.to_string(),
+            trade_type: TradeType::Futures,
+            lang: None,
+            statistics_type: None,
+        };
+        let trade_req = DayTradeParamRequest {
+            variety_id: variety.to_string(),
+            trade_type: TradeType::Futures,
+            lang: Lang::default(),
+        };
+        let settle_req = SettleParamRequest {
+            variety_id: variety.to_string(),
+            trade_date: trade_date.to_string(),
+            trade_type: TradeType::Futures,
+            lang: Lang::default(),
+        };
+        let receipt_req = WarehouseReceiptRequest {
+            variety_id: variety.to_string(),
+            trade_date: trade_date.to_string(),
+        };
+
+        let (quotes, trade_params, settle_params, warehouse_receipt) = tokio::try_join!(
+            self.market.get_day_quotes(&quotes_req, None),
+            self.trade.get_day_trade_param(&trade_req, None),
+            self.settle.get_settle_param(&settle_req, None),
+            self.market.get_warehouse_receipt(&receipt_req, None),
+        )?;
+        Ok(VarietySnapshot {
+            variety: variety.to_string(),
+            trade_date: trade_date.to_string(),
+            quotes,
+            trade_params,
+            settle_params,
+            warehouse_receipt,
+        })
+    }
+
+    /// Fetch the delivery fee table, warehouse premiums and settlement
+    /// parameters of a variety concurrently, for estimating delivery costs.
+    ///
+    /// Fails if any of the requests fails.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    pub async fn delivery_cost_estimator(
+        &self,
+        variety: &str,
+        trade_date: &str,
+    ) -> Result<DeliveryCostEstimator> {
+        let settle_req = SettleParamRequest {
+            variety_id: variety.to_string(),
+            trade_date: trade_date.to_string(),
+            trade_type: TradeType::Futures,
+            lang: Lang::default(),
+        };
+        let (costs, premiums, settle_params) = tokio::try_join!(
+            self.delivery
+                .get_delivery_cost(variety, VarietyType::Physical, None),
+            self.delivery
+                .get_warehouse_premium(variety, trade_date, None),
+            self.settle.get_settle_param(&settle_req, None),
+        )?;
+        Ok(DeliveryCostEstimator::new(variety)
+            .with_delivery_costs(costs)
+            .with_warehouse_premiums(premiums.entity_list)
+            .with_settle_params(settle_params))
+    }
+
+    /// Fetch a variety's settlement parameters, the trading parameters of
+    /// all varieties and the variety's contracts from the
+    /// [catalog](Self::catalog) concurrently, for computing trading fees.
+    ///
+    /// Fails if any of the requests fails.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    pub async fn fee_calculator(&self, variety: &str, trade_date: &str) -> Result<FeeCalculator> {
+        let settle_req = SettleParamRequest {
+            variety_id: variety.to_string(),
+            trade_date: trade_date.to_string(),
+            trade_type: TradeType::Futures,
+            lang: Lang::default(),
+        };
+        let (settle_params, trading_params, contracts) = tokio::try_join!(
+            self.settle.get_settle_param(&settle_req, None),
+            self.trade.get_trading_param(None, None),
+            self.catalog.contracts(variety, TradeType::Futures),
+        )?;
+        Ok(FeeCalculator::new()
+            .with_settle_params(settle_params)
+            .with_trading_params(trading_params)
+            .with_contracts(contracts.to_vec()))
+    }
+
+    /// Fetch a variety's current trade parameters, the trading parameters
+    /// of all varieties and the variety's contracts from the
+    /// [catalog](Self::catalog) concurrently, for computing margins.
+    ///
+    /// Fails if any of the requests fails.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    pub async fn margin_calculator(&self, variety: &str) -> Result<MarginCalculator> {
+        let trade_req = DayTradeParamRequest {
+            variety_id: variety.to_string(),
+            trade_type: TradeType::Futures,
+            lang: Lang::default(),
+        };
+        let (trade_params, trading_params, contracts) = tokio::try_join!(
+            self.trade.get_day_trade_param(&trade_req, None),
+            self.trade.get_trading_param(None, None),
+            self.catalog.contracts(variety, TradeType::Futures),
+        )?;
+        Ok(MarginCalculator::new()
+            .with_trade_params(trade_params)
+            .with_trading_params(trading_params)
+            .with_contracts(contracts.to_vec()))
+    }
+
+    /// Fetch a variety's factory spot agios and day quotes for a trade date
+    /// and compute each factory's basis (see
+    /// [`basis_table`](crate::analytics::basis_table)).
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "fb")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    /// * `contract_id` - Contract to take the basis against; `None` for the main contract
+    pub async fn basis(
+        &self,
+        variety: &str,
+        trade_date: &str,
+        contract_id: Option<&str>,
+    ) -> Result<Vec<BasisRow>> {
+        let (agios, quotes) = fetch_basis_inputs(self, variety, trade_date).await?;
+        Ok(basis_table(&agios, &quotes, contract_id))
+    }
+
+    /// Compute factory basis tables for every trading day in a date range.
+    ///
+    /// Requests each trading day from `start_date` to `end_date`
+    /// (inclusive), per the calendar in `download` or every weekday without
+    /// one, bounded by its concurrency and rate limits. Days without agios
+    /// or quotes are left out. Fails with the first error that persists
+    /// after retries.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "fb")
+    /// * `start_date` - First trade date (YYYYMMDD format)
+    /// * `end_date` - Last trade date (YYYYMMDD format)
+    /// * `contract_id` - Contract to take the basis against; `None` for each day's main contract
+    /// * `download` - Concurrency, rate, retry and calendar settings
+    pub async fn basis_series(
+        &self,
+        variety: &str,
+        start_date: &str,
+        end_date: &str,
+        contract_id: Option<&str>,
+        download: &DownloadOptions,
+    ) -> Result<BTreeMap<String, Vec<BasisRow>>> {
+        let dates = download.trading_days(start_date, end_date)?;
+        let client = self.clone();
+        let variety = variety.to_string();
+        let contract_id = contract_id.map(str::to_string);
+        fetch_dates(
+            dates,
+            download,
+            |rows: &Vec<BasisRow>| !rows.is_empty(),
+            move |trade_date| {
+                let client = client.clone();
+                let variety = variety.clone();
+                let contract_id = contract_id.clone();
+                async move {
+                    let (agios, quotes) =
+                        fetch_basis_inputs(&client, &variety, &trade_date).await?;
+                    Ok(basis_table(&agios, &quotes, contract_id.as_deref()))
+                }
+            },
+        )
+        .await
+    }
+
+    /// Check that a contract exists and is trading on a given date.
+    ///
+    /// Use this before requesting rankings or settlement data for a specific
+    /// contract to get a typed reason instead of an opaque parameter error
+    /// from the exchange. Currently listed contracts are checked against the
+    /// [catalog](Self::catalog); contracts that are no longer listed are
+    /// looked up in that day's quotes.
+    ///
+    /// # Arguments
+    /// * `contract_id` - Contract ID (e.g. "m2505" or "m2505-C-3000")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    pub async fn validate_contract(
+        &self,
+        contract_id: &str,
+        trade_date: &str,
+    ) -> Result<ContractStatus> {
+        let contract = ContractId::parse(contract_id)?;
+        let (variety_id, trade_type) = (contract.variety.clone(), contract.trade_type());
+        let day = day_number(trade_date).ok_or_else(|| {
+            Error::validation(
+                "trade_date",
+                format!("expected a YYYYMMDD date, got {:?}", trade_date),
+            )
+        })?;
+        let trade_date = format_day(day);
+
+        let contracts = self.catalog.contracts(&variety_id, trade_type).await?;
+        if contracts.is_empty() {
+            return Ok(ContractStatus::UnknownVariety { variety_id });
+        }
+
+        if let Some(info) = contracts
+            .iter()
+            .find(|c| c.contract_id.eq_ignore_ascii_case(contract_id))
+        {
+            let start = normalize_date(&info.start_trade_date);
+            let end = normalize_date(&info.end_trade_date);
+            if start
+                .as_deref()
+                .and_then(day_number)
+                .is_some_and(|d| day < d)
+            {
+                return Ok(ContractStatus::NotYetListed {
+                    start_trade_date: start,
+                });
+            }
+            if end.as_deref().and_then(day_number).is_some_and(|d| day > d) {
+                return Ok(ContractStatus::Expired {
+                    end_trade_date: end,
+                });
+            }
+            return Ok(ContractStatus::Active);
+        }
+
+        // Not currently listed: check whether it traded on that date
+        let req = QuotesRequest {
+            variety_id: Some(variety_id),
+            variety: None,
+            trade_date: trade_date.clone(),
+            trade_type,
+            lang: Some(Lang::Zh),
+            statistics_type: (trade_type == TradeType::Options).then_some(StatisticsType::Contract),
+        };
+        let quotes = self.market.get_day_quotes(&req, None).await?;
+        if quotes
+            .iter()
+            .any(|q| q.contract_id.eq_ignore_ascii_case(contract_id))
+        {
+            return Ok(ContractStatus::Active);
+        }
+
+        let month = contract.delivery_month();
+        if month.as_str() < &trade_date[..6] {
+            return Ok(ContractStatus::Expired {
+                end_trade_date: None,
+            });
+        }
+
+        // Beyond the furthest listed delivery month: not listed yet
+        let last_listed = contracts
+            .iter()
+            .filter_map(|c| contract_month(&c.contract_id))
+            .max();
+        if last_listed.is_some_and(|last| month > last) {
+            return Ok(ContractStatus::NotYetListed {
+                start_trade_date: None,
+            });
+        }
+        Ok(ContractStatus::NotFound)
+    }
+}
+
+/// Factory spot agios and futures day quotes of a variety on a trade date.
+async fn fetch_basis_inputs(
+    client: &Client,
+    variety: &str,
+    trade_date: &str,
+) -> Result<(Vec<FactorySpotAgio>, Vec<Quote>)> {
+    let agio_req = FactorySpotAgioRequest {
+        variety_id: variety.to_string(),
+        trade_date: trade_date.to_string(),
+    };
+    let quotes_req = QuotesRequest {
+        variety_id: Some(variety.to_string()),
+        variety: None,
+        trade_date: trade_date.to_string(),
+        trade_type: TradeType::Futures,
+        lang: None,
+        statistics_type: None,
+    };
+    let opts = RequestOptions::new().with_trade_type(TradeType::Futures);
+    tokio::try_join!(
+        client.delivery.get_factory_spot_agio(&agio_req, None),
+        client.market.get_day_quotes(&quotes_req, Some(opts)),
+    )
 }
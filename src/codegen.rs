@@ -0,0 +1,567 @@
+//! Draft endpoint wrappers from recorded traffic.
+//!
+//! Custom or extended DCE gateways often expose endpoints this crate does not
+//! ship. Given recorded request/response pairs for such an endpoint,
+//! [`generate`] infers field types from the JSON and emits draft Rust code in
+//! the crate's conventions: a request struct, response struct(s) and a
+//! service method skeleton. The output is a starting point to be reviewed and
+//! documented, not a finished binding.
+//!
+//! ```
+//! use dceapi_rs::codegen::{generate, Fixture};
+//!
+//! let fixture = Fixture::from_json(r#"{
+//!     "path": "/dceapi/forward/publicweb/custom/spotPrice",
+//!     "request": {"varietyId": "a", "tradeDate": "20250930"},
+//!     "response": {"code": 200, "msg": "success", "data": [
+//!         {"varietyName": "豆一", "spotPrice": "4120", "volume": 12}
+//!     ]}
+//! }"#)?;
+//!
+//! let code = generate("SpotPrice", &[fixture])?;
+//! assert!(code.models.contains("pub struct SpotPriceRequest"));
+//! assert!(code.service.contains("pub async fn get_spot_price"));
+//! # Ok::<(), dceapi_rs::Error>(())
+//! ```
+
+use std::fmt::Write as _;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+
+/// A recorded request/response pair for one endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    /// Endpoint path (e.g. "/dceapi/forward/publicweb/quotesdata/dayQuotes").
+    pub path: String,
+    /// Request body as sent.
+    #[serde(default)]
+    pub request: Value,
+    /// Response body as received, with or without the `{code, msg, data}` envelope.
+    pub response: Value,
+}
+
+impl Fixture {
+    /// Parse a fixture from JSON with `path`, `request` and `response` keys.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| Error::parse(json, e.to_string()))
+    }
+
+    /// Response payload with the API envelope removed.
+    fn payload(&self) -> &Value {
+        match &self.response {
+            Value::Object(map) if map.contains_key("code") && map.contains_key("data") => {
+                &map["data"]
+            }
+            other => other,
+        }
+    }
+}
+
+/// Draft code generated for one endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedEndpoint {
+    /// Request and response structs, for `models.rs`.
+    pub models: String,
+    /// Path constant and service method skeleton, for a service module.
+    pub service: String,
+}
+
+/// Generate draft code for an endpoint from one or more fixtures.
+///
+/// `name` is the PascalCase base name: the request becomes `{name}Request`,
+/// the response `{name}` and the method `get_{name}` in snake case. Fields
+/// missing from some fixtures become optional; numbers seen both as integers
+/// and floats become `f64`.
+///
+/// # Errors
+/// Returns a validation error if `name` is not a valid identifier, no
+/// fixtures are given, or the fixtures disagree on the endpoint path.
+pub fn generate(name: &str, fixtures: &[Fixture]) -> Result<GeneratedEndpoint> {
+    let valid_name = name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric());
+    if !valid_name {
+        return Err(Error::validation(
+            "name",
+            "name must be a PascalCase identifier",
+        ));
+    }
+    let Some(first) = fixtures.first() else {
+        return Err(Error::validation(
+            "fixtures",
+            "at least one fixture is required",
+        ));
+    };
+    if fixtures.iter().any(|f| f.path != first.path) {
+        return Err(Error::validation(
+            "fixtures",
+            "all fixtures must record the same endpoint path",
+        ));
+    }
+
+    let request = fixtures
+        .iter()
+        .map(|f| infer(&f.request))
+        .fold(Shape::Unknown, merge);
+    let response = fixtures
+        .iter()
+        .map(|f| infer(f.payload()))
+        .fold(Shape::Unknown, merge);
+
+    let request_type = format!("{}Request", name);
+    let mut models = String::new();
+    match &request {
+        Shape::Object(object) => render_request(&request_type, object, &mut models),
+        _ => render_request(&request_type, &ObjectShape::default(), &mut models),
+    }
+
+    let mut structs = Vec::new();
+    let return_type = response_type(name, &response, &mut structs);
+    for (struct_name, object) in &structs {
+        models.push('\n');
+        render_response(struct_name, object, &mut models);
+    }
+
+    let method = snake_case(name);
+    let constant = format!("PATH_GET_{}", method.to_uppercase());
+    let mut service = String::new();
+    let _ = writeln!(service, "/// API endpoint for {}.", words(name));
+    let _ = writeln!(service, "const {}: &str = \"{}\";", constant, first.path);
+    service.push('\n');
+    let _ = writeln!(service, "    /// Get {}.", words(name));
+    let _ = writeln!(service, "    ///");
+    let _ = writeln!(service, "    /// # Arguments");
+    let _ = writeln!(service, "    /// * `req` - Request parameters");
+    let _ = writeln!(service, "    /// * `opts` - Optional request options");
+    let _ = writeln!(service, "    pub async fn get_{}(", method);
+    let _ = writeln!(service, "        &self,");
+    let _ = writeln!(service, "        req: &{},", request_type);
+    let _ = writeln!(service, "        opts: Option<RequestOptions>,");
+    let _ = writeln!(service, "    ) -> Result<{}> {{", return_type);
+    let _ = writeln!(
+        service,
+        "        self.client.do_post({}, req, opts).await",
+        constant
+    );
+    let _ = writeln!(service, "    }}");
+
+    Ok(GeneratedEndpoint { models, service })
+}
+
+/// Inferred JSON shape, merged across samples.
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    /// Only `null` (or nothing) seen.
+    Unknown,
+    Bool,
+    Int,
+    Float,
+    Str,
+    /// Incompatible types seen.
+    Any,
+    Array(Box<Shape>),
+    Object(ObjectShape),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ObjectShape {
+    samples: usize,
+    fields: Vec<FieldShape>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FieldShape {
+    key: String,
+    shape: Shape,
+    seen: usize,
+    nullable: bool,
+}
+
+impl FieldShape {
+    /// Whether the field may be absent or null.
+    fn is_optional(&self, samples: usize) -> bool {
+        self.nullable || self.seen < samples
+    }
+}
+
+fn infer(value: &Value) -> Shape {
+    match value {
+        Value::Null => Shape::Unknown,
+        Value::Bool(_) => Shape::Bool,
+        Value::Number(n) if n.is_f64() => Shape::Float,
+        Value::Number(_) => Shape::Int,
+        Value::String(_) => Shape::Str,
+        Value::Array(items) => Shape::Array(Box::new(
+            items.iter().map(infer).fold(Shape::Unknown, merge),
+        )),
+        Value::Object(map) => Shape::Object(ObjectShape {
+            samples: 1,
+            fields: map
+                .iter()
+                .map(|(key, value)| FieldShape {
+                    key: key.clone(),
+                    shape: infer(value),
+                    seen: 1,
+                    nullable: value.is_null(),
+                })
+                .collect(),
+        }),
+    }
+}
+
+fn merge(a: Shape, b: Shape) -> Shape {
+    match (a, b) {
+        (Shape::Unknown, other) | (other, Shape::Unknown) => other,
+        (Shape::Int, Shape::Float) | (Shape::Float, Shape::Int) => Shape::Float,
+        (Shape::Array(a), Shape::Array(b)) => Shape::Array(Box::new(merge(*a, *b))),
+        (Shape::Object(mut a), Shape::Object(b)) => {
+            a.samples += b.samples;
+            for field in b.fields {
+                match a.fields.iter_mut().find(|f| f.key == field.key) {
+                    Some(existing) => {
+                        existing.shape = merge(existing.shape.clone(), field.shape);
+                        existing.seen += field.seen;
+                        existing.nullable |= field.nullable;
+                    }
+                    None => a.fields.push(field),
+                }
+            }
+            Shape::Object(a)
+        }
+        (a, b) if a == b => a,
+        _ => Shape::Any,
+    }
+}
+
+/// Rust type for a response shape, collecting nested structs to emit.
+fn response_type(name: &str, shape: &Shape, structs: &mut Vec<(String, ObjectShape)>) -> String {
+    match shape {
+        Shape::Bool => "bool".to_string(),
+        Shape::Int => "i64".to_string(),
+        Shape::Float => "f64".to_string(),
+        Shape::Str => "String".to_string(),
+        Shape::Unknown | Shape::Any => "Value".to_string(),
+        Shape::Array(item) => format!("Vec<{}>", response_type(name, item, structs)),
+        Shape::Object(object) => {
+            structs.push((name.to_string(), object.clone()));
+            name.to_string()
+        }
+    }
+}
+
+fn render_request(name: &str, object: &ObjectShape, out: &mut String) {
+    let _ = writeln!(
+        out,
+        "/// Request for {}.",
+        words(name.trim_end_matches("Request"))
+    );
+    let _ = writeln!(out, "#[derive(Debug, Clone, Serialize)]");
+    let _ = writeln!(out, "#[serde(rename_all = \"camelCase\")]");
+    let _ = writeln!(out, "pub struct {} {{", name);
+    for field in &object.fields {
+        let ty = match &field.shape {
+            Shape::Bool => "bool",
+            Shape::Int => "i32",
+            Shape::Float => "f64",
+            Shape::Str => "String",
+            _ => "Value",
+        };
+        let _ = writeln!(out, "    /// TODO: document `{}`.", field.key);
+        let field_name = field_ident(&field.key);
+        if needs_rename(&field.key) {
+            let _ = writeln!(out, "    #[serde(rename = \"{}\")]", field.key);
+        }
+        if field.is_optional(object.samples) {
+            let _ = writeln!(
+                out,
+                "    #[serde(skip_serializing_if = \"Option::is_none\")]"
+            );
+            let _ = writeln!(out, "    pub {}: Option<{}>,", field_name, ty);
+        } else {
+            let _ = writeln!(out, "    pub {}: {},", field_name, ty);
+        }
+    }
+    let _ = writeln!(out, "}}");
+}
+
+fn render_response(name: &str, object: &ObjectShape, out: &mut String) {
+    let mut nested = Vec::new();
+    let _ = writeln!(out, "/// {} entry.", capitalize(&words(name)));
+    let _ = writeln!(out, "#[derive(Debug, Clone, Deserialize)]");
+    let _ = writeln!(out, "#[serde(rename_all = \"camelCase\")]");
+    let _ = writeln!(out, "pub struct {} {{", name);
+    for field in &object.fields {
+        let field_name = field_ident(&field.key);
+        let nested_name = format!(
+            "{}{}",
+            name,
+            pascal_case(field_name.trim_start_matches("r#"))
+        );
+        let ty = response_type(&nested_name, &field.shape, &mut nested);
+        let rename = if needs_rename(&field.key) {
+            format!("rename = \"{}\", ", field.key)
+        } else {
+            String::new()
+        };
+
+        let _ = writeln!(out, "    /// TODO: document `{}`.", field.key);
+        match &field.shape {
+            Shape::Str => {
+                let _ = writeln!(
+                    out,
+                    "    #[serde({}default, deserialize_with = \"deserialize_nullable_string\")]",
+                    rename
+                );
+                let _ = writeln!(out, "    pub {}: String,", field_name);
+            }
+            Shape::Bool | Shape::Int | Shape::Float | Shape::Object(_)
+                if field.is_optional(object.samples) =>
+            {
+                let _ = writeln!(out, "    #[serde({}default)]", rename);
+                let _ = writeln!(out, "    pub {}: Option<{}>,", field_name, ty);
+            }
+            // Generated structs do not implement `Default`, so a required
+            // nested object has no default to fall back on.
+            Shape::Object(_) => {
+                if !rename.is_empty() {
+                    let _ = writeln!(out, "    #[serde({})]", rename.trim_end_matches(", "));
+                }
+                let _ = writeln!(out, "    pub {}: {},", field_name, ty);
+            }
+            _ => {
+                let _ = writeln!(out, "    #[serde({}default)]", rename);
+                let _ = writeln!(out, "    pub {}: {},", field_name, ty);
+            }
+        }
+    }
+    let _ = writeln!(out, "}}");
+
+    for (struct_name, nested_object) in nested {
+        out.push('\n');
+        render_response(&struct_name, &nested_object, out);
+    }
+}
+
+/// Rust keywords, strict and reserved, that can be used as raw identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Keywords that cannot be raw identifiers.
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "super"];
+
+/// Whether `rename_all = "camelCase"` on the field name does not reproduce
+/// the JSON key.
+fn needs_rename(key: &str) -> bool {
+    let snake = snake_case(key);
+    field_ident(key).trim_start_matches("r#") != snake || camel_case(&snake) != key
+}
+
+/// Snake-case field name, escaping Rust keywords and keys that do not start
+/// with a letter.
+fn field_ident(key: &str) -> String {
+    let name = snake_case(key);
+    if name.is_empty() {
+        "field".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("field_{}", name)
+    } else if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+fn snake_case(s: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+fn camel_case(snake: &str) -> String {
+    let pascal = pascal_case(snake);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(c) => c.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn pascal_case(s: &str) -> String {
+    snake_case(s).split('_').map(capitalize).collect()
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Lowercase words of a PascalCase name ("SpotPrice" -> "spot price").
+fn words(name: &str) -> String {
+    snake_case(name).replace('_', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(json: &str) -> Fixture {
+        Fixture::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn generates_models_and_service() {
+        let code = generate(
+            "SpotPrice",
+            &[fixture(
+                r#"{
+                    "path": "/dceapi/forward/publicweb/custom/spotPrice",
+                    "request": {"varietyId": "a", "tradeDate": "20250930"},
+                    "response": {"code": 200, "msg": "success", "data": [
+                        {"varietyName": "豆一", "spotPrice": 4120.5, "volume": 12,
+                         "detail": {"area": "东北"}}
+                    ]}
+                }"#,
+            )],
+        )
+        .unwrap();
+
+        let expected_models = r#"/// Request for spot price.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPriceRequest {
+    /// TODO: document `tradeDate`.
+    pub trade_date: String,
+    /// TODO: document `varietyId`.
+    pub variety_id: String,
+}
+
+/// Spot price entry.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPrice {
+    /// TODO: document `detail`.
+    pub detail: SpotPriceDetail,
+    /// TODO: document `spotPrice`.
+    #[serde(default)]
+    pub spot_price: f64,
+    /// TODO: document `varietyName`.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub variety_name: String,
+    /// TODO: document `volume`.
+    #[serde(default)]
+    pub volume: i64,
+}
+
+/// Spot price detail entry.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPriceDetail {
+    /// TODO: document `area`.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub area: String,
+}
+"#;
+        assert_eq!(code.models, expected_models);
+
+        let expected_service = r#"/// API endpoint for spot price.
+const PATH_GET_SPOT_PRICE: &str = "/dceapi/forward/publicweb/custom/spotPrice";
+
+    /// Get spot price.
+    ///
+    /// # Arguments
+    /// * `req` - Request parameters
+    /// * `opts` - Optional request options
+    pub async fn get_spot_price(
+        &self,
+        req: &SpotPriceRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<SpotPrice>> {
+        self.client.do_post(PATH_GET_SPOT_PRICE, req, opts).await
+    }
+"#;
+        assert_eq!(code.service, expected_service);
+    }
+
+    #[test]
+    fn fields_missing_from_a_fixture_become_optional() {
+        let code = generate(
+            "Rate",
+            &[
+                fixture(r#"{"path": "/p", "response": {"rate": 1, "note": "x"}}"#),
+                fixture(r#"{"path": "/p", "response": {"rate": 1.5}}"#),
+            ],
+        )
+        .unwrap();
+        assert!(code
+            .models
+            .contains("    #[serde(default)]\n    pub rate: f64,"));
+        assert!(code.service.contains("-> Result<Rate>"));
+    }
+
+    #[test]
+    fn escapes_keywords_and_invalid_identifiers() {
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("async"), "r#async");
+        assert_eq!(field_ident("dyn"), "r#dyn");
+        assert_eq!(field_ident("self"), "self_");
+        assert_eq!(field_ident("Self"), "self_");
+        assert_eq!(field_ident("crate"), "crate_");
+        assert_eq!(field_ident("super"), "super_");
+        assert_eq!(field_ident("1stPrice"), "field_1st_price");
+        assert_eq!(field_ident("名称"), "field");
+        assert_eq!(field_ident("tradeDate"), "trade_date");
+
+        assert!(!needs_rename("type"));
+        assert!(!needs_rename("tradeDate"));
+        assert!(needs_rename("self"));
+        assert!(needs_rename("1stPrice"));
+        assert!(needs_rename("trade_date"));
+    }
+
+    #[test]
+    fn renames_nested_objects_with_escaped_keys() {
+        let code = generate(
+            "Item",
+            &[fixture(
+                r#"{"path": "/p", "response": {"self": {"id": 1}}}"#,
+            )],
+        )
+        .unwrap();
+        assert!(code
+            .models
+            .contains("    #[serde(rename = \"self\")]\n    pub self_: ItemSelf,"));
+        assert!(code.models.contains("pub struct ItemSelf {"));
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(generate("spotPrice", &[]).is_err());
+        assert!(generate("SpotPrice", &[]).is_err());
+        let a = fixture(r#"{"path": "/a", "response": {}}"#);
+        let b = fixture(r#"{"path": "/b", "response": {}}"#);
+        assert!(generate("SpotPrice", &[a, b]).is_err());
+    }
+}
@@ -1,21 +1,28 @@
 //! Configuration for the DCE API client.
 
 use std::env;
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::error::{Error, Result};
+use crate::auth::{ApiKeyAuth, AuthScheme};
+use crate::catalog::CatalogPolicy;
+use crate::error::{Result, ValidationErrors};
+use crate::models::{Lang, TradeType};
 
 /// Default API base URL.
 pub const DEFAULT_BASE_URL: &str = "http://www.dce.com.cn";
 
+/// Exchange website that relative article URLs are resolved against.
+pub const DCE_SITE_URL: &str = "http://www.dce.com.cn";
+
 /// Default HTTP timeout in seconds.
 pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
 /// Default language.
-pub const DEFAULT_LANG: &str = "zh";
+pub const DEFAULT_LANG: Lang = Lang::Zh;
 
-/// Default trade type (1 = futures).
-pub const DEFAULT_TRADE_TYPE: i32 = 1;
+/// Default trade type.
+pub const DEFAULT_TRADE_TYPE: TradeType = TradeType::Futures;
 
 /// Environment variable name for API key.
 pub const ENV_API_KEY: &str = "DCE_API_KEY";
@@ -38,11 +45,27 @@ pub struct Config {
     /// HTTP request timeout. Defaults to 30 seconds.
     pub timeout: Duration,
 
-    /// Language for API responses. "zh" or "en". Defaults to "zh".
-    pub lang: String,
+    /// Language for API responses. Defaults to [`Lang::Zh`].
+    pub lang: Lang,
+
+    /// Trade type. Defaults to [`TradeType::Futures`].
+    pub trade_type: TradeType,
+
+    /// Staleness policy for the shared contract catalog. Defaults to read-through
+    /// with a 12 hour TTL.
+    pub catalog_policy: CatalogPolicy,
 
-    /// Trade type. 1 = futures, 2 = options. Defaults to 1.
-    pub trade_type: i32,
+    /// Authentication scheme applied to every request. Defaults to [`ApiKeyAuth`]
+    /// (apikey header plus bearer token).
+    pub auth_scheme: Arc<dyn AuthScheme>,
+
+    /// Fail on unknown response fields and on nulls in non-optional fields
+    /// instead of defaulting them. Defaults to false.
+    pub strict_parsing: bool,
+
+    /// Remove subtotal and total rows (小计/总计) from quote responses.
+    /// Defaults to false.
+    pub drop_subtotals: bool,
 }
 
 impl Default for Config {
@@ -61,8 +84,12 @@ impl Config {
             api_key: String::new(),
             secret: String::new(),
             timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
-            lang: DEFAULT_LANG.to_string(),
+            lang: DEFAULT_LANG,
             trade_type: DEFAULT_TRADE_TYPE,
+            catalog_policy: CatalogPolicy::default(),
+            auth_scheme: Arc::new(ApiKeyAuth),
+            strict_parsing: false,
+            drop_subtotals: false,
         }
     }
 
@@ -100,29 +127,67 @@ impl Config {
         self
     }
 
-    /// Set the language.
-    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+    /// Set the language. Also accepts `"zh"`/`"en"` through the deprecated
+    /// lenient conversion.
+    pub fn with_lang(mut self, lang: impl Into<Lang>) -> Self {
         self.lang = lang.into();
         self
     }
 
-    /// Set the trade type.
-    pub fn with_trade_type(mut self, trade_type: i32) -> Self {
-        self.trade_type = trade_type;
+    /// Set the trade type. Also accepts `"1"`/`"2"` and `1`/`2` through the
+    /// deprecated lenient conversions.
+    pub fn with_trade_type(mut self, trade_type: impl Into<TradeType>) -> Self {
+        self.trade_type = trade_type.into();
+        self
+    }
+
+    /// Set the contract catalog staleness policy.
+    pub fn with_catalog_policy(mut self, policy: CatalogPolicy) -> Self {
+        self.catalog_policy = policy;
+        self
+    }
+
+    /// Set the authentication scheme (e.g. [`MtlsAuth`](crate::MtlsAuth) for gateways
+    /// requiring client certificates).
+    pub fn with_auth_scheme(mut self, scheme: impl AuthScheme + 'static) -> Self {
+        self.auth_scheme = Arc::new(scheme);
+        self
+    }
+
+    /// Enable or disable strict response parsing.
+    ///
+    /// In strict mode, responses containing fields the models do not know, or
+    /// nulls in fields that are not optional, fail with [`Error::Parse`](crate::Error::Parse)
+    /// so schema changes upstream are detected immediately. Unknown fields
+    /// are detected in every response data model (through its
+    /// [`Extras`](crate::Extras)), but not in the response envelope or the
+    /// token response.
+    pub fn with_strict_parsing(mut self, strict: bool) -> Self {
+        self.strict_parsing = strict;
+        self
+    }
+
+    /// Enable or disable removal of subtotal rows from quote responses.
+    ///
+    /// Can be overridden per request with
+    /// [`RequestOptions::with_drop_subtotals`](crate::RequestOptions::with_drop_subtotals).
+    pub fn with_drop_subtotals(mut self, drop: bool) -> Self {
+        self.drop_subtotals = drop;
         self
     }
 
     /// Validate the configuration.
     ///
-    /// Returns an error if required fields are missing.
+    /// Returns an error listing every required field that is missing.
     pub fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
         if self.api_key.is_empty() {
-            return Err(Error::validation("api_key", "API key is required"));
+            errors.add("api_key", "API key is required");
         }
         if self.secret.is_empty() {
-            return Err(Error::validation("secret", "secret is required"));
+            errors.add("secret", "secret is required");
         }
-        Ok(())
+        errors.into_result()
     }
 
     /// Apply default values to empty fields.
@@ -133,11 +198,5 @@ impl Config {
         if self.timeout.is_zero() {
             self.timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS);
         }
-        if self.lang.is_empty() {
-            self.lang = DEFAULT_LANG.to_string();
-        }
-        if self.trade_type == 0 {
-            self.trade_type = DEFAULT_TRADE_TYPE;
-        }
     }
 }
@@ -0,0 +1,349 @@
+//! Continuous main-contract (主力连续) series.
+//!
+//! Futures contracts expire, so a long price history of a variety has to be
+//! stitched from several contracts. [`ContinuousSeries`] follows the
+//! dominant contract day by day, records every roll and can back-adjust
+//! earlier prices so the series has no gaps at the rolls.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candle;
+use crate::contract_id::ContractId;
+use crate::models::Quote;
+
+/// Measure used to pick the dominant contract of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RollBy {
+    /// Highest open interest, ties broken by volume.
+    #[default]
+    OpenInterest,
+    /// Highest volume, ties broken by open interest.
+    Volume,
+}
+
+impl RollBy {
+    /// Sort key of a contract's open interest and volume, largest first.
+    pub(crate) fn key(&self, open_interest: i64, volume: i64) -> (i64, i64) {
+        match self {
+            RollBy::OpenInterest => (open_interest, volume),
+            RollBy::Volume => (volume, open_interest),
+        }
+    }
+}
+
+/// Price adjustment applied to the days before each roll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Adjustment {
+    /// Raw prices of the contract held each day.
+    #[default]
+    None,
+    /// Add the close difference between the new and old contract at each
+    /// roll to all earlier prices.
+    Difference,
+    /// Multiply all earlier prices by the close ratio between the new and
+    /// old contract at each roll.
+    Ratio,
+}
+
+/// Options for building a continuous series.
+#[derive(Debug, Clone, Default)]
+pub struct ContinuousOptions {
+    /// How the dominant contract is picked. Defaults to open interest.
+    pub roll_by: RollBy,
+    /// Back-adjustment of prices before rolls. Defaults to none.
+    pub adjustment: Adjustment,
+}
+
+impl ContinuousOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how the dominant contract is picked.
+    pub fn with_roll_by(mut self, roll_by: RollBy) -> Self {
+        self.roll_by = roll_by;
+        self
+    }
+
+    /// Set the price adjustment.
+    pub fn with_adjustment(mut self, adjustment: Adjustment) -> Self {
+        self.adjustment = adjustment;
+        self
+    }
+}
+
+/// A switch of the main contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Roll {
+    /// First trade date (YYYYMMDD) on the new contract.
+    pub date: String,
+    /// Contract held before the roll.
+    pub from: String,
+    /// Contract held from the roll date.
+    pub to: String,
+    /// Close of the old contract on the roll date, if it still traded.
+    pub from_close: Option<f64>,
+    /// Close of the new contract on the roll date.
+    pub to_close: f64,
+}
+
+impl Roll {
+    /// Close of the new contract minus the old one, if both traded.
+    pub fn gap(&self) -> Option<f64> {
+        self.from_close.map(|from| self.to_close - from)
+    }
+}
+
+/// Daily candles of the main contract of one variety.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContinuousSeries {
+    /// Variety ID (e.g. "m").
+    pub variety: String,
+    /// One candle per trade date, in date order. Each keeps the ID of the
+    /// contract it was taken from.
+    pub candles: Vec<Candle>,
+    /// Main contract switches, in date order.
+    pub rolls: Vec<Roll>,
+}
+
+impl ContinuousSeries {
+    /// Build a series from day quotes keyed by trade date, as returned by
+    /// [`MarketService::get_day_quotes_range`](crate::MarketService::get_day_quotes_range).
+    ///
+    /// Only futures contracts of `variety` are considered. The main contract
+    /// only rolls forward: it switches when a later contract becomes
+    /// dominant, or when the current one no longer trades.
+    pub fn build(
+        variety: &str,
+        days: &BTreeMap<String, Vec<Quote>>,
+        options: &ContinuousOptions,
+    ) -> ContinuousSeries {
+        let mut candles = Vec::new();
+        let mut rolls = Vec::new();
+        // Index into `candles` of the first candle after each roll.
+        let mut roll_starts = Vec::new();
+        let mut current: Option<ContractId> = None;
+
+        for (date, quotes) in days {
+            let day: Vec<(ContractId, Candle)> = quotes
+                .iter()
+                .filter_map(|q| {
+                    let id = q.parsed_contract_id()?;
+                    if id.is_option() || !id.variety.eq_ignore_ascii_case(variety) {
+                        return None;
+                    }
+                    Some((id, Candle::from_quote(q, date.as_str()).ok()?))
+                })
+                .collect();
+            let Some((dominant, _)) = day.iter().max_by_key(|(id, c)| {
+                (
+                    options.roll_by.key(c.open_interest, c.volume),
+                    std::cmp::Reverse(id.clone()),
+                )
+            }) else {
+                continue;
+            };
+
+            let held = current
+                .as_ref()
+                .and_then(|cur| day.iter().find(|(id, _)| id == cur));
+            let next = match (&current, held) {
+                (Some(cur), Some(_)) if dominant <= cur => cur.clone(),
+                _ => dominant.clone(),
+            };
+
+            let Some((_, candle)) = day.iter().find(|(id, _)| *id == next) else {
+                continue;
+            };
+            if let Some(cur) = current.as_ref().filter(|cur| **cur != next) {
+                rolls.push(Roll {
+                    date: date.clone(),
+                    from: cur.to_string(),
+                    to: next.to_string(),
+                    from_close: held.map(|(_, c)| c.close),
+                    to_close: candle.close,
+                });
+                roll_starts.push(candles.len());
+            }
+            candles.push(candle.clone());
+            current = Some(next);
+        }
+
+        adjust(&mut candles, &rolls, &roll_starts, options.adjustment);
+        ContinuousSeries {
+            variety: variety.to_string(),
+            candles,
+            rolls,
+        }
+    }
+
+    /// Main contract ID on a trade date.
+    pub fn contract_on(&self, date: &str) -> Option<&str> {
+        self.candles
+            .iter()
+            .find(|c| c.date == date)
+            .map(|c| c.contract_id.as_str())
+    }
+
+    /// Whether the series has no candles.
+    pub fn is_empty(&self) -> bool {
+        self.candles.is_empty()
+    }
+}
+
+/// Back-adjust the candles before each roll, latest roll first so the
+/// adjustments accumulate.
+fn adjust(candles: &mut [Candle], rolls: &[Roll], roll_starts: &[usize], adjustment: Adjustment) {
+    if adjustment == Adjustment::None {
+        return;
+    }
+    for (roll, &start) in rolls.iter().zip(roll_starts).rev() {
+        let Some(from_close) = roll.from_close else {
+            continue;
+        };
+        // Each price p becomes p * scale + offset.
+        let (scale, offset) = match adjustment {
+            Adjustment::Difference => (1.0, roll.to_close - from_close),
+            Adjustment::Ratio if from_close != 0.0 => (roll.to_close / from_close, 0.0),
+            _ => continue,
+        };
+        for candle in &mut candles[..start] {
+            for price in [
+                &mut candle.open,
+                &mut candle.high,
+                &mut candle.low,
+                &mut candle.close,
+            ] {
+                *price = *price * scale + offset;
+            }
+            candle.settle = candle.settle.map(|p| p * scale + offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(contract_id: &str, close: f64, open_interest: i64, volume: i64) -> Quote {
+        serde_json::from_value(serde_json::json!({
+            "contractId": contract_id,
+            "open": close, "high": close, "low": close, "close": close,
+            "openInterest": open_interest, "volumn": volume,
+        }))
+        .unwrap()
+    }
+
+    fn days() -> BTreeMap<String, Vec<Quote>> {
+        BTreeMap::from([
+            (
+                "20250102".to_string(),
+                vec![
+                    quote("m2505", 3000.0, 100, 10),
+                    quote("m2509", 3050.0, 50, 80),
+                    quote("m2505-C-3000", 120.0, 1000, 1000),
+                    quote("y2505", 8000.0, 5000, 5000),
+                ],
+            ),
+            (
+                "20250103".to_string(),
+                vec![
+                    quote("m2505", 3010.0, 60, 10),
+                    quote("m2509", 3080.0, 120, 90),
+                ],
+            ),
+            (
+                "20250106".to_string(),
+                vec![
+                    quote("m2505", 3020.0, 200, 10),
+                    quote("m2509", 3090.0, 100, 90),
+                ],
+            ),
+            ("20250107".to_string(), vec![quote("m2601", 3200.0, 10, 10)]),
+        ])
+    }
+
+    #[test]
+    fn roll_by_orders_the_measures() {
+        assert_eq!(RollBy::OpenInterest.key(100, 5), (100, 5));
+        assert_eq!(RollBy::Volume.key(100, 5), (5, 100));
+    }
+
+    #[test]
+    fn rolls_forward_only() {
+        let series = ContinuousSeries::build("M", &days(), &ContinuousOptions::new());
+        let held: Vec<&str> = series
+            .candles
+            .iter()
+            .map(|c| c.contract_id.as_str())
+            .collect();
+        // m2505 regains the most open interest on the 6th, but the series
+        // does not roll back to it.
+        assert_eq!(held, ["m2505", "m2509", "m2509", "m2601"]);
+        assert_eq!(series.variety, "M");
+        assert_eq!(series.contract_on("20250106"), Some("m2509"));
+        assert_eq!(series.contract_on("20250108"), None);
+
+        let rolls: Vec<_> = series
+            .rolls
+            .iter()
+            .map(|r| (r.date.as_str(), r.from.as_str(), r.to.as_str(), r.gap()))
+            .collect();
+        assert_eq!(
+            rolls,
+            [
+                ("20250103", "m2505", "m2509", Some(70.0)),
+                ("20250107", "m2509", "m2601", None),
+            ]
+        );
+        assert_eq!(series.candles[0].close, 3000.0);
+    }
+
+    #[test]
+    fn volume_picks_the_most_traded_contract() {
+        let options = ContinuousOptions::new().with_roll_by(RollBy::Volume);
+        let series = ContinuousSeries::build("m", &days(), &options);
+        assert_eq!(series.contract_on("20250102"), Some("m2509"));
+        assert_eq!(series.rolls.len(), 1);
+    }
+
+    #[test]
+    fn ties_go_to_the_nearer_contract() {
+        let days = BTreeMap::from([(
+            "20250102".to_string(),
+            vec![
+                quote("m2509", 3050.0, 100, 10),
+                quote("m2505", 3000.0, 100, 10),
+            ],
+        )]);
+        let series = ContinuousSeries::build("m", &days, &ContinuousOptions::new());
+        assert_eq!(series.contract_on("20250102"), Some("m2505"));
+    }
+
+    #[test]
+    fn adjustments_shift_prices_before_rolls() {
+        let build = |adjustment| {
+            let options = ContinuousOptions::new().with_adjustment(adjustment);
+            let series = ContinuousSeries::build("m", &days(), &options);
+            series.candles.iter().map(|c| c.close).collect::<Vec<_>>()
+        };
+        assert_eq!(build(Adjustment::None), [3000.0, 3080.0, 3090.0, 3200.0]);
+        // The roll to m2601 has no old close and leaves prices unadjusted.
+        assert_eq!(
+            build(Adjustment::Difference),
+            [3070.0, 3080.0, 3090.0, 3200.0]
+        );
+        let ratio = build(Adjustment::Ratio);
+        assert!((ratio[0] - 3000.0 * 3080.0 / 3010.0).abs() < 1e-9);
+        assert_eq!(ratio[1..], [3080.0, 3090.0, 3200.0]);
+    }
+
+    #[test]
+    fn empty_days_give_an_empty_series() {
+        let series = ContinuousSeries::build("c", &days(), &ContinuousOptions::new());
+        assert!(series.is_empty() && series.rolls.is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+//! Parsing of contract IDs.
+//!
+//! DCE contract IDs encode the variety, delivery month and, for options,
+//! the option type and strike: "m2505", "a2501-C-4500". Arbitrage
+//! contracts combine two legs under a strategy prefix: "SP m2505&m2509".
+//! [`ContractId`] and [`ArbitrageId`] split these into typed parts so
+//! quotes can be grouped and sorted without string handling.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::models::{ArbitrageContract, ContractInfo, Quote, TradeType};
+use crate::variety::VarietyCode;
+
+/// Option type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OptionKind {
+    /// Call option ("C").
+    Call,
+    /// Put option ("P").
+    Put,
+}
+
+impl OptionKind {
+    /// Code used in contract IDs ("C" or "P").
+    pub fn code(&self) -> &'static str {
+        match self {
+            OptionKind::Call => "C",
+            OptionKind::Put => "P",
+        }
+    }
+}
+
+impl fmt::Display for OptionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Option part of a contract ID.
+#[derive(Debug, Clone, Copy)]
+pub struct OptionSpec {
+    /// Call or put.
+    pub kind: OptionKind,
+    /// Strike price.
+    pub strike: f64,
+}
+
+impl PartialEq for OptionSpec {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OptionSpec {}
+
+impl Hash for OptionSpec {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.strike.to_bits().hash(state);
+    }
+}
+
+impl PartialOrd for OptionSpec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OptionSpec {
+    /// Orders by strike, then calls before puts.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.strike
+            .total_cmp(&other.strike)
+            .then(self.kind.cmp(&other.kind))
+    }
+}
+
+/// A parsed futures or options contract ID.
+///
+/// Orders by variety, delivery month, then strike and option type, with
+/// futures before options of the same month.
+///
+/// ```
+/// use dceapi_rs::{ContractId, OptionKind};
+///
+/// let id: ContractId = "a2501-C-4500".parse().unwrap();
+/// assert_eq!(id.variety, "a");
+/// assert_eq!((id.year, id.month), (2025, 1));
+/// assert_eq!(id.option.unwrap().kind, OptionKind::Call);
+/// assert_eq!(id.option.unwrap().strike, 4500.0);
+/// assert_eq!(id.to_string(), "a2501-C-4500");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ContractId {
+    /// Variety code, lower case ("m").
+    pub variety: String,
+    /// Delivery year (2025).
+    pub year: i32,
+    /// Delivery month (1-12).
+    pub month: u32,
+    /// Option type and strike, for option contracts.
+    pub option: Option<OptionSpec>,
+}
+
+impl ContractId {
+    /// Parse a contract ID such as "m2505", "a2501-C-4500" or "m2505P3000".
+    pub fn parse(raw: &str) -> Result<ContractId> {
+        let invalid = |reason: &str| {
+            Error::validation(
+                "contract_id",
+                format!("invalid contract ID {:?}: {}", raw, reason),
+            )
+        };
+
+        let text = raw.trim();
+        let digits_at = text
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .ok_or_else(|| invalid("missing delivery month"))?;
+        let (variety, rest) = text.split_at(digits_at);
+        if variety.is_empty() {
+            return Err(invalid("missing variety code"));
+        }
+        let month_digits = rest
+            .get(..4)
+            .filter(|d| d.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(|| invalid("expected a YYMM delivery month"))?;
+        let year = 2000 + month_digits[..2].parse::<i32>().unwrap_or_default();
+        let month = month_digits[2..].parse::<u32>().unwrap_or_default();
+        if !(1..=12).contains(&month) {
+            return Err(invalid("delivery month out of range"));
+        }
+
+        let suffix = rest[4..].trim_start_matches('-');
+        let option = if suffix.is_empty() {
+            None
+        } else {
+            let kind = match suffix.chars().next() {
+                Some('C' | 'c') => OptionKind::Call,
+                Some('P' | 'p') => OptionKind::Put,
+                _ => return Err(invalid("expected C or P after the delivery month")),
+            };
+            let strike = suffix[1..]
+                .trim_start_matches('-')
+                .parse::<f64>()
+                .ok()
+                .filter(|s| s.is_finite() && *s >= 0.0)
+                .ok_or_else(|| invalid("invalid strike"))?;
+            Some(OptionSpec { kind, strike })
+        };
+
+        Ok(ContractId {
+            variety: variety.to_lowercase(),
+            year,
+            month,
+            option,
+        })
+    }
+
+    /// Variety as a [`VarietyCode`].
+    pub fn variety_code(&self) -> VarietyCode {
+        VarietyCode::from(self.variety.as_str())
+    }
+
+    /// Delivery month as YYYYMM.
+    pub fn delivery_month(&self) -> String {
+        format!("{:04}{:02}", self.year, self.month)
+    }
+
+    /// Underlying futures contract (the ID itself for futures).
+    pub fn underlying(&self) -> ContractId {
+        ContractId {
+            option: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether this is an option contract.
+    pub fn is_option(&self) -> bool {
+        self.option.is_some()
+    }
+
+    /// Trade type the contract is listed under.
+    pub fn trade_type(&self) -> TradeType {
+        if self.is_option() {
+            TradeType::Options
+        } else {
+            TradeType::Futures
+        }
+    }
+
+    /// Whether this is a call option.
+    pub fn is_call(&self) -> bool {
+        self.option.is_some_and(|o| o.kind == OptionKind::Call)
+    }
+
+    /// Whether this is a put option.
+    pub fn is_put(&self) -> bool {
+        self.option.is_some_and(|o| o.kind == OptionKind::Put)
+    }
+
+    /// Strike price, for option contracts.
+    pub fn strike(&self) -> Option<f64> {
+        self.option.map(|o| o.strike)
+    }
+}
+
+impl FromStr for ContractId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ContractId::parse(s)
+    }
+}
+
+impl fmt::Display for ContractId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{:02}{:02}", self.variety, self.year % 100, self.month)?;
+        if let Some(option) = &self.option {
+            write!(f, "-{}-{}", option.kind, option.strike)?;
+        }
+        Ok(())
+    }
+}
+
+/// A parsed arbitrage contract ID such as "SP m2505&m2509".
+///
+/// ```
+/// use dceapi_rs::ArbitrageId;
+///
+/// let id: ArbitrageId = "SPC a2505&m2505".parse().unwrap();
+/// assert_eq!(id.strategy, "SPC");
+/// assert_eq!(id.legs[0].variety, "a");
+/// assert_eq!(id.legs[1].variety, "m");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArbitrageId {
+    /// Strategy prefix, e.g. "SP" (calendar spread) or "SPC"
+    /// (inter-commodity spread).
+    pub strategy: String,
+    /// Contract legs in order.
+    pub legs: Vec<ContractId>,
+}
+
+impl ArbitrageId {
+    /// Parse an arbitrage contract ID.
+    pub fn parse(raw: &str) -> Result<ArbitrageId> {
+        let text = raw.trim();
+        let (strategy, legs) = match text.split_once(char::is_whitespace) {
+            Some((strategy, legs)) => (strategy, legs.trim()),
+            None => {
+                let at = text
+                    .find(|c: char| !c.is_ascii_uppercase())
+                    .unwrap_or(text.len());
+                text.split_at(at)
+            }
+        };
+        if strategy.is_empty() || !legs.contains('&') {
+            return Err(Error::validation(
+                "arbi_contract_id",
+                format!(
+                    "invalid arbitrage contract ID {:?}, expected \"SP m2505&m2509\"",
+                    raw
+                ),
+            ));
+        }
+        let legs = legs
+            .split('&')
+            .map(ContractId::parse)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(ArbitrageId {
+            strategy: strategy.to_string(),
+            legs,
+        })
+    }
+}
+
+impl FromStr for ArbitrageId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ArbitrageId::parse(s)
+    }
+}
+
+impl fmt::Display for ArbitrageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.strategy)?;
+        for (i, leg) in self.legs.iter().enumerate() {
+            if i > 0 {
+                f.write_str("&")?;
+            }
+            write!(f, "{}", leg)?;
+        }
+        Ok(())
+    }
+}
+
+impl Quote {
+    /// Parsed `contract_id`, or `None` for summary rows and night quotes
+    /// that only carry `deliv_month`.
+    pub fn parsed_contract_id(&self) -> Option<ContractId> {
+        ContractId::parse(&self.contract_id).ok()
+    }
+}
+
+impl ContractInfo {
+    /// Parsed `contract_id`.
+    pub fn parsed_contract_id(&self) -> Option<ContractId> {
+        ContractId::parse(&self.contract_id).ok()
+    }
+}
+
+impl ArbitrageContract {
+    /// Parsed `arbi_contract_id`.
+    pub fn parsed_arbi_contract_id(&self) -> Option<ArbitrageId> {
+        ArbitrageId::parse(&self.arbi_contract_id).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(raw: &str) -> ContractId {
+        ContractId::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn parses_futures_and_option_forms() {
+        let futures = id(" M2509 ");
+        assert_eq!(futures.variety, "m");
+        assert_eq!((futures.year, futures.month), (2025, 9));
+        assert_eq!(futures.delivery_month(), "202509");
+        assert_eq!(futures.trade_type(), TradeType::Futures);
+        assert_eq!(futures.to_string(), "m2509");
+
+        for raw in ["m2505-P-3000", "m2505P3000", "m2505-p3000", "m2505P-3000"] {
+            let option = id(raw);
+            assert!(option.is_put() && !option.is_call(), "{raw}");
+            assert_eq!(option.strike(), Some(3000.0), "{raw}");
+            assert_eq!(option.trade_type(), TradeType::Options, "{raw}");
+            assert_eq!(option.underlying(), id("m2505"), "{raw}");
+            assert_eq!(option.to_string(), "m2505-P-3000", "{raw}");
+        }
+        assert_eq!(id("jm2601-C-1250.5").strike(), Some(1250.5));
+        assert_eq!(id("jm2601-C-1250.5").variety, "jm");
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        for raw in [
+            "",
+            "m",
+            "2509",
+            "m25",
+            "m25a9",
+            "m2500",
+            "m2513",
+            "m2509-X-3000",
+            "m2509-C-",
+            "m2509-C-abc",
+            "m2509-C-inf",
+        ] {
+            let err = ContractId::parse(raw).unwrap_err();
+            assert!(matches!(err, Error::Validation { .. }), "{raw}: {err}");
+        }
+    }
+
+    #[test]
+    fn orders_by_variety_month_then_strike() {
+        let mut ids: Vec<ContractId> = [
+            "m2601",
+            "m2509-P-3000",
+            "m2509-C-3000",
+            "a2601",
+            "m2509-C-2950",
+            "m2509",
+        ]
+        .iter()
+        .map(|raw| id(raw))
+        .collect();
+        ids.sort();
+        let sorted: Vec<String> = ids.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            sorted,
+            [
+                "a2601",
+                "m2509",
+                "m2509-C-2950",
+                "m2509-C-3000",
+                "m2509-P-3000",
+                "m2601",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_arbitrage_ids_with_and_without_a_space() {
+        for raw in ["SP m2505&m2509", "SPm2505&m2509", " SP  m2505&m2509 "] {
+            let arbitrage = ArbitrageId::parse(raw).unwrap();
+            assert_eq!(arbitrage.strategy, "SP", "{raw}");
+            assert_eq!(arbitrage.legs, vec![id("m2505"), id("m2509")], "{raw}");
+            assert_eq!(arbitrage.to_string(), "SP m2505&m2509", "{raw}");
+        }
+        let inter = ArbitrageId::parse("SPC y2509&p2509").unwrap();
+        assert_eq!(inter.strategy, "SPC");
+        assert_eq!(inter.legs[1].variety, "p");
+    }
+
+    #[test]
+    fn rejects_malformed_arbitrage_ids() {
+        for raw in [
+            "",
+            "SP",
+            "SP m2505",
+            "m2505&m2509",
+            "SP m2505&",
+            "SP m2505&x",
+        ] {
+            let err = ArbitrageId::parse(raw).unwrap_err();
+            assert!(matches!(err, Error::Validation { .. }), "{raw}: {err}");
+        }
+    }
+}
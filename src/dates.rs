@@ -0,0 +1,163 @@
+//! Typed date accessors (feature `chrono`).
+//!
+//! The API returns dates as strings in several layouts ("20250930",
+//! "2025-09-30", "2025-09-30 10:00:00"). With the `chrono` feature enabled,
+//! models with date fields gain `*_naive()` accessors returning
+//! [`NaiveDate`]. Naive values are exchange local time; attach the
+//! Asia/Shanghai zone with [`to_exchange_time`].
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+
+use crate::catalog::contract_month;
+use crate::delivery_month::DeliveryMonth;
+use crate::models::*;
+
+/// Exchange time zone (Asia/Shanghai).
+pub const EXCHANGE_TZ: Tz = chrono_tz::Asia::Shanghai;
+
+/// Parse a date such as "20250930", "2025-09-30" or "2025/09/30".
+///
+/// A trailing time ("2025-09-30 10:00:00") is ignored.
+pub fn parse_date(raw: &str) -> Option<NaiveDate> {
+    let date = raw.trim().split([' ', 'T']).next()?;
+    ["%Y%m%d", "%Y-%m-%d", "%Y/%m/%d"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(date, fmt).ok())
+}
+
+/// Parse a date and time such as "2025-09-30 10:00:00".
+///
+/// Dates without a time are returned at midnight.
+pub fn parse_datetime(raw: &str) -> Option<NaiveDateTime> {
+    let raw = raw.trim();
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y/%m/%d %H:%M:%S",
+        "%Y%m%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y%m%d%H%M%S",
+    ]
+    .iter()
+    .find_map(|fmt| NaiveDateTime::parse_from_str(raw, fmt).ok())
+    .or_else(|| parse_date(raw).map(|d| d.and_time(NaiveTime::MIN)))
+}
+
+/// Parse a month such as "202509" or "2025-09" as its first day.
+///
+/// Accepts the formats of [`DeliveryMonth::parse`].
+pub fn parse_month(raw: &str) -> Option<NaiveDate> {
+    let month = DeliveryMonth::parse(raw).ok()?;
+    NaiveDate::from_ymd_opt(month.year(), month.month(), 1)
+}
+
+/// Interpret an exchange local time in the Asia/Shanghai zone.
+pub fn to_exchange_time(datetime: NaiveDateTime) -> Option<DateTime<Tz>> {
+    EXCHANGE_TZ.from_local_datetime(&datetime).single()
+}
+
+/// Add `*_naive()` date accessors for string date fields.
+macro_rules! date_accessors {
+    ($($model:ident { $($field:ident => $method:ident),* $(,)? })*) => {
+        $(
+            impl $model {
+                $(
+                    #[doc = concat!(
+                        "`", stringify!($field), "` as a date, or `None` if empty or unparseable."
+                    )]
+                    pub fn $method(&self) -> Option<NaiveDate> {
+                        parse_date(&self.$field)
+                    }
+                )*
+            }
+        )*
+    };
+}
+
+date_accessors! {
+    Article {
+        show_date => show_date_naive,
+        release_date => release_date_naive,
+        create_date => create_date_naive,
+    }
+    TradeDate { date => date_naive }
+    DeliveryData { delivery_date => delivery_date_naive }
+    DeliveryMatch { match_date => match_date_naive }
+    WarehouseReceiptDetail { gen_date => gen_date_naive }
+    DeliveryCost {
+        start_date => start_date_naive,
+        end_date => end_date_naive,
+    }
+    WarehousePremium { valid_date => valid_date_naive }
+    TradeParam { trade_date => trade_date_naive }
+    ContractInfo {
+        start_trade_date => start_trade_date_naive,
+        end_trade_date => end_trade_date_naive,
+        end_delivery_date => end_delivery_date_naive,
+    }
+    ContractMonthMaxVolume {
+        max_amount_date => max_amount_date_naive,
+        min_amount_date => min_amount_date_naive,
+    }
+    ContractMonthMaxTurnover {
+        max_turnover_date => max_turnover_date_naive,
+        min_turnover_date => min_turnover_date_naive,
+    }
+    ContractMonthMaxOpeni {
+        max_openi_date => max_openi_date_naive,
+        min_openi_date => min_openi_date_naive,
+    }
+    ContractMonthMaxPrice {
+        high_date => high_date_naive,
+        low_date => low_date_naive,
+    }
+    RiseFallEvent { trade_date => trade_date_naive }
+    DivisionPriceInfo { calculate_date => calculate_date_naive }
+    NewContractInfo { start_trade_date => start_trade_date_naive }
+    MainSeriesInfo { trade_date => trade_date_naive }
+    RollDeliverySellerIntention { trade_date => trade_date_naive }
+    BondedDelivery { delivery_date => delivery_date_naive }
+}
+
+impl Article {
+    /// `release_date` as an Asia/Shanghai timestamp.
+    pub fn released_at(&self) -> Option<DateTime<Tz>> {
+        parse_datetime(&self.release_date).and_then(to_exchange_time)
+    }
+}
+
+impl Quote {
+    /// First day of the contract's delivery month.
+    ///
+    /// Taken from the contract ID ("m2509" -> 2025-09-01), or from
+    /// `deliv_month` for night quotes.
+    pub fn delivery_month_naive(&self) -> Option<NaiveDate> {
+        if let Some(month) = contract_month(&self.contract_id) {
+            return parse_month(&month);
+        }
+        let digits = self.deliv_month.trim();
+        match digits.len() {
+            4 => parse_month(&format!("20{}", digits)),
+            _ => parse_month(digits),
+        }
+    }
+}
+
+impl DivisionPriceInfo {
+    /// Calculation date and time as an Asia/Shanghai timestamp.
+    ///
+    /// `calculate_time` may hold a full timestamp or only a time of day, in
+    /// which case it is combined with `calculate_date`.
+    pub fn calculated_at(&self) -> Option<DateTime<Tz>> {
+        let time = self.calculate_time.trim();
+        let datetime = match ["%H:%M:%S", "%H:%M"]
+            .iter()
+            .find_map(|fmt| NaiveTime::parse_from_str(time, fmt).ok())
+        {
+            Some(time) => self.calculate_date_naive()?.and_time(time),
+            None => parse_datetime(time)?,
+        };
+        to_exchange_time(datetime)
+    }
+}
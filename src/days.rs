@@ -0,0 +1,155 @@
+//! Calendar arithmetic on YYYYMMDD dates.
+//!
+//! Dates are converted to day numbers (days since 1970-01-01) so ranges can
+//! be iterated and compared without the optional chrono dependency.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::catalog::normalize_date;
+
+/// Offset of exchange time (UTC+8) from UTC, in seconds.
+const EXCHANGE_UTC_OFFSET_SECS: i64 = 8 * 3600;
+
+/// Seconds since 1970-01-01 00:00 in exchange time.
+pub(crate) fn exchange_seconds() -> i64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    secs + EXCHANGE_UTC_OFFSET_SECS
+}
+
+/// Day number of the current date in exchange time.
+pub(crate) fn today() -> i64 {
+    exchange_seconds().div_euclid(86_400)
+}
+
+/// Days since 1970-01-01 of a date such as "20250930" or "2025-09-30".
+pub(crate) fn day_number(raw: &str) -> Option<i64> {
+    let date = normalize_date(raw)?;
+    let year: i64 = date[..4].parse().ok()?;
+    let month: i64 = date[4..6].parse().ok()?;
+    let day: i64 = date[6..].parse().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(civil_day(year, month, day))
+}
+
+/// Day number of a valid calendar date.
+fn civil_day(year: i64, month: i64, day: i64) -> i64 {
+    // Days from civil date, with March as the first month of the year.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Format a day number as YYYYMMDD.
+pub(crate) fn format_day(day_number: i64) -> String {
+    let z = day_number + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+/// Day of the week, 0 for Monday through 6 for Sunday.
+pub(crate) fn weekday(day_number: i64) -> u32 {
+    // 1970-01-01 was a Thursday.
+    (day_number + 3).rem_euclid(7) as u32
+}
+
+/// Whether a day falls on Monday to Friday.
+pub(crate) fn is_weekday(day_number: i64) -> bool {
+    weekday(day_number) < 5
+}
+
+/// ISO 8601 week-numbering year and week (1-53) of a day.
+pub(crate) fn iso_week(day_number: i64) -> (i64, u32) {
+    // The week belongs to the year of its Thursday.
+    let thursday = day_number - i64::from(weekday(day_number)) + 3;
+    let year: i64 = format_day(thursday)[..4].parse().unwrap_or_default();
+    (year, ((thursday - civil_day(year, 1, 1)) / 7 + 1) as u32)
+}
+
+/// Number of days in a month.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_numbers_count_from_the_epoch() {
+        assert_eq!(day_number("19700101"), Some(0));
+        assert_eq!(day_number("19691231"), Some(-1));
+        assert_eq!(day_number("20000301"), Some(11_017));
+        assert_eq!(day_number("2025-09-30"), Some(20_361));
+        assert_eq!(format_day(0), "19700101");
+        assert_eq!(format_day(-1), "19691231");
+        assert_eq!(format_day(20_361), "20250930");
+    }
+
+    #[test]
+    fn round_trips_every_day_across_leap_years() {
+        // 1896 to 2104 spans the skipped leap days of 1900 and 2100 and the
+        // kept one of 2000.
+        let start = day_number("18960101").unwrap();
+        let end = day_number("21041231").unwrap();
+        for day in start..=end {
+            assert_eq!(day_number(&format_day(day)), Some(day));
+        }
+        assert_eq!(format_day(day_number("20240228").unwrap() + 1), "20240229");
+        assert_eq!(format_day(day_number("20250228").unwrap() + 1), "20250301");
+    }
+
+    #[test]
+    fn leap_days_follow_the_gregorian_rules() {
+        assert!(day_number("20240229").is_some());
+        assert!(day_number("20000229").is_some());
+        assert!(day_number("20250229").is_none());
+        assert!(day_number("19000229").is_none());
+        assert!(day_number("21000229").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for raw in [
+            "", "2025093", "20250001", "20251301", "20250100", "20250431", "20250932", "date",
+        ] {
+            assert_eq!(day_number(raw), None, "{raw}");
+        }
+    }
+
+    #[test]
+    fn weekdays_and_iso_weeks() {
+        let day = |raw| day_number(raw).unwrap();
+        // 2025-09-29 was a Monday.
+        assert_eq!(weekday(day("20250929")), 0);
+        assert_eq!(weekday(day("20251005")), 6);
+        assert!(is_weekday(day("20251003")));
+        assert!(!is_weekday(day("20251004")));
+
+        assert_eq!(iso_week(day("20250101")), (2025, 1));
+        assert_eq!(iso_week(day("20241230")), (2025, 1));
+        assert_eq!(iso_week(day("20210103")), (2020, 53));
+        assert_eq!(iso_week(day("20201231")), (2020, 53));
+        assert_eq!(iso_week(day("20251228")), (2025, 52));
+    }
+}
@@ -0,0 +1,215 @@
+//! Decimal-typed market and settlement models (feature `decimal`).
+//!
+//! The API returns prices as strings, sometimes empty, "-" for no trade, or
+//! with thousands separators ("1,234.5"). The models here parse those fields
+//! into [`Decimal`] once at deserialization time; values that are not
+//! numbers become `None`.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer};
+
+use crate::models::{
+    deserialize_lenient_i64, deserialize_nullable_string, is_subtotal_label, numeric_text, Quote,
+    SettleParam,
+};
+
+/// Parse a price string into a decimal.
+///
+/// Accepts thousands separators and a leading `+`; returns `None` for empty
+/// strings, placeholders such as "-" or "--", and anything else that is not
+/// a number.
+pub fn parse_decimal(raw: &str) -> Option<Decimal> {
+    let cleaned = numeric_text(raw)?;
+    let cleaned = cleaned.strip_prefix('+').unwrap_or(&cleaned);
+    cleaned
+        .parse::<Decimal>()
+        .or_else(|_| Decimal::from_scientific(cleaned))
+        .ok()
+}
+
+/// Deserialize a price given as a string, number or null.
+fn deserialize_decimal<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Str(String),
+        Int(i64),
+        Float(f64),
+    }
+
+    Ok(match Option::<Raw>::deserialize(deserializer)? {
+        Some(Raw::Str(s)) => parse_decimal(&s),
+        Some(Raw::Int(i)) => Some(Decimal::from(i)),
+        Some(Raw::Float(f)) => Decimal::try_from(f).ok(),
+        None => None,
+    })
+}
+
+/// Decimal of a price already parsed as a float.
+fn to_decimal(value: Option<f64>) -> Option<Decimal> {
+    value.and_then(|v| Decimal::try_from(v).ok())
+}
+
+/// Quote data with decimal prices.
+///
+/// Deserializes from the same JSON as [`Quote`], or converts from one.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecimalQuote {
+    /// Variety name.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub variety: String,
+    /// Contract ID.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub contract_id: String,
+    /// Delivery month (for night quotes).
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub deliv_month: String,
+    /// Open price.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub open: Option<Decimal>,
+    /// High price.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub high: Option<Decimal>,
+    /// Low price.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub low: Option<Decimal>,
+    /// Close price.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub close: Option<Decimal>,
+    /// Last clearing price (前结算价).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub last_clear: Option<Decimal>,
+    /// Last price (最新价, for night quotes).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub last_price: Option<Decimal>,
+    /// Clearing/settlement price (结算价).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub clear_price: Option<Decimal>,
+    /// Price difference (涨跌).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub diff: Option<Decimal>,
+    /// Price difference 1 (涨跌1).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub diff1: Option<Decimal>,
+    /// Volume (成交量).
+    #[serde(
+        rename = "volumn",
+        default,
+        deserialize_with = "deserialize_lenient_i64"
+    )]
+    pub volume: i64,
+    /// Open interest (持仓量).
+    #[serde(default, deserialize_with = "deserialize_lenient_i64")]
+    pub open_interest: i64,
+    /// Open interest difference (持仓量变化).
+    #[serde(
+        rename = "diffI",
+        default,
+        deserialize_with = "deserialize_lenient_i64"
+    )]
+    pub diff_i: i64,
+    /// Turnover (成交额).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub turnover: Option<Decimal>,
+    /// Delta (期权).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub delta: Option<Decimal>,
+    /// Implied volatility (隐含波动率).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub implied_volatility: Option<Decimal>,
+}
+
+impl DecimalQuote {
+    /// Whether this is a subtotal or total row rather than a contract.
+    pub fn is_subtotal(&self) -> bool {
+        is_subtotal_label(&self.variety)
+    }
+}
+
+impl From<&Quote> for DecimalQuote {
+    fn from(quote: &Quote) -> Self {
+        DecimalQuote {
+            variety: quote.variety_label().to_string(),
+            contract_id: quote.contract_id.clone(),
+            deliv_month: quote.deliv_month.clone(),
+            open: to_decimal(quote.open),
+            high: to_decimal(quote.high),
+            low: to_decimal(quote.low),
+            close: to_decimal(quote.close),
+            last_clear: to_decimal(quote.last_clear),
+            last_price: to_decimal(quote.last_price),
+            clear_price: to_decimal(quote.clear_price),
+            diff: to_decimal(quote.diff),
+            diff1: to_decimal(quote.diff1),
+            volume: quote.volume,
+            open_interest: quote.open_interest,
+            diff_i: quote.diff_i,
+            turnover: parse_decimal(quote.turnover_text()),
+            delta: to_decimal(quote.delta),
+            implied_volatility: parse_decimal(&quote.implied_volatility),
+        }
+    }
+}
+
+/// Settlement parameters with decimal prices, fees and margin rates.
+///
+/// Deserializes from the same JSON as [`SettleParam`], or converts from one.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecimalSettleParam {
+    /// Variety code.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub variety: String,
+    /// Contract ID.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub contract_id: String,
+    /// Clearing/settlement price.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub clear_price: Option<Decimal>,
+    /// Open fee.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub open_fee: Option<Decimal>,
+    /// Offset fee.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub offset_fee: Option<Decimal>,
+    /// Short open fee (intraday).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub short_open_fee: Option<Decimal>,
+    /// Short offset fee (intraday).
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub short_offset_fee: Option<Decimal>,
+    /// Speculative buy margin rate.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub spec_buy_rate: Option<Decimal>,
+    /// Speculative sell margin rate.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub spec_sell_rate: Option<Decimal>,
+    /// Hedge buy margin rate.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub hedge_buy_rate: Option<Decimal>,
+    /// Hedge sell margin rate.
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub hedge_sell_rate: Option<Decimal>,
+}
+
+impl From<&SettleParam> for DecimalSettleParam {
+    fn from(param: &SettleParam) -> Self {
+        DecimalSettleParam {
+            variety: param.variety.clone(),
+            contract_id: param.contract_id.clone(),
+            clear_price: parse_decimal(&param.clear_price),
+            open_fee: parse_decimal(&param.open_fee),
+            offset_fee: parse_decimal(&param.offset_fee),
+            short_open_fee: parse_decimal(&param.short_open_fee),
+            short_offset_fee: parse_decimal(&param.short_offset_fee),
+            spec_buy_rate: parse_decimal(&param.spec_buy_rate),
+            spec_sell_rate: parse_decimal(&param.spec_sell_rate),
+            hedge_buy_rate: parse_decimal(&param.hedge_buy_rate),
+            hedge_sell_rate: parse_decimal(&param.hedge_sell_rate),
+        }
+    }
+}
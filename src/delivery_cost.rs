@@ -0,0 +1,230 @@
+//! All-in cost of taking delivery.
+//!
+//! Combines the delivery fee table, warehouse premiums and settlement
+//! parameters of a variety to estimate what taking delivery of a number of
+//! lots at a given warehouse costs: the goods at the settlement price
+//! adjusted by the warehouse premium or discount, the delivery fee, and the
+//! margin held during the delivery month.
+
+use crate::error::{Error, Result};
+use crate::models::{
+    parse_optional_f64, parse_rate, DeliveryCost, Lang, SettleParam, WarehousePremium,
+};
+use crate::variety::VarietyCode;
+
+/// Estimated cost of taking delivery, in yuan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeliveryCostEstimate {
+    /// Contract ID.
+    pub contract_id: String,
+    /// Warehouse code.
+    pub wh_code: String,
+    /// Warehouse name.
+    pub wh_name: String,
+    /// Number of lots.
+    pub lots: i64,
+    /// Quantity in contract units (tons for most varieties).
+    pub quantity: f64,
+    /// Settlement price per unit.
+    pub settle_price: f64,
+    /// Warehouse premium per unit; negative for a discount.
+    pub premium_per_unit: f64,
+    /// Goods value at the settlement price.
+    pub goods_value: f64,
+    /// Premium (or discount, negative) for the whole quantity.
+    pub premium: f64,
+    /// Delivery fee.
+    pub delivery_fee: f64,
+    /// Margin held on the position during the delivery month. It is
+    /// returned at delivery and is not part of [`total`](Self::total).
+    pub margin: f64,
+}
+
+impl DeliveryCostEstimate {
+    /// Goods value adjusted by the premium plus the delivery fee.
+    pub fn total(&self) -> f64 {
+        self.goods_value + self.premium + self.delivery_fee
+    }
+
+    /// [`total`](Self::total) per unit of quantity.
+    pub fn per_unit(&self) -> f64 {
+        if self.quantity == 0.0 {
+            0.0
+        } else {
+            self.total() / self.quantity
+        }
+    }
+}
+
+/// Estimates delivery costs of one variety.
+///
+/// Fill it with responses fetched for the same variety and trade date, or
+/// let [`Client::delivery_cost_estimator`](crate::Client::delivery_cost_estimator)
+/// fetch them.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(client: dceapi_rs::Client) -> dceapi_rs::Result<()> {
+/// let estimator = client.delivery_cost_estimator("m", "20250515").await?;
+/// let estimate = estimator.estimate("m2505", 10, "中储粮镇江")?;
+/// println!("{:.0} yuan ({:.2}/t)", estimate.total(), estimate.per_unit());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeliveryCostEstimator {
+    variety: VarietyCode,
+    delivery_costs: Vec<DeliveryCost>,
+    premiums: Vec<WarehousePremium>,
+    settle_params: Vec<SettleParam>,
+}
+
+impl DeliveryCostEstimator {
+    /// Create an estimator for a variety (e.g. "m") without data.
+    pub fn new(variety: impl Into<VarietyCode>) -> Self {
+        DeliveryCostEstimator {
+            variety: variety.into(),
+            delivery_costs: Vec::new(),
+            premiums: Vec::new(),
+            settle_params: Vec::new(),
+        }
+    }
+
+    /// Set the delivery fee table (from
+    /// [`DeliveryService::get_delivery_cost`](crate::DeliveryService::get_delivery_cost)).
+    /// Rows of other varieties are ignored.
+    pub fn with_delivery_costs(mut self, costs: Vec<DeliveryCost>) -> Self {
+        self.delivery_costs = costs;
+        self
+    }
+
+    /// Set the warehouse premiums (from
+    /// [`DeliveryService::get_warehouse_premium`](crate::DeliveryService::get_warehouse_premium)).
+    pub fn with_warehouse_premiums(mut self, premiums: Vec<WarehousePremium>) -> Self {
+        self.premiums = premiums;
+        self
+    }
+
+    /// Set the settlement parameters (from
+    /// [`SettleService::get_settle_param`](crate::SettleService::get_settle_param)).
+    pub fn with_settle_params(mut self, params: Vec<SettleParam>) -> Self {
+        self.settle_params = params;
+        self
+    }
+
+    /// Warehouse premiums, for listing the warehouses that can be estimated.
+    pub fn warehouse_premiums(&self) -> &[WarehousePremium] {
+        &self.premiums
+    }
+
+    /// Estimate the cost of taking delivery of `lots` lots of a contract at
+    /// a warehouse.
+    ///
+    /// The warehouse is matched by code or name. If it has several premium
+    /// rows (one per brand), their average applies. The delivery fee is
+    /// charged per lot or per unit as the fee table states, plus its fee
+    /// rate on the goods value when one is given. The margin uses the fee
+    /// table's delivery-month rate, falling back to the contract's
+    /// speculative buy margin rate.
+    ///
+    /// # Arguments
+    /// * `contract_id` - Contract ID (e.g. "m2505")
+    /// * `lots` - Number of lots
+    /// * `warehouse` - Warehouse code or name
+    pub fn estimate(
+        &self,
+        contract_id: &str,
+        lots: i64,
+        warehouse: &str,
+    ) -> Result<DeliveryCostEstimate> {
+        if lots <= 0 {
+            return Err(Error::validation("lots", "lots must be positive"));
+        }
+        let contract_size = self.variety.contract_size().ok_or_else(|| {
+            Error::missing_data(format!("contract size of {} is not known", self.variety))
+        })?;
+        let param = self
+            .settle_params
+            .iter()
+            .find(|p| p.contract_id.eq_ignore_ascii_case(contract_id))
+            .ok_or_else(|| {
+                Error::missing_data(format!("no settlement parameters for {}", contract_id))
+            })?;
+        let settle_price = parse_optional_f64(&param.clear_price).ok_or_else(|| {
+            Error::missing_data(format!("no settlement price for {}", contract_id))
+        })?;
+
+        let warehouse = warehouse.trim();
+        let rows: Vec<&WarehousePremium> = self
+            .premiums
+            .iter()
+            .filter(|p| p.wh_code.trim() == warehouse || p.wh_name.trim() == warehouse)
+            .collect();
+        let first = rows.first().ok_or_else(|| {
+            Error::missing_data(format!("no premium for warehouse {}", warehouse))
+        })?;
+        let agios: Vec<f64> = rows
+            .iter()
+            .filter_map(|p| parse_optional_f64(&p.avg_agio))
+            .collect();
+        let premium_per_unit = if agios.is_empty() {
+            0.0
+        } else {
+            agios.iter().sum::<f64>() / agios.len() as f64
+        };
+
+        let quantity = lots as f64 * contract_size;
+        let goods_value = quantity * settle_price;
+        let cost = self.delivery_cost();
+        let delivery_fee = cost.map_or(0.0, |c| {
+            let fee = parse_optional_f64(&c.delivery_fee).unwrap_or_default();
+            let per_lot = c.unit.contains('手') || c.unit.to_ascii_lowercase().contains("lot");
+            let fixed = if per_lot {
+                fee * lots as f64
+            } else {
+                fee * quantity
+            };
+            fixed + parse_rate(&c.fee_rate).unwrap_or_default() * goods_value
+        });
+        let margin_rate = cost
+            .and_then(|c| parse_rate(&c.earnest_rate))
+            .or_else(|| parse_rate(&param.spec_buy_rate))
+            .unwrap_or_default();
+
+        Ok(DeliveryCostEstimate {
+            contract_id: param.contract_id.clone(),
+            wh_code: first.wh_code.clone(),
+            wh_name: first.wh_name.clone(),
+            lots,
+            quantity,
+            settle_price,
+            premium_per_unit,
+            goods_value,
+            premium: premium_per_unit * quantity,
+            delivery_fee,
+            margin: margin_rate * goods_value,
+        })
+    }
+
+    /// Fee table row of the variety, matched by name or code. A table with
+    /// a single row is taken as the variety's.
+    fn delivery_cost(&self) -> Option<&DeliveryCost> {
+        let names = [
+            self.variety.code(),
+            self.variety.name(Lang::Zh),
+            self.variety.name(Lang::En),
+        ];
+        self.delivery_costs
+            .iter()
+            .find(|c| {
+                names
+                    .iter()
+                    .any(|n| c.variety.trim().eq_ignore_ascii_case(n))
+            })
+            .or(match self.delivery_costs.as_slice() {
+                [only] => Some(only),
+                _ => None,
+            })
+    }
+}
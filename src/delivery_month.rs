@@ -0,0 +1,200 @@
+//! Delivery month type.
+//!
+//! Month-range requests take months as YYYYMM. [`DeliveryMonth`] validates
+//! the value once, orders chronologically and supports month arithmetic, so
+//! ranges can be generated and compared without string handling. Its parser
+//! is the one other month fields in the crate are read with.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{Error, Result};
+
+/// A calendar month, serialized as YYYYMM.
+///
+/// ```
+/// use dceapi_rs::DeliveryMonth;
+///
+/// let start: DeliveryMonth = "202511".parse().unwrap();
+/// let end = DeliveryMonth::new(2026, 2).unwrap();
+/// assert_eq!(start.next().unwrap().to_string(), "202512");
+/// assert_eq!(start.months_until(end), 3);
+/// assert!(start < end);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeliveryMonth {
+    year: i32,
+    month: u32,
+}
+
+impl DeliveryMonth {
+    /// Create a month from a year (1-9999) and month (1-12).
+    pub fn new(year: i32, month: u32) -> Result<DeliveryMonth> {
+        if !(1..=9999).contains(&year) {
+            return Err(Error::validation(
+                "month",
+                format!("year must be between 1 and 9999, got {}", year),
+            ));
+        }
+        if !(1..=12).contains(&month) {
+            return Err(Error::validation(
+                "month",
+                format!("month must be between 1 and 12, got {}", month),
+            ));
+        }
+        Ok(DeliveryMonth { year, month })
+    }
+
+    /// Parse a month such as "202509" or "2025-09".
+    pub fn parse(raw: &str) -> Result<DeliveryMonth> {
+        let invalid =
+            || Error::validation("month", format!("expected a YYYYMM month, got {:?}", raw));
+        let text = raw.trim();
+        let digits: String = match text.split_once(['-', '/']) {
+            Some((year, month)) if month.len() <= 2 => format!("{}{:0>2}", year, month),
+            Some(_) => return Err(invalid()),
+            None => text.to_string(),
+        };
+        if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let year = digits[..4].parse().map_err(|_| invalid())?;
+        let month = digits[4..].parse().map_err(|_| invalid())?;
+        DeliveryMonth::new(year, month).map_err(|_| invalid())
+    }
+
+    /// Year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Month (1-12).
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// The following month; an error after December 9999.
+    pub fn next(&self) -> Result<DeliveryMonth> {
+        self.add_months(1)
+    }
+
+    /// The preceding month; an error before January of year 1.
+    pub fn prev(&self) -> Result<DeliveryMonth> {
+        self.add_months(-1)
+    }
+
+    /// The month `months` later (or earlier, if negative).
+    ///
+    /// Fails if the result falls outside years 1 to 9999.
+    pub fn add_months(&self, months: i32) -> Result<DeliveryMonth> {
+        let index = self.index().checked_add(months).ok_or_else(|| {
+            Error::validation("month", format!("cannot add {} months to {}", months, self))
+        })?;
+        DeliveryMonth::new(index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+    }
+
+    /// Number of months from `self` to `other`; negative if `other` is
+    /// earlier.
+    pub fn months_until(&self, other: DeliveryMonth) -> i32 {
+        other.index() - self.index()
+    }
+
+    /// Months from `self` to `end`, inclusive; empty if `end` is earlier.
+    pub fn range_to(&self, end: DeliveryMonth) -> Vec<DeliveryMonth> {
+        // Every month between two valid months is valid.
+        (0..=self.months_until(end))
+            .filter_map(|offset| self.add_months(offset).ok())
+            .collect()
+    }
+
+    /// Months since year 0.
+    fn index(&self) -> i32 {
+        self.year * 12 + self.month as i32 - 1
+    }
+}
+
+impl fmt::Display for DeliveryMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}{:02}", self.year, self.month)
+    }
+}
+
+impl FromStr for DeliveryMonth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        DeliveryMonth::parse(s)
+    }
+}
+
+impl TryFrom<&str> for DeliveryMonth {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        DeliveryMonth::parse(value)
+    }
+}
+
+impl Serialize for DeliveryMonth {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Accepts "202509", "2025-09" or the number 202509.
+impl<'de> Deserialize<'de> for DeliveryMonth {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Str(String),
+            Int(u32),
+        }
+
+        let raw = match Raw::deserialize(deserializer)? {
+            Raw::Str(s) => s,
+            Raw::Int(i) => i.to_string(),
+        };
+        DeliveryMonth::parse(&raw).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month(raw: &str) -> DeliveryMonth {
+        DeliveryMonth::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn parses_month_formats() {
+        assert_eq!(month("202509"), DeliveryMonth::new(2025, 9).unwrap());
+        assert_eq!(month(" 2025-9 "), DeliveryMonth::new(2025, 9).unwrap());
+        assert_eq!(month("2025/09"), DeliveryMonth::new(2025, 9).unwrap());
+        for raw in ["202513", "20250", "2025-009", "000001", "abcdef", ""] {
+            assert!(DeliveryMonth::parse(raw).is_err(), "{raw}");
+        }
+    }
+
+    #[test]
+    fn month_arithmetic_stays_in_range() {
+        assert_eq!(month("202512").next().unwrap(), month("202601"));
+        assert_eq!(month("202501").prev().unwrap(), month("202412"));
+        assert_eq!(month("202511").add_months(-23).unwrap(), month("202312"));
+        assert!(month("999912").next().is_err());
+        assert!(month("000101").prev().is_err());
+        assert!(month("202501").add_months(i32::MAX).is_err());
+    }
+
+    #[test]
+    fn ranges_are_inclusive() {
+        let range = month("202511").range_to(month("202602"));
+        let range: Vec<String> = range.iter().map(ToString::to_string).collect();
+        assert_eq!(range, ["202511", "202512", "202601", "202602"]);
+        assert!(month("202602").range_to(month("202511")).is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Intraday settlement reference price series.
+//!
+//! The division price endpoint returns one row per contract and calculation
+//! time, with the time as text. [`DivisionSeries`] parses the times and
+//! groups the rows into a time-ordered series per contract.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::days::exchange_seconds;
+use crate::error::{Error, Result};
+use crate::models::DivisionPriceInfo;
+
+/// Time of day, ordered chronologically and displayed as HH:MM:SS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TimeOfDay {
+    /// Hour (0-23).
+    pub hour: u32,
+    /// Minute (0-59).
+    pub minute: u32,
+    /// Second (0-59).
+    pub second: u32,
+}
+
+impl TimeOfDay {
+    /// Parse a time such as "14:30", "14:30:00" or a timestamp ending in one
+    /// ("2025-01-02 14:30:00").
+    pub fn parse(raw: &str) -> Result<TimeOfDay> {
+        let invalid = || Error::validation("time", format!("invalid time of day {:?}", raw));
+        let time = raw.trim().rsplit([' ', 'T']).next().ok_or_else(invalid)?;
+        let mut parts = time.split(':').map(|p| p.parse::<u32>().ok());
+        let (Some(Some(hour)), Some(Some(minute))) = (parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+        let second = match parts.next() {
+            Some(Some(second)) => second,
+            Some(None) => return Err(invalid()),
+            None => 0,
+        };
+        if parts.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+            return Err(invalid());
+        }
+        Ok(TimeOfDay {
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Current time of day in exchange time (UTC+8).
+    pub fn now() -> TimeOfDay {
+        TimeOfDay::from_seconds(exchange_seconds().rem_euclid(86_400) as u32)
+    }
+
+    /// Time of day a number of seconds after midnight, wrapping at 24 hours.
+    pub fn from_seconds(seconds: u32) -> TimeOfDay {
+        let seconds = seconds % 86_400;
+        TimeOfDay {
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+        }
+    }
+
+    /// Seconds since midnight.
+    pub fn seconds(&self) -> u32 {
+        self.hour * 3600 + self.minute * 60 + self.second
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        TimeOfDay::parse(s)
+    }
+}
+
+/// One settlement reference price calculation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DivisionPoint {
+    /// Calculation time.
+    pub time: TimeOfDay,
+    /// Settlement reference price.
+    pub clear_price: f64,
+    /// Settlement reference implied volatility (options only).
+    pub volatility: f64,
+}
+
+/// Settlement reference prices per contract, in time order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DivisionSeries {
+    /// Calculation date, taken from the first row.
+    pub trade_date: String,
+    /// Points per contract ID, sorted by time.
+    pub contracts: BTreeMap<String, Vec<DivisionPoint>>,
+}
+
+impl DivisionSeries {
+    /// Group division price rows into per-contract series.
+    ///
+    /// Rows without a contract ID or with an unparseable time are skipped.
+    /// If a contract has several rows for the same time, the last one wins.
+    pub fn from_rows(rows: &[DivisionPriceInfo]) -> DivisionSeries {
+        let mut series = DivisionSeries::default();
+        for row in rows {
+            if row.contract_id.is_empty() {
+                continue;
+            }
+            let Ok(time) = TimeOfDay::parse(&row.calculate_time) else {
+                continue;
+            };
+            if series.trade_date.is_empty() {
+                series.trade_date = row.calculate_date.clone();
+            }
+            let points = series.contracts.entry(row.contract_id.clone()).or_default();
+            points.retain(|p| p.time != time);
+            points.push(DivisionPoint {
+                time,
+                clear_price: row.clear_price,
+                volatility: row.volatility,
+            });
+        }
+        for points in series.contracts.values_mut() {
+            points.sort_by_key(|p| p.time);
+        }
+        series
+    }
+
+    /// Points of one contract.
+    pub fn get(&self, contract_id: &str) -> Option<&[DivisionPoint]> {
+        self.contracts.get(contract_id).map(Vec::as_slice)
+    }
+
+    /// Latest point of one contract.
+    pub fn latest(&self, contract_id: &str) -> Option<&DivisionPoint> {
+        self.get(contract_id)?.last()
+    }
+
+    /// Points of every contract at the given time.
+    pub fn at(&self, time: TimeOfDay) -> BTreeMap<&str, &DivisionPoint> {
+        self.contracts
+            .iter()
+            .filter_map(|(id, points)| {
+                let point = points.iter().find(|p| p.time == time)?;
+                Some((id.as_str(), point))
+            })
+            .collect()
+    }
+
+    /// Whether the series has no points.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+}
@@ -0,0 +1,421 @@
+//! Pacing and retries for multi-request downloads.
+//!
+//! Methods that issue many requests (such as
+//! [`MarketService::get_day_quotes_range`](crate::MarketService::get_day_quotes_range))
+//! take [`DownloadOptions`] to bound concurrency and request rate, to
+//! retry transient failures and to pick the trading days of a date range
+//! from a [`Calendar`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::{sleep, Instant};
+
+use crate::calendar::Calendar;
+use crate::days::{day_number, format_day};
+use crate::error::{Error, Result};
+
+/// Default number of requests in flight.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default minimum interval between request starts (10 requests per second).
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default number of retries for transient errors.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default delay before the first retry.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest interval [`DownloadOptions::with_qps`] sets: one request a day.
+const MAX_QPS_INTERVAL: Duration = Duration::from_secs(86_400);
+
+/// Concurrency, rate, retry and calendar settings for multi-request downloads.
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    /// Maximum number of requests in flight. Defaults to 4.
+    pub concurrency: usize,
+    /// Minimum interval between request starts; `Duration::ZERO` disables
+    /// pacing. Defaults to 100 ms.
+    pub min_interval: Duration,
+    /// Retries per request for transient errors (see
+    /// [`Error::is_transient`](crate::Error::is_transient)). Defaults to 3.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further retry.
+    /// Defaults to 500 ms.
+    pub retry_delay: Duration,
+    /// Trading calendar deciding which dates of a range are requested.
+    /// Without one, every weekday is requested. Defaults to `None`.
+    pub calendar: Option<Arc<Calendar>>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DownloadOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        DownloadOptions {
+            concurrency: DEFAULT_CONCURRENCY,
+            min_interval: DEFAULT_MIN_INTERVAL,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            calendar: None,
+        }
+    }
+
+    /// Set the maximum number of requests in flight (at least 1).
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Limit the request rate to `qps` requests per second.
+    ///
+    /// A rate that is not positive disables pacing; rates below one request
+    /// a day are raised to one a day.
+    pub fn with_qps(mut self, qps: f64) -> Self {
+        self.min_interval = if qps > 0.0 {
+            Duration::try_from_secs_f64(1.0 / qps)
+                .map_or(MAX_QPS_INTERVAL, |interval| interval.min(MAX_QPS_INTERVAL))
+        } else {
+            Duration::ZERO
+        };
+        self
+    }
+
+    /// Set the minimum interval between request starts.
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Set the number of retries for transient errors.
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set the delay before the first retry.
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Request only the trading days of `calendar` (see
+    /// [`MarketService::update_calendar`](crate::MarketService::update_calendar)).
+    pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = Some(Arc::new(calendar));
+        self
+    }
+
+    /// Trading days from `start_date` to `end_date` (inclusive): those of
+    /// the calendar, or every weekday without one.
+    pub(crate) fn trading_days(&self, start_date: &str, end_date: &str) -> Result<Vec<String>> {
+        match &self.calendar {
+            Some(calendar) => calendar.trading_days_between(start_date, end_date),
+            None => Calendar::new().trading_days_between(start_date, end_date),
+        }
+    }
+}
+
+/// Spaces request starts at least `interval` apart across tasks.
+#[derive(Debug)]
+struct Pacer {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl Pacer {
+    /// Create a pacer with the given minimum interval.
+    fn new(interval: Duration) -> Self {
+        Pacer {
+            interval,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait for the next request slot.
+    async fn wait(&self) {
+        if self.interval.is_zero() {
+            return;
+        }
+        let mut next = self.next.lock().await;
+        let now = Instant::now();
+        if *next > now {
+            sleep(*next - now).await;
+        }
+        *next = (*next).max(now) + self.interval;
+    }
+}
+
+/// Run a request, waiting for the pacer before every attempt and retrying
+/// transient errors with exponential backoff.
+async fn retry<T, F, Fut>(options: &DownloadOptions, pacer: &Pacer, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        pacer.wait().await;
+        match f().await {
+            Err(err) if err.is_transient() && attempt < options.max_retries => {
+                sleep(options.retry_delay * 2u32.saturating_pow(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Split the dates from `start_date` to `end_date` (inclusive) into
+/// consecutive windows of at most `days` calendar days, as (start, end)
+/// pairs in YYYYMMDD format.
+pub(crate) fn date_windows(
+    start_date: &str,
+    end_date: &str,
+    days: u32,
+) -> Result<Vec<(String, String)>> {
+    if days == 0 {
+        return Err(Error::validation(
+            "window_days",
+            "window must be at least one day",
+        ));
+    }
+    let start = day_number(start_date)
+        .ok_or_else(|| Error::validation("start_date", format!("invalid date {:?}", start_date)))?;
+    let end = day_number(end_date)
+        .ok_or_else(|| Error::validation("end_date", format!("invalid date {:?}", end_date)))?;
+    if start > end {
+        return Err(Error::validation(
+            "end_date",
+            format!("end date {} is before start date {}", end_date, start_date),
+        ));
+    }
+    Ok((start..=end)
+        .step_by(days as usize)
+        .map(|from| {
+            (
+                format_day(from),
+                format_day((from + days as i64 - 1).min(end)),
+            )
+        })
+        .collect())
+}
+
+/// Fetch date windows one after another, paced by the rate limit in
+/// `options` and retrying transient errors, and concatenate the results in
+/// window order. Fails with the first error that persists after retries.
+pub(crate) async fn fetch_windows<T, F, Fut>(
+    windows: Vec<(String, String)>,
+    options: &DownloadOptions,
+    fetch: F,
+) -> Result<Vec<T>>
+where
+    F: Fn(String, String) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    let pacer = Pacer::new(options.min_interval);
+    let mut rows = Vec::new();
+    for (start, end) in windows {
+        rows.extend(retry(options, &pacer, || fetch(start.clone(), end.clone())).await?);
+    }
+    Ok(rows)
+}
+
+/// Results of a batch of requests keyed by variety, date or another key.
+///
+/// Failed requests do not fail the batch; their errors are collected per key.
+#[derive(Debug)]
+pub struct BatchResult<T> {
+    /// Successful results per key.
+    pub values: HashMap<String, T>,
+    /// Errors per key that failed after retries.
+    pub errors: HashMap<String, Error>,
+}
+
+impl<T> Default for BatchResult<T> {
+    fn default() -> Self {
+        BatchResult {
+            values: HashMap::new(),
+            errors: HashMap::new(),
+        }
+    }
+}
+
+impl<T> BatchResult<T> {
+    /// Whether every request succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Fetch one result per key, bounded by the concurrency and rate limits in
+/// `options` and retrying transient errors. Every key gets a result.
+pub(crate) async fn fetch_each<T, F, Fut>(
+    keys: Vec<String>,
+    options: &DownloadOptions,
+    fetch: F,
+) -> BatchResult<T>
+where
+    T: Send + 'static,
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+{
+    let mut tasks = spawn_each(keys, options, fetch);
+    let mut batch = BatchResult::default();
+    while let Some((key, result)) = join_next(&mut tasks).await {
+        match result {
+            Ok(value) => {
+                batch.values.insert(key, value);
+            }
+            Err(err) => {
+                batch.errors.insert(key, err);
+            }
+        }
+    }
+    batch
+}
+
+/// Fetch one result per date, bounded by the concurrency and rate limits in
+/// `options` and retrying transient errors.
+///
+/// Results for which `keep` returns `false` (such as empty holiday
+/// responses) are left out. Fails with the first error that persists after
+/// retries.
+pub(crate) async fn fetch_dates<T, F, Fut>(
+    dates: Vec<String>,
+    options: &DownloadOptions,
+    keep: fn(&T) -> bool,
+    fetch: F,
+) -> Result<BTreeMap<String, T>>
+where
+    T: Send + 'static,
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+{
+    let mut tasks = spawn_each(dates, options, fetch);
+    let mut by_date = BTreeMap::new();
+    while let Some((date, result)) = join_next(&mut tasks).await {
+        let value = result?;
+        if keep(&value) {
+            by_date.insert(date, value);
+        }
+    }
+    Ok(by_date)
+}
+
+/// Spawn one paced, retried request per key.
+fn spawn_each<T, F, Fut>(
+    keys: Vec<String>,
+    options: &DownloadOptions,
+    fetch: F,
+) -> JoinSet<(String, Result<T>)>
+where
+    T: Send + 'static,
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(options.concurrency.max(1)));
+    let pacer = Arc::new(Pacer::new(options.min_interval));
+    let fetch = Arc::new(fetch);
+    let mut tasks = JoinSet::new();
+    for key in keys {
+        let options = options.clone();
+        let semaphore = Arc::clone(&semaphore);
+        let pacer = Arc::clone(&pacer);
+        let fetch = Arc::clone(&fetch);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = retry(&options, &pacer, || fetch(key.clone())).await;
+            (key, result)
+        });
+    }
+    tasks
+}
+
+/// Wait for the next finished request, resuming any panic it raised.
+async fn join_next<T: 'static>(
+    tasks: &mut JoinSet<(String, Result<T>)>,
+) -> Option<(String, Result<T>)> {
+    let joined = tasks.join_next().await?;
+    Some(joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows(start_date: &str, end_date: &str, days: u32) -> Vec<(String, String)> {
+        date_windows(start_date, end_date, days).unwrap()
+    }
+
+    fn pair(start: &str, end: &str) -> (String, String) {
+        (start.to_string(), end.to_string())
+    }
+
+    #[test]
+    fn windows_cover_the_range_with_a_short_last_window() {
+        assert_eq!(
+            windows("20250125", "20250305", 15),
+            [
+                pair("20250125", "20250208"),
+                pair("20250209", "20250223"),
+                pair("20250224", "20250305"),
+            ]
+        );
+        assert_eq!(
+            windows("20231225", "20240103", 7),
+            [pair("20231225", "20231231"), pair("20240101", "20240103")]
+        );
+    }
+
+    #[test]
+    fn single_day_and_exact_ranges() {
+        assert_eq!(
+            windows("2025-09-30", "2025-09-30", 30),
+            [pair("20250930", "20250930")]
+        );
+        assert_eq!(
+            windows("20250901", "20250930", 30),
+            [pair("20250901", "20250930")]
+        );
+        assert_eq!(windows("20240228", "20240301", 1).len(), 3);
+    }
+
+    #[test]
+    fn qps_sets_a_bounded_interval() {
+        let interval = |qps| DownloadOptions::new().with_qps(qps).min_interval;
+        assert_eq!(interval(4.0), Duration::from_millis(250));
+        assert_eq!(interval(f64::INFINITY), Duration::ZERO);
+        for qps in [0.0, -1.0, f64::NAN] {
+            assert_eq!(interval(qps), Duration::ZERO);
+        }
+        for qps in [1e-300, f64::MIN_POSITIVE, 1e-6] {
+            assert_eq!(interval(qps), MAX_QPS_INTERVAL);
+        }
+    }
+
+    #[test]
+    fn invalid_windows_are_rejected() {
+        for (start_date, end_date, days, field) in [
+            ("20250101", "20250131", 0, "window_days"),
+            ("20250132", "20250201", 7, "start_date"),
+            ("20250101", "2025", 7, "end_date"),
+            ("20250201", "20250131", 7, "end_date"),
+        ] {
+            match date_windows(start_date, end_date, days) {
+                Err(Error::Validation { field: f, .. }) => assert_eq!(f, field),
+                other => panic!("expected a {} error, got {:?}", field, other),
+            }
+        }
+    }
+}
@@ -8,6 +8,8 @@
 //! - 500: Server error
 //! - 501: Rate limit
 
+use std::fmt;
+
 use thiserror::Error;
 
 /// API error codes as defined by the DCE API.
@@ -75,6 +77,51 @@ pub enum Error {
         message: String,
     },
 
+    /// Multiple validation errors for request parameters.
+    #[error("validation errors: {0}")]
+    ValidationErrors(ValidationErrors),
+
+    /// Failed to export data to an external format.
+    #[error("export error: {message}")]
+    Export {
+        /// Description of the failure.
+        message: String,
+    },
+
+    /// Reading or writing a local file failed.
+    #[error("I/O error on {path}: {source}")]
+    Io {
+        /// Path of the file.
+        path: String,
+        /// The underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Data needed for a calculation is missing from the responses given.
+    #[error("missing data: {message}")]
+    MissingData {
+        /// What is missing.
+        message: String,
+    },
+
+    /// A contract catalog lookup found no loaded data under
+    /// [`CatalogPolicy::Manual`](crate::CatalogPolicy::Manual).
+    #[error("contracts for '{variety_id}' (trade type {trade_type}) are not loaded")]
+    CatalogNotLoaded {
+        /// Variety ID looked up.
+        variety_id: String,
+        /// Trade type looked up.
+        trade_type: String,
+    },
+
+    /// The client configuration is invalid.
+    #[error("configuration error: {message}")]
+    Config {
+        /// Description of the problem.
+        message: String,
+    },
+
     /// JSON parsing error.
     #[error("parse error: {err}, raw response: {raw_response}")]
     Parse {
@@ -117,11 +164,79 @@ impl Error {
         }
     }
 
+    /// Create a new export error.
+    pub fn export(message: impl Into<String>) -> Self {
+        Error::Export {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new I/O error for a file.
+    pub fn io(path: impl AsRef<std::path::Path>, source: std::io::Error) -> Self {
+        Error::Io {
+            path: path.as_ref().display().to_string(),
+            source,
+        }
+    }
+
+    /// Create a new missing data error.
+    pub fn missing_data(message: impl Into<String>) -> Self {
+        Error::MissingData {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new error for a catalog lookup of data that is not loaded.
+    pub fn catalog_not_loaded(
+        variety_id: impl Into<String>,
+        trade_type: impl fmt::Display,
+    ) -> Self {
+        Error::CatalogNotLoaded {
+            variety_id: variety_id.into(),
+            trade_type: trade_type.to_string(),
+        }
+    }
+
+    /// Create a new configuration error.
+    pub fn config(message: impl Into<String>) -> Self {
+        Error::Config {
+            message: message.into(),
+        }
+    }
+
+    /// Get all field validation errors carried by this error.
+    ///
+    /// Returns a single entry for [`Error::Validation`], every entry for
+    /// [`Error::ValidationErrors`], and an empty list for other errors.
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            Error::Validation { field, message } => vec![FieldError {
+                field: field.clone(),
+                message: message.clone(),
+            }],
+            Error::ValidationErrors(errors) => errors.errors().to_vec(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Check if this is a token expired error.
     pub fn is_token_expired(&self) -> bool {
         matches!(self, Error::Api { code, .. } if *code == ErrorCode::TokenExpired as i32)
     }
 
+    /// Check if the error is likely temporary and the request worth retrying
+    /// (network failures, rate limiting and server errors).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Network(_) => true,
+            Error::Api { code, .. } => matches!(
+                ErrorCode::from_code(*code),
+                Some(ErrorCode::RateLimit | ErrorCode::ServerError)
+            ),
+            _ => false,
+        }
+    }
+
     /// Get the error code if this is an API error.
     pub fn error_code(&self) -> Option<ErrorCode> {
         if let Error::Api { code, .. } = self {
@@ -132,5 +247,104 @@ impl Error {
     }
 }
 
+/// A validation failure for a single field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Path of the field that failed validation (e.g. "start_month").
+    pub field: String,
+    /// What was expected of the field.
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field '{}': {}", self.field, self.message)
+    }
+}
+
+/// A collection of field validation failures.
+///
+/// Validation code collects every problem it finds instead of stopping at the
+/// first one, then converts the collection into a [`Result`] with
+/// [`ValidationErrors::into_result`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    /// Create an empty collection.
+    pub fn new() -> Self {
+        ValidationErrors { errors: Vec::new() }
+    }
+
+    /// Record a validation failure for a field.
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Merge another collection, prefixing its field paths with `prefix`.
+    ///
+    /// A field "trade_date" merged with prefix "request" becomes "request.trade_date".
+    pub fn merge(&mut self, prefix: &str, other: ValidationErrors) {
+        for error in other.errors {
+            let field = if prefix.is_empty() {
+                error.field
+            } else {
+                format!("{}.{}", prefix, error.field)
+            };
+            self.errors.push(FieldError {
+                field,
+                message: error.message,
+            });
+        }
+    }
+
+    /// Check whether no failures were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Get the number of recorded failures.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Get the recorded failures.
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// Convert into a result.
+    ///
+    /// Returns `Ok(())` if empty, [`Error::Validation`] for a single failure and
+    /// [`Error::ValidationErrors`] for several.
+    pub fn into_result(mut self) -> Result<()> {
+        match self.errors.len() {
+            0 => Ok(()),
+            1 => {
+                let error = self.errors.remove(0);
+                Err(Error::validation(error.field, error.message))
+            }
+            _ => Err(Error::ValidationErrors(self)),
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
 /// Result type alias for DCE API operations.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,336 @@
+//! One subscription for every watcher.
+//!
+//! [`Events`] starts the watchers for the sources it is given and merges
+//! what they detect into a single [`Watch`] of [`Event`]s, so one loop can
+//! handle news, limit events, warehouse receipts, new listings and margin
+//! changes.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, Mutex};
+
+use crate::client::Client;
+use crate::error::Result;
+use crate::http::RequestOptions;
+use crate::models::{ColumnId, TradeType};
+use crate::notify::Event;
+use crate::services::{MarketService, NewsService, TradeService};
+use crate::shutdown::Shutdown;
+use crate::watch::Watch;
+
+/// A watcher to start and its filter.
+#[derive(Debug, Clone)]
+enum Source {
+    News {
+        columns: Vec<ColumnId>,
+        interval: Duration,
+    },
+    LimitEvents {
+        variety_id: String,
+        interval: Duration,
+    },
+    ReceiptAlerts {
+        variety_id: String,
+        interval: Duration,
+        threshold: i64,
+    },
+    NewContracts {
+        trade_type: TradeType,
+        interval: Duration,
+    },
+    MarginChanges {
+        interval: Duration,
+    },
+}
+
+/// Merged subscription to several watchers.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use dceapi_rs::{Client, ColumnId, Event, Events};
+///
+/// # async fn run(client: Client) {
+/// let minute = Duration::from_secs(60);
+/// let mut events = Events::new(&client)
+///     .with_news([ColumnId::BusinessNotices], minute)
+///     .with_limit_events("all", minute)
+///     .with_margin_changes(10 * minute)
+///     .subscribe();
+/// while let Some(event) = events.next().await {
+///     match event {
+///         Ok(Event::Articles(articles)) => println!("{} new articles", articles.len()),
+///         Ok(event) => println!("{}", event),
+///         Err(err) => eprintln!("poll failed: {}", err),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Events {
+    news: NewsService,
+    market: MarketService,
+    trade: TradeService,
+    shutdown: Shutdown,
+    sources: Vec<Source>,
+    opts: Option<RequestOptions>,
+}
+
+impl Events {
+    /// Create a subscription with no sources, using a client's services.
+    pub fn new(client: &Client) -> Self {
+        Events {
+            news: client.news.clone(),
+            market: client.market.clone(),
+            trade: client.trade.clone(),
+            shutdown: client.shutdown_token(),
+            sources: Vec::new(),
+            opts: None,
+        }
+    }
+
+    /// Set the request options used by every source.
+    pub fn with_request_options(mut self, opts: RequestOptions) -> Self {
+        self.opts = Some(opts);
+        self
+    }
+
+    /// Watch columns for new articles; see
+    /// [`NewsService::watch`](crate::NewsService::watch).
+    pub fn with_news(
+        mut self,
+        columns: impl IntoIterator<Item = impl Into<ColumnId>>,
+        interval: Duration,
+    ) -> Self {
+        self.sources.push(Source::News {
+            columns: columns.into_iter().map(Into::into).collect(),
+            interval,
+        });
+        self
+    }
+
+    /// Watch limit-up/limit-down events of a variety ("all" for all
+    /// varieties); see
+    /// [`MarketService::watch_rise_fall_events`](crate::MarketService::watch_rise_fall_events).
+    pub fn with_limit_events(mut self, variety_id: &str, interval: Duration) -> Self {
+        self.sources.push(Source::LimitEvents {
+            variety_id: variety_id.to_string(),
+            interval,
+        });
+        self
+    }
+
+    /// Watch warehouse receipt moves of at least `threshold` lots; see
+    /// [`MarketService::watch_warehouse_receipts`](crate::MarketService::watch_warehouse_receipts).
+    pub fn with_warehouse_receipts(
+        mut self,
+        variety_id: &str,
+        interval: Duration,
+        threshold: i64,
+    ) -> Self {
+        self.sources.push(Source::ReceiptAlerts {
+            variety_id: variety_id.to_string(),
+            interval,
+            threshold,
+        });
+        self
+    }
+
+    /// Watch newly listed contracts; see
+    /// [`TradeService::watch_new_contracts`](crate::TradeService::watch_new_contracts).
+    pub fn with_new_contracts(mut self, trade_type: TradeType, interval: Duration) -> Self {
+        self.sources.push(Source::NewContracts {
+            trade_type,
+            interval,
+        });
+        self
+    }
+
+    /// Watch margin rate changes; see
+    /// [`TradeService::watch_margin_changes`](crate::TradeService::watch_margin_changes).
+    pub fn with_margin_changes(mut self, interval: Duration) -> Self {
+        self.sources.push(Source::MarginChanges { interval });
+        self
+    }
+
+    /// Start every source and merge their items, in the order they arrive.
+    ///
+    /// Failed polls of any source are yielded as errors; the source keeps
+    /// polling. Dropping the returned handle, or shutting down the client,
+    /// stops every source. Must be called within a Tokio runtime.
+    pub fn subscribe(self) -> Watch<Event> {
+        // Sources start once, outside the supervised task, so a restart of
+        // the task forwards from the same watchers instead of starting new
+        // ones that would yield their current snapshots again.
+        let (merged, rx) = mpsc::channel(self.sources.len().max(1));
+        for source in self.sources {
+            let opts = self.opts.clone();
+            let merged = merged.clone();
+            match source {
+                Source::News { columns, interval } => {
+                    tokio::spawn(forward(self.news.watch(columns, interval, opts), merged));
+                }
+                Source::LimitEvents {
+                    variety_id,
+                    interval,
+                } => {
+                    let watch = self
+                        .market
+                        .watch_rise_fall_events(&variety_id, interval, opts);
+                    tokio::spawn(forward(watch, merged));
+                }
+                Source::ReceiptAlerts {
+                    variety_id,
+                    interval,
+                    threshold,
+                } => {
+                    let watch = self.market.watch_warehouse_receipts(
+                        &variety_id,
+                        interval,
+                        threshold,
+                        opts,
+                    );
+                    tokio::spawn(forward(watch, merged));
+                }
+                Source::NewContracts {
+                    trade_type,
+                    interval,
+                } => {
+                    let watch = self.trade.watch_new_contracts(trade_type, interval, opts);
+                    tokio::spawn(forward(watch, merged));
+                }
+                Source::MarginChanges { interval } => {
+                    let watch = self.trade.watch_margin_changes(interval, opts);
+                    tokio::spawn(forward(watch, merged));
+                }
+            }
+        }
+        // Dropping the receiver when the task stops stops every source.
+        let rx = Arc::new(Mutex::new(rx));
+        Watch::spawn("events", &self.shutdown, move |tx| {
+            let rx = Arc::clone(&rx);
+            async move {
+                let mut rx = rx.lock().await;
+                while let Some(item) = rx.recv().await {
+                    if !tx.send(item).await {
+                        break;
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Pass a watcher's items on as events until either side stops.
+async fn forward<T: Into<Event>>(mut watch: Watch<T>, tx: mpsc::Sender<Result<Event>>) {
+    loop {
+        let item = tokio::select! {
+            item = watch.next() => item,
+            _ = tx.closed() => None,
+        };
+        let Some(item) = item else {
+            break;
+        };
+        if tx.send(item.map(Into::into)).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use tokio::time::{sleep, Instant};
+
+    use super::*;
+    use crate::catalog::CatalogPolicy;
+    use crate::config::Config;
+    use crate::error::Error;
+    use crate::models::Article;
+    use crate::supervisor::TaskState;
+
+    /// Sets its flag when dropped.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Wait up to five seconds for `done` to hold.
+    async fn eventually(done: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() {
+            assert!(Instant::now() < deadline, "condition not reached in time");
+            sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn forward_passes_items_and_errors_on_as_events() {
+        let watch = Watch::spawn("test", &Shutdown::new(), |tx| async move {
+            tx.send(Ok(Vec::<Article>::new())).await;
+            tx.send(Err(Error::validation("poll", "failed"))).await;
+            std::future::pending::<()>().await;
+        });
+        let (merged, mut rx) = mpsc::channel(1);
+        tokio::spawn(forward(watch, merged));
+
+        let event = rx.recv().await.unwrap().unwrap();
+        assert!(
+            matches!(&event, Event::Articles(a) if a.is_empty()),
+            "{event}"
+        );
+        let err = rx.recv().await.unwrap().unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }), "{err}");
+    }
+
+    #[tokio::test]
+    async fn forward_stops_its_watcher_when_the_receiver_closes() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&dropped);
+        let watch: Watch<Vec<Article>> = Watch::spawn("test", &Shutdown::new(), move |_tx| {
+            let flag = DropFlag(Arc::clone(&flag));
+            async move {
+                let _flag = flag;
+                std::future::pending::<()>().await;
+            }
+        });
+        let (merged, rx) = mpsc::channel(1);
+        let forwarder = tokio::spawn(forward(watch, merged));
+
+        sleep(Duration::from_millis(20)).await;
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(rx);
+        eventually(|| forwarder.is_finished()).await;
+        eventually(|| dropped.load(Ordering::SeqCst)).await;
+    }
+
+    #[tokio::test]
+    async fn subscription_ends_on_client_shutdown() {
+        // A server that never answers, so the sources stay in their first poll.
+        let silent = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = Config::new()
+            .with_api_key("key")
+            .with_secret("secret")
+            .with_base_url(format!("http://{}", silent.local_addr().unwrap()))
+            .with_catalog_policy(CatalogPolicy::Manual);
+        let client = Client::new(config).unwrap();
+        let hour = Duration::from_secs(3600);
+        let mut events = Events::new(&client)
+            .with_news([ColumnId::BusinessNotices], hour)
+            .with_margin_changes(hour)
+            .subscribe();
+        assert_eq!(events.health().state, TaskState::Running);
+
+        client.shutdown().await;
+        assert!(events.next().await.is_none());
+        assert_eq!(events.health().state, TaskState::Stopped);
+        assert_eq!(client.shutdown_token().running_tasks(), 0);
+    }
+}
@@ -0,0 +1,365 @@
+//! Arrow record batch and Parquet export.
+//!
+//! Converts datasets into Arrow [`RecordBatch`]es with typed columns:
+//! prices, fees and rates that arrive as strings become nullable `Float64`
+//! columns (empty or "-" values become nulls), quantities become `Int64`,
+//! and names and codes stay `Utf8`. Batches can then be written as Parquet
+//! files for columnar storage of downloaded history.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::ArrowError;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use crate::error::{Error, Result};
+use crate::models::{
+    parse_optional_f64, DailyRankingResponse, Quote, SettleParam, WarehouseReceipt,
+};
+
+/// Convert day/night/week/month quotes into a record batch.
+///
+/// # Example
+///
+/// ```no_run
+/// use dceapi_rs::export::{quotes_to_record_batch, write_parquet};
+///
+/// # fn example(quotes: &[dceapi_rs::Quote]) -> dceapi_rs::Result<()> {
+/// let batch = quotes_to_record_batch(quotes)?;
+/// write_parquet("quotes-20250930.parquet", &[batch])?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn quotes_to_record_batch(quotes: &[Quote]) -> Result<RecordBatch> {
+    record_batch(vec![
+        ("variety", text(quotes.iter().map(|q| q.variety_label()))),
+        (
+            "contract_id",
+            text(quotes.iter().map(|q| q.contract_id.as_str())),
+        ),
+        (
+            "deliv_month",
+            text(quotes.iter().map(|q| q.deliv_month.as_str())),
+        ),
+        ("open", number(quotes.iter().map(|q| q.open))),
+        ("high", number(quotes.iter().map(|q| q.high))),
+        ("low", number(quotes.iter().map(|q| q.low))),
+        ("close", number(quotes.iter().map(|q| q.close))),
+        ("last_clear", number(quotes.iter().map(|q| q.last_clear))),
+        ("clear_price", number(quotes.iter().map(|q| q.clear_price))),
+        ("diff", number(quotes.iter().map(|q| q.diff))),
+        ("volume", int(quotes.iter().map(|q| Some(q.volume)))),
+        (
+            "open_interest",
+            int(quotes.iter().map(|q| Some(q.open_interest))),
+        ),
+        ("diff_i", int(quotes.iter().map(|q| Some(q.diff_i)))),
+        ("turnover", float(quotes.iter().map(|q| q.turnover_text()))),
+    ])
+}
+
+/// Convert settlement parameters into a record batch.
+pub fn settle_params_to_record_batch(params: &[SettleParam]) -> Result<RecordBatch> {
+    record_batch(vec![
+        ("variety", text(params.iter().map(|p| p.variety.as_str()))),
+        (
+            "contract_id",
+            text(params.iter().map(|p| p.contract_id.as_str())),
+        ),
+        (
+            "clear_price",
+            float(params.iter().map(|p| p.clear_price.as_str())),
+        ),
+        (
+            "open_fee",
+            float(params.iter().map(|p| p.open_fee.as_str())),
+        ),
+        (
+            "offset_fee",
+            float(params.iter().map(|p| p.offset_fee.as_str())),
+        ),
+        (
+            "short_open_fee",
+            float(params.iter().map(|p| p.short_open_fee.as_str())),
+        ),
+        (
+            "short_offset_fee",
+            float(params.iter().map(|p| p.short_offset_fee.as_str())),
+        ),
+        ("style", text(params.iter().map(|p| p.style.as_str()))),
+        (
+            "spec_buy_rate",
+            float(params.iter().map(|p| p.spec_buy_rate.as_str())),
+        ),
+        (
+            "spec_sell_rate",
+            float(params.iter().map(|p| p.spec_sell_rate.as_str())),
+        ),
+        (
+            "hedge_buy_rate",
+            float(params.iter().map(|p| p.hedge_buy_rate.as_str())),
+        ),
+        (
+            "hedge_sell_rate",
+            float(params.iter().map(|p| p.hedge_sell_rate.as_str())),
+        ),
+    ])
+}
+
+/// Convert warehouse receipt details into a record batch.
+pub fn warehouse_receipts_to_record_batch(receipt: &WarehouseReceipt) -> Result<RecordBatch> {
+    let rows = &receipt.entity_list;
+    record_batch(vec![
+        ("variety", text(rows.iter().map(|r| r.variety.as_str()))),
+        ("gen_date", text(rows.iter().map(|r| r.gen_date.as_str()))),
+        ("wh_abbr", text(rows.iter().map(|r| r.wh_abbr.as_str()))),
+        (
+            "delivery_abbr",
+            text(rows.iter().map(|r| r.delivery_abbr.as_str())),
+        ),
+        (
+            "last_wbill_qty",
+            int(rows.iter().map(|r| Some(r.last_wbill_qty))),
+        ),
+        (
+            "reg_wbill_qty",
+            int(rows.iter().map(|r| Some(r.reg_wbill_qty))),
+        ),
+        (
+            "logout_wbill_qty",
+            int(rows.iter().map(|r| Some(r.logout_wbill_qty))),
+        ),
+        ("wbill_qty", int(rows.iter().map(|r| Some(r.wbill_qty)))),
+        ("diff", int(rows.iter().map(|r| Some(r.diff)))),
+    ])
+}
+
+/// Convert daily member rankings into a record batch, with the volume,
+/// buy and sell lists side by side. Missing entries of shorter lists are
+/// null.
+pub fn rankings_to_record_batch(ranking: &DailyRankingResponse) -> Result<RecordBatch> {
+    let len = ranking
+        .qty_future_list
+        .len()
+        .max(ranking.buy_future_list.len())
+        .max(ranking.sell_future_list.len());
+    let qty = |i: usize| ranking.qty_future_list.get(i);
+    let buy = |i: usize| ranking.buy_future_list.get(i);
+    let sell = |i: usize| ranking.sell_future_list.get(i);
+    let rank = |i: usize| {
+        [qty(i), buy(i), sell(i)]
+            .into_iter()
+            .flatten()
+            .find_map(|r| r.rank.trim().parse().ok())
+    };
+    record_batch(vec![
+        (
+            "contract_id",
+            text((0..len).map(|_| ranking.contract_id.as_str())),
+        ),
+        ("rank", int((0..len).map(rank))),
+        (
+            "qty_abbr",
+            text((0..len).map(|i| qty(i).map_or("", |r| r.qty_abbr.as_str()))),
+        ),
+        (
+            "today_qty",
+            int((0..len).map(|i| qty(i).map(|r| r.today_qty))),
+        ),
+        ("qty_sub", int((0..len).map(|i| qty(i).map(|r| r.qty_sub)))),
+        (
+            "buy_abbr",
+            text((0..len).map(|i| buy(i).map_or("", |r| r.buy_abbr.as_str()))),
+        ),
+        (
+            "today_buy_qty",
+            int((0..len).map(|i| buy(i).map(|r| r.today_buy_qty))),
+        ),
+        ("buy_sub", int((0..len).map(|i| buy(i).map(|r| r.buy_sub)))),
+        (
+            "sell_abbr",
+            text((0..len).map(|i| sell(i).map_or("", |r| r.sell_abbr.as_str()))),
+        ),
+        (
+            "today_sell_qty",
+            int((0..len).map(|i| sell(i).map(|r| r.today_sell_qty))),
+        ),
+        (
+            "sell_sub",
+            int((0..len).map(|i| sell(i).map(|r| r.sell_sub))),
+        ),
+    ])
+}
+
+/// Write record batches to a Parquet file (Snappy-compressed).
+///
+/// All batches must share the first batch's schema, for example several
+/// days of quotes each converted with [`quotes_to_record_batch`]. Writing
+/// no batches is an error.
+pub fn write_parquet(path: impl AsRef<Path>, batches: &[RecordBatch]) -> Result<()> {
+    let path = path.as_ref();
+    let schema = batches
+        .first()
+        .ok_or_else(|| Error::validation("batches", "no record batches to write"))?
+        .schema();
+    let file = File::create(path).map_err(|e| {
+        Error::export(format!(
+            "failed to create parquet file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema, Some(props)).map_err(parquet_error)?;
+    for batch in batches {
+        writer.write(batch).map_err(parquet_error)?;
+    }
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// Text column.
+fn text<'a>(values: impl Iterator<Item = &'a str>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+/// Float column from strings; values that do not parse are null.
+fn float<'a>(values: impl Iterator<Item = &'a str>) -> ArrayRef {
+    Arc::new(Float64Array::from_iter(values.map(parse_optional_f64)))
+}
+
+/// Float column from parsed values.
+fn number(values: impl Iterator<Item = Option<f64>>) -> ArrayRef {
+    Arc::new(Float64Array::from_iter(values))
+}
+
+/// Integer column.
+fn int(values: impl Iterator<Item = Option<i64>>) -> ArrayRef {
+    Arc::new(Int64Array::from_iter(values))
+}
+
+/// Build a record batch from named columns.
+fn record_batch(columns: Vec<(&str, ArrayRef)>) -> Result<RecordBatch> {
+    RecordBatch::try_from_iter(columns).map_err(arrow_error)
+}
+
+/// Convert an Arrow error into a crate error.
+fn arrow_error(err: ArrowError) -> Error {
+    Error::export(format!("failed to build record batch: {}", err))
+}
+
+/// Convert a Parquet error into a crate error.
+fn parquet_error(err: parquet::errors::ParquetError) -> Error {
+    Error::export(format!("failed to write parquet: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::Array;
+    use arrow_schema::DataType;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use serde_json::json;
+
+    use super::*;
+
+    fn quotes() -> Vec<Quote> {
+        serde_json::from_value(json!([
+            {
+                "variety": "豆粕", "contractId": "m2601", "open": "2900", "close": "2910",
+                "volumn": 120, "openInterest": 3000, "turnover": "1,234.5",
+            },
+            { "variety": "豆粕", "contractId": "m2605", "open": "-", "turnover": "" },
+        ]))
+        .unwrap()
+    }
+
+    fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> &'a T {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<T>()
+            .unwrap()
+    }
+
+    #[test]
+    fn quote_columns_are_typed_with_nulls_for_placeholders() {
+        let batch = quotes_to_record_batch(&quotes()).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let schema = batch.schema();
+        assert_eq!(
+            schema.field_with_name("variety").unwrap().data_type(),
+            &DataType::Utf8
+        );
+        assert_eq!(
+            schema.field_with_name("open").unwrap().data_type(),
+            &DataType::Float64
+        );
+        assert_eq!(
+            schema.field_with_name("volume").unwrap().data_type(),
+            &DataType::Int64
+        );
+
+        let open = column::<Float64Array>(&batch, "open");
+        assert_eq!(open.value(0), 2900.0);
+        assert!(open.is_null(1));
+        let turnover = column::<Float64Array>(&batch, "turnover");
+        assert_eq!(turnover.value(0), 1234.5);
+        assert!(turnover.is_null(1));
+        assert_eq!(column::<Int64Array>(&batch, "volume").value(0), 120);
+        assert_eq!(
+            column::<StringArray>(&batch, "contract_id").value(1),
+            "m2605"
+        );
+    }
+
+    #[test]
+    fn ranking_lists_line_up_by_row() {
+        let ranking: DailyRankingResponse = serde_json::from_value(json!({
+            "contractId": "m2601",
+            "qtyFutureList": [
+                { "rank": "1", "qtyAbbr": "甲", "todayQty": 500 },
+                { "rank": "2", "qtyAbbr": "乙", "todayQty": 400 },
+            ],
+            "buyFutureList": [{ "rank": "1", "buyAbbr": "丙", "todayBuyQty": 300 }],
+        }))
+        .unwrap();
+        let batch = rankings_to_record_batch(&ranking).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let rank = column::<Int64Array>(&batch, "rank");
+        assert_eq!((rank.value(0), rank.value(1)), (1, 2));
+        assert_eq!(column::<StringArray>(&batch, "buy_abbr").value(1), "");
+        let buy = column::<Int64Array>(&batch, "today_buy_qty");
+        assert_eq!(buy.value(0), 300);
+        assert!(buy.is_null(1));
+        assert_eq!(
+            column::<Int64Array>(&batch, "today_sell_qty").null_count(),
+            2
+        );
+    }
+
+    #[test]
+    fn parquet_files_hold_every_batch() {
+        let path = std::env::temp_dir().join(format!("quotes-{}.parquet", std::process::id()));
+        let batch = quotes_to_record_batch(&quotes()).unwrap();
+        write_parquet(&path, &[batch.clone(), batch]).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+        assert_eq!(rows, 4);
+        std::fs::remove_file(&path).unwrap();
+
+        let err = write_parquet(&path, &[]).unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }), "{err}");
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,321 @@
+//! RSS 2.0 and Atom feeds of articles.
+//!
+//! Renders article lists, such as a page of announcements, as feed
+//! documents that standard feed readers can subscribe to. Publication dates
+//! are taken as exchange time (UTC+8).
+
+use std::fmt::Write;
+
+pub use crate::config::DCE_SITE_URL;
+use crate::days::{day_number, format_day, weekday};
+use crate::models::{Article, ColumnId, Lang};
+
+/// Feed document format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FeedFormat {
+    /// RSS 2.0.
+    #[default]
+    Rss,
+    /// Atom (RFC 4287).
+    Atom,
+}
+
+/// Feed metadata and rendering.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::export::{ArticleFeed, FeedFormat};
+/// use dceapi_rs::{Article, ColumnId, Lang};
+///
+/// let feed = ArticleFeed::for_column(&ColumnId::BusinessNotices, Lang::En);
+/// let xml = feed.render(FeedFormat::Rss, &[] as &[Article]);
+/// assert!(xml.contains("<title>Business Announcements and Notices</title>"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArticleFeed {
+    title: String,
+    link: String,
+    description: String,
+    base_url: String,
+}
+
+impl ArticleFeed {
+    /// Create a feed with a title and a link to the page it mirrors.
+    pub fn new(title: impl Into<String>, link: impl Into<String>) -> Self {
+        ArticleFeed {
+            title: title.into(),
+            link: link.into(),
+            description: String::new(),
+            base_url: DCE_SITE_URL.to_string(),
+        }
+    }
+
+    /// Create a feed titled after a column, linking to the exchange site.
+    pub fn for_column(column_id: &ColumnId, lang: Lang) -> Self {
+        ArticleFeed::new(column_id.name(lang), DCE_SITE_URL)
+    }
+
+    /// Set the feed description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Set the site that relative article URLs are resolved against
+    /// (default [`DCE_SITE_URL`]).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Render articles in the given format.
+    pub fn render(&self, format: FeedFormat, articles: &[Article]) -> String {
+        match format {
+            FeedFormat::Rss => self.rss(articles),
+            FeedFormat::Atom => self.atom(articles),
+        }
+    }
+
+    /// Render articles as an RSS 2.0 document, in the given order.
+    pub fn rss(&self, articles: &[Article]) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<rss version=\"2.0\">\n<channel>\n");
+        element(&mut out, 1, "title", &self.title);
+        element(&mut out, 1, "link", &self.link);
+        element(&mut out, 1, "description", &self.description);
+        for article in articles {
+            out.push_str("  <item>\n");
+            element(&mut out, 2, "title", &article.title);
+            if let Some(link) = self.article_link(article) {
+                element(&mut out, 2, "link", &link);
+            }
+            let _ = writeln!(
+                out,
+                "    <guid isPermaLink=\"false\">{}</guid>",
+                escape(&article.id)
+            );
+            if let Some(date) = published(article).map(|p| p.rfc822()) {
+                element(&mut out, 2, "pubDate", &date);
+            }
+            if !article.summary.is_empty() {
+                element(&mut out, 2, "description", &article.summary);
+            }
+            out.push_str("  </item>\n");
+        }
+        out.push_str("</channel>\n</rss>\n");
+        out
+    }
+
+    /// Render articles as an Atom document, in the given order.
+    ///
+    /// The feed's `updated` time is that of the newest article.
+    pub fn atom(&self, articles: &[Article]) -> String {
+        let updated = articles
+            .iter()
+            .filter_map(published)
+            .max()
+            .unwrap_or_default();
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        element(&mut out, 1, "title", &self.title);
+        element(&mut out, 1, "id", &self.link);
+        let _ = writeln!(out, "  <link href=\"{}\"/>", escape(&self.link));
+        element(&mut out, 1, "updated", &updated.rfc3339());
+        if !self.description.is_empty() {
+            element(&mut out, 1, "subtitle", &self.description);
+        }
+        for article in articles {
+            out.push_str("  <entry>\n");
+            element(&mut out, 2, "title", &article.title);
+            element(
+                &mut out,
+                2,
+                "id",
+                &format!("urn:dce:article:{}", article.id),
+            );
+            if let Some(link) = self.article_link(article) {
+                let _ = writeln!(out, "    <link href=\"{}\"/>", escape(&link));
+            }
+            let date = published(article).unwrap_or(updated);
+            element(&mut out, 2, "updated", &date.rfc3339());
+            if !article.summary.is_empty() {
+                element(&mut out, 2, "summary", &article.summary);
+            }
+            out.push_str("  </entry>\n");
+        }
+        out.push_str("</feed>\n");
+        out
+    }
+
+    /// Absolute link to an article, from its static or dynamic URL.
+    fn article_link(&self, article: &Article) -> Option<String> {
+        let url = [&article.article_static_url, &article.article_dynamic_url]
+            .into_iter()
+            .map(|url| url.trim())
+            .find(|url| !url.is_empty())?;
+        if url.starts_with("http://") || url.starts_with("https://") {
+            Some(url.to_string())
+        } else {
+            Some(format!(
+                "{}/{}",
+                self.base_url.trim_end_matches('/'),
+                url.trim_start_matches('/')
+            ))
+        }
+    }
+}
+
+/// Publication time in exchange time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+struct Published {
+    day: i64,
+    seconds: u32,
+}
+
+impl Published {
+    /// RFC 822 date, as used by RSS ("Tue, 30 Sep 2025 09:30:00 +0800").
+    fn rfc822(&self) -> String {
+        const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        let (year, month, day) = self.date();
+        format!(
+            "{}, {:02} {} {} {} +0800",
+            WEEKDAYS[weekday(self.day) as usize],
+            day,
+            MONTHS[month as usize - 1],
+            year,
+            self.time()
+        )
+    }
+
+    /// RFC 3339 timestamp, as used by Atom ("2025-09-30T09:30:00+08:00").
+    fn rfc3339(&self) -> String {
+        let (year, month, day) = self.date();
+        format!("{:04}-{:02}-{:02}T{}+08:00", year, month, day, self.time())
+    }
+
+    /// Year, month and day.
+    fn date(&self) -> (i64, u32, u32) {
+        let date = format_day(self.day);
+        (
+            date[..4].parse().unwrap_or_default(),
+            date[4..6].parse().unwrap_or(1),
+            date[6..].parse().unwrap_or(1),
+        )
+    }
+
+    /// Time of day as HH:MM:SS.
+    fn time(&self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}",
+            self.seconds / 3600,
+            self.seconds / 60 % 60,
+            self.seconds % 60
+        )
+    }
+}
+
+/// Publication time of an article from `show_date` (falling back to
+/// `release_date`), which may carry a time after the date.
+fn published(article: &Article) -> Option<Published> {
+    [&article.show_date, &article.release_date]
+        .into_iter()
+        .find_map(|raw| {
+            let digits: Vec<u32> = raw.chars().filter_map(|c| c.to_digit(10)).collect();
+            let date: String = digits.iter().take(8).map(|d| d.to_string()).collect();
+            let day = day_number(&date)?;
+            let time = &digits[8..digits.len().min(14)];
+            let field = |i: usize| time.get(i * 2..i * 2 + 2).map_or(0, |p| p[0] * 10 + p[1]);
+            let seconds = (field(0) * 3600 + field(1) * 60 + field(2)).min(86_399);
+            Some(Published { day, seconds })
+        })
+}
+
+/// Write `<name>text</name>` on its own line, indented by `depth` levels.
+fn element(out: &mut String, depth: usize, name: &str, text: &str) {
+    let _ = writeln!(
+        out,
+        "{}<{}>{}</{}>",
+        "  ".repeat(depth),
+        name,
+        escape(text),
+        name
+    );
+}
+
+/// Escape text for XML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn article(id: &str, title: &str, show_date: &str, url: &str) -> Article {
+        serde_json::from_value(json!({
+            "id": id,
+            "title": title,
+            "showDate": show_date,
+            "articleStaticUrl": url,
+        }))
+        .unwrap()
+    }
+
+    fn feed() -> ArticleFeed {
+        ArticleFeed::new("Notices", "https://example.org/notices")
+            .with_description("Exchange <notices>")
+            .with_base_url("https://example.org/")
+    }
+
+    #[test]
+    fn rss_items_carry_links_dates_and_escaped_text() {
+        let articles = [
+            article("1", "Margin & limits", "2025-09-30 09:30:00", "/a/1.html"),
+            article("2", "Holiday", "20251009", "https://cdn.example.org/2.html"),
+        ];
+        let xml = feed().rss(&articles);
+        assert!(xml.contains("<description>Exchange &lt;notices&gt;</description>"));
+        assert!(xml.contains("<title>Margin &amp; limits</title>"));
+        assert!(xml.contains("<link>https://example.org/a/1.html</link>"));
+        assert!(xml.contains("<pubDate>Tue, 30 Sep 2025 09:30:00 +0800</pubDate>"));
+        assert!(xml.contains("<link>https://cdn.example.org/2.html</link>"));
+        assert!(xml.contains("<pubDate>Thu, 09 Oct 2025 00:00:00 +0800</pubDate>"));
+        assert_eq!(xml.matches("<item>").count(), 2);
+        assert!(xml.find("Margin").unwrap() < xml.find("Holiday").unwrap());
+    }
+
+    #[test]
+    fn atom_feed_is_updated_with_the_newest_article() {
+        let articles = [
+            article("1", "Old", "2025-09-30 09:30:00", ""),
+            article("2", "New", "2025-10-09 15:05:07", "b.html"),
+            article("3", "Undated", "", ""),
+        ];
+        let xml = feed().render(FeedFormat::Atom, &articles);
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<subtitle>Exchange &lt;notices&gt;</subtitle>"));
+        assert_eq!(xml.matches("2025-10-09T15:05:07+08:00").count(), 3);
+        assert!(xml.contains("<updated>2025-09-30T09:30:00+08:00</updated>"));
+        assert!(xml.contains("<id>urn:dce:article:2</id>"));
+        assert!(xml.contains("<link href=\"https://example.org/b.html\"/>"));
+        assert_eq!(xml.matches("<link href=").count(), 2);
+    }
+}
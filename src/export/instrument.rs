@@ -0,0 +1,246 @@
+//! Instrument reference data export from the contract catalog.
+//!
+//! Converts [`ContractInfo`] records into normalized [`InstrumentReference`]
+//! rows and renders them either as a flat CSV file or as FIX
+//! SecurityDefinition-like `tag=value` records, with a configurable mapping
+//! from output columns to instrument fields.
+
+use crate::catalog::contract_month;
+use crate::models::ContractInfo;
+
+/// ISO 10383 market identifier code of the Dalian Commodity Exchange.
+pub const DCE_MIC: &str = "XDCE";
+
+/// Settlement currency of DCE contracts.
+pub const DCE_CURRENCY: &str = "CNY";
+
+/// FIX field delimiter (SOH).
+pub const FIX_SOH: char = '\u{1}';
+
+/// Normalized instrument reference record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstrumentReference {
+    /// Exchange symbol (contract ID, e.g. "m2505").
+    pub symbol: String,
+    /// Market identifier code ("XDCE").
+    pub exchange: String,
+    /// Variety (product) code.
+    pub product: String,
+    /// Security type: "FUT" for futures, "OPT" for options.
+    pub security_type: String,
+    /// Contract month (YYYYMM), empty if it cannot be derived.
+    pub maturity_month_year: String,
+    /// First trading date (YYYYMMDD).
+    pub issue_date: String,
+    /// Last trading date (YYYYMMDD).
+    pub maturity_date: String,
+    /// Last delivery date (YYYYMMDD).
+    pub last_delivery_date: String,
+    /// Contract multiplier (trading unit).
+    pub contract_multiplier: i32,
+    /// Minimum price increment.
+    pub min_price_increment: String,
+    /// Currency ("CNY").
+    pub currency: String,
+}
+
+impl From<&ContractInfo> for InstrumentReference {
+    fn from(info: &ContractInfo) -> Self {
+        let security_type = if info.trade_type == "2" || info.contract_id.contains('-') {
+            "OPT"
+        } else {
+            "FUT"
+        };
+
+        InstrumentReference {
+            symbol: info.contract_id.clone(),
+            exchange: DCE_MIC.to_string(),
+            product: if info.variety_order.is_empty() {
+                info.variety.clone()
+            } else {
+                info.variety_order.clone()
+            },
+            security_type: security_type.to_string(),
+            maturity_month_year: contract_month(&info.contract_id).unwrap_or_default(),
+            issue_date: info.start_trade_date.clone(),
+            maturity_date: info.end_trade_date.clone(),
+            last_delivery_date: info.end_delivery_date.clone(),
+            contract_multiplier: info.unit,
+            min_price_increment: info.tick.clone(),
+            currency: DCE_CURRENCY.to_string(),
+        }
+    }
+}
+
+/// A field of [`InstrumentReference`] that can be mapped to an output column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstrumentField {
+    /// Exchange symbol.
+    Symbol,
+    /// Market identifier code.
+    Exchange,
+    /// Variety (product) code.
+    Product,
+    /// Security type.
+    SecurityType,
+    /// Contract month (YYYYMM).
+    MaturityMonthYear,
+    /// First trading date.
+    IssueDate,
+    /// Last trading date.
+    MaturityDate,
+    /// Last delivery date.
+    LastDeliveryDate,
+    /// Contract multiplier.
+    ContractMultiplier,
+    /// Minimum price increment.
+    MinPriceIncrement,
+    /// Currency.
+    Currency,
+}
+
+impl InstrumentField {
+    /// Read this field from a record as a string.
+    pub fn value(&self, record: &InstrumentReference) -> String {
+        match self {
+            InstrumentField::Symbol => record.symbol.clone(),
+            InstrumentField::Exchange => record.exchange.clone(),
+            InstrumentField::Product => record.product.clone(),
+            InstrumentField::SecurityType => record.security_type.clone(),
+            InstrumentField::MaturityMonthYear => record.maturity_month_year.clone(),
+            InstrumentField::IssueDate => record.issue_date.clone(),
+            InstrumentField::MaturityDate => record.maturity_date.clone(),
+            InstrumentField::LastDeliveryDate => record.last_delivery_date.clone(),
+            InstrumentField::ContractMultiplier => record.contract_multiplier.to_string(),
+            InstrumentField::MinPriceIncrement => record.min_price_increment.clone(),
+            InstrumentField::Currency => record.currency.clone(),
+        }
+    }
+}
+
+/// Exporter that renders instruments with a configurable column mapping.
+///
+/// # Example
+///
+/// ```no_run
+/// use dceapi_rs::export::{InstrumentExporter, InstrumentField};
+///
+/// # fn example(contracts: &[dceapi_rs::ContractInfo]) {
+/// let csv = InstrumentExporter::csv()
+///     .with_column("ric", InstrumentField::Symbol)
+///     .export_csv(contracts);
+///
+/// let fix = InstrumentExporter::fix().export_fix(contracts, '|');
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentExporter {
+    columns: Vec<(String, InstrumentField)>,
+}
+
+impl InstrumentExporter {
+    /// Create an exporter with no columns.
+    pub fn new() -> Self {
+        InstrumentExporter {
+            columns: Vec::new(),
+        }
+    }
+
+    /// Create an exporter with a flat reference CSV layout.
+    pub fn csv() -> Self {
+        Self::new()
+            .with_column("symbol", InstrumentField::Symbol)
+            .with_column("exchange", InstrumentField::Exchange)
+            .with_column("product", InstrumentField::Product)
+            .with_column("security_type", InstrumentField::SecurityType)
+            .with_column("maturity_month_year", InstrumentField::MaturityMonthYear)
+            .with_column("issue_date", InstrumentField::IssueDate)
+            .with_column("maturity_date", InstrumentField::MaturityDate)
+            .with_column("last_delivery_date", InstrumentField::LastDeliveryDate)
+            .with_column("contract_multiplier", InstrumentField::ContractMultiplier)
+            .with_column("min_price_increment", InstrumentField::MinPriceIncrement)
+            .with_column("currency", InstrumentField::Currency)
+    }
+
+    /// Create an exporter keyed by FIX SecurityDefinition tag numbers.
+    pub fn fix() -> Self {
+        Self::new()
+            .with_column("55", InstrumentField::Symbol)
+            .with_column("207", InstrumentField::Exchange)
+            .with_column("1151", InstrumentField::Product)
+            .with_column("167", InstrumentField::SecurityType)
+            .with_column("200", InstrumentField::MaturityMonthYear)
+            .with_column("225", InstrumentField::IssueDate)
+            .with_column("541", InstrumentField::MaturityDate)
+            .with_column("231", InstrumentField::ContractMultiplier)
+            .with_column("969", InstrumentField::MinPriceIncrement)
+            .with_column("15", InstrumentField::Currency)
+    }
+
+    /// Append an output column mapped to an instrument field.
+    pub fn with_column(mut self, name: impl Into<String>, field: InstrumentField) -> Self {
+        self.columns.push((name.into(), field));
+        self
+    }
+
+    /// Get the configured columns.
+    pub fn columns(&self) -> &[(String, InstrumentField)] {
+        &self.columns
+    }
+
+    /// Render contracts as CSV with a header row.
+    pub fn export_csv(&self, contracts: &[ContractInfo]) -> String {
+        let mut out = String::new();
+        let header: Vec<String> = self
+            .columns
+            .iter()
+            .map(|(name, _)| csv_escape(name))
+            .collect();
+        out.push_str(&header.join(","));
+        out.push('\n');
+
+        for info in contracts {
+            let record = InstrumentReference::from(info);
+            let row: Vec<String> = self
+                .columns
+                .iter()
+                .map(|(_, field)| csv_escape(&field.value(&record)))
+                .collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Render each contract as a `name=value` record joined by `delimiter`.
+    ///
+    /// With [`InstrumentExporter::fix`] and [`FIX_SOH`] this yields the body
+    /// fields of a FIX SecurityDefinition message; use `'|'` for readable logs.
+    /// Empty values are omitted.
+    pub fn export_fix(&self, contracts: &[ContractInfo], delimiter: char) -> Vec<String> {
+        contracts
+            .iter()
+            .map(|info| {
+                let record = InstrumentReference::from(info);
+                let fields: Vec<String> = self
+                    .columns
+                    .iter()
+                    .filter_map(|(name, field)| {
+                        let value = field.value(&record);
+                        (!value.is_empty()).then(|| format!("{}={}", name, value))
+                    })
+                    .collect();
+                fields.join(&delimiter.to_string())
+            })
+            .collect()
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or newline.
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,165 @@
+//! Newline-delimited JSON (JSON Lines) export.
+//!
+//! Writes records one per line to any [`AsyncWrite`] as they arrive, so
+//! large downloads can be streamed to files, pipes or log shippers without
+//! collecting them first.
+
+use std::future::poll_fn;
+use std::pin::Pin;
+
+use futures_core::Stream;
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+
+use crate::error::{Error, Result};
+
+/// Streaming JSON Lines writer.
+///
+/// Output is buffered; call [`finish`](Self::finish) (or
+/// [`flush`](Self::flush)) to make sure everything reaches the underlying
+/// writer.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::export::JsonLinesWriter;
+/// use dceapi_rs::VarietyCode;
+///
+/// # tokio_test::block_on(async {
+/// let mut writer = JsonLinesWriter::new(Vec::new());
+/// writer.write_all(["m", "y"].map(VarietyCode::from)).await?;
+/// let out = writer.finish().await?;
+/// assert_eq!(String::from_utf8(out).unwrap(), "\"m\"\n\"y\"\n");
+/// # Ok::<(), dceapi_rs::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct JsonLinesWriter<W: AsyncWrite + Unpin> {
+    writer: BufWriter<W>,
+    line: Vec<u8>,
+    written: u64,
+}
+
+impl<W: AsyncWrite + Unpin> JsonLinesWriter<W> {
+    /// Create a writer over `writer`.
+    pub fn new(writer: W) -> Self {
+        JsonLinesWriter {
+            writer: BufWriter::new(writer),
+            line: Vec::new(),
+            written: 0,
+        }
+    }
+
+    /// Number of records written so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Write one record as a line.
+    pub async fn write<T: Serialize + ?Sized>(&mut self, record: &T) -> Result<()> {
+        self.line.clear();
+        serde_json::to_writer(&mut self.line, record)
+            .map_err(|e| Error::export(format!("failed to serialize record: {}", e)))?;
+        self.line.push(b'\n');
+        self.writer
+            .write_all(&self.line)
+            .await
+            .map_err(write_error)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Write every record of a collection, one per line.
+    pub async fn write_all<I>(&mut self, records: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Serialize,
+    {
+        for record in records {
+            self.write(&record).await?;
+        }
+        Ok(())
+    }
+
+    /// Write records from a stream, such as a [`Watch`](crate::Watch),
+    /// until it ends. Each record is written as soon as it arrives.
+    pub async fn write_stream<S>(&mut self, mut records: S) -> Result<()>
+    where
+        S: Stream + Unpin,
+        S::Item: Serialize,
+    {
+        while let Some(record) = poll_fn(|cx| Pin::new(&mut records).poll_next(cx)).await {
+            self.write(&record).await?;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered lines to the underlying writer.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.writer.flush().await.map_err(write_error)
+    }
+
+    /// Flush buffered lines and return the underlying writer.
+    pub async fn finish(mut self) -> Result<W> {
+        self.flush().await?;
+        Ok(self.writer.into_inner())
+    }
+}
+
+/// Convert a write failure into a crate error.
+fn write_error(err: std::io::Error) -> Error {
+    Error::export(format!("failed to write JSON lines: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::task::{Context, Poll};
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// Stream over a vector, ready on every poll.
+    struct Items(std::vec::IntoIter<serde_json::Value>);
+
+    impl Stream for Items {
+        type Item = serde_json::Value;
+
+        fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    #[tokio::test]
+    async fn records_are_written_one_per_line() {
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer
+            .write(&json!({ "title": "line one\nline two" }))
+            .await
+            .unwrap();
+        writer.write_all([1, 2]).await.unwrap();
+        writer
+            .write_stream(Items(vec![json!("a"), json!(null)].into_iter()))
+            .await
+            .unwrap();
+        assert_eq!(writer.written(), 5);
+
+        let out = String::from_utf8(writer.finish().await.unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "{\"title\":\"line one\\nline two\"}\n1\n2\n\"a\"\nnull\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn unserializable_records_are_not_counted() {
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        let record: BTreeMap<(i32, i32), i32> = BTreeMap::from([((1, 2), 3)]);
+        let err = writer.write(&record).await.unwrap_err();
+        assert!(matches!(err, Error::Export { .. }), "{err}");
+        writer.write("ok").await.unwrap();
+        assert_eq!(writer.written(), 1);
+        assert_eq!(writer.finish().await.unwrap(), b"\"ok\"\n");
+    }
+}
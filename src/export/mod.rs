@@ -0,0 +1,24 @@
+//! Exporters that convert API responses into external data formats.
+
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "feed")]
+mod feed;
+mod instrument;
+mod jsonl;
+#[cfg(feature = "xlsx")]
+mod xlsx;
+
+#[cfg(feature = "arrow")]
+pub use arrow::{
+    quotes_to_record_batch, rankings_to_record_batch, settle_params_to_record_batch,
+    warehouse_receipts_to_record_batch, write_parquet,
+};
+#[cfg(feature = "feed")]
+pub use feed::{ArticleFeed, FeedFormat, DCE_SITE_URL};
+pub use instrument::{
+    InstrumentExporter, InstrumentField, InstrumentReference, DCE_CURRENCY, DCE_MIC, FIX_SOH,
+};
+pub use jsonl::JsonLinesWriter;
+#[cfg(feature = "xlsx")]
+pub use xlsx::XlsxReport;
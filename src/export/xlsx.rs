@@ -0,0 +1,320 @@
+//! Excel (xlsx) report export.
+//!
+//! Builds a workbook with one sheet per dataset. Numeric values that arrive
+//! as strings are written as numbers so that spreadsheets can sum and sort
+//! them; values that do not parse are kept as text.
+
+use std::path::Path;
+
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+
+use crate::error::{Error, Result};
+use crate::models::{
+    parse_optional_f64, DailyRankingResponse, Quote, SettleParam, WarehouseReceipt,
+};
+
+/// A single cell value.
+enum Cell {
+    Text(String),
+    Number(f64),
+}
+
+impl Cell {
+    /// Text cell.
+    fn text(value: &str) -> Cell {
+        Cell::Text(value.to_string())
+    }
+
+    /// Numeric cell from a string, falling back to text if it does not parse.
+    fn numeric(value: &str) -> Cell {
+        match parse_optional_f64(value) {
+            Some(number) => Cell::Number(number),
+            None => Cell::Text(value.to_string()),
+        }
+    }
+
+    /// Numeric cell, or an empty cell if the value is missing.
+    fn optional(value: Option<f64>) -> Cell {
+        value.map_or_else(|| Cell::text(""), Cell::Number)
+    }
+
+    /// Numeric cell from an integer.
+    fn int(value: i64) -> Cell {
+        Cell::Number(value as f64)
+    }
+}
+
+/// Multi-sheet Excel report.
+///
+/// # Example
+///
+/// ```no_run
+/// use dceapi_rs::export::XlsxReport;
+///
+/// # fn example(quotes: &[dceapi_rs::Quote], params: &[dceapi_rs::SettleParam]) -> dceapi_rs::Result<()> {
+/// let mut report = XlsxReport::new();
+/// report.add_quotes("quotes", quotes)?;
+/// report.add_settle_params("settle", params)?;
+/// report.save("daily-report.xlsx")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct XlsxReport {
+    workbook: Workbook,
+    header_format: Format,
+    number_format: Format,
+}
+
+impl Default for XlsxReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for XlsxReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XlsxReport").finish_non_exhaustive()
+    }
+}
+
+impl XlsxReport {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        XlsxReport {
+            workbook: Workbook::new(),
+            header_format: Format::new().set_bold(),
+            number_format: Format::new().set_num_format("#,##0.####"),
+        }
+    }
+
+    /// Add a sheet with day/night/week/month quotes.
+    pub fn add_quotes(&mut self, sheet_name: &str, quotes: &[Quote]) -> Result<&mut Self> {
+        let headers = [
+            "variety",
+            "contract_id",
+            "open",
+            "high",
+            "low",
+            "close",
+            "last_clear",
+            "clear_price",
+            "diff",
+            "volume",
+            "open_interest",
+            "diff_i",
+            "turnover",
+        ];
+        let rows = quotes.iter().map(|q| {
+            vec![
+                Cell::text(q.variety_label()),
+                Cell::text(&q.contract_id),
+                Cell::optional(q.open),
+                Cell::optional(q.high),
+                Cell::optional(q.low),
+                Cell::optional(q.close),
+                Cell::optional(q.last_clear),
+                Cell::optional(q.clear_price),
+                Cell::optional(q.diff),
+                Cell::int(q.volume),
+                Cell::int(q.open_interest),
+                Cell::int(q.diff_i),
+                Cell::numeric(q.turnover_text()),
+            ]
+        });
+        self.add_table(sheet_name, &headers, rows)
+    }
+
+    /// Add a sheet with settlement parameters.
+    pub fn add_settle_params(
+        &mut self,
+        sheet_name: &str,
+        params: &[SettleParam],
+    ) -> Result<&mut Self> {
+        let headers = [
+            "variety",
+            "contract_id",
+            "clear_price",
+            "open_fee",
+            "offset_fee",
+            "short_open_fee",
+            "short_offset_fee",
+            "style",
+            "spec_buy_rate",
+            "spec_sell_rate",
+            "hedge_buy_rate",
+            "hedge_sell_rate",
+        ];
+        let rows = params.iter().map(|p| {
+            vec![
+                Cell::text(&p.variety),
+                Cell::text(&p.contract_id),
+                Cell::numeric(&p.clear_price),
+                Cell::numeric(&p.open_fee),
+                Cell::numeric(&p.offset_fee),
+                Cell::numeric(&p.short_open_fee),
+                Cell::numeric(&p.short_offset_fee),
+                Cell::text(&p.style),
+                Cell::numeric(&p.spec_buy_rate),
+                Cell::numeric(&p.spec_sell_rate),
+                Cell::numeric(&p.hedge_buy_rate),
+                Cell::numeric(&p.hedge_sell_rate),
+            ]
+        });
+        self.add_table(sheet_name, &headers, rows)
+    }
+
+    /// Add a sheet with warehouse receipt details.
+    pub fn add_warehouse_receipts(
+        &mut self,
+        sheet_name: &str,
+        receipt: &WarehouseReceipt,
+    ) -> Result<&mut Self> {
+        let headers = [
+            "variety",
+            "gen_date",
+            "wh_abbr",
+            "delivery_abbr",
+            "last_wbill_qty",
+            "reg_wbill_qty",
+            "logout_wbill_qty",
+            "wbill_qty",
+            "diff",
+        ];
+        let rows = receipt.entity_list.iter().map(|r| {
+            vec![
+                Cell::text(&r.variety),
+                Cell::text(&r.gen_date),
+                Cell::text(&r.wh_abbr),
+                Cell::text(&r.delivery_abbr),
+                Cell::int(r.last_wbill_qty),
+                Cell::int(r.reg_wbill_qty),
+                Cell::int(r.logout_wbill_qty),
+                Cell::int(r.wbill_qty),
+                Cell::int(r.diff),
+            ]
+        });
+        self.add_table(sheet_name, &headers, rows)
+    }
+
+    /// Add a sheet with daily member rankings (volume, buy and sell side by side).
+    pub fn add_rankings(
+        &mut self,
+        sheet_name: &str,
+        ranking: &DailyRankingResponse,
+    ) -> Result<&mut Self> {
+        let headers = [
+            "rank",
+            "qty_abbr",
+            "today_qty",
+            "qty_sub",
+            "buy_abbr",
+            "today_buy_qty",
+            "buy_sub",
+            "sell_abbr",
+            "today_sell_qty",
+            "sell_sub",
+        ];
+        let len = ranking
+            .qty_future_list
+            .len()
+            .max(ranking.buy_future_list.len())
+            .max(ranking.sell_future_list.len());
+        let rows = (0..len).map(|i| {
+            let qty = ranking.qty_future_list.get(i);
+            let buy = ranking.buy_future_list.get(i);
+            let sell = ranking.sell_future_list.get(i);
+            let rank = [qty, buy, sell]
+                .into_iter()
+                .flatten()
+                .map(|r| r.rank.as_str())
+                .next()
+                .unwrap_or_default();
+            vec![
+                Cell::numeric(rank),
+                Cell::text(qty.map(|r| r.qty_abbr.as_str()).unwrap_or_default()),
+                qty.map(|r| Cell::int(r.today_qty))
+                    .unwrap_or(Cell::text("")),
+                qty.map(|r| Cell::int(r.qty_sub)).unwrap_or(Cell::text("")),
+                Cell::text(buy.map(|r| r.buy_abbr.as_str()).unwrap_or_default()),
+                buy.map(|r| Cell::int(r.today_buy_qty))
+                    .unwrap_or(Cell::text("")),
+                buy.map(|r| Cell::int(r.buy_sub)).unwrap_or(Cell::text("")),
+                Cell::text(sell.map(|r| r.sell_abbr.as_str()).unwrap_or_default()),
+                sell.map(|r| Cell::int(r.today_sell_qty))
+                    .unwrap_or(Cell::text("")),
+                sell.map(|r| Cell::int(r.sell_sub))
+                    .unwrap_or(Cell::text("")),
+            ]
+        });
+        self.add_table(sheet_name, &headers, rows)
+    }
+
+    /// Save the workbook to a file.
+    pub fn save(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        self.workbook.save(path).map_err(xlsx_error)
+    }
+
+    /// Render the workbook into an in-memory buffer.
+    pub fn to_bytes(&mut self) -> Result<Vec<u8>> {
+        self.workbook.save_to_buffer().map_err(xlsx_error)
+    }
+
+    /// Add a sheet with a bold, frozen header row followed by the given rows.
+    fn add_table(
+        &mut self,
+        sheet_name: &str,
+        headers: &[&str],
+        rows: impl Iterator<Item = Vec<Cell>>,
+    ) -> Result<&mut Self> {
+        let header_format = self.header_format.clone();
+        let number_format = self.number_format.clone();
+        let sheet = self.workbook.add_worksheet();
+        write_table(
+            sheet,
+            sheet_name,
+            headers,
+            rows,
+            &header_format,
+            &number_format,
+        )
+        .map_err(xlsx_error)?;
+        Ok(self)
+    }
+}
+
+/// Write a header row and data rows into a worksheet.
+fn write_table(
+    sheet: &mut Worksheet,
+    sheet_name: &str,
+    headers: &[&str],
+    rows: impl Iterator<Item = Vec<Cell>>,
+    header_format: &Format,
+    number_format: &Format,
+) -> std::result::Result<(), XlsxError> {
+    sheet.set_name(sheet_name)?;
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *header, header_format)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+
+    for (i, row) in rows.enumerate() {
+        let row_idx = (i + 1) as u32;
+        for (col, cell) in row.into_iter().enumerate() {
+            match cell {
+                Cell::Text(value) => {
+                    sheet.write_string(row_idx, col as u16, value)?;
+                }
+                Cell::Number(value) => {
+                    sheet.write_number_with_format(row_idx, col as u16, value, number_format)?;
+                }
+            }
+        }
+    }
+    sheet.autofit();
+    Ok(())
+}
+
+/// Convert an xlsx writer error into a crate error.
+fn xlsx_error(err: XlsxError) -> Error {
+    Error::export(format!("failed to write xlsx: {}", err))
+}
@@ -0,0 +1,381 @@
+//! Trading fees.
+//!
+//! The exchange charges fees either per lot (元/手) or as a ratio of the
+//! traded value (成交额比例), as the trading parameters' `feeStyle` states,
+//! with separate rates for opening, offsetting and intraday (短线) trades.
+//! [`FeeCalculator`] applies these rates to a trade.
+
+use crate::catalog::contract_size;
+use crate::contract_id::ContractId;
+use crate::error::{Error, Result};
+use crate::margin::HedgeFlag;
+use crate::models::{is_ratio_label, parse_optional_f64, ContractInfo, SettleParam, TradingParam};
+
+/// How a fee is charged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeeStyle {
+    /// Yuan per lot (元/手).
+    PerLot,
+    /// Fraction of the traded value (成交额比例).
+    Ratio,
+}
+
+impl FeeStyle {
+    /// Parse a published fee style such as "元/手", "成交额比例" or their
+    /// English labels.
+    pub fn parse(label: &str) -> Option<FeeStyle> {
+        let label = label.trim().to_lowercase();
+        if label.is_empty() {
+            None
+        } else if is_ratio_label(&label) {
+            Some(FeeStyle::Ratio)
+        } else if ["手", "lot", "absolute", "绝对"]
+            .iter()
+            .any(|k| label.contains(k))
+        {
+            Some(FeeStyle::PerLot)
+        } else {
+            None
+        }
+    }
+}
+
+/// Kind of trade a fee is charged on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeeKind {
+    /// Opening a position.
+    Open,
+    /// Offsetting a position opened on an earlier day.
+    Offset,
+    /// Opening a position that is offset the same day.
+    IntradayOpen,
+    /// Offsetting a position opened the same day.
+    IntradayOffset,
+}
+
+/// Fee charged on a trade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fee {
+    /// Contract ID.
+    pub contract_id: String,
+    /// Kind of trade.
+    pub kind: FeeKind,
+    /// Speculation or hedging.
+    pub hedge: HedgeFlag,
+    /// Number of lots.
+    pub lots: i64,
+    /// How the fee is charged.
+    pub style: FeeStyle,
+    /// Yuan per lot, or a fraction of the traded value.
+    pub rate: f64,
+    /// Fee in yuan.
+    pub fee: f64,
+}
+
+/// Computes trading fees from published fee rates.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::{FeeCalculator, FeeKind, HedgeFlag, TradingParam};
+///
+/// let param = |variety: &str, fee: &str, style: &str| -> TradingParam {
+///     serde_json::from_value(serde_json::json!({
+///         "varietyId": variety, "specOpenFee": fee, "feeStyle": style,
+///     }))
+///     .unwrap()
+/// };
+/// let calculator = FeeCalculator::new().with_trading_params(vec![
+///     param("m", "1.5", "元/手"),
+///     param("i", "0.0001", "成交额比例"),
+/// ]);
+///
+/// let fee = calculator.fee("m2509", FeeKind::Open, HedgeFlag::Speculation, 10, 3000.0).unwrap();
+/// assert_eq!(fee.fee, 15.0);
+/// // 10 lots of 100 tons at 800 yuan, at 0.01% of the traded value.
+/// let fee = calculator.fee("i2509", FeeKind::Open, HedgeFlag::Speculation, 10, 800.0).unwrap();
+/// assert!((fee.fee - 80.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FeeCalculator {
+    trading_params: Vec<TradingParam>,
+    settle_params: Vec<SettleParam>,
+    contracts: Vec<ContractInfo>,
+}
+
+impl FeeCalculator {
+    /// Create a calculator without rates.
+    pub fn new() -> Self {
+        FeeCalculator::default()
+    }
+
+    /// Set the per-variety trading parameters (from
+    /// [`TradeService::get_trading_param`](crate::TradeService::get_trading_param)).
+    pub fn with_trading_params(mut self, params: Vec<TradingParam>) -> Self {
+        self.trading_params = params;
+        self
+    }
+
+    /// Set the per-contract settlement parameters (from
+    /// [`SettleService::get_settle_param`](crate::SettleService::get_settle_param)).
+    pub fn with_settle_params(mut self, params: Vec<SettleParam>) -> Self {
+        self.settle_params = params;
+        self
+    }
+
+    /// Set the contract information (from the
+    /// [contract catalog](crate::Client::catalog)) whose trading units size
+    /// ratio fees. Contracts not listed use their variety's standard
+    /// contract size.
+    pub fn with_contracts(mut self, contracts: Vec<ContractInfo>) -> Self {
+        self.contracts = contracts;
+        self
+    }
+
+    /// Fee for trading `lots` lots of a contract at `price`.
+    ///
+    /// Speculative fees come from the contract's settlement parameters when
+    /// set, and otherwise, like hedging fees, from the variety's trading
+    /// parameters. A rate written with "%", "‱" or "元/手" carries its own
+    /// style; any other rate takes the variety's `feeStyle`, and is an
+    /// [`Error::MissingData`] when that is not published either. `price` is
+    /// only used for ratio fees.
+    ///
+    /// # Arguments
+    /// * `contract_id` - Contract ID (e.g. "m2509")
+    /// * `kind` - Kind of trade
+    /// * `hedge` - Speculation or hedging
+    /// * `lots` - Number of lots
+    /// * `price` - Trade price per unit
+    pub fn fee(
+        &self,
+        contract_id: &str,
+        kind: FeeKind,
+        hedge: HedgeFlag,
+        lots: i64,
+        price: f64,
+    ) -> Result<Fee> {
+        if lots <= 0 {
+            return Err(Error::validation("lots", "lots must be positive"));
+        }
+        let contract_id = contract_id.trim();
+        let contract = ContractId::parse(contract_id)?;
+        let variety = self
+            .trading_params
+            .iter()
+            .find(|p| p.variety_id.trim().eq_ignore_ascii_case(&contract.variety));
+        let settle = self
+            .settle_params
+            .iter()
+            .find(|p| p.contract_id.trim().eq_ignore_ascii_case(contract_id))
+            .filter(|_| hedge == HedgeFlag::Speculation)
+            .map(|p| match kind {
+                FeeKind::Open => &p.open_fee,
+                FeeKind::Offset => &p.offset_fee,
+                FeeKind::IntradayOpen => &p.short_open_fee,
+                FeeKind::IntradayOffset => &p.short_offset_fee,
+            });
+        let published = variety.map(|p| match (hedge, kind) {
+            (HedgeFlag::Speculation, FeeKind::Open) => &p.spec_open_fee,
+            (HedgeFlag::Speculation, FeeKind::Offset) => &p.spec_offset_fee,
+            (HedgeFlag::Speculation, FeeKind::IntradayOpen) => &p.spec_short_open_fee,
+            (HedgeFlag::Speculation, FeeKind::IntradayOffset) => &p.spec_short_offset_fee,
+            (HedgeFlag::Hedging, FeeKind::Open) => &p.hedge_open_fee,
+            (HedgeFlag::Hedging, FeeKind::Offset) => &p.hedge_offset_fee,
+            (HedgeFlag::Hedging, FeeKind::IntradayOpen) => &p.hedge_short_open_fee,
+            (HedgeFlag::Hedging, FeeKind::IntradayOffset) => &p.hedge_short_offset_fee,
+        });
+        let (rate, written_style) = settle
+            .and_then(|raw| fee_value(raw))
+            .or_else(|| published.and_then(|raw| fee_value(raw)))
+            .ok_or_else(|| Error::missing_data(format!("no fee rate for {}", contract_id)))?;
+
+        let style = written_style
+            .or_else(|| variety.and_then(|p| FeeStyle::parse(p.fee_style_label())))
+            .ok_or_else(|| Error::missing_data(format!("no fee style for {}", contract_id)))?;
+        let fee = match style {
+            FeeStyle::PerLot => rate * lots as f64,
+            FeeStyle::Ratio => {
+                if !(price.is_finite() && price > 0.0) {
+                    return Err(Error::validation("price", "price must be positive"));
+                }
+                rate * lots as f64 * contract_size(&self.contracts, &contract)? * price
+            }
+        };
+        Ok(Fee {
+            contract_id: contract_id.to_string(),
+            kind,
+            hedge,
+            lots,
+            style,
+            rate,
+            fee,
+        })
+    }
+
+    /// Fees for opening and offsetting `lots` lots, on the same day when
+    /// `intraday` is set.
+    ///
+    /// # Arguments
+    /// * `contract_id` - Contract ID (e.g. "m2509")
+    /// * `hedge` - Speculation or hedging
+    /// * `lots` - Number of lots
+    /// * `open_price` - Opening price per unit
+    /// * `close_price` - Offsetting price per unit
+    /// * `intraday` - Whether the position is offset the day it is opened
+    pub fn round_trip(
+        &self,
+        contract_id: &str,
+        hedge: HedgeFlag,
+        lots: i64,
+        open_price: f64,
+        close_price: f64,
+        intraday: bool,
+    ) -> Result<f64> {
+        let (open, offset) = if intraday {
+            (FeeKind::IntradayOpen, FeeKind::IntradayOffset)
+        } else {
+            (FeeKind::Open, FeeKind::Offset)
+        };
+        Ok(self.fee(contract_id, open, hedge, lots, open_price)?.fee
+            + self.fee(contract_id, offset, hedge, lots, close_price)?.fee)
+    }
+}
+
+/// Parse a fee rate, with the style its unit gives away: "0.01%" and
+/// "1‱" are ratios, "1.5元/手" is per lot.
+fn fee_value(raw: &str) -> Option<(f64, Option<FeeStyle>)> {
+    let raw = raw.trim();
+    if let Some(number) = raw.strip_suffix('%') {
+        return Some((parse_optional_f64(number)? / 100.0, Some(FeeStyle::Ratio)));
+    }
+    if let Some(number) = raw.strip_suffix('‱') {
+        return Some((
+            parse_optional_f64(number)? / 10_000.0,
+            Some(FeeStyle::Ratio),
+        ));
+    }
+    if let Some(number) = raw.strip_suffix("元/手") {
+        return Some((parse_optional_f64(number)?, Some(FeeStyle::PerLot)));
+    }
+    Some((parse_optional_f64(raw)?, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculator(fee: &str, style: &str) -> FeeCalculator {
+        let param = serde_json::from_value(serde_json::json!({
+            "varietyId": "m", "specOpenFee": fee, "hedgeOpenFee": "1", "feeStyle": style,
+        }))
+        .unwrap();
+        FeeCalculator::new().with_trading_params(vec![param])
+    }
+
+    fn open(calculator: &FeeCalculator, hedge: HedgeFlag) -> Result<Fee> {
+        calculator.fee("m2509", FeeKind::Open, hedge, 2, 3000.0)
+    }
+
+    #[test]
+    fn written_unit_overrides_fee_style() {
+        let fee = open(&calculator("0.01%", "元/手"), HedgeFlag::Speculation).unwrap();
+        assert_eq!(fee.style, FeeStyle::Ratio);
+        // 2 lots of 10 tons at 3,000 yuan, at 0.01%.
+        assert!((fee.fee - 6.0).abs() < 1e-9);
+        let fee = open(&calculator("2元/手", "成交额比例"), HedgeFlag::Speculation).unwrap();
+        assert_eq!((fee.style, fee.fee), (FeeStyle::PerLot, 4.0));
+    }
+
+    #[test]
+    fn bare_rates_take_the_fee_style() {
+        let fee = open(&calculator("0.005", "元/手"), HedgeFlag::Speculation).unwrap();
+        assert_eq!((fee.style, fee.fee), (FeeStyle::PerLot, 0.01));
+        let fee = open(&calculator("0.0001", "Ratio"), HedgeFlag::Speculation).unwrap();
+        assert_eq!((fee.style, fee.rate), (FeeStyle::Ratio, 0.0001));
+    }
+
+    #[test]
+    fn bare_rate_without_fee_style_is_missing_data() {
+        let err = open(&calculator("0.0001", ""), HedgeFlag::Speculation).unwrap_err();
+        assert!(matches!(err, Error::MissingData { .. }), "{err}");
+    }
+
+    #[test]
+    fn settlement_fees_override_speculative_rates() {
+        let settle = serde_json::from_value(serde_json::json!({
+            "contractId": "m2509", "openFee": "3",
+        }))
+        .unwrap();
+        let calculator = calculator("1", "元/手").with_settle_params(vec![settle]);
+        assert_eq!(open(&calculator, HedgeFlag::Speculation).unwrap().fee, 6.0);
+        assert_eq!(open(&calculator, HedgeFlag::Hedging).unwrap().fee, 2.0);
+    }
+
+    #[test]
+    fn parses_fee_styles() {
+        assert_eq!(FeeStyle::parse("成交额比例"), Some(FeeStyle::Ratio));
+        assert_eq!(
+            FeeStyle::parse(" Percent of turnover"),
+            Some(FeeStyle::Ratio)
+        );
+        assert_eq!(FeeStyle::parse("元/手"), Some(FeeStyle::PerLot));
+        assert_eq!(FeeStyle::parse("Absolute"), Some(FeeStyle::PerLot));
+        assert_eq!(FeeStyle::parse(""), None);
+        assert_eq!(FeeStyle::parse("other"), None);
+    }
+
+    #[test]
+    fn parses_written_fee_units() {
+        assert_eq!(fee_value(" 1.5元/手 "), Some((1.5, Some(FeeStyle::PerLot))));
+        assert_eq!(fee_value("1‱"), Some((0.0001, Some(FeeStyle::Ratio))));
+        assert_eq!(fee_value("0.01%"), Some((0.0001, Some(FeeStyle::Ratio))));
+        assert_eq!(fee_value("2"), Some((2.0, None)));
+        assert_eq!(fee_value("-"), None);
+        assert_eq!(fee_value("%"), None);
+        assert_eq!(fee_value(""), None);
+    }
+
+    #[test]
+    fn unpublished_settlement_fees_fall_back_to_trading_params() {
+        let settle = serde_json::from_value(serde_json::json!({
+            "contractId": "m2509", "openFee": "-",
+        }))
+        .unwrap();
+        let calculator = calculator("1", "元/手").with_settle_params(vec![settle]);
+        assert_eq!(open(&calculator, HedgeFlag::Speculation).unwrap().fee, 2.0);
+    }
+
+    #[test]
+    fn round_trips_use_intraday_rates_when_intraday() {
+        let param = serde_json::from_value(serde_json::json!({
+            "varietyId": "m", "feeStyle": "元/手",
+            "specOpenFee": "1.5", "specOffsetFee": "1.5",
+            "specShortOpenFee": "1.5", "specShortOffsetFee": "0",
+        }))
+        .unwrap();
+        let calculator = FeeCalculator::new().with_trading_params(vec![param]);
+        let round_trip = |intraday| {
+            calculator
+                .round_trip("m2509", HedgeFlag::Speculation, 4, 3000.0, 3100.0, intraday)
+                .unwrap()
+        };
+        assert_eq!(round_trip(false), 12.0);
+        assert_eq!(round_trip(true), 6.0);
+    }
+
+    #[test]
+    fn invalid_trades_are_rejected() {
+        let calculator = calculator("0.01%", "");
+        for (lots, price) in [(0, 3000.0), (-2, 3000.0), (2, 0.0), (2, f64::INFINITY)] {
+            let err = calculator
+                .fee("m2509", FeeKind::Open, HedgeFlag::Speculation, lots, price)
+                .unwrap_err();
+            assert!(matches!(err, Error::Validation { .. }), "{err}");
+        }
+        let err = calculator
+            .fee("y2509", FeeKind::Open, HedgeFlag::Speculation, 2, 3000.0)
+            .unwrap_err();
+        assert!(matches!(err, Error::MissingData { .. }), "{err}");
+    }
+}
@@ -0,0 +1,400 @@
+//! Plain text and Markdown conversion of article HTML.
+//!
+//! Article content arrives as an HTML fragment. The converters here keep
+//! the document structure (paragraphs, headings, lists and tables) and the
+//! targets of links and images, and drop scripts, styles and layout markup.
+
+use scraper::{ElementRef, Html, Node};
+
+use crate::models::Article;
+
+/// Convert an HTML fragment to plain text.
+///
+/// Links are written as "text (url)" and images as "[image: url]".
+///
+/// ```
+/// use dceapi_rs::html_to_text;
+///
+/// let text = html_to_text("<p>关于<b>调整</b>保证金的通知</p><p><a href=\"/a.pdf\">附件</a></p>");
+/// assert_eq!(text, "关于调整保证金的通知\n\n附件 (/a.pdf)");
+/// ```
+pub fn html_to_text(html: &str) -> String {
+    convert(html, false)
+}
+
+/// Convert an HTML fragment to Markdown.
+///
+/// ```
+/// use dceapi_rs::html_to_markdown;
+///
+/// let md = html_to_markdown("<h2>通知</h2><ul><li>豆粕</li><li>豆油</li></ul>");
+/// assert_eq!(md, "## 通知\n\n- 豆粕\n- 豆油");
+/// ```
+pub fn html_to_markdown(html: &str) -> String {
+    convert(html, true)
+}
+
+impl Article {
+    /// Content as plain text; see [`html_to_text`].
+    pub fn text(&self) -> String {
+        html_to_text(&self.content)
+    }
+
+    /// Content as Markdown; see [`html_to_markdown`].
+    pub fn markdown(&self) -> String {
+        html_to_markdown(&self.content)
+    }
+}
+
+/// Parse and render a fragment.
+fn convert(html: &str, markdown: bool) -> String {
+    let fragment = Html::parse_fragment(html);
+    let mut renderer = Renderer::new(markdown);
+    renderer.children(fragment.root_element());
+    renderer.finish()
+}
+
+/// Accumulates rendered output.
+struct Renderer {
+    out: String,
+    markdown: bool,
+    /// Open lists, innermost last: the next item number for ordered lists.
+    lists: Vec<Option<usize>>,
+    /// Whether whitespace is kept as is (inside `<pre>`).
+    preformatted: bool,
+}
+
+impl Renderer {
+    /// Create an empty renderer.
+    fn new(markdown: bool) -> Self {
+        Renderer {
+            out: String::new(),
+            markdown,
+            lists: Vec::new(),
+            preformatted: false,
+        }
+    }
+
+    /// Render an element's children into a separate buffer.
+    fn nested(&self, element: ElementRef<'_>) -> String {
+        let mut inner = Renderer::new(self.markdown);
+        inner.children(element);
+        inner.finish()
+    }
+
+    /// Render every child node of an element.
+    fn children(&mut self, element: ElementRef<'_>) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Render one element.
+    fn element(&mut self, element: ElementRef<'_>) {
+        let name = element.value().name();
+        match name {
+            "script" | "style" | "head" | "title" | "noscript" => {}
+            "br" => self.out.push('\n'),
+            "hr" => {
+                self.block();
+                if self.markdown {
+                    self.out.push_str("---");
+                    self.block();
+                }
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block();
+                if self.markdown {
+                    let level = name[1..].parse().unwrap_or(1);
+                    self.out.push_str(&"#".repeat(level));
+                    self.out.push(' ');
+                }
+                self.children(element);
+                self.block();
+            }
+            "ul" | "ol" => {
+                // Nested lists continue their parent item without a blank line.
+                let nested = !self.lists.is_empty();
+                if nested {
+                    self.line();
+                } else {
+                    self.block();
+                }
+                self.lists.push((name == "ol").then_some(1));
+                self.children(element);
+                self.lists.pop();
+                if nested {
+                    self.line();
+                } else {
+                    self.block();
+                }
+            }
+            "li" => {
+                self.line();
+                let depth = self.lists.len().saturating_sub(1);
+                self.out.push_str(&"  ".repeat(depth));
+                match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        self.out.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => self.out.push_str("- "),
+                }
+                self.children(element);
+                self.line();
+            }
+            "table" => {
+                self.block();
+                self.table(element);
+                self.block();
+            }
+            "blockquote" => {
+                self.block();
+                let inner = self.nested(element);
+                let prefix = if self.markdown { "> " } else { "  " };
+                let quoted: Vec<String> = inner
+                    .lines()
+                    .map(|line| format!("{}{}", prefix, line).trim_end().to_string())
+                    .collect();
+                self.out.push_str(&quoted.join("\n"));
+                self.block();
+            }
+            "pre" => {
+                self.block();
+                let fence = self.markdown;
+                if fence {
+                    self.out.push_str("```\n");
+                }
+                let was = std::mem::replace(&mut self.preformatted, true);
+                self.children(element);
+                self.preformatted = was;
+                if fence {
+                    self.line();
+                    self.out.push_str("```");
+                }
+                self.block();
+            }
+            "a" => {
+                let label = self.nested(element);
+                let href = element
+                    .value()
+                    .attr("href")
+                    .map(str::trim)
+                    .filter(|h| !h.is_empty() && !h.starts_with("javascript:"));
+                match href {
+                    Some(href) if self.markdown => {
+                        self.out.push_str(&format!("[{}]({})", label, href));
+                    }
+                    Some(href) if label.is_empty() || label == href => self.out.push_str(href),
+                    Some(href) => self.out.push_str(&format!("{} ({})", label, href)),
+                    None => self.out.push_str(&label),
+                }
+            }
+            "img" => {
+                let src = element.value().attr("src").unwrap_or_default().trim();
+                let alt = element.value().attr("alt").unwrap_or_default().trim();
+                if src.is_empty() {
+                    self.out.push_str(alt);
+                } else if self.markdown {
+                    self.out.push_str(&format!("![{}]({})", alt, src));
+                } else {
+                    self.out.push_str(&format!("[image: {}]", src));
+                }
+            }
+            "strong" | "b" | "em" | "i" if self.markdown => {
+                let mark = if matches!(name, "strong" | "b") {
+                    "**"
+                } else {
+                    "*"
+                };
+                let inner = self.nested(element);
+                if inner.is_empty() {
+                    return;
+                }
+                self.out.push_str(mark);
+                self.out.push_str(&inner);
+                self.out.push_str(mark);
+            }
+            "p" | "div" | "section" | "article" | "header" | "footer" | "center" | "dl" | "dt"
+            | "dd" | "figure" | "figcaption" => {
+                self.block();
+                self.children(element);
+                self.block();
+            }
+            _ => self.children(element),
+        }
+    }
+
+    /// Render a table, one row per line.
+    ///
+    /// Markdown tables get a header separator after the first row; plain
+    /// text cells are separated by tabs.
+    fn table(&mut self, table: ElementRef<'_>) {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        collect_rows(table, &mut |row| {
+            let cells = row
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                .map(|cell| {
+                    let text = self.nested(cell);
+                    text.split_whitespace().collect::<Vec<_>>().join(" ")
+                })
+                .collect();
+            rows.push(cells);
+        });
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut lines = Vec::new();
+        for (i, mut row) in rows.into_iter().enumerate() {
+            if self.markdown {
+                row.resize(width, String::new());
+                let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+                lines.push(format!("| {} |", cells.join(" | ")));
+                if i == 0 {
+                    lines.push(format!("|{}", " --- |".repeat(width)));
+                }
+            } else {
+                lines.push(row.join("\t"));
+            }
+        }
+        self.out.push_str(&lines.join("\n"));
+    }
+
+    /// Append text, collapsing whitespace outside `<pre>`.
+    fn text(&mut self, text: &str) {
+        if self.preformatted {
+            self.out.push_str(text);
+            return;
+        }
+        let mut pending_space = text.starts_with(is_html_space);
+        for word in text.split(is_html_space).filter(|w| !w.is_empty()) {
+            if pending_space && !self.out.is_empty() && !self.out.ends_with([' ', '\n']) {
+                self.out.push(' ');
+            }
+            self.out.push_str(word);
+            pending_space = true;
+        }
+        if text.ends_with(is_html_space) && !self.out.is_empty() && !self.out.ends_with(['\n']) {
+            self.out.push(' ');
+        }
+    }
+
+    /// Start a new line unless already at the start of one.
+    fn line(&mut self) {
+        self.trim_trailing_spaces();
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// Start a new paragraph unless already at the start of one.
+    fn block(&mut self) {
+        self.line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    /// Remove spaces at the end of the output.
+    fn trim_trailing_spaces(&mut self) {
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+    }
+
+    /// The rendered output, without trailing spaces on lines and without
+    /// runs of blank lines.
+    fn finish(self) -> String {
+        let mut out = String::with_capacity(self.out.len());
+        let mut blank = false;
+        for line in self.out.lines().map(str::trim_end) {
+            if line.is_empty() {
+                blank = !out.is_empty();
+                continue;
+            }
+            if blank {
+                out.push('\n');
+                blank = false;
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(line);
+        }
+        out
+    }
+}
+
+/// Call `f` for every `<tr>` of a table, including those in `<thead>`,
+/// `<tbody>` and `<tfoot>`, but not those of nested tables.
+fn collect_rows<'a>(element: ElementRef<'a>, f: &mut impl FnMut(ElementRef<'a>)) {
+    for child in element.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "tr" => f(child),
+            "thead" | "tbody" | "tfoot" => collect_rows(child, f),
+            _ => {}
+        }
+    }
+}
+
+/// Whitespace collapsed by HTML rendering, plus non-breaking spaces.
+fn is_html_space(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\u{0c}' | '\u{a0}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_are_dropped_and_whitespace_collapsed() {
+        let html = "<style>p { color: red }</style><div>\n  豆粕   期货\n <script>alert(1)</script></div><p>第二段<br>换行</p>";
+        assert_eq!(html_to_text(html), "豆粕 期货\n\n第二段\n换行");
+    }
+
+    #[test]
+    fn tables_render_as_rows() {
+        let html = "<table><thead><tr><th>品种</th><th>保证金</th></tr></thead>\
+                    <tbody><tr><td>豆粕</td><td>7%</td></tr><tr><td>a|b</td></tr></tbody></table>";
+        assert_eq!(html_to_text(html), "品种\t保证金\n豆粕\t7%\na|b");
+        assert_eq!(
+            html_to_markdown(html),
+            "| 品种 | 保证金 |\n| --- | --- |\n| 豆粕 | 7% |\n| a\\|b |  |"
+        );
+    }
+
+    #[test]
+    fn markdown_keeps_lists_links_images_and_emphasis() {
+        let html = "<ol><li>一<ul><li>甲</li></ul></li><li><b>二</b></li></ol>\
+                    <p><a href=\"/n.pdf\">附件</a> <a href=\"javascript:void(0)\">打印</a>\
+                    <img src=\"/c.png\" alt=\"图\"></p><blockquote><p>引用</p></blockquote>\
+                    <pre>a  b\n  c</pre>";
+        assert_eq!(
+            html_to_markdown(html),
+            "1. 一\n  - 甲\n2. **二**\n\n[附件](/n.pdf) 打印![图](/c.png)\n\n> 引用\n\n```\na  b\n  c\n```"
+        );
+        assert_eq!(
+            html_to_text(html),
+            "1. 一\n  - 甲\n2. 二\n\n附件 (/n.pdf) 打印[image: /c.png]\n\n  引用\n\na  b\n  c"
+        );
+    }
+
+    #[test]
+    fn articles_convert_their_content() {
+        let article: Article = serde_json::from_value(serde_json::json!({
+            "content": "<h3>通知</h3><p>内容</p>",
+        }))
+        .unwrap();
+        assert_eq!(article.text(), "通知\n\n内容");
+        assert_eq!(article.markdown(), "### 通知\n\n内容");
+        assert_eq!(html_to_text(""), "");
+    }
+}
@@ -6,19 +6,24 @@ use std::sync::Arc;
 
 use reqwest::Client as HttpClient;
 use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
+use crate::auth::AuthContext;
 use crate::config::Config;
 use crate::error::{Error, ErrorCode, Result};
-use crate::models::ApiResponse;
+use crate::models::{with_strict_parsing, ApiResponse, Lang, TradeType};
+use crate::shutdown::Shutdown;
 use crate::token::TokenManager;
 
 /// Request options that can be set per-request.
 #[derive(Debug, Clone)]
 pub struct RequestOptions {
-    /// Trade type override (1 = futures, 2 = options).
-    pub trade_type: Option<i32>,
+    /// Trade type override (futures or options).
+    pub trade_type: Option<TradeType>,
     /// Language override.
-    pub lang: Option<String>,
+    pub lang: Option<Lang>,
+    /// Override for removing subtotal rows from quote responses.
+    pub drop_subtotals: Option<bool>,
 }
 
 impl Default for RequestOptions {
@@ -33,20 +38,63 @@ impl RequestOptions {
         RequestOptions {
             trade_type: None,
             lang: None,
+            drop_subtotals: None,
         }
     }
 
-    /// Set trade type.
-    pub fn with_trade_type(mut self, trade_type: i32) -> Self {
-        self.trade_type = Some(trade_type);
+    /// Set trade type. Also accepts `"1"`/`"2"` and `1`/`2` through the
+    /// deprecated lenient conversions.
+    pub fn with_trade_type(mut self, trade_type: impl Into<TradeType>) -> Self {
+        self.trade_type = Some(trade_type.into());
         self
     }
 
-    /// Set language.
-    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+    /// Set language. Also accepts `"zh"`/`"en"` through the deprecated lenient
+    /// conversion.
+    pub fn with_lang(mut self, lang: impl Into<Lang>) -> Self {
         self.lang = Some(lang.into());
         self
     }
+
+    /// Remove (or keep) subtotal and total rows in quote responses,
+    /// overriding [`Config::drop_subtotals`].
+    pub fn with_drop_subtotals(mut self, drop: bool) -> Self {
+        self.drop_subtotals = Some(drop);
+        self
+    }
+}
+
+/// Response bodies at least this large are parsed with simd-json.
+#[cfg(feature = "simd-json")]
+const SIMD_JSON_THRESHOLD: usize = 64 * 1024;
+
+/// Decode the common response envelope.
+///
+/// With the `simd-json` feature enabled, large bodies (such as all-variety
+/// warehouse receipts or full option quote lists) are parsed with simd-json.
+/// Any simd-json failure falls back to serde_json so behavior is unchanged.
+fn decode_response(resp_text: &str) -> Result<ApiResponse> {
+    #[cfg(feature = "simd-json")]
+    if resp_text.len() >= SIMD_JSON_THRESHOLD {
+        let mut bytes = resp_text.as_bytes().to_vec();
+        if let Ok(api_resp) = simd_json::serde::from_slice::<ApiResponse>(&mut bytes) {
+            return Ok(api_resp);
+        }
+    }
+
+    serde_json::from_str(resp_text)
+        .map_err(|e| Error::parse(resp_text, format!("failed to parse response: {}", e)))
+}
+
+/// Write downloaded bytes, reporting failures as export errors.
+async fn write_chunk<W>(writer: &mut W, bytes: &[u8]) -> Result<()>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    writer
+        .write_all(bytes)
+        .await
+        .map_err(|e| Error::export(format!("failed to write download: {}", e)))
 }
 
 /// Base HTTP client for API requests.
@@ -55,6 +103,7 @@ pub struct BaseClient {
     config: Arc<Config>,
     http_client: HttpClient,
     token_manager: Arc<TokenManager>,
+    shutdown: Shutdown,
 }
 
 impl BaseClient {
@@ -64,9 +113,22 @@ impl BaseClient {
             config: Arc::new(config),
             http_client,
             token_manager,
+            shutdown: Shutdown::new(),
         }
     }
 
+    /// Shutdown token observed by background tasks started from this
+    /// client's services.
+    pub fn shutdown_token(&self) -> &Shutdown {
+        &self.shutdown
+    }
+
+    /// Whether subtotal rows should be removed for a request.
+    pub(crate) fn drop_subtotals(&self, opts: Option<&RequestOptions>) -> bool {
+        opts.and_then(|o| o.drop_subtotals)
+            .unwrap_or(self.config.drop_subtotals)
+    }
+
     /// Execute an HTTP request.
     ///
     /// Handles token management, serialization, and response parsing.
@@ -120,22 +182,33 @@ impl BaseClient {
         let mut request = self.http_client.request(method.clone(), &url);
 
         // Set headers
+        let trade_type = opts.trade_type.unwrap_or(self.config.trade_type);
+        let lang = opts.lang.unwrap_or(self.config.lang);
         request = request
             .header("Content-Type", "application/json")
-            .header("Authorization", format!("Bearer {}", token))
-            .header("apikey", &self.config.api_key)
-            .header(
-                "tradeType",
-                opts.trade_type.unwrap_or(self.config.trade_type).to_string(),
-            );
-
-        if let Some(lang) = opts.lang.as_ref().or(Some(&self.config.lang)) {
-            request = request.header("lang", lang);
-        }
-
-        // Set body if present
-        if let Some(body) = body {
-            request = request.json(body);
+            .header("tradeType", trade_type.as_str())
+            .header("lang", lang.as_str());
+
+        // Serialize body if present
+        let body = match body {
+            Some(body) => serde_json::to_vec(body).map_err(|e| {
+                Error::validation("body", format!("failed to serialize request: {}", e))
+            })?,
+            None => Vec::new(),
+        };
+
+        // Apply authentication
+        let ctx = AuthContext {
+            api_key: &self.config.api_key,
+            token: Some(&token),
+            method,
+            url: &url,
+            body: &body,
+        };
+        request = self.config.auth_scheme.apply(request, &ctx)?;
+
+        if !body.is_empty() {
+            request = request.body(body);
         }
 
         // Send request
@@ -154,15 +227,16 @@ impl BaseClient {
         R: DeserializeOwned,
     {
         // Parse API response
-        let api_resp: ApiResponse = serde_json::from_str(resp_text).map_err(|e| {
-            Error::parse(resp_text, format!("failed to parse response: {}", e))
-        })?;
+        let api_resp = decode_response(resp_text)?;
 
         // Handle response based on code
         match ErrorCode::from_code(api_resp.code) {
             Some(ErrorCode::Success) => {
                 // Success - deserialize data
-                serde_json::from_value(api_resp.data).map_err(|e| {
+                with_strict_parsing(self.config.strict_parsing, || {
+                    serde_json::from_value(api_resp.data)
+                })
+                .map_err(|e| {
                     Error::parse(
                         resp_text,
                         format!("failed to deserialize response data: {}", e),
@@ -226,6 +300,81 @@ impl BaseClient {
             .await
     }
 
+    /// Download a file and stream its bytes to a writer, returning the
+    /// number of bytes written.
+    ///
+    /// Relative URLs are resolved against the API base URL. Requests to the
+    /// API host carry the same authentication as API calls; other hosts get
+    /// a plain GET, so credentials never leave the API host. A JSON error
+    /// envelope in place of the file is reported as an API error.
+    pub async fn download<W>(
+        &self,
+        url: &str,
+        writer: &mut W,
+        opts: Option<RequestOptions>,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let url = url.trim();
+        if url.is_empty() {
+            return Err(Error::validation("url", "download URL is empty"));
+        }
+        let base_url = self.config.base_url.trim_end_matches('/');
+        let url = if url.starts_with("http://") || url.starts_with("https://") {
+            url.to_string()
+        } else {
+            format!("{}/{}", base_url, url.trim_start_matches('/'))
+        };
+
+        let mut request = self.http_client.get(&url);
+        if url.starts_with(&format!("{}/", base_url)) {
+            let opts = opts.unwrap_or_default();
+            let token = self.token_manager.token().await?;
+            let trade_type = opts.trade_type.unwrap_or(self.config.trade_type);
+            let lang = opts.lang.unwrap_or(self.config.lang);
+            request = request
+                .header("tradeType", trade_type.as_str())
+                .header("lang", lang.as_str());
+            let ctx = AuthContext {
+                api_key: &self.config.api_key,
+                token: Some(&token),
+                method: &reqwest::Method::GET,
+                url: &url,
+                body: &[],
+            };
+            request = self.config.auth_scheme.apply(request, &ctx)?;
+        }
+
+        let mut response = request.send().await?.error_for_status()?;
+        let is_json = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/json"));
+        if is_json {
+            let body = response.bytes().await?;
+            if let Ok(api_resp) = serde_json::from_slice::<ApiResponse>(&body) {
+                if ErrorCode::from_code(api_resp.code) != Some(ErrorCode::Success) {
+                    return Err(Error::api(api_resp.code, api_resp.msg));
+                }
+            }
+            write_chunk(writer, &body).await?;
+            return Ok(body.len() as u64);
+        }
+
+        let mut written = 0u64;
+        while let Some(chunk) = response.chunk().await? {
+            write_chunk(writer, &chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| Error::export(format!("failed to write download: {}", e)))?;
+        Ok(written)
+    }
+
     /// Get reference to the config.
     pub fn config(&self) -> &Config {
         &self.config
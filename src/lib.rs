@@ -13,13 +13,17 @@
 //! - **Trade**: Trading parameters and contract information
 //! - **Settlement**: Settlement parameters
 //!
+//! The [`analytics`] module provides offline helpers that aggregate and
+//! cross-check data returned by these services, and the [`export`] module
+//! converts responses into external formats.
+//!
 //! # Quick Start
 //!
 //! ```no_run
-//! use dceapi::{Client, Config};
+//! use dceapi_rs::{Client, Config};
 //!
 //! #[tokio::main]
-//! async fn main() -> dceapi::Result<()> {
+//! async fn main() -> dceapi_rs::Result<()> {
 //!     // Create client with credentials
 //!     let config = Config::new()
 //!         .with_api_key("your-api-key")
@@ -46,9 +50,9 @@
 //! You can also create a client from environment variables:
 //!
 //! ```no_run
-//! use dceapi::Client;
+//! use dceapi_rs::Client;
 //!
-//! # async fn example() -> dceapi::Result<()> {
+//! # async fn example() -> dceapi_rs::Result<()> {
 //! // Set DCE_API_KEY and DCE_SECRET environment variables
 //! let client = Client::from_env()?;
 //! # Ok(())
@@ -61,48 +65,179 @@
 //! - `Error::Api` - API returned an error response
 //! - `Error::Auth` - Authentication failed
 //! - `Error::Network` - Network or HTTP error
-//! - `Error::Validation` - Invalid request parameters
+//! - `Error::Validation` - Invalid request parameter
+//! - `Error::ValidationErrors` - Several invalid request parameters at once
 //! - `Error::Parse` - Failed to parse response
+//! - `Error::Export` - Failed to export data to an external format
+//! - `Error::Io` - Failed to read or write a local file
+//! - `Error::MissingData` - Data needed for a calculation is missing
+//! - `Error::CatalogNotLoaded` - Contract catalog data not loaded by a manual refresh
+//! - `Error::Config` - Invalid client configuration
 //!
 //! # Request Options
 //!
 //! Most methods accept optional `RequestOptions` to override defaults:
 //!
 //! ```no_run
-//! use dceapi::{Client, Config, RequestOptions};
+//! use dceapi_rs::{Client, Config, Lang, RequestOptions, TradeType};
 //!
-//! # async fn example() -> dceapi::Result<()> {
+//! # async fn example() -> dceapi_rs::Result<()> {
 //! # let client = Client::new(Config::new().with_api_key("k").with_secret("s"))?;
 //! let opts = RequestOptions::new()
-//!     .with_trade_type(2)  // Options instead of futures
-//!     .with_lang("en");    // English language
+//!     .with_trade_type(TradeType::Options)  // Options instead of futures
+//!     .with_lang(Lang::En);                 // English language
 //!
 //! let varieties = client.common.get_variety_list(Some(opts)).await?;
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Request Builders
+//!
+//! Every request model has a `builder()` that checks required and
+//! conflicting fields before the request is sent:
+//!
+//! ```
+//! use dceapi_rs::QuotesRequest;
+//!
+//! # fn example() -> dceapi_rs::Result<()> {
+//! let req = QuotesRequest::builder()
+//!     .variety("a")
+//!     .trade_date("20250930")
+//!     .futures()
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Authentication Schemes
+//!
+//! Requests are authenticated with the `apikey` header and a bearer token by
+//! default ([`ApiKeyAuth`]). Deployments behind an internal gateway can plug in
+//! a different [`AuthScheme`], such as [`MtlsAuth`] for client certificates or
+//! `HmacHeaderAuth` (feature `hmac-auth`) for signed headers:
+//!
+//! ```no_run
+//! use dceapi_rs::{Client, Config, MtlsAuth};
+//!
+//! # fn example() -> dceapi_rs::Result<()> {
+//! let identity = std::fs::read("client.pem").expect("client identity");
+//! let config = Config::from_env().with_auth_scheme(MtlsAuth::new(identity));
+//! let client = Client::new(config)?;
+//! # Ok(())
+//! # }
+//! ```
 
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+pub mod analytics;
+mod auth;
+mod backfill;
+mod builders;
+mod calendar;
+mod candle;
+mod catalog;
 mod client;
+#[cfg(feature = "codegen")]
+pub mod codegen;
 mod config;
+mod continuous;
+mod contract_id;
+#[cfg(feature = "chrono")]
+mod dates;
+mod days;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod delivery_cost;
+mod delivery_month;
+mod division_series;
+mod download;
 mod error;
+mod events;
+pub mod export;
+mod fee;
+#[cfg(feature = "html")]
+mod html;
 mod http;
+mod margin;
 mod models;
+mod news_sync;
+mod notify;
+mod option_chain;
+mod position_limit;
+mod quote_list;
+mod scheduler;
 mod services;
+mod session;
+mod shutdown;
+mod snapshot;
+mod spread;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod supervisor;
 mod token;
+mod units;
+mod validation;
+mod variety;
+mod watch;
 
 // Re-export main types
+#[cfg(feature = "hmac-auth")]
+pub use auth::HmacHeaderAuth;
+pub use auth::{ApiKeyAuth, AuthContext, AuthScheme, MtlsAuth};
+pub use backfill::{Backfill, BackfillPartition, BackfillProgress};
+pub use calendar::Calendar;
+pub use candle::{Candle, CandlePeriod};
+pub use catalog::{CatalogPolicy, ContractCatalog, ContractStatus, DEFAULT_CATALOG_TTL_SECS};
 pub use client::Client;
-pub use config::{Config, DEFAULT_BASE_URL, DEFAULT_LANG, DEFAULT_TIMEOUT_SECS, DEFAULT_TRADE_TYPE};
-pub use error::{Error, ErrorCode, Result};
+pub use config::{
+    Config, DCE_SITE_URL, DEFAULT_BASE_URL, DEFAULT_LANG, DEFAULT_TIMEOUT_SECS, DEFAULT_TRADE_TYPE,
+};
+pub use continuous::{Adjustment, ContinuousOptions, ContinuousSeries, Roll, RollBy};
+pub use contract_id::{ArbitrageId, ContractId, OptionKind, OptionSpec};
+#[cfg(feature = "chrono")]
+pub use dates::{parse_date, parse_datetime, parse_month, to_exchange_time, EXCHANGE_TZ};
+#[cfg(feature = "decimal")]
+pub use decimal::{parse_decimal, DecimalQuote, DecimalSettleParam};
+pub use delivery_cost::{DeliveryCostEstimate, DeliveryCostEstimator};
+pub use delivery_month::DeliveryMonth;
+pub use division_series::{DivisionPoint, DivisionSeries, TimeOfDay};
+pub use download::{BatchResult, DownloadOptions};
+pub use error::{Error, ErrorCode, FieldError, Result, ValidationErrors};
+pub use events::Events;
+pub use fee::{Fee, FeeCalculator, FeeKind, FeeStyle};
+#[cfg(feature = "html")]
+pub use html::{html_to_markdown, html_to_text};
 pub use http::RequestOptions;
+pub use margin::{HedgeFlag, MarginCalculator, MarginPhase, MarginRequirement, Position, Side};
+pub use news_sync::{FileSyncStore, MemorySyncStore, NewsSync, SyncState, SyncStore};
+pub use notify::{Event, NotificationSink, SendFuture, StdoutSink, WebhookSink};
+pub use option_chain::{OptionChain, OptionSeries, StrikeRow};
+pub use position_limit::{
+    Holder, LimitCheck, LimitKind, PositionLimitChecker, PositionLimitReport,
+};
+pub use quote_list::QuoteListExt;
+pub use scheduler::{JobRun, Scheduler};
+pub use session::{LatestQuotes, PollSchedule, Session, SessionKind};
+pub use shutdown::Shutdown;
+pub use snapshot::VarietySnapshot;
+pub use spread::SpreadQuote;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+pub use supervisor::{Supervisor, TaskHealth, TaskState};
 pub use token::TokenManager;
+pub use units::{Amount, AmountUnit};
+pub use validation::Validate;
+pub use variety::VarietyCode;
+pub use watch::Watch;
 
 // Re-export all models
 pub use models::*;
 
+// Re-export request builders
+pub use builders::*;
+
 // Re-export services for direct access
 pub use services::{
     CommonService, DeliveryService, MarketService, MemberService, NewsService, SettleService,
@@ -110,4 +245,4 @@ pub use services::{
 };
 
 // Re-export news helper
-pub use services::news::is_valid_column_id;
+pub use services::news::{column_name, is_valid_column_id};
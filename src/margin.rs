@@ -0,0 +1,558 @@
+//! Margin required to hold a futures position.
+//!
+//! [`MarginCalculator`] applies the exchange's published margin rates to a
+//! position. A contract's rate from the day's trade parameters is used when
+//! it is known; otherwise the variety's trading parameters apply, including
+//! the raised rates from day N of the month before delivery, day N+1 and
+//! day N+2 onwards. [`MarginPhase`] tells which of these rates applies to a
+//! contract on a trade date.
+
+use std::fmt;
+
+use crate::calendar::Calendar;
+use crate::catalog::contract_size;
+use crate::contract_id::ContractId;
+use crate::days::{day_number, format_day};
+use crate::delivery_month::DeliveryMonth;
+use crate::error::{Error, Result};
+use crate::models::{parse_rate, rate_fraction, ContractInfo, TradeParam, TradingParam};
+
+/// Side of a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Side {
+    /// Long (buy) position.
+    Buy,
+    /// Short (sell) position.
+    Sell,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Side::Buy => "buy",
+            Side::Sell => "sell",
+        })
+    }
+}
+
+/// Purpose of a position, which decides its margin rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum HedgeFlag {
+    /// Speculation (the default).
+    #[default]
+    Speculation,
+    /// Hedging.
+    Hedging,
+}
+
+/// A futures position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    /// Contract ID (e.g. "m2509").
+    pub contract_id: String,
+    /// Long or short.
+    pub side: Side,
+    /// Number of lots.
+    pub lots: i64,
+    /// Price per unit the margin is charged on, usually the last settlement
+    /// price.
+    pub price: f64,
+    /// Speculation or hedging.
+    pub hedge: HedgeFlag,
+}
+
+impl Position {
+    /// Create a speculative position.
+    pub fn new(contract_id: impl Into<String>, side: Side, lots: i64, price: f64) -> Self {
+        Position {
+            contract_id: contract_id.into(),
+            side,
+            lots,
+            price,
+            hedge: HedgeFlag::Speculation,
+        }
+    }
+
+    /// Mark the position as a hedge.
+    pub fn hedging(mut self) -> Self {
+        self.hedge = HedgeFlag::Hedging;
+        self
+    }
+}
+
+/// Margin phase of a contract as its delivery month approaches.
+///
+/// The exchange raises margins in steps from the N-th trading day of the
+/// month before delivery, where N is set per variety.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MarginPhase {
+    /// Before day N of the month before delivery.
+    Normal,
+    /// Day N of the month before delivery.
+    N,
+    /// Day N+1 of the month before delivery.
+    N1,
+    /// Day N+2 of the month before delivery and later.
+    N2,
+    /// The delivery month.
+    DeliveryMonth,
+}
+
+impl MarginPhase {
+    /// Phase of a contract on a trade date.
+    ///
+    /// Trading days are counted with `calendar`; a date that is not a
+    /// trading day takes the phase of the next trading day.
+    ///
+    /// # Arguments
+    /// * `contract_id` - Contract ID (e.g. "m2509")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    /// * `n` - The variety's day N (1 for the first trading day of the
+    ///   month before delivery)
+    /// * `calendar` - Trading calendar
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dceapi_rs::{Calendar, MarginPhase};
+    ///
+    /// let calendar = Calendar::new();
+    /// // 2025-08-15 is the 11th weekday of August.
+    /// let phase = MarginPhase::for_date("m2509", "20250815", 10, &calendar).unwrap();
+    /// assert_eq!(phase, MarginPhase::N1);
+    /// ```
+    pub fn for_date(
+        contract_id: &str,
+        trade_date: &str,
+        n: usize,
+        calendar: &Calendar,
+    ) -> Result<MarginPhase> {
+        if n == 0 {
+            return Err(Error::validation("n", "day N starts at 1"));
+        }
+        let contract = ContractId::parse(contract_id)?;
+        let date = day_number(trade_date).ok_or_else(|| {
+            Error::validation("trade_date", format!("invalid date {:?}", trade_date))
+        })?;
+        let delivery = DeliveryMonth::new(contract.year, contract.month)?;
+        let month_start = |month: DeliveryMonth| day_number(&format!("{}01", month));
+        let (Some(delivery_start), Some(prev_start)) =
+            (month_start(delivery), month_start(delivery.prev()?))
+        else {
+            return Err(Error::validation(
+                "contract_id",
+                "delivery month out of range",
+            ));
+        };
+
+        if date >= delivery_start {
+            return Ok(MarginPhase::DeliveryMonth);
+        }
+        if date < prev_start {
+            return Ok(MarginPhase::Normal);
+        }
+        let days = calendar
+            .trading_days_between(&format_day(prev_start), &format_day(delivery_start - 1))?;
+        let date = format_day(date);
+        let day = days.iter().take_while(|d| **d < date).count() + 1;
+        Ok(if day < n {
+            MarginPhase::Normal
+        } else if day == n {
+            MarginPhase::N
+        } else if day == n + 1 {
+            MarginPhase::N1
+        } else {
+            MarginPhase::N2
+        })
+    }
+}
+
+/// Margin required for a position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarginRequirement {
+    /// Contract ID.
+    pub contract_id: String,
+    /// Long or short.
+    pub side: Side,
+    /// Speculation or hedging.
+    pub hedge: HedgeFlag,
+    /// Number of lots.
+    pub lots: i64,
+    /// Quantity in contract units (tons for most varieties).
+    pub quantity: f64,
+    /// Price the margin is charged on.
+    pub price: f64,
+    /// Margin rate applied, as a fraction.
+    pub rate: f64,
+    /// Required margin in yuan.
+    pub margin: f64,
+    /// Phase whose variety rate applied, or `None` if the contract's rate
+    /// came from the trade parameters.
+    pub phase: Option<MarginPhase>,
+}
+
+/// Computes margins from published margin rates.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::{MarginCalculator, MarginPhase, Position, Side, TradingParam};
+///
+/// let param: TradingParam = serde_json::from_value(serde_json::json!({
+///     "varietyId": "m",
+///     "tradingMarginRateSpeculation": "7%",
+///     "tradingMarginRateHedging": "6%",
+///     "tradingMarginRateN1": "10%",
+/// }))
+/// .unwrap();
+/// let calculator = MarginCalculator::new().with_trading_params(vec![param]);
+///
+/// let position = Position::new("m2509", Side::Buy, 10, 3000.0);
+/// let normal = calculator.margin(&position, MarginPhase::Normal).unwrap();
+/// assert!((normal.margin - 21_000.0).abs() < 1e-6);
+/// let hedge = calculator.margin(&position.clone().hedging(), MarginPhase::Normal).unwrap();
+/// assert!((hedge.margin - 18_000.0).abs() < 1e-6);
+/// let n1 = calculator.margin(&position, MarginPhase::N1).unwrap();
+/// assert_eq!(n1.rate, 0.1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MarginCalculator {
+    trade_params: Vec<TradeParam>,
+    trading_params: Vec<TradingParam>,
+    contracts: Vec<ContractInfo>,
+}
+
+impl MarginCalculator {
+    /// Create a calculator without rates.
+    pub fn new() -> Self {
+        MarginCalculator::default()
+    }
+
+    /// Set the per-contract trade parameters (from
+    /// [`TradeService::get_day_trade_param`](crate::TradeService::get_day_trade_param)).
+    pub fn with_trade_params(mut self, params: Vec<TradeParam>) -> Self {
+        self.trade_params = params;
+        self
+    }
+
+    /// Set the per-variety trading parameters (from
+    /// [`TradeService::get_trading_param`](crate::TradeService::get_trading_param)).
+    pub fn with_trading_params(mut self, params: Vec<TradingParam>) -> Self {
+        self.trading_params = params;
+        self
+    }
+
+    /// Set the contract information (from the
+    /// [contract catalog](crate::Client::catalog)) whose trading units size
+    /// the positions. Contracts not listed use their variety's standard
+    /// contract size.
+    pub fn with_contracts(mut self, contracts: Vec<ContractInfo>) -> Self {
+        self.contracts = contracts;
+        self
+    }
+
+    /// Margin rate of a contract, as a fraction.
+    ///
+    /// The contract's rate from the trade parameters applies when it is
+    /// set; `phase` is then ignored. Otherwise the variety's trading
+    /// parameter rate for `phase` applies. A phase the variety publishes no
+    /// rate for keeps the rate of the phase before it. The trading
+    /// parameters carry no delivery-month rate, so that phase needs the
+    /// contract's trade parameters.
+    ///
+    /// The exchange charges the same rate on both sides, so the side of
+    /// the position does not change the rate.
+    pub fn rate(&self, contract_id: &str, hedge: HedgeFlag, phase: MarginPhase) -> Result<f64> {
+        self.lookup(contract_id, hedge, phase).map(|(rate, _)| rate)
+    }
+
+    /// Margin rate and the phase whose variety rate applied.
+    fn lookup(
+        &self,
+        contract_id: &str,
+        hedge: HedgeFlag,
+        phase: MarginPhase,
+    ) -> Result<(f64, Option<MarginPhase>)> {
+        let contract_id = contract_id.trim();
+        let contract_rate = self
+            .trade_params
+            .iter()
+            .find(|p| p.contract_id.trim().eq_ignore_ascii_case(contract_id))
+            .and_then(|p| match hedge {
+                HedgeFlag::Speculation => p.spec_buy_rate,
+                HedgeFlag::Hedging => p.hedge_buy_rate,
+            });
+        if let Some(rate) = contract_rate {
+            return Ok((rate_fraction(rate), None));
+        }
+
+        let contract = ContractId::parse(contract_id)?;
+        if phase == MarginPhase::DeliveryMonth {
+            return Err(Error::missing_data(format!(
+                "no delivery-month margin rate for {}",
+                contract_id
+            )));
+        }
+        let param = self
+            .trading_params
+            .iter()
+            .find(|p| p.variety_id.trim().eq_ignore_ascii_case(&contract.variety))
+            .ok_or_else(|| Error::missing_data(format!("no margin rates for {}", contract_id)))?;
+        let normal = match hedge {
+            HedgeFlag::Speculation => &param.trading_margin_rate_speculation,
+            HedgeFlag::Hedging => &param.trading_margin_rate_hedging,
+        };
+        let day_n = match hedge {
+            HedgeFlag::Speculation => &param.trading_margin_rate_speculation_n,
+            HedgeFlag::Hedging => &param.trading_margin_rate_hedging_n,
+        };
+        let steps = [
+            normal,
+            day_n,
+            &param.trading_margin_rate_n1,
+            &param.trading_margin_rate_n2,
+        ];
+        let last = match phase {
+            MarginPhase::Normal => 0,
+            MarginPhase::N => 1,
+            MarginPhase::N1 => 2,
+            _ => 3,
+        };
+        steps[..=last]
+            .iter()
+            .rev()
+            .find_map(|raw| parse_rate(raw))
+            .map(|rate| (rate, Some(phase)))
+            .ok_or_else(|| Error::missing_data(format!("no margin rate for {}", contract_id)))
+    }
+
+    /// Margin required for a position.
+    ///
+    /// # Arguments
+    /// * `position` - The position
+    /// * `phase` - Margin phase of the contract (see [`MarginPhase::for_date`])
+    pub fn margin(&self, position: &Position, phase: MarginPhase) -> Result<MarginRequirement> {
+        if position.lots <= 0 {
+            return Err(Error::validation("lots", "lots must be positive"));
+        }
+        if !(position.price.is_finite() && position.price > 0.0) {
+            return Err(Error::validation("price", "price must be positive"));
+        }
+        let contract = ContractId::parse(&position.contract_id)?;
+        let contract_size = contract_size(&self.contracts, &contract)?;
+        let (rate, phase) = self.lookup(&position.contract_id, position.hedge, phase)?;
+        let quantity = position.lots as f64 * contract_size;
+        Ok(MarginRequirement {
+            contract_id: position.contract_id.trim().to_string(),
+            side: position.side,
+            hedge: position.hedge,
+            lots: position.lots,
+            quantity,
+            price: position.price,
+            rate,
+            margin: quantity * position.price * rate,
+            phase,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contract_rates_of_one_are_percent() {
+        let param = |rate: f64| -> TradeParam {
+            serde_json::from_value(
+                serde_json::json!({ "contractId": "m2509", "specBuyRate": rate }),
+            )
+            .unwrap()
+        };
+        let rate = |rate: f64| {
+            MarginCalculator::new()
+                .with_trade_params(vec![param(rate)])
+                .rate("m2509", HedgeFlag::Speculation, MarginPhase::Normal)
+                .unwrap()
+        };
+        assert_eq!(rate(1.0), 0.01);
+        assert_eq!(rate(8.0), 0.08);
+        assert_eq!(rate(0.08), 0.08);
+    }
+
+    fn phase(trade_date: &str, n: usize) -> MarginPhase {
+        MarginPhase::for_date("m2509", trade_date, n, &Calendar::new()).unwrap()
+    }
+
+    #[test]
+    fn phases_count_trading_days_of_the_month_before_delivery() {
+        // August 2025 starts on a Friday; the 14th is its 10th weekday.
+        assert_eq!(phase("20250731", 10), MarginPhase::Normal);
+        assert_eq!(phase("20250813", 10), MarginPhase::Normal);
+        assert_eq!(phase("20250814", 10), MarginPhase::N);
+        assert_eq!(phase("20250815", 10), MarginPhase::N1);
+        assert_eq!(phase("20250818", 10), MarginPhase::N2);
+        assert_eq!(phase("20250829", 10), MarginPhase::N2);
+        assert_eq!(phase("20250901", 10), MarginPhase::DeliveryMonth);
+        assert_eq!(phase("20250801", 1), MarginPhase::N);
+    }
+
+    #[test]
+    fn non_trading_days_take_the_next_trading_days_phase() {
+        // Saturday the 16th counts as Monday the 18th, day 12.
+        assert_eq!(phase("20250816", 10), MarginPhase::N2);
+        // Saturday the 2nd counts as Monday the 4th, day 2.
+        assert_eq!(phase("20250802", 2), MarginPhase::N);
+
+        let mut calendar = Calendar::new();
+        calendar.add_holiday("20250804").unwrap();
+        let phase = MarginPhase::for_date("m2509", "20250802", 2, &calendar).unwrap();
+        assert_eq!(phase, MarginPhase::N);
+        let phase = MarginPhase::for_date("m2509", "20250805", 2, &calendar).unwrap();
+        assert_eq!(phase, MarginPhase::N);
+    }
+
+    #[test]
+    fn january_contracts_count_december_of_the_year_before() {
+        let calendar = Calendar::new();
+        let phase = |date| MarginPhase::for_date("m2601", date, 1, &calendar).unwrap();
+        assert_eq!(phase("20251128"), MarginPhase::Normal);
+        assert_eq!(phase("20251201"), MarginPhase::N);
+        assert_eq!(phase("20251202"), MarginPhase::N1);
+        assert_eq!(phase("20260105"), MarginPhase::DeliveryMonth);
+    }
+
+    #[test]
+    fn invalid_phase_arguments_are_rejected() {
+        let calendar = Calendar::new();
+        for (contract_id, trade_date, n) in [
+            ("m2509", "20250815", 0),
+            ("m2509", "20250230", 10),
+            ("m2509", "2025815", 10),
+            ("2509", "20250815", 10),
+        ] {
+            let err = MarginPhase::for_date(contract_id, trade_date, n, &calendar).unwrap_err();
+            assert!(matches!(err, Error::Validation { .. }), "{err}");
+        }
+    }
+
+    fn variety_calculator(rates: serde_json::Value) -> MarginCalculator {
+        let mut param = serde_json::json!({ "varietyId": "m" });
+        param
+            .as_object_mut()
+            .unwrap()
+            .extend(rates.as_object().unwrap().clone());
+        MarginCalculator::new().with_trading_params(vec![serde_json::from_value(param).unwrap()])
+    }
+
+    #[test]
+    fn unpublished_phase_rates_keep_the_rate_before() {
+        let calculator = variety_calculator(serde_json::json!({
+            "tradingMarginRateSpeculation": "7%",
+            "tradingMarginRateHedging": "6%",
+            "tradingMarginRateHedgingN": "8%",
+            "tradingMarginRateN2": "-",
+        }));
+        let rate = |hedge, phase| calculator.rate("m2509", hedge, phase).unwrap();
+        assert_eq!(rate(HedgeFlag::Speculation, MarginPhase::Normal), 0.07);
+        assert_eq!(rate(HedgeFlag::Speculation, MarginPhase::N), 0.07);
+        assert_eq!(rate(HedgeFlag::Speculation, MarginPhase::N2), 0.07);
+        assert_eq!(rate(HedgeFlag::Hedging, MarginPhase::N), 0.08);
+        assert_eq!(rate(HedgeFlag::Hedging, MarginPhase::N1), 0.08);
+    }
+
+    #[test]
+    fn later_phases_use_the_shared_step_rates() {
+        let calculator = variety_calculator(serde_json::json!({
+            "tradingMarginRateSpeculation": "7",
+            "tradingMarginRateSpeculationN": "0.09",
+            "tradingMarginRateN1": "10%",
+            "tradingMarginRateN2": "15",
+        }));
+        let rate = |phase| {
+            calculator
+                .rate("M2509", HedgeFlag::Speculation, phase)
+                .unwrap()
+        };
+        assert_eq!(rate(MarginPhase::Normal), 0.07);
+        assert_eq!(rate(MarginPhase::N), 0.09);
+        assert_eq!(rate(MarginPhase::N1), 0.1);
+        assert_eq!(rate(MarginPhase::N2), 0.15);
+    }
+
+    #[test]
+    fn contract_rates_override_variety_rates_in_every_phase() {
+        let trade_param: TradeParam = serde_json::from_value(serde_json::json!({
+            "contractId": "m2509", "specBuyRate": 0.2,
+        }))
+        .unwrap();
+        let calculator = variety_calculator(serde_json::json!({
+            "tradingMarginRateSpeculation": "7%",
+            "tradingMarginRateHedging": "6%",
+        }))
+        .with_trade_params(vec![trade_param]);
+        let position = Position::new(" m2509 ", Side::Sell, 2, 3000.0);
+        let margin = calculator
+            .margin(&position, MarginPhase::DeliveryMonth)
+            .unwrap();
+        assert_eq!((margin.rate, margin.phase), (0.2, None));
+        assert_eq!(margin.contract_id, "m2509");
+        assert!((margin.margin - 12_000.0).abs() < 1e-6);
+
+        // The contract publishes no hedging rate, so the variety's applies.
+        let hedge = calculator
+            .margin(&position.hedging(), MarginPhase::N1)
+            .unwrap();
+        assert_eq!((hedge.rate, hedge.phase), (0.06, Some(MarginPhase::N1)));
+    }
+
+    #[test]
+    fn missing_rates_are_missing_data() {
+        let calculator = variety_calculator(serde_json::json!({
+            "tradingMarginRateSpeculation": "7%",
+        }));
+        for (contract_id, hedge, phase) in [
+            ("m2509", HedgeFlag::Speculation, MarginPhase::DeliveryMonth),
+            ("m2509", HedgeFlag::Hedging, MarginPhase::Normal),
+            ("y2509", HedgeFlag::Speculation, MarginPhase::Normal),
+        ] {
+            let err = calculator.rate(contract_id, hedge, phase).unwrap_err();
+            assert!(matches!(err, Error::MissingData { .. }), "{err}");
+        }
+    }
+
+    #[test]
+    fn listed_trading_units_size_positions() {
+        let info: ContractInfo =
+            serde_json::from_value(serde_json::json!({ "contractId": "m2509", "unit": 20 }))
+                .unwrap();
+        let calculator = variety_calculator(serde_json::json!({
+            "tradingMarginRateSpeculation": "5%",
+        }))
+        .with_contracts(vec![info]);
+        let margin = |contract_id| {
+            let position = Position::new(contract_id, Side::Buy, 2, 3000.0);
+            calculator.margin(&position, MarginPhase::Normal).unwrap()
+        };
+        assert_eq!(margin("m2509").quantity, 40.0);
+        assert!((margin("m2509").margin - 6_000.0).abs() < 1e-6);
+        assert_eq!(margin("m2601").quantity, 20.0);
+    }
+
+    #[test]
+    fn margin_rejects_empty_positions_and_bad_prices() {
+        let calculator = variety_calculator(serde_json::json!({
+            "tradingMarginRateSpeculation": "7%",
+        }));
+        for position in [
+            Position::new("m2509", Side::Buy, 0, 3000.0),
+            Position::new("m2509", Side::Buy, -1, 3000.0),
+            Position::new("m2509", Side::Buy, 1, 0.0),
+            Position::new("m2509", Side::Buy, 1, f64::NAN),
+        ] {
+            let err = calculator
+                .margin(&position, MarginPhase::Normal)
+                .unwrap_err();
+            assert!(matches!(err, Error::Validation { .. }), "{err}");
+        }
+    }
+}
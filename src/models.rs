@@ -2,34 +2,225 @@
 //!
 //! This module contains all request and response structures used by the API.
 
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use serde::de::Error as _;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use crate::delivery_month::DeliveryMonth;
+use crate::error::Error;
+use crate::units::{Amount, AmountUnit};
+
+thread_local! {
+    static STRICT_PARSING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with strict parsing enabled or disabled on the current thread.
+///
+/// In strict mode the deserialization helpers reject nulls and [`Extras`]
+/// rejects unknown fields instead of collecting them.
+///
+/// The previous setting is restored even if `f` panics.
+pub(crate) fn with_strict_parsing<T>(strict: bool, f: impl FnOnce() -> T) -> T {
+    let _restore = StrictParsingGuard(STRICT_PARSING.with(|s| s.replace(strict)));
+    f()
+}
+
+/// Restores the previous strict parsing setting when dropped.
+struct StrictParsingGuard(bool);
+
+impl Drop for StrictParsingGuard {
+    fn drop(&mut self) {
+        STRICT_PARSING.with(|s| s.set(self.0));
+    }
+}
+
+/// Check whether strict parsing is enabled on the current thread.
+fn strict_parsing() -> bool {
+    STRICT_PARSING.with(Cell::get)
+}
+
 /// Helper function to deserialize a nullable string as an empty string.
-fn deserialize_nullable_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+pub(crate) fn deserialize_nullable_string<'de, D>(deserializer: D) -> Result<String, D::Error>
 where
     D: Deserializer<'de>,
 {
     let opt: Option<String> = Option::deserialize(deserializer)?;
+    if opt.is_none() && strict_parsing() {
+        return Err(D::Error::custom("unexpected null"));
+    }
     Ok(opt.unwrap_or_default())
 }
 
-/// Deserialize a string or number to i64
-fn deserialize_string_or_i64<'de, D>(deserializer: D) -> Result<i64, D::Error>
+/// Parse a numeric string, treating the API's missing-value sentinels as `None`.
+///
+/// Empty strings, "-" and "--" (and anything else that is not a finite
+/// number) yield `None`; thousands separators are ignored.
+pub fn parse_optional_f64(raw: &str) -> Option<f64> {
+    numeric_text(raw)?
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+}
+
+/// A numeric string without surrounding whitespace and thousands
+/// separators, or `None` for the API's missing-value sentinels (empty
+/// strings, "-" and "--").
+pub(crate) fn numeric_text(raw: &str) -> Option<String> {
+    let cleaned: String = raw.trim().chars().filter(|&c| c != ',').collect();
+    if cleaned.is_empty() || cleaned.chars().all(|c| c == '-') {
+        return None;
+    }
+    Some(cleaned)
+}
+
+/// Parse a rate such as "0.1", "10%" or "10" (taken as percent) into a
+/// fraction.
+pub(crate) fn parse_rate(raw: &str) -> Option<f64> {
+    let raw = raw.trim();
+    let value = parse_optional_f64(raw.trim_end_matches('%'))?;
+    if raw.ends_with('%') {
+        Some(value / 100.0)
+    } else {
+        Some(rate_fraction(value))
+    }
+}
+
+/// A published rate as a fraction: values of 1 and above are taken as
+/// percent, as no margin or price limit rate reaches 100%.
+pub(crate) fn rate_fraction(value: f64) -> f64 {
+    if value >= 1.0 {
+        value / 100.0
+    } else {
+        value
+    }
+}
+
+/// Raw numeric value as sent by the API: a JSON number or a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawNumber {
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+/// Helper function to deserialize a number or numeric string as `Option<f64>`.
+///
+/// Null and sentinel strings ("", "-", "--") become `None`.
+pub(crate) fn deserialize_optional_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<RawNumber>::deserialize(deserializer)? {
+        Some(RawNumber::Int(i)) => Some(i as f64),
+        Some(RawNumber::Float(f)) => Some(f),
+        Some(RawNumber::Str(s)) => parse_optional_f64(&s),
+        None => None,
+    })
+}
+
+/// Helper function to deserialize an integer that may arrive as a sentinel string.
+///
+/// Null and sentinel strings become 0, matching the field's default.
+/// Numbers with a fractional part are rejected rather than truncated.
+pub(crate) fn deserialize_lenient_i64<'de, D>(deserializer: D) -> Result<i64, D::Error>
 where
     D: Deserializer<'de>,
 {
-    use serde::de::Error;
-    use serde_json::Value;
+    let value = match Option::<RawNumber>::deserialize(deserializer)? {
+        Some(RawNumber::Int(i)) => return Ok(i),
+        Some(RawNumber::Float(f)) => f,
+        Some(RawNumber::Str(s)) => match parse_optional_f64(&s) {
+            Some(v) => v,
+            None => return Ok(0),
+        },
+        None if strict_parsing() => return Err(D::Error::custom("unexpected null")),
+        None => return Ok(0),
+    };
+    if value.fract() != 0.0 || value.abs() >= i64::MAX as f64 {
+        return Err(D::Error::custom(format!(
+            "expected an integer, got {}",
+            value
+        )));
+    }
+    Ok(value as i64)
+}
+
+/// Response fields not covered by a model.
+///
+/// The API regularly adds fields; response models collect any they do not
+/// know into an `extra` map so the data stays accessible until the SDK
+/// models it. Every response data model has one; only the [`ApiResponse`]
+/// envelope and [`TokenResponse`] ignore unknown fields.
+///
+/// ```
+/// let quote: dceapi_rs::Quote =
+///     serde_json::from_str(r#"{"contractId": "m2509", "volumn": "-", "newField": "x"}"#)?;
+/// assert_eq!(quote.contract_id, "m2509");
+/// assert_eq!(quote.extra.get_str("newField"), Some("x"));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Extras(pub BTreeMap<String, Value>);
+
+impl<'de> Deserialize<'de> for Extras {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = BTreeMap::<String, Value>::deserialize(deserializer)?;
+        if !fields.is_empty() && strict_parsing() {
+            let names: Vec<&str> = fields.keys().map(String::as_str).collect();
+            return Err(D::Error::custom(format!(
+                "unknown fields: {}",
+                names.join(", ")
+            )));
+        }
+        Ok(Extras(fields))
+    }
+}
+
+impl Extras {
+    /// Get a field by its JSON name.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Get a field as a string, if it is one.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(Value::as_str)
+    }
+
+    /// Check whether no unknown fields were received.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the number of unknown fields.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Iterate over the unknown fields in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.0.iter()
+    }
+}
+
+// JSON values never hold NaN, so equality is reflexive.
+impl Eq for Extras {}
 
-    let value = Value::deserialize(deserializer)?;
-    match value {
-        Value::Number(n) => n.as_i64().ok_or_else(|| D::Error::custom("invalid number")),
-        Value::String(s) => s
-            .parse::<i64>()
-            .map_err(|_| D::Error::custom("invalid string number")),
-        Value::Null => Ok(0),
-        _ => Err(D::Error::custom("expected string or number")),
+impl Hash for Extras {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (key, value) in &self.0 {
+            key.hash(state);
+            value.to_string().hash(state);
+        }
     }
 }
 
@@ -38,7 +229,7 @@ where
 // ============================================================================
 
 /// API common response wrapper.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApiResponse {
     /// Response code.
     pub code: i32,
@@ -51,7 +242,7 @@ pub struct ApiResponse {
 }
 
 /// Token response from authentication endpoint.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenResponse {
     /// Token type (e.g., "Bearer").
@@ -63,12 +254,195 @@ pub struct TokenResponse {
     pub expires_in: i32,
 }
 
+// ============================================================================
+// Request Parameter Enums (请求参数枚举)
+// ============================================================================
+
+/// Trade type (交易类型). Serialized as "1" (futures) or "2" (options).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TradeType {
+    /// Futures (期货), wire value "1".
+    #[default]
+    Futures,
+    /// Options (期权), wire value "2".
+    Options,
+}
+
+impl TradeType {
+    /// Get the wire value ("1" or "2").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeType::Futures => "1",
+            TradeType::Options => "2",
+        }
+    }
+
+    /// Get the numeric code (1 or 2).
+    pub fn code(&self) -> i32 {
+        match self {
+            TradeType::Futures => 1,
+            TradeType::Options => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for TradeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses "1" or "futures" and "2" or "options" (case-insensitive).
+impl std::str::FromStr for TradeType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.trim() {
+            "1" => Ok(TradeType::Futures),
+            "2" => Ok(TradeType::Options),
+            other if other.eq_ignore_ascii_case("futures") => Ok(TradeType::Futures),
+            other if other.eq_ignore_ascii_case("options") => Ok(TradeType::Options),
+            _ => Err(Error::validation(
+                "trade_type",
+                format!("invalid trade type {:?}", s),
+            )),
+        }
+    }
+}
+
+/// Deprecated lenient conversion: "2" or "options" is [`TradeType::Options`]
+/// and anything else silently becomes futures. Kept so `with_trade_type("2")`
+/// still compiles; parse with [`str::parse`] to reject unknown values.
+impl From<&str> for TradeType {
+    fn from(value: &str) -> Self {
+        value.parse().unwrap_or_default()
+    }
+}
+
+/// Deprecated lenient conversion, see `From<&str>`.
+impl From<String> for TradeType {
+    fn from(value: String) -> Self {
+        TradeType::from(value.as_str())
+    }
+}
+
+/// Deprecated lenient conversion: 2 is [`TradeType::Options`] and anything
+/// else silently becomes futures. Use `TryFrom<i64>` to reject unknown codes.
+impl From<i32> for TradeType {
+    fn from(value: i32) -> Self {
+        TradeType::try_from(i64::from(value)).unwrap_or_default()
+    }
+}
+
+/// Converts the numeric codes 1 and 2.
+impl TryFrom<i64> for TradeType {
+    type Error = Error;
+
+    fn try_from(value: i64) -> Result<Self, Error> {
+        match value {
+            1 => Ok(TradeType::Futures),
+            2 => Ok(TradeType::Options),
+            _ => Err(Error::validation(
+                "trade_type",
+                format!("invalid trade type {}", value),
+            )),
+        }
+    }
+}
+
+impl Serialize for TradeType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TradeType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::String(s) => s.parse().map_err(D::Error::custom),
+            Value::Number(n) => n
+                .as_i64()
+                .ok_or_else(|| D::Error::custom(format!("invalid trade type {}", n)))
+                .and_then(|code| TradeType::try_from(code).map_err(D::Error::custom)),
+            other => Err(serde::de::Error::custom(format!(
+                "invalid trade type: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Response language (语言). Serialized as "zh" or "en".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    /// Chinese (中文), wire value "zh".
+    #[default]
+    Zh,
+    /// English (英文), wire value "en".
+    En,
+}
+
+impl Lang {
+    /// Get the wire value ("zh" or "en").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Lang::Zh => "zh",
+            Lang::En => "en",
+        }
+    }
+}
+
+impl std::fmt::Display for Lang {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses "zh" or "en" (case-insensitive).
+impl std::str::FromStr for Lang {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.trim() {
+            other if other.eq_ignore_ascii_case("zh") => Ok(Lang::Zh),
+            other if other.eq_ignore_ascii_case("en") => Ok(Lang::En),
+            _ => Err(Error::validation(
+                "lang",
+                format!("invalid language {:?}", s),
+            )),
+        }
+    }
+}
+
+/// Deprecated lenient conversion: "en" is [`Lang::En`] and anything else
+/// silently becomes Chinese. Kept so `with_lang("en")` still compiles; parse
+/// with [`str::parse`] to reject unknown values.
+impl From<&str> for Lang {
+    fn from(value: &str) -> Self {
+        value.parse().unwrap_or_default()
+    }
+}
+
+/// Deprecated lenient conversion, see `From<&str>`.
+impl From<String> for Lang {
+    fn from(value: String) -> Self {
+        Lang::from(value.as_str())
+    }
+}
+
 // ============================================================================
 // News/Article Models (资讯数据模型)
 // ============================================================================
 
 /// Article information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Article {
     /// Article ID.
@@ -123,14 +497,165 @@ pub struct Article {
     /// Creation date.
     #[serde(default, deserialize_with = "deserialize_nullable_string")]
     pub create_date: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
+}
+
+/// Article column (资讯栏目).
+///
+/// Serialized as the numeric column ID string. Use [`ColumnId::Custom`] for
+/// columns not listed here.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ColumnId {
+    /// 244 - 业务公告与通知 (business announcements and notices).
+    BusinessNotices,
+    /// 245 - 活动公告与通知 (event announcements and notices).
+    ActivityNotices,
+    /// 246 - 交易所新闻-文媒 (exchange news).
+    ExchangeNews,
+    /// 248 - 媒体看大商所-文媒 (media coverage of the exchange).
+    MediaCoverage,
+    /// 1076 - 今日提示 (daily tips).
+    DailyTips,
+    /// 242 - 新闻发布 (press releases).
+    PressReleases,
+    /// Any other column ID.
+    Custom(String),
+}
+
+impl ColumnId {
+    /// All documented columns.
+    pub const ALL: [ColumnId; 6] = [
+        ColumnId::BusinessNotices,
+        ColumnId::ActivityNotices,
+        ColumnId::ExchangeNews,
+        ColumnId::MediaCoverage,
+        ColumnId::DailyTips,
+        ColumnId::PressReleases,
+    ];
+
+    /// Get the column ID sent to the API (e.g. "244").
+    pub fn id(&self) -> &str {
+        match self {
+            ColumnId::BusinessNotices => "244",
+            ColumnId::ActivityNotices => "245",
+            ColumnId::ExchangeNews => "246",
+            ColumnId::MediaCoverage => "248",
+            ColumnId::DailyTips => "1076",
+            ColumnId::PressReleases => "242",
+            ColumnId::Custom(id) => id,
+        }
+    }
+
+    /// Get the display name in the given language.
+    ///
+    /// Custom columns return their ID.
+    pub fn name(&self, lang: Lang) -> &str {
+        match self {
+            ColumnId::Custom(id) => id,
+            column => column.label(lang).unwrap_or_default(),
+        }
+    }
+
+    /// Get the display name of a documented column in the given language,
+    /// or `None` for custom columns.
+    pub fn label(&self, lang: Lang) -> Option<&'static str> {
+        let label = match (self, lang) {
+            (ColumnId::BusinessNotices, Lang::Zh) => "业务公告与通知",
+            (ColumnId::BusinessNotices, Lang::En) => "Business Announcements and Notices",
+            (ColumnId::ActivityNotices, Lang::Zh) => "活动公告与通知",
+            (ColumnId::ActivityNotices, Lang::En) => "Event Announcements and Notices",
+            (ColumnId::ExchangeNews, Lang::Zh) => "交易所新闻",
+            (ColumnId::ExchangeNews, Lang::En) => "Exchange News",
+            (ColumnId::MediaCoverage, Lang::Zh) => "媒体看大商所",
+            (ColumnId::MediaCoverage, Lang::En) => "DCE in the Media",
+            (ColumnId::DailyTips, Lang::Zh) => "今日提示",
+            (ColumnId::DailyTips, Lang::En) => "Daily Tips",
+            (ColumnId::PressReleases, Lang::Zh) => "新闻发布",
+            (ColumnId::PressReleases, Lang::En) => "Press Releases",
+            (ColumnId::Custom(_), _) => return None,
+        };
+        Some(label)
+    }
+
+    /// Find a documented column by its display name in either language.
+    ///
+    /// English names match case-insensitively, and the "-文媒" suffix used
+    /// in the official documentation is accepted on Chinese names.
+    ///
+    /// ```
+    /// use dceapi_rs::ColumnId;
+    ///
+    /// assert_eq!(ColumnId::from_name("今日提示"), Some(ColumnId::DailyTips));
+    /// assert_eq!(ColumnId::from_name("交易所新闻-文媒"), Some(ColumnId::ExchangeNews));
+    /// assert_eq!(ColumnId::from_name("press releases"), Some(ColumnId::PressReleases));
+    /// assert_eq!(ColumnId::from_name("244"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<ColumnId> {
+        let name = name.trim();
+        let zh = name.strip_suffix("-文媒").unwrap_or(name);
+        ColumnId::ALL.into_iter().find(|c| {
+            c.label(Lang::Zh) == Some(zh)
+                || c.label(Lang::En)
+                    .is_some_and(|en| en.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Whether this is one of the documented columns.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, ColumnId::Custom(_))
+    }
+}
+
+impl std::fmt::Display for ColumnId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// Maps documented IDs to their variant; anything else becomes [`ColumnId::Custom`].
+impl From<&str> for ColumnId {
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+        ColumnId::ALL
+            .into_iter()
+            .find(|c| c.id() == value)
+            .unwrap_or_else(|| ColumnId::Custom(value.to_string()))
+    }
+}
+
+impl From<String> for ColumnId {
+    fn from(value: String) -> Self {
+        ColumnId::from(value.as_str())
+    }
+}
+
+impl Serialize for ColumnId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for ColumnId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = String::deserialize(deserializer)?;
+        Ok(ColumnId::from(id))
+    }
 }
 
 /// Request for paginated article list.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetArticleByPageRequest {
-    /// Column ID (e.g., "244" for announcements).
-    pub column_id: String,
+    /// Column (e.g. [`ColumnId::BusinessNotices`] for "244").
+    pub column_id: ColumnId,
     /// Page number (1-indexed).
     pub page_no: i32,
     /// Page size.
@@ -140,7 +665,7 @@ pub struct GetArticleByPageRequest {
 }
 
 /// Response for paginated article list.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetArticleByPageResponse {
     /// Column ID.
@@ -155,6 +680,9 @@ pub struct GetArticleByPageResponse {
     pub result_list: Vec<Article>,
     /// Total count of articles.
     pub total_count: i32,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 // ============================================================================
@@ -162,7 +690,7 @@ pub struct GetArticleByPageResponse {
 // ============================================================================
 
 /// Trade date information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TradeDate {
     /// Trade date string.
@@ -172,10 +700,13 @@ pub struct TradeDate {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub date: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Variety (commodity) information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Variety {
     /// Variety code/ID.
@@ -209,6 +740,9 @@ pub struct Variety {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub variety_type: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 // ============================================================================
@@ -216,7 +750,7 @@ pub struct Variety {
 // ============================================================================
 
 /// Quote data for a contract.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Quote {
     /// Variety name.
@@ -244,44 +778,44 @@ pub struct Quote {
     )]
     pub deliv_month: String,
     /// Open price.
-    #[serde(default, deserialize_with = "deserialize_nullable_string")]
-    pub open: String,
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    pub open: Option<f64>,
     /// High price.
-    #[serde(default, deserialize_with = "deserialize_nullable_string")]
-    pub high: String,
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    pub high: Option<f64>,
     /// Low price.
-    #[serde(default, deserialize_with = "deserialize_nullable_string")]
-    pub low: String,
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    pub low: Option<f64>,
     /// Close price.
-    #[serde(default, deserialize_with = "deserialize_nullable_string")]
-    pub close: String,
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    pub close: Option<f64>,
     /// Last clearing price (前结算价).
     #[serde(
         rename = "lastClear",
         default,
-        deserialize_with = "deserialize_nullable_string"
+        deserialize_with = "deserialize_optional_f64"
     )]
-    pub last_clear: String,
+    pub last_clear: Option<f64>,
     /// Last price (最新价, for night quotes).
     #[serde(
         rename = "lastPrice",
         default,
-        deserialize_with = "deserialize_nullable_string"
+        deserialize_with = "deserialize_optional_f64"
     )]
-    pub last_price: String,
+    pub last_price: Option<f64>,
     /// Clearing/settlement price (结算价).
     #[serde(
         rename = "clearPrice",
         default,
-        deserialize_with = "deserialize_nullable_string"
+        deserialize_with = "deserialize_optional_f64"
     )]
-    pub clear_price: String,
+    pub clear_price: Option<f64>,
     /// Price difference (涨跌).
-    #[serde(default, deserialize_with = "deserialize_nullable_string")]
-    pub diff: String,
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    pub diff: Option<f64>,
     /// Price difference 1 (涨跌1).
-    #[serde(default, deserialize_with = "deserialize_nullable_string")]
-    pub diff1: String,
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    pub diff1: Option<f64>,
     /// Declare price (买价/卖价, for night quotes).
     #[serde(
         rename = "declarePrice",
@@ -290,13 +824,25 @@ pub struct Quote {
     )]
     pub declare_price: String,
     /// Volume (成交量).
-    #[serde(rename = "volumn", default)]
+    #[serde(
+        rename = "volumn",
+        default,
+        deserialize_with = "deserialize_lenient_i64"
+    )]
     pub volume: i64,
     /// Open interest (持仓量).
-    #[serde(rename = "openInterest", default)]
+    #[serde(
+        rename = "openInterest",
+        default,
+        deserialize_with = "deserialize_lenient_i64"
+    )]
     pub open_interest: i64,
     /// Open interest difference (持仓量变化).
-    #[serde(rename = "diffI", default)]
+    #[serde(
+        rename = "diffI",
+        default,
+        deserialize_with = "deserialize_lenient_i64"
+    )]
     pub diff_i: i64,
     /// Turnover (成交额).
     #[serde(default, deserialize_with = "deserialize_nullable_string")]
@@ -316,10 +862,14 @@ pub struct Quote {
     )]
     pub turnover_en: String,
     /// Delta (期权).
-    #[serde(default, deserialize_with = "deserialize_nullable_string")]
-    pub delta: String,
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    pub delta: Option<f64>,
     /// Exercise quantity sum (行权量).
-    #[serde(rename = "matchQtySum", default)]
+    #[serde(
+        rename = "matchQtySum",
+        default,
+        deserialize_with = "deserialize_lenient_i64"
+    )]
     pub match_qty_sum: i64,
     /// Turnover difference.
     #[serde(
@@ -369,6 +919,129 @@ pub struct Quote {
     /// Average open interest (日均持仓量).
     #[serde(rename = "avgOpenInterest", default)]
     pub avg_open_interest: i64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
+}
+
+impl Quote {
+    /// Whether this is a subtotal or total row (e.g. "豆一小计", "总计")
+    /// rather than a contract.
+    pub fn is_subtotal(&self) -> bool {
+        is_subtotal_label(self.variety_label())
+    }
+
+    /// Variety name, or the English name when `variety` is empty (as in
+    /// some English responses).
+    pub fn variety_label(&self) -> &str {
+        or_fallback(&self.variety, &self.variety_en)
+    }
+
+    /// Raw turnover, or the English-format turnover when `turnover` is
+    /// empty (as in some English responses).
+    pub fn turnover_text(&self) -> &str {
+        or_fallback(&self.turnover, &self.turnover_en)
+    }
+
+    /// Price change against the previous settlement price (涨跌).
+    ///
+    /// Uses the published `diff` when present, otherwise the close (or last
+    /// price for night quotes) minus the previous settlement price.
+    pub fn change(&self) -> Option<f64> {
+        self.diff.or_else(|| {
+            let price = self.close.or(self.last_price)?;
+            Some(price - self.last_clear?)
+        })
+    }
+
+    /// Price change in percent of the previous settlement price.
+    pub fn change_pct(&self) -> Option<f64> {
+        let base = self.last_clear.filter(|v| *v != 0.0)?;
+        Some(self.change()? / base * 100.0)
+    }
+
+    /// Turnover in yuan.
+    pub fn turnover_yuan(&self) -> Option<f64> {
+        self.turnover_amount().map(|amount| amount.base_value())
+    }
+
+    /// Turnover with its unit.
+    ///
+    /// Quote turnover is published in 万元 (ten thousand yuan) unless the
+    /// value carries its own 万/亿 suffix.
+    pub fn turnover_amount(&self) -> Option<Amount> {
+        Amount::parse(self.turnover_text(), AmountUnit::TenThousand)
+    }
+}
+
+/// `value`, or `fallback` if `value` is blank.
+pub(crate) fn or_fallback<'a>(value: &'a str, fallback: &'a str) -> &'a str {
+    if value.trim().is_empty() {
+        fallback
+    } else {
+        value
+    }
+}
+
+/// Whether a fee or limit style label gives values as a ratio (比例) rather
+/// than an absolute amount.
+pub(crate) fn is_ratio_label(label: &str) -> bool {
+    let label = label.trim().to_lowercase();
+    ["比例", "ratio", "percent", "turnover", "%"]
+        .iter()
+        .any(|k| label.contains(k))
+}
+
+/// Whether a variety label marks a subtotal or total row.
+pub(crate) fn is_subtotal_label(variety: &str) -> bool {
+    let label = variety.trim();
+    ["小计", "总计", "合计"].iter().any(|s| label.ends_with(s))
+        || label.to_ascii_lowercase().ends_with("total")
+}
+
+/// Aggregation level for option day quotes (统计类型).
+///
+/// Serialized as 0 (contract), 1 (series) or 2 (variety).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StatisticsType {
+    /// Per option contract (合约), wire value 0.
+    #[default]
+    Contract,
+    /// Per option series (系列), wire value 1.
+    Series,
+    /// Per variety (品种), wire value 2.
+    Variety,
+}
+
+impl StatisticsType {
+    /// Get the wire value (0 to 2).
+    pub fn code(&self) -> i32 {
+        match self {
+            StatisticsType::Contract => 0,
+            StatisticsType::Series => 1,
+            StatisticsType::Variety => 2,
+        }
+    }
+}
+
+/// Converts 1 and 2 to their variants; anything else is contract level.
+impl From<i32> for StatisticsType {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => StatisticsType::Series,
+            2 => StatisticsType::Variety,
+            _ => StatisticsType::Contract,
+        }
+    }
+}
+
+impl Serialize for StatisticsType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.code())
+    }
 }
 
 /// Request for day/night quotes.
@@ -384,13 +1057,13 @@ pub struct QuotesRequest {
     /// Trade date (YYYYMMDD format).
     pub trade_date: String,
     /// Trade type ("1" = futures, "2" = options).
-    pub trade_type: String,
+    pub trade_type: TradeType,
     /// Language.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub lang: Option<String>,
-    /// Statistics type for options: 0=contract, 1=series, 2=variety.
+    pub lang: Option<Lang>,
+    /// Statistics level for option quotes (contract, series or variety).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub statistics_type: Option<i32>,
+    pub statistics_type: Option<StatisticsType>,
 }
 
 /// Request for weekly quotes.
@@ -422,7 +1095,7 @@ pub struct MonthQuotesRequest {
 // ============================================================================
 
 /// Delivery data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeliveryData {
     /// Variety name.
@@ -440,6 +1113,9 @@ pub struct DeliveryData {
     /// Delivery amount.
     #[serde(default, deserialize_with = "deserialize_nullable_string")]
     pub delivery_amt: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for delivery data.
@@ -448,16 +1124,151 @@ pub struct DeliveryData {
 pub struct DeliveryDataRequest {
     /// Variety ID.
     pub variety_id: String,
-    /// Start month (YYYYMM format).
-    pub start_month: String,
-    /// End month (YYYYMM format).
-    pub end_month: String,
-    /// Variety type ("0" = physical delivery, "1" = average price delivery).
-    pub variety_type: String,
+    /// Start month.
+    pub start_month: DeliveryMonth,
+    /// End month.
+    pub end_month: DeliveryMonth,
+    /// Delivery mode of the varieties to include.
+    pub variety_type: VarietyType,
+}
+
+/// Delivery mode of a variety (品种类型).
+///
+/// Serialized as "0" (physical delivery) or "1" (average price delivery).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VarietyType {
+    /// Physical delivery (实物交割), wire value "0".
+    #[default]
+    Physical,
+    /// Average price delivery (平均价交割), wire value "1".
+    AveragePrice,
+}
+
+impl VarietyType {
+    /// Get the wire value ("0" or "1").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VarietyType::Physical => "0",
+            VarietyType::AveragePrice => "1",
+        }
+    }
+
+    /// Parse a wire value or delivery mode name strictly.
+    ///
+    /// Accepts "0"/"1" and the Chinese and English names; returns `None`
+    /// for anything else.
+    pub fn from_code(value: &str) -> Option<VarietyType> {
+        match value.trim() {
+            "0" | "实物交割" => Some(VarietyType::Physical),
+            "1" | "平均价交割" => Some(VarietyType::AveragePrice),
+            other if other.eq_ignore_ascii_case("physical") => Some(VarietyType::Physical),
+            other if other.eq_ignore_ascii_case("average price") => Some(VarietyType::AveragePrice),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for VarietyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses a wire value or delivery mode name (see
+/// [`from_code`](VarietyType::from_code)).
+impl std::str::FromStr for VarietyType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        VarietyType::from_code(s).ok_or_else(|| {
+            Error::validation("variety_type", format!("invalid variety type {:?}", s))
+        })
+    }
+}
+
+impl TryFrom<&str> for VarietyType {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Error> {
+        value.parse()
+    }
+}
+
+impl Serialize for VarietyType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Delivery way (交割方式) of a delivery response row.
+///
+/// Serialized as the published label; labels other than one-time and
+/// rolling delivery are kept as [`DeliveryWay::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeliveryWay {
+    /// One-time delivery (一次性交割).
+    OneTime,
+    /// Rolling delivery (滚动交割).
+    Rolling,
+    /// Any other published delivery way.
+    Other(String),
+}
+
+impl DeliveryWay {
+    /// Parse a published delivery way in Chinese or English.
+    pub fn parse(label: &str) -> DeliveryWay {
+        let label = label.trim();
+        let lower = label.to_lowercase();
+        if label.contains("一次性") || lower.contains("one-time") || lower.contains("one time") {
+            DeliveryWay::OneTime
+        } else if label.contains("滚动") || lower.contains("rolling") {
+            DeliveryWay::Rolling
+        } else {
+            DeliveryWay::Other(label.to_string())
+        }
+    }
+
+    /// Get the Chinese label, or the published label of
+    /// [`DeliveryWay::Other`].
+    pub fn label(&self) -> &str {
+        match self {
+            DeliveryWay::OneTime => "一次性交割",
+            DeliveryWay::Rolling => "滚动交割",
+            DeliveryWay::Other(label) => label,
+        }
+    }
+}
+
+impl std::fmt::Display for DeliveryWay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl Serialize for DeliveryWay {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.label())
+    }
+}
+
+/// Helper function to deserialize a delivery way; null and empty labels
+/// become `None`.
+fn deserialize_delivery_way<'de, D>(deserializer: D) -> Result<Option<DeliveryWay>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let label = deserialize_nullable_string(deserializer)?;
+    Ok((!label.trim().is_empty()).then(|| DeliveryWay::parse(&label)))
 }
 
 /// Delivery match data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeliveryMatch {
     /// Contract ID.
@@ -478,6 +1289,9 @@ pub struct DeliveryMatch {
     /// Delivery price.
     #[serde(default, deserialize_with = "deserialize_nullable_string")]
     pub delivery_price: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for delivery match data.
@@ -488,14 +1302,14 @@ pub struct DeliveryMatchRequest {
     pub variety_id: String,
     /// Contract ID ("all" for all contracts).
     pub contract_id: String,
-    /// Start month (YYYYMM format).
-    pub start_month: String,
-    /// End month (YYYYMM format).
-    pub end_month: String,
+    /// Start month.
+    pub start_month: DeliveryMonth,
+    /// End month.
+    pub end_month: DeliveryMonth,
 }
 
 /// Warehouse receipt daily report response.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WarehouseReceipt {
     /// Entity list containing warehouse receipt details.
@@ -522,10 +1336,13 @@ pub struct WarehouseReceipt {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub if_agio_brand_flag: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Warehouse receipt detail entry.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WarehouseReceiptDetail {
     /// Variety order.
@@ -595,6 +1412,9 @@ pub struct WarehouseReceiptDetail {
     /// Difference (lots).
     #[serde(default)]
     pub diff: i64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for warehouse receipt data (daily report).
@@ -608,7 +1428,7 @@ pub struct WarehouseReceiptRequest {
 }
 
 /// Delivery cost data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeliveryCost {
     /// Variety name.
@@ -632,11 +1452,14 @@ pub struct DeliveryCost {
     /// End date.
     #[serde(default, deserialize_with = "deserialize_nullable_string")]
     pub end_date: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Warehouse premium data.
 /// Warehouse premium response wrapper.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WarehousePremiumResponse {
     /// Entity list containing warehouse premium details.
@@ -649,10 +1472,13 @@ pub struct WarehousePremiumResponse {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub if_agio_flag: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Warehouse premium data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WarehousePremium {
     /// Variety ID.
@@ -699,6 +1525,9 @@ pub struct WarehousePremium {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub brand_abbr: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 // ============================================================================
@@ -706,7 +1535,7 @@ pub struct WarehousePremium {
 // ============================================================================
 
 /// Ranking data entry.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Ranking {
     /// Rank position.
@@ -751,6 +1580,9 @@ pub struct Ranking {
     /// Sell quantity change.
     #[serde(rename = "sellSub", default)]
     pub sell_sub: i64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for daily ranking.
@@ -764,11 +1596,11 @@ pub struct DailyRankingRequest {
     /// Trade date.
     pub trade_date: String,
     /// Trade type ("1" = futures, "2" = options).
-    pub trade_type: String,
+    pub trade_type: TradeType,
 }
 
 /// Response for daily ranking.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DailyRankingResponse {
     /// Contract ID.
@@ -805,6 +1637,9 @@ pub struct DailyRankingResponse {
     /// Sell ranking list.
     #[serde(rename = "sellFutureList", default)]
     pub sell_future_list: Vec<Ranking>,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for phase ranking.
@@ -818,11 +1653,11 @@ pub struct PhaseRankingRequest {
     /// End month.
     pub end_month: String,
     /// Trade type.
-    pub trade_type: String,
+    pub trade_type: TradeType,
 }
 
 /// Phase ranking data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PhaseRanking {
     /// Sequence number.
@@ -854,6 +1689,9 @@ pub struct PhaseRanking {
     /// Amount ratio.
     #[serde(rename = "amtRatio", default)]
     pub amt_ratio: f64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 // ============================================================================
@@ -861,7 +1699,7 @@ pub struct PhaseRanking {
 // ============================================================================
 
 /// Trade parameter data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TradeParam {
     /// Contract ID.
@@ -872,40 +1710,84 @@ pub struct TradeParam {
     )]
     pub contract_id: String,
     /// Speculative buy margin rate.
-    #[serde(rename = "specBuyRate", default)]
-    pub spec_buy_rate: f64,
+    #[serde(
+        rename = "specBuyRate",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
+    pub spec_buy_rate: Option<f64>,
     /// Speculative buy margin.
-    #[serde(rename = "specBuy", default)]
-    pub spec_buy: f64,
+    #[serde(
+        rename = "specBuy",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
+    pub spec_buy: Option<f64>,
     /// Hedge buy margin rate.
-    #[serde(rename = "hedgeBuyRate", default)]
-    pub hedge_buy_rate: f64,
+    #[serde(
+        rename = "hedgeBuyRate",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
+    pub hedge_buy_rate: Option<f64>,
     /// Hedge buy margin.
-    #[serde(rename = "hedgeBuy", default)]
-    pub hedge_buy: f64,
+    #[serde(
+        rename = "hedgeBuy",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
+    pub hedge_buy: Option<f64>,
     /// Rise limit rate.
-    #[serde(rename = "riseLimitRate", default)]
-    pub rise_limit_rate: f64,
+    #[serde(
+        rename = "riseLimitRate",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
+    pub rise_limit_rate: Option<f64>,
     /// Rise limit price.
-    #[serde(rename = "riseLimit", default)]
-    pub rise_limit: f64,
+    #[serde(
+        rename = "riseLimit",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
+    pub rise_limit: Option<f64>,
     /// Fall limit price.
-    #[serde(rename = "fallLimit", default)]
-    pub fall_limit: f64,
+    #[serde(
+        rename = "fallLimit",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
+    pub fall_limit: Option<f64>,
     /// Position limit style.
     #[serde(default, deserialize_with = "deserialize_nullable_string")]
     pub style: String,
     /// Non-futures company member futures position quota.
-    #[serde(rename = "selfTotBuyPosiQuota", default)]
+    #[serde(
+        rename = "selfTotBuyPosiQuota",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
     pub self_tot_buy_posi_quota: Option<f64>,
     /// Non-futures company member options position quota.
-    #[serde(rename = "selfTotBuyPosiQuotaSerLimit", default)]
+    #[serde(
+        rename = "selfTotBuyPosiQuotaSerLimit",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
     pub self_tot_buy_posi_quota_ser_limit: Option<f64>,
     /// Client futures position quota.
-    #[serde(rename = "clientBuyPosiQuota", default)]
+    #[serde(
+        rename = "clientBuyPosiQuota",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
     pub client_buy_posi_quota: Option<f64>,
     /// Client options position quota.
-    #[serde(rename = "clientBuyPosiQuotaSerLimit", default)]
+    #[serde(
+        rename = "clientBuyPosiQuotaSerLimit",
+        default,
+        deserialize_with = "deserialize_optional_f64"
+    )]
     pub client_buy_posi_quota_ser_limit: Option<f64>,
     /// Contract limit.
     #[serde(
@@ -928,6 +1810,9 @@ pub struct TradeParam {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub trade_date: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for day trade parameters.
@@ -937,13 +1822,13 @@ pub struct DayTradeParamRequest {
     /// Variety ID.
     pub variety_id: String,
     /// Trade type.
-    pub trade_type: String,
+    pub trade_type: TradeType,
     /// Language.
-    pub lang: String,
+    pub lang: Lang,
 }
 
 /// Contract information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractInfo {
     /// Contract ID.
@@ -997,6 +1882,9 @@ pub struct ContractInfo {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub trade_type: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for contract information.
@@ -1006,13 +1894,13 @@ pub struct ContractInfoRequest {
     /// Variety ID.
     pub variety_id: String,
     /// Trade type.
-    pub trade_type: String,
+    pub trade_type: TradeType,
     /// Language.
-    pub lang: String,
+    pub lang: Lang,
 }
 
 /// Arbitrage contract information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArbitrageContract {
     /// Arbitrage strategy name.
@@ -1042,6 +1930,9 @@ pub struct ArbitrageContract {
     /// Minimum tick.
     #[serde(default)]
     pub tick: f64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for arbitrage contracts.
@@ -1049,7 +1940,7 @@ pub struct ArbitrageContract {
 #[serde(rename_all = "camelCase")]
 pub struct ArbitrageContractRequest {
     /// Language.
-    pub lang: String,
+    pub lang: Lang,
 }
 
 // ============================================================================
@@ -1057,7 +1948,7 @@ pub struct ArbitrageContractRequest {
 // ============================================================================
 
 /// Settlement parameter data.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SettleParam {
     /// Variety code.
@@ -1143,6 +2034,56 @@ pub struct SettleParam {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub hedge_sell_rate: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
+}
+
+impl SettleParam {
+    /// Settlement price as a number, or `None` if missing.
+    pub fn clear_price_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.clear_price)
+    }
+
+    /// Open fee as a number, or `None` if missing.
+    pub fn open_fee_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.open_fee)
+    }
+
+    /// Offset fee as a number, or `None` if missing.
+    pub fn offset_fee_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.offset_fee)
+    }
+
+    /// Intraday open fee as a number, or `None` if missing.
+    pub fn short_open_fee_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.short_open_fee)
+    }
+
+    /// Intraday offset fee as a number, or `None` if missing.
+    pub fn short_offset_fee_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.short_offset_fee)
+    }
+
+    /// Speculative buy margin rate as a number, or `None` if missing.
+    pub fn spec_buy_rate_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.spec_buy_rate)
+    }
+
+    /// Speculative sell margin rate as a number, or `None` if missing.
+    pub fn spec_sell_rate_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.spec_sell_rate)
+    }
+
+    /// Hedge buy margin rate as a number, or `None` if missing.
+    pub fn hedge_buy_rate_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.hedge_buy_rate)
+    }
+
+    /// Hedge sell margin rate as a number, or `None` if missing.
+    pub fn hedge_sell_rate_f64(&self) -> Option<f64> {
+        parse_optional_f64(&self.hedge_sell_rate)
+    }
 }
 
 /// Request for settlement parameters.
@@ -1154,9 +2095,9 @@ pub struct SettleParamRequest {
     /// Trade date.
     pub trade_date: String,
     /// Trade type.
-    pub trade_type: String,
+    pub trade_type: TradeType,
     /// Language.
-    pub lang: String,
+    pub lang: Lang,
 }
 
 // ============================================================================
@@ -1170,13 +2111,13 @@ pub struct VarietyMonthYearStatRequest {
     /// Trade month (YYYYMM format).
     pub trade_month: String,
     /// Trade type ("1" = futures, "2" = options).
-    pub trade_type: String,
+    pub trade_type: TradeType,
     /// Language ("zh" or "en").
-    pub lang: String,
+    pub lang: Lang,
 }
 
 /// Variety monthly/yearly statistics.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VarietyMonthYearStat {
     /// Variety name.
@@ -1261,26 +2202,125 @@ pub struct VarietyMonthYearStat {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub openi_chain: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for contract monthly max statistics.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractMonthMaxRequest {
-    /// Start month (YYYYMM format).
-    pub start_month: String,
-    /// End month (YYYYMM format).
-    pub end_month: String,
-    /// Statistics content: "0"=volume, "1"=turnover, "2"=open_interest, "3"=price.
-    pub stat_content: String,
+    /// Start month.
+    pub start_month: DeliveryMonth,
+    /// End month.
+    pub end_month: DeliveryMonth,
+    /// Statistics content (volume, turnover, open interest or price).
+    pub stat_content: StatContent,
     /// Trade type ("1" = futures, "2" = options).
-    pub trade_type: String,
+    pub trade_type: TradeType,
     /// Language ("zh" or "en").
-    pub lang: String,
+    pub lang: Lang,
+}
+
+/// Statistics content for contract monthly max statistics (统计内容).
+///
+/// Serialized as "0" (volume), "1" (turnover), "2" (open interest) or "3" (price).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StatContent {
+    /// Volume (成交量), wire value "0".
+    #[default]
+    Volume,
+    /// Turnover (成交额), wire value "1".
+    Turnover,
+    /// Open interest (持仓量), wire value "2".
+    OpenInterest,
+    /// Price (价格), wire value "3".
+    Price,
+}
+
+impl StatContent {
+    /// Get the wire value ("0" to "3").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatContent::Volume => "0",
+            StatContent::Turnover => "1",
+            StatContent::OpenInterest => "2",
+            StatContent::Price => "3",
+        }
+    }
+}
+
+impl std::fmt::Display for StatContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Converts "1", "2" and "3" to their variants; anything else is volume.
+impl From<&str> for StatContent {
+    fn from(value: &str) -> Self {
+        match value.trim() {
+            "1" => StatContent::Turnover,
+            "2" => StatContent::OpenInterest,
+            "3" => StatContent::Price,
+            _ => StatContent::Volume,
+        }
+    }
+}
+
+impl Serialize for StatContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Result of [`get_contract_month_max`](crate::MarketService::get_contract_month_max),
+/// typed by the requested [`StatContent`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum ContractMonthMaxResult {
+    /// Volume statistics.
+    Volume(Vec<ContractMonthMaxVolume>),
+    /// Turnover statistics.
+    Turnover(Vec<ContractMonthMaxTurnover>),
+    /// Open interest statistics.
+    OpenInterest(Vec<ContractMonthMaxOpeni>),
+    /// Price statistics.
+    Price(Vec<ContractMonthMaxPrice>),
+}
+
+impl ContractMonthMaxResult {
+    /// Get the statistics content of this result.
+    pub fn stat_content(&self) -> StatContent {
+        match self {
+            ContractMonthMaxResult::Volume(_) => StatContent::Volume,
+            ContractMonthMaxResult::Turnover(_) => StatContent::Turnover,
+            ContractMonthMaxResult::OpenInterest(_) => StatContent::OpenInterest,
+            ContractMonthMaxResult::Price(_) => StatContent::Price,
+        }
+    }
+
+    /// Get the number of rows.
+    pub fn len(&self) -> usize {
+        match self {
+            ContractMonthMaxResult::Volume(rows) => rows.len(),
+            ContractMonthMaxResult::Turnover(rows) => rows.len(),
+            ContractMonthMaxResult::OpenInterest(rows) => rows.len(),
+            ContractMonthMaxResult::Price(rows) => rows.len(),
+        }
+    }
+
+    /// Check whether the result has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 /// Contract monthly max - Volume statistics.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractMonthMaxVolume {
     /// Contract ID.
@@ -1316,10 +2356,13 @@ pub struct ContractMonthMaxVolume {
     /// Average daily volume.
     #[serde(rename = "avgAmount", default)]
     pub avg_amount: i64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Contract monthly max - Turnover statistics.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractMonthMaxTurnover {
     /// Contract ID.
@@ -1371,10 +2414,13 @@ pub struct ContractMonthMaxTurnover {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub avg_turnover: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Contract monthly max - Open Interest statistics.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractMonthMaxOpeni {
     /// Contract ID.
@@ -1410,10 +2456,13 @@ pub struct ContractMonthMaxOpeni {
     /// Average daily open interest.
     #[serde(rename = "avgOpeni", default)]
     pub avg_openi: i64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Contract monthly max - Price statistics.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractMonthMaxPrice {
     /// Contract ID.
@@ -1456,6 +2505,9 @@ pub struct ContractMonthMaxPrice {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub clear_price: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for rise/fall event (trading limit) query.
@@ -1469,11 +2521,11 @@ pub struct RiseFallEventRequest {
     /// Variety ID ("all" for all varieties).
     pub variety_id: String,
     /// Language ("zh" or "en").
-    pub lang: String,
+    pub lang: Lang,
 }
 
 /// Rise/fall event (trading limit) information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RiseFallEvent {
     /// Trade date.
@@ -1496,6 +2548,9 @@ pub struct RiseFallEvent {
     /// Number of times.
     #[serde(default)]
     pub times: i32,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for division price info.
@@ -1507,11 +2562,11 @@ pub struct DivisionPriceInfoRequest {
     /// Trade date (YYYYMMDD format).
     pub trade_date: String,
     /// Trade type ("1" = futures, "2" = options).
-    pub trade_type: String,
+    pub trade_type: TradeType,
 }
 
 /// Division price information (分时结算参考价).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DivisionPriceInfo {
     /// Calculate date (交易日期).
@@ -1562,6 +2617,17 @@ pub struct DivisionPriceInfo {
     /// Volatility (结算参考隐含波动率).
     #[serde(default)]
     pub volatility: f64,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
+}
+
+impl DivisionPriceInfo {
+    /// Variety name, or the English name when `variety_name` is empty (as
+    /// in some English responses).
+    pub fn variety_label(&self) -> &str {
+        or_fallback(&self.variety_name, &self.variety_en_name)
+    }
 }
 
 // ============================================================================
@@ -1573,11 +2639,11 @@ pub struct DivisionPriceInfo {
 #[serde(rename_all = "camelCase")]
 pub struct TradingParamRequest {
     /// Language ("zh" or "en").
-    pub lang: String,
+    pub lang: Lang,
 }
 
 /// Trading parameters for a variety.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TradingParam {
     /// Variety ID.
@@ -1783,6 +2849,17 @@ pub struct TradingParam {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub max_hand: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
+}
+
+impl TradingParam {
+    /// Fee style, or the English fee style when `fee_style` is empty (as in
+    /// some English responses).
+    pub fn fee_style_label(&self) -> &str {
+        or_fallback(&self.fee_style, &self.fee_style_en)
+    }
 }
 
 /// Request for margin arbitrage performance parameters.
@@ -1794,7 +2871,7 @@ pub struct MarginArbiPerfParaRequest {
 }
 
 /// Margin arbitrage performance parameters.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarginArbiPerfPara {
     /// Variety.
@@ -1849,6 +2926,9 @@ pub struct MarginArbiPerfPara {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub short_offset_fee: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for new contract information.
@@ -1858,14 +2938,14 @@ pub struct NewContractInfoRequest {
     /// Trade date (YYYYMMDD format).
     pub trade_date: String,
     /// Trade type ("1" = futures, "2" = options).
-    pub trade_type: String,
+    pub trade_type: TradeType,
     /// Language.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub lang: Option<String>,
+    pub lang: Option<Lang>,
 }
 
 /// New contract information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewContractInfo {
     /// Trade type.
@@ -1920,6 +3000,9 @@ pub struct NewContractInfo {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub no_fall_limit: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for main series information (做市商持续报价合约).
@@ -1933,7 +3016,7 @@ pub struct MainSeriesInfoRequest {
 }
 
 /// Main series information (market maker contracts).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MainSeriesInfo {
     /// Trade date.
@@ -1964,6 +3047,9 @@ pub struct MainSeriesInfo {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub contract_id: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 // ============================================================================
@@ -1981,7 +3067,7 @@ pub struct TcCongregateDeliveryRequest {
 }
 
 /// TC congregate delivery information.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TcCongregateDelivery {
     /// Variety ID.
@@ -2067,6 +3153,9 @@ pub struct TcCongregateDelivery {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub wh_group_name: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for roll delivery seller intention (滚动交割卖方交割意向表).
@@ -2080,7 +3169,7 @@ pub struct RollDeliverySellerIntentionRequest {
 }
 
 /// Roll delivery seller intention.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RollDeliverySellerIntention {
     /// Variety ID.
@@ -2183,13 +3272,16 @@ pub struct RollDeliverySellerIntention {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub wh_group_name: String,
-    /// Delivery way.
+    /// Delivery way, or `None` when not published.
     #[serde(
         rename = "deliveryWay",
         default,
-        deserialize_with = "deserialize_nullable_string"
+        deserialize_with = "deserialize_delivery_way"
     )]
-    pub delivery_way: String,
+    pub delivery_way: Option<DeliveryWay>,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for bonded delivery settlement price (交割结算价).
@@ -2203,7 +3295,7 @@ pub struct BondedDeliveryRequest {
 }
 
 /// Bonded delivery settlement price.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BondedDelivery {
     /// Delivery date.
@@ -2213,13 +3305,13 @@ pub struct BondedDelivery {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub delivery_date: String,
-    /// Delivery way.
+    /// Delivery way, or `None` when not published.
     #[serde(
         rename = "deliveryWay",
         default,
-        deserialize_with = "deserialize_nullable_string"
+        deserialize_with = "deserialize_delivery_way"
     )]
-    pub delivery_way: String,
+    pub delivery_way: Option<DeliveryWay>,
     /// Variety ID (e.g. i-铁矿石).
     #[serde(
         rename = "varietyId",
@@ -2255,6 +3347,9 @@ pub struct BondedDelivery {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub delivery_price: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for TD bonded delivery settlement price (保税交割结算价).
@@ -2281,7 +3376,7 @@ pub struct FactorySpotAgioRequest {
 }
 
 /// Factory spot agio (price difference for fiberboard).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FactorySpotAgio {
     /// Sequence number.
@@ -2346,6 +3441,9 @@ pub struct FactorySpotAgio {
     /// Telephone.
     #[serde(default, deserialize_with = "deserialize_nullable_string")]
     pub tel: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
 }
 
 /// Request for plywood delivery commodity.
@@ -2357,7 +3455,7 @@ pub struct PlywoodDeliveryCommodityRequest {
 }
 
 /// Plywood delivery commodity.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlywoodDeliveryCommodity {
     /// Apply ID.
@@ -2394,4 +3492,114 @@ pub struct PlywoodDeliveryCommodity {
         deserialize_with = "deserialize_nullable_string"
     )]
     pub upload_file_name: String,
+    /// Fields not covered by this model.
+    #[serde(flatten)]
+    pub extra: Extras,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_parsing_is_restored_after_a_panic() {
+        let result = std::panic::catch_unwind(|| with_strict_parsing(true, || panic!("parse")));
+        assert!(result.is_err());
+        assert!(!strict_parsing());
+        let quote = with_strict_parsing(true, || {
+            serde_json::from_value::<Quote>(serde_json::json!({ "newField": 1 }))
+        });
+        assert!(quote.is_err());
+        assert!(!strict_parsing());
+    }
+
+    #[test]
+    fn trade_types_and_languages_parse_strictly() {
+        assert_eq!("2".parse::<TradeType>().unwrap(), TradeType::Options);
+        assert_eq!(" Futures".parse::<TradeType>().unwrap(), TradeType::Futures);
+        assert!("3".parse::<TradeType>().is_err());
+        assert!(TradeType::try_from(0_i64).is_err());
+        assert_eq!(
+            serde_json::from_value::<TradeType>(2.into()).unwrap(),
+            TradeType::Options
+        );
+        assert!(serde_json::from_value::<TradeType>("x".into()).is_err());
+
+        assert_eq!("EN".parse::<Lang>().unwrap(), Lang::En);
+        assert!(matches!(
+            "fr".parse::<Lang>(),
+            Err(Error::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn lenient_conversions_fall_back_to_defaults() {
+        assert_eq!(TradeType::from("options"), TradeType::Options);
+        assert_eq!(TradeType::from(2), TradeType::Options);
+        assert_eq!(TradeType::from("3"), TradeType::Futures);
+        assert_eq!(Lang::from("en"), Lang::En);
+        assert_eq!(Lang::from("fr".to_string()), Lang::Zh);
+
+        let opts = crate::RequestOptions::new()
+            .with_trade_type("2")
+            .with_lang("en");
+        assert_eq!(opts.trade_type, Some(TradeType::Options));
+        assert_eq!(opts.lang, Some(Lang::En));
+    }
+
+    #[test]
+    fn variety_types_parse_strictly() {
+        assert_eq!(
+            "1".parse::<VarietyType>().unwrap(),
+            VarietyType::AveragePrice
+        );
+        assert_eq!(
+            VarietyType::try_from(" Physical ").unwrap(),
+            VarietyType::Physical
+        );
+        assert!(matches!(
+            "2".parse::<VarietyType>(),
+            Err(Error::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn delivery_ways_are_typed() {
+        let way = |value: Value| -> Option<DeliveryWay> {
+            let row: BondedDelivery =
+                serde_json::from_value(serde_json::json!({ "deliveryWay": value })).unwrap();
+            row.delivery_way
+        };
+        assert_eq!(way("一次性交割".into()), Some(DeliveryWay::OneTime));
+        assert_eq!(way("Rolling Delivery".into()), Some(DeliveryWay::Rolling));
+        assert_eq!(
+            way("期转现".into()),
+            Some(DeliveryWay::Other("期转现".to_string()))
+        );
+        assert_eq!(way("".into()), None);
+        assert_eq!(way(Value::Null), None);
+        assert_eq!(
+            serde_json::to_value(DeliveryWay::Rolling).unwrap(),
+            Value::from("滚动交割")
+        );
+    }
+
+    #[test]
+    fn quote_numbers_parse_sentinels_and_reject_fractional_counts() {
+        let quote = |value: Value| serde_json::from_value::<Quote>(value);
+        let parsed = quote(serde_json::json!({
+            "open": "3,017", "close": 3020.5, "lastPrice": "-", "high": "", "volumn": "12.0",
+            "openInterest": "--",
+        }))
+        .unwrap();
+        assert_eq!(parsed.open, Some(3017.0));
+        assert_eq!(parsed.close, Some(3020.5));
+        assert_eq!(parsed.last_price, None);
+        assert_eq!(parsed.high, None);
+        assert_eq!(parsed.volume, 12);
+        assert_eq!(parsed.open_interest, 0);
+
+        assert!(quote(serde_json::json!({ "volumn": 12.7 })).is_err());
+        assert!(quote(serde_json::json!({ "openInterest": "1,000.5" })).is_err());
+    }
 }
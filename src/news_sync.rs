@@ -0,0 +1,293 @@
+//! Incremental article sync.
+//!
+//! [`NewsSync`] remembers, per column, the newest publication date seen and
+//! the article IDs published on it, and on each run returns only the
+//! articles published since. The state lives in a [`SyncStore`], so it can
+//! be kept in memory, in a JSON file or in an application's own database.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::http::RequestOptions;
+use crate::models::{Article, ColumnId, GetArticleByPageRequest};
+use crate::services::news::article_date;
+use crate::services::NewsService;
+
+/// Page size used when looking for new articles.
+const SYNC_PAGE_SIZE: i32 = 50;
+
+/// What a column has been synced up to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    /// Newest publication date seen (YYYYMMDD).
+    pub last_date: String,
+    /// IDs of the articles seen that were published on `last_date`.
+    pub seen_ids: BTreeSet<String>,
+}
+
+impl SyncState {
+    /// Whether an article published on `date` was not seen yet.
+    fn is_new(&self, date: &str, id: &str) -> bool {
+        date > self.last_date.as_str() || (date == self.last_date && !self.seen_ids.contains(id))
+    }
+
+    /// State after a run that found `fresh` (date, article) pairs, sorted
+    /// by date, or `None` if nothing new was found.
+    fn advance(state: Option<&SyncState>, fresh: &[(String, Article)]) -> Option<SyncState> {
+        let (newest, _) = fresh.last()?;
+        let mut next = state.cloned().unwrap_or_default();
+        if *newest > next.last_date {
+            next.last_date = newest.clone();
+            next.seen_ids.clear();
+        }
+        next.seen_ids.extend(
+            fresh
+                .iter()
+                .filter(|(date, _)| *date == next.last_date)
+                .map(|(_, a)| a.id.clone()),
+        );
+        Some(next)
+    }
+}
+
+/// Storage for per-column sync state.
+pub trait SyncStore: Send + Sync {
+    /// State of a column, or `None` if it has never been synced.
+    fn load(&self, column_id: &ColumnId) -> Result<Option<SyncState>>;
+
+    /// Replace the state of a column.
+    fn save(&self, column_id: &ColumnId, state: &SyncState) -> Result<()>;
+}
+
+/// Sync state kept in memory for the life of the process.
+#[derive(Debug, Default)]
+pub struct MemorySyncStore {
+    states: Mutex<HashMap<String, SyncState>>,
+}
+
+impl MemorySyncStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SyncStore for MemorySyncStore {
+    fn load(&self, column_id: &ColumnId) -> Result<Option<SyncState>> {
+        let states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(states.get(column_id.id()).cloned())
+    }
+
+    fn save(&self, column_id: &ColumnId, state: &SyncState) -> Result<()> {
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        states.insert(column_id.id().to_string(), state.clone());
+        Ok(())
+    }
+}
+
+/// Sync state of all columns kept in one JSON file.
+///
+/// The file is read on every load and rewritten on every save, which suits
+/// the handful of columns the exchange publishes.
+#[derive(Debug)]
+pub struct FileSyncStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileSyncStore {
+    /// Create a store backed by a file; a missing file means no column
+    /// has been synced.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSyncStore {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Read all column states from the file.
+    fn read(&self) -> Result<BTreeMap<String, SyncState>> {
+        let json = match fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(err) => return Err(Error::io(&self.path, err)),
+        };
+        serde_json::from_str(&json).map_err(|e| Error::parse(json.as_str(), e.to_string()))
+    }
+}
+
+impl SyncStore for FileSyncStore {
+    fn load(&self, column_id: &ColumnId) -> Result<Option<SyncState>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read()?.remove(column_id.id()))
+    }
+
+    fn save(&self, column_id: &ColumnId, state: &SyncState) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut states = self.read()?;
+        states.insert(column_id.id().to_string(), state.clone());
+        let json =
+            serde_json::to_string_pretty(&states).map_err(|e| Error::io(&self.path, e.into()))?;
+        fs::write(&self.path, json).map_err(|e| Error::io(&self.path, e))
+    }
+}
+
+/// Fetches the articles published in a column since the previous run.
+#[derive(Debug)]
+pub struct NewsSync<S> {
+    news: NewsService,
+    store: S,
+}
+
+impl<S: SyncStore> NewsSync<S> {
+    /// Create a sync over a news service and a state store.
+    pub fn new(news: NewsService, store: S) -> Self {
+        NewsSync { news, store }
+    }
+
+    /// The state store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Fetch the articles published in a column since the previous run,
+    /// in chronological order, and record them as seen.
+    ///
+    /// Pages through the column from the newest article until it reaches
+    /// articles that were already seen. The first run for a column returns
+    /// the newest page of articles. Articles without a publication date are
+    /// skipped. The state is only saved once the whole delta was fetched,
+    /// so a failed run is repeated in full by the next one.
+    ///
+    /// # Arguments
+    /// * `column_id` - Column (a [`ColumnId`] or its ID string)
+    /// * `opts` - Optional request options
+    pub async fn sync(
+        &self,
+        column_id: impl Into<ColumnId>,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<Article>> {
+        let column_id = column_id.into();
+        let state = self.store.load(&column_id)?;
+        let is_new = |date: &str, id: &str| state.as_ref().is_none_or(|s| s.is_new(date, id));
+
+        let mut fresh: Vec<(String, Article)> = Vec::new();
+        let mut page_no = 1;
+        loop {
+            let req = GetArticleByPageRequest {
+                column_id: column_id.clone(),
+                page_no,
+                page_size: SYNC_PAGE_SIZE,
+                site_id: 0,
+            };
+            let resp = self.news.get_article_by_page(req, opts.clone()).await?;
+            let page_len = resp.result_list.len();
+
+            let mut reached_seen = false;
+            for article in resp.result_list {
+                let Some(date) = article_date(&article) else {
+                    continue;
+                };
+                if !is_new(&date, &article.id) {
+                    reached_seen = true;
+                } else if !fresh.iter().any(|(_, a)| a.id == article.id) {
+                    fresh.push((date, article));
+                }
+            }
+
+            let fetched = (page_no as i64) * (SYNC_PAGE_SIZE as i64);
+            if state.is_none()
+                || reached_seen
+                || page_len < SYNC_PAGE_SIZE as usize
+                || fetched >= resp.total_count as i64
+            {
+                break;
+            }
+            page_no += 1;
+        }
+
+        fresh.sort_by(|a, b| a.0.cmp(&b.0));
+        if let Some(next) = SyncState::advance(state.as_ref(), &fresh) {
+            self.store.save(&column_id, &next)?;
+        }
+        Ok(fresh.into_iter().map(|(_, article)| article).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(articles: &[(&str, &str)]) -> Vec<(String, Article)> {
+        articles
+            .iter()
+            .map(|&(date, id)| {
+                let article = serde_json::from_value(serde_json::json!({ "id": id })).unwrap();
+                (date.to_string(), article)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn state_advances_to_the_newest_day() {
+        assert_eq!(SyncState::advance(None, &[]), None);
+
+        let first = SyncState::advance(None, &found(&[("20250929", "1"), ("20250930", "2")]));
+        let first = first.unwrap();
+        assert_eq!(first.last_date, "20250930");
+        assert_eq!(first.seen_ids, BTreeSet::from(["2".to_string()]));
+        assert!(!first.is_new("20250929", "9"));
+        assert!(!first.is_new("20250930", "2"));
+        assert!(first.is_new("20250930", "3"));
+        assert!(first.is_new("20251009", "2"));
+
+        // Another article on the same day adds to the seen IDs.
+        let second = SyncState::advance(Some(&first), &found(&[("20250930", "3")])).unwrap();
+        assert_eq!(second.last_date, "20250930");
+        assert_eq!(second.seen_ids.len(), 2);
+
+        // A later day starts a new set.
+        let third = SyncState::advance(Some(&second), &found(&[("20251009", "4")])).unwrap();
+        assert_eq!(third.last_date, "20251009");
+        assert_eq!(third.seen_ids, BTreeSet::from(["4".to_string()]));
+    }
+
+    #[test]
+    fn stores_keep_one_state_per_column() {
+        let state = SyncState {
+            last_date: "20250930".to_string(),
+            seen_ids: BTreeSet::from(["2".to_string()]),
+        };
+        let path = std::env::temp_dir().join(format!("news-sync-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let file = FileSyncStore::new(&path);
+        let memory = MemorySyncStore::new();
+
+        for store in [&memory as &dyn SyncStore, &file] {
+            assert_eq!(store.load(&ColumnId::BusinessNotices).unwrap(), None);
+            store.save(&ColumnId::BusinessNotices, &state).unwrap();
+            store
+                .save(&ColumnId::Custom("999".to_string()), &SyncState::default())
+                .unwrap();
+            assert_eq!(
+                store.load(&ColumnId::BusinessNotices).unwrap(),
+                Some(state.clone())
+            );
+        }
+
+        // A new store over the same file sees the saved state.
+        assert_eq!(
+            FileSyncStore::new(&path)
+                .load(&ColumnId::BusinessNotices)
+                .unwrap(),
+            Some(state)
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
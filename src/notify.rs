@@ -0,0 +1,256 @@
+//! Notification sinks for watcher events.
+//!
+//! A [`NotificationSink`] delivers [`Event`]s somewhere a person will see
+//! them. [`Watch::notify`] forwards everything a watcher yields to a sink,
+//! so routing alerts needs no glue code beyond choosing the sink.
+
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::pin::Pin;
+
+use serde::Serialize;
+
+use crate::analytics::{MarginChange, ReceiptAlert};
+use crate::error::{Error, Result};
+use crate::models::{Article, NewContractInfo, RiseFallEvent};
+use crate::services::news::article_date;
+use crate::watch::Watch;
+
+/// Future returned by [`NotificationSink::send`].
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// Something a watcher detected.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "items", rename_all = "snake_case")]
+pub enum Event {
+    /// New articles (from [`NewsService::watch`](crate::NewsService::watch)).
+    Articles(Vec<Article>),
+    /// New limit-up/limit-down events (from
+    /// [`MarketService::watch_rise_fall_events`](crate::MarketService::watch_rise_fall_events)).
+    LimitEvents(Vec<RiseFallEvent>),
+    /// Warehouse receipt moves (from
+    /// [`MarketService::watch_warehouse_receipts`](crate::MarketService::watch_warehouse_receipts)).
+    ReceiptAlerts(Vec<ReceiptAlert>),
+    /// Newly listed contracts (from
+    /// [`TradeService::watch_new_contracts`](crate::TradeService::watch_new_contracts)).
+    NewContracts(Vec<NewContractInfo>),
+    /// Margin rate changes (from
+    /// [`TradeService::watch_margin_changes`](crate::TradeService::watch_margin_changes)).
+    MarginChanges(Vec<MarginChange>),
+}
+
+impl Event {
+    /// Short name of the event kind (e.g. "articles" or "limit_events"),
+    /// as used in webhook payloads.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Articles(_) => "articles",
+            Event::LimitEvents(_) => "limit_events",
+            Event::ReceiptAlerts(_) => "receipt_alerts",
+            Event::NewContracts(_) => "new_contracts",
+            Event::MarginChanges(_) => "margin_changes",
+        }
+    }
+
+    /// Whether the event carries no items.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Event::Articles(items) => items.is_empty(),
+            Event::LimitEvents(items) => items.is_empty(),
+            Event::ReceiptAlerts(items) => items.is_empty(),
+            Event::NewContracts(items) => items.is_empty(),
+            Event::MarginChanges(items) => items.is_empty(),
+        }
+    }
+}
+
+/// Human-readable summary: a heading line followed by one line per item.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Articles(articles) => {
+                write!(f, "{} new article(s)", articles.len())?;
+                for article in articles {
+                    write!(f, "\n- {}", article.title)?;
+                    if let Some(date) = article_date(article) {
+                        write!(f, " ({})", date)?;
+                    }
+                }
+            }
+            Event::LimitEvents(events) => {
+                write!(f, "{} limit event(s)", events.len())?;
+                for event in events {
+                    write!(
+                        f,
+                        "\n- {} {} x{} ({})",
+                        event.contract_id, event.direction, event.times, event.trade_date
+                    )?;
+                }
+            }
+            Event::ReceiptAlerts(alerts) => {
+                write!(f, "{} warehouse receipt move(s)", alerts.len())?;
+                for alert in alerts {
+                    write!(
+                        f,
+                        "\n- {}: {} -> {} ({:+}) ({})",
+                        alert.variety,
+                        alert.previous_total,
+                        alert.total,
+                        alert.delta(),
+                        alert.trade_date
+                    )?;
+                }
+            }
+            Event::NewContracts(contracts) => {
+                write!(f, "{} new contract(s)", contracts.len())?;
+                for contract in contracts {
+                    write!(
+                        f,
+                        "\n- {} (from {})",
+                        contract.contract_id, contract.start_trade_date
+                    )?;
+                }
+            }
+            Event::MarginChanges(changes) => {
+                write!(f, "{} margin change(s)", changes.len())?;
+                for change in changes {
+                    write!(
+                        f,
+                        "\n- {}: speculation {} -> {}, hedging {} -> {}",
+                        change.variety_name,
+                        change.old_speculation,
+                        change.speculation,
+                        change.old_hedging,
+                        change.hedging
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<Article>> for Event {
+    fn from(articles: Vec<Article>) -> Self {
+        Event::Articles(articles)
+    }
+}
+
+impl From<Vec<RiseFallEvent>> for Event {
+    fn from(events: Vec<RiseFallEvent>) -> Self {
+        Event::LimitEvents(events)
+    }
+}
+
+impl From<Vec<ReceiptAlert>> for Event {
+    fn from(alerts: Vec<ReceiptAlert>) -> Self {
+        Event::ReceiptAlerts(alerts)
+    }
+}
+
+impl From<Vec<NewContractInfo>> for Event {
+    fn from(contracts: Vec<NewContractInfo>) -> Self {
+        Event::NewContracts(contracts)
+    }
+}
+
+impl From<Vec<MarginChange>> for Event {
+    fn from(changes: Vec<MarginChange>) -> Self {
+        Event::MarginChanges(changes)
+    }
+}
+
+/// Destination for watcher events.
+///
+/// Implementations are shared with background tasks, so they must be
+/// thread-safe.
+pub trait NotificationSink: Debug + Send + Sync {
+    /// Deliver one event.
+    fn send<'a>(&'a self, event: &'a Event) -> SendFuture<'a>;
+}
+
+/// Writes each event's summary to standard output.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+impl NotificationSink for StdoutSink {
+    fn send<'a>(&'a self, event: &'a Event) -> SendFuture<'a> {
+        Box::pin(async move {
+            println!("[{}] {}", event.kind(), event);
+            Ok(())
+        })
+    }
+}
+
+/// Posts each event as JSON to a webhook URL.
+///
+/// The body is `{"kind": ..., "text": ..., "items": [...]}`, where `text`
+/// is the event's summary, so chat webhooks that display a `text` field
+/// work without a template. Non-2xx responses are errors.
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    url: String,
+    http_client: reqwest::Client,
+}
+
+impl WebhookSink {
+    /// Create a sink posting to `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        WebhookSink {
+            url: url.into(),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use an existing HTTP client (e.g. one with a proxy or timeout).
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn send<'a>(&'a self, event: &'a Event) -> SendFuture<'a> {
+        Box::pin(async move {
+            let mut body = serde_json::to_value(event)
+                .map_err(|e| Error::export(format!("failed to serialize event: {}", e)))?;
+            if let Some(body) = body.as_object_mut() {
+                body.insert("text".to_string(), event.to_string().into());
+            }
+            self.http_client
+                .post(&self.url)
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+}
+
+impl<T: Into<Event>> Watch<T> {
+    /// Send every item to `sink` until the poller stops.
+    ///
+    /// Failed polls and failed deliveries are passed to `on_error`;
+    /// notifying continues after both.
+    pub async fn notify<S>(mut self, sink: &S, mut on_error: impl FnMut(Error))
+    where
+        S: NotificationSink + ?Sized,
+    {
+        while let Some(item) = self.next().await {
+            let event = match item {
+                Ok(item) => item.into(),
+                Err(err) => {
+                    on_error(err);
+                    continue;
+                }
+            };
+            if event.is_empty() {
+                continue;
+            }
+            if let Err(err) = sink.send(&event).await {
+                on_error(err);
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Option chains built from option day quotes.
+//!
+//! [`OptionChain`] groups a variety's option quotes by series (the
+//! underlying futures contract, e.g. "m2505") and strike, with the call and
+//! put of each strike side by side. With the options contract information
+//! from the contract catalog, each series also carries its last trade date.
+
+use std::collections::BTreeMap;
+
+use crate::catalog::normalize_date;
+use crate::contract_id::{ContractId, OptionKind};
+use crate::days::day_number;
+use crate::models::{ContractInfo, Quote};
+
+/// Call and put quotes at one strike.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrikeRow {
+    /// Strike price.
+    pub strike: f64,
+    /// Call quote, if listed.
+    pub call: Option<Quote>,
+    /// Put quote, if listed.
+    pub put: Option<Quote>,
+}
+
+/// All strikes of one option series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionSeries {
+    /// Series ID, the underlying futures contract (e.g. "m2505").
+    pub series_id: String,
+    /// Underlying futures contract.
+    pub underlying: ContractId,
+    /// Strikes in ascending order.
+    pub strikes: Vec<StrikeRow>,
+    /// Last trade date (YYYYMMDD) of the series' options, if known (see
+    /// [`OptionChain::with_contracts`]).
+    pub end_trade_date: Option<String>,
+}
+
+impl OptionSeries {
+    /// Calendar days from `trade_date` to the series' last trade date, or
+    /// `None` if either is not known.
+    pub fn days_to_expiry(&self, trade_date: &str) -> Option<i64> {
+        Some(day_number(self.end_trade_date.as_deref()?)? - day_number(trade_date)?)
+    }
+
+    /// Row for a strike price.
+    pub fn strike(&self, strike: f64) -> Option<&StrikeRow> {
+        self.strikes.iter().find(|row| row.strike == strike)
+    }
+
+    /// Call quotes in ascending strike order.
+    pub fn calls(&self) -> impl Iterator<Item = &Quote> {
+        self.strikes.iter().filter_map(|row| row.call.as_ref())
+    }
+
+    /// Put quotes in ascending strike order.
+    pub fn puts(&self) -> impl Iterator<Item = &Quote> {
+        self.strikes.iter().filter_map(|row| row.put.as_ref())
+    }
+}
+
+/// Option quotes of one variety and trade date, by series and strike.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OptionChain {
+    /// Variety ID.
+    pub variety: String,
+    /// Trade date (YYYYMMDD).
+    pub trade_date: String,
+    /// Series by series ID, in delivery month order.
+    pub series: BTreeMap<String, OptionSeries>,
+}
+
+impl OptionChain {
+    /// Build a chain from option day quotes.
+    ///
+    /// Rows whose contract ID is not an option contract (such as summary
+    /// rows) are skipped.
+    pub fn from_quotes(
+        variety: impl Into<String>,
+        trade_date: impl Into<String>,
+        quotes: &[Quote],
+    ) -> OptionChain {
+        let mut series: BTreeMap<String, OptionSeries> = BTreeMap::new();
+        for quote in quotes {
+            let Some(id) = quote.parsed_contract_id() else {
+                continue;
+            };
+            let Some(option) = id.option else {
+                continue;
+            };
+            let underlying = id.underlying();
+            let entry = series
+                .entry(underlying.to_string())
+                .or_insert_with(|| OptionSeries {
+                    series_id: underlying.to_string(),
+                    underlying,
+                    strikes: Vec::new(),
+                    end_trade_date: None,
+                });
+            let index = match entry
+                .strikes
+                .binary_search_by(|row| row.strike.total_cmp(&option.strike))
+            {
+                Ok(index) => index,
+                Err(index) => {
+                    entry.strikes.insert(
+                        index,
+                        StrikeRow {
+                            strike: option.strike,
+                            call: None,
+                            put: None,
+                        },
+                    );
+                    index
+                }
+            };
+            let row = &mut entry.strikes[index];
+            match option.kind {
+                OptionKind::Call => row.call = Some(quote.clone()),
+                OptionKind::Put => row.put = Some(quote.clone()),
+            }
+        }
+        OptionChain {
+            variety: variety.into(),
+            trade_date: trade_date.into(),
+            series,
+        }
+    }
+
+    /// Set each series' last trade date from options contract information,
+    /// as returned by the [contract catalog](crate::Client::catalog) for
+    /// the variety's options.
+    pub fn with_contracts(mut self, contracts: &[ContractInfo]) -> Self {
+        for info in contracts {
+            let Some(id) = ContractId::parse(&info.contract_id)
+                .ok()
+                .filter(ContractId::is_option)
+            else {
+                continue;
+            };
+            if let Some(series) = self.series.get_mut(&id.underlying().to_string()) {
+                if series.end_trade_date.is_none() {
+                    series.end_trade_date = normalize_date(&info.end_trade_date);
+                }
+            }
+        }
+        self
+    }
+
+    /// Series by ID (e.g. "m2505").
+    pub fn get(&self, series_id: &str) -> Option<&OptionSeries> {
+        self.series.get(series_id)
+    }
+
+    /// Series IDs in delivery month order.
+    pub fn series_ids(&self) -> impl Iterator<Item = &str> {
+        self.series.keys().map(String::as_str)
+    }
+
+    /// All option quotes in the chain.
+    pub fn quotes(&self) -> impl Iterator<Item = &Quote> {
+        self.series.values().flat_map(|series| {
+            series
+                .strikes
+                .iter()
+                .flat_map(|row| row.call.iter().chain(row.put.iter()))
+        })
+    }
+
+    /// Whether the chain has no series.
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contracts_set_series_expiry() {
+        let quote = |contract_id: &str| -> Quote {
+            serde_json::from_value(serde_json::json!({ "contractId": contract_id })).unwrap()
+        };
+        let info = |contract_id: &str, end_trade_date: &str| -> ContractInfo {
+            serde_json::from_value(serde_json::json!({
+                "contractId": contract_id, "endTradeDate": end_trade_date,
+            }))
+            .unwrap()
+        };
+        let chain = OptionChain::from_quotes(
+            "m",
+            "20250801",
+            &[
+                quote("m2509-C-3000"),
+                quote("m2509-P-3000"),
+                quote("m2601-C-3100"),
+            ],
+        )
+        .with_contracts(&[
+            info("m2509", "20250912"),
+            info("m2509-C-2900", "2025-08-07"),
+            info("m2509-P-3000", "20250807"),
+            info("m2605-C-3000", "20260410"),
+        ]);
+
+        let series = chain.get("m2509").unwrap();
+        assert_eq!(series.end_trade_date.as_deref(), Some("20250807"));
+        assert_eq!(series.days_to_expiry("20250801"), Some(6));
+        assert_eq!(
+            series.strike(3000.0).map(|row| row.put.is_some()),
+            Some(true)
+        );
+        let unlisted = chain.get("m2601").unwrap();
+        assert_eq!(unlisted.end_trade_date, None);
+        assert_eq!(unlisted.days_to_expiry("20250801"), None);
+        assert_eq!(chain.series_ids().collect::<Vec<_>>(), ["m2509", "m2601"]);
+    }
+}
@@ -0,0 +1,359 @@
+//! Exchange position limits.
+//!
+//! The day's trade parameters publish each contract's position quotas for
+//! non-futures-company members and for clients, separately for futures and
+//! options, along with contract and variety limits. Limits apply to each
+//! side (long or short) separately. [`PositionLimitChecker`] totals a set of
+//! positions per contract and variety and compares them with these limits.
+
+use std::collections::BTreeMap;
+
+use crate::contract_id::ContractId;
+use crate::error::{Error, Result};
+use crate::margin::{Position, Side};
+use crate::models::{is_ratio_label, parse_optional_f64, TradeParam};
+
+/// Holder a position limit applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Holder {
+    /// Non-futures-company member (非期货公司会员).
+    Member,
+    /// Client (客户).
+    Client,
+}
+
+/// Limit a position is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitKind {
+    /// The holder's position quota for the contract.
+    Quota,
+    /// The contract limit.
+    Contract,
+    /// The variety limit, over all contracts of the variety.
+    Variety,
+}
+
+/// A position total compared with one limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitCheck {
+    /// Contract ID, or the variety code for [`LimitKind::Variety`].
+    pub subject: String,
+    /// Side the total is held on.
+    pub side: Side,
+    /// Limit checked.
+    pub kind: LimitKind,
+    /// Limit in lots.
+    pub limit: f64,
+    /// Position total in lots.
+    pub position: i64,
+}
+
+impl LimitCheck {
+    /// Lots that can still be added before the limit is exceeded; negative
+    /// when it is.
+    pub fn headroom(&self) -> f64 {
+        self.limit - self.position as f64
+    }
+
+    /// Whether the position exceeds the limit.
+    pub fn is_breached(&self) -> bool {
+        self.headroom() < 0.0
+    }
+}
+
+/// Position totals checked against the limits that apply to them.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PositionLimitReport {
+    /// Contract checks in contract and side order, then variety checks.
+    pub checks: Vec<LimitCheck>,
+}
+
+impl PositionLimitReport {
+    /// Whether no limit is exceeded.
+    pub fn is_within_limits(&self) -> bool {
+        !self.checks.iter().any(LimitCheck::is_breached)
+    }
+
+    /// Checks whose limit is exceeded.
+    pub fn breaches(&self) -> impl Iterator<Item = &LimitCheck> {
+        self.checks.iter().filter(|c| c.is_breached())
+    }
+
+    /// The binding limit: the check with the least headroom.
+    pub fn binding(&self) -> Option<&LimitCheck> {
+        self.checks
+            .iter()
+            .min_by(|a, b| a.headroom().total_cmp(&b.headroom()))
+    }
+}
+
+/// Checks positions against the exchange's position limits.
+///
+/// # Example
+///
+/// ```
+/// use dceapi_rs::{Holder, LimitKind, Position, PositionLimitChecker, Side, TradeParam};
+///
+/// let param: TradeParam = serde_json::from_value(serde_json::json!({
+///     "contractId": "m2509", "clientBuyPosiQuota": 5000, "varietyLimit": "8000",
+/// }))
+/// .unwrap();
+/// let checker = PositionLimitChecker::new().with_trade_params(vec![param]);
+///
+/// let held = Position::new("m2509", Side::Buy, 4500, 3000.0);
+/// let proposed = Position::new("m2509", Side::Buy, 800, 3000.0);
+/// let report = checker.check(Holder::Client, &[held, proposed]).unwrap();
+/// assert!(!report.is_within_limits());
+/// let binding = report.binding().unwrap();
+/// assert_eq!(binding.kind, LimitKind::Quota);
+/// assert_eq!(binding.headroom(), -300.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PositionLimitChecker {
+    trade_params: Vec<TradeParam>,
+}
+
+impl PositionLimitChecker {
+    /// Create a checker without limits.
+    pub fn new() -> Self {
+        PositionLimitChecker::default()
+    }
+
+    /// Set the trade parameters (from
+    /// [`TradeService::get_day_trade_param`](crate::TradeService::get_day_trade_param)).
+    /// Include the options trade parameters to check option positions
+    /// against their own quotas.
+    pub fn with_trade_params(mut self, params: Vec<TradeParam>) -> Self {
+        self.trade_params = params;
+        self
+    }
+
+    /// Check positions held by one holder against the limits.
+    ///
+    /// Pass the positions already held together with the proposed one.
+    /// Lots are totalled per contract and side; futures lots are also
+    /// totalled per variety and side for the variety limit. Option
+    /// positions are checked against the options quota of their own trade
+    /// parameters, or of their underlying contract's. Limits that are not
+    /// published, and quotas of contracts whose limit style is a share of
+    /// open interest, are not checked.
+    ///
+    /// # Arguments
+    /// * `holder` - Member or client
+    /// * `positions` - Positions to total
+    pub fn check(&self, holder: Holder, positions: &[Position]) -> Result<PositionLimitReport> {
+        let mut contracts: BTreeMap<(String, Side), i64> = BTreeMap::new();
+        for position in positions {
+            if position.lots < 0 {
+                return Err(Error::validation("lots", "lots must not be negative"));
+            }
+            let contract_id = position.contract_id.trim().to_string();
+            *contracts.entry((contract_id, position.side)).or_default() += position.lots;
+        }
+
+        let mut checks = Vec::new();
+        let mut varieties: BTreeMap<(String, Side), (i64, Option<f64>)> = BTreeMap::new();
+        for ((contract_id, side), lots) in contracts {
+            let contract = ContractId::parse(&contract_id)?;
+            let param = self.param(&contract_id, &contract).ok_or_else(|| {
+                Error::missing_data(format!("no position limits for {}", contract_id))
+            })?;
+
+            let quota = match (holder, contract.is_option()) {
+                (Holder::Member, false) => param.self_tot_buy_posi_quota,
+                (Holder::Member, true) => param.self_tot_buy_posi_quota_ser_limit,
+                (Holder::Client, false) => param.client_buy_posi_quota,
+                (Holder::Client, true) => param.client_buy_posi_quota_ser_limit,
+            };
+            let limits = [
+                (
+                    LimitKind::Quota,
+                    quota.filter(|_| !is_ratio_label(&param.style)),
+                ),
+                (
+                    LimitKind::Contract,
+                    parse_optional_f64(&param.contract_limit),
+                ),
+            ];
+            for (kind, limit) in limits {
+                if let Some(limit) = limit {
+                    checks.push(LimitCheck {
+                        subject: contract_id.clone(),
+                        side,
+                        kind,
+                        limit,
+                        position: lots,
+                    });
+                }
+            }
+
+            if !contract.is_option() {
+                let variety = varieties.entry((contract.variety, side)).or_default();
+                variety.0 += lots;
+                variety.1 = variety.1.or(parse_optional_f64(&param.variety_limit));
+            }
+        }
+        for ((variety, side), (lots, limit)) in varieties {
+            if let Some(limit) = limit {
+                checks.push(LimitCheck {
+                    subject: variety,
+                    side,
+                    kind: LimitKind::Variety,
+                    limit,
+                    position: lots,
+                });
+            }
+        }
+        Ok(PositionLimitReport { checks })
+    }
+
+    /// Trade parameters of a contract, or of an option's underlying.
+    fn param(&self, contract_id: &str, contract: &ContractId) -> Option<&TradeParam> {
+        let find = |id: &str| {
+            self.trade_params
+                .iter()
+                .find(|p| p.contract_id.trim().eq_ignore_ascii_case(id))
+        };
+        find(contract_id).or_else(|| {
+            contract
+                .is_option()
+                .then(|| find(&contract.underlying().to_string()))
+                .flatten()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(value: serde_json::Value) -> TradeParam {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn checker() -> PositionLimitChecker {
+        PositionLimitChecker::new().with_trade_params(vec![
+            param(serde_json::json!({
+                "contractId": "m2509", "style": "绝对值",
+                "selfTotBuyPosiQuota": 20000, "clientBuyPosiQuota": 5000,
+                "selfTotBuyPosiQuotaSerLimit": 2000, "clientBuyPosiQuotaSerLimit": 1000,
+                "contractLimit": "-", "varietyLimit": "8000",
+            })),
+            param(serde_json::json!({
+                "contractId": "m2601", "style": "比例",
+                "clientBuyPosiQuota": 0.1, "contractLimit": "6000", "varietyLimit": "8000",
+            })),
+            param(serde_json::json!({
+                "contractId": "m2509-C-3000", "clientBuyPosiQuotaSerLimit": 300,
+            })),
+        ])
+    }
+
+    fn check(kind: LimitKind, report: &PositionLimitReport, subject: &str) -> Vec<(Side, i64)> {
+        report
+            .checks
+            .iter()
+            .filter(|c| c.kind == kind && c.subject == subject)
+            .map(|c| (c.side, c.position))
+            .collect()
+    }
+
+    #[test]
+    fn totals_lots_per_contract_side_and_variety() {
+        let report = checker()
+            .check(
+                Holder::Client,
+                &[
+                    Position::new("m2509", Side::Buy, 3000, 3000.0),
+                    Position::new(" m2509 ", Side::Buy, 1000, 3000.0),
+                    Position::new("m2509", Side::Sell, 500, 3000.0),
+                    Position::new("m2601", Side::Buy, 4500, 3100.0),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            check(LimitKind::Quota, &report, "m2509"),
+            vec![(Side::Buy, 4000), (Side::Sell, 500)]
+        );
+        // The m2601 quota is a share of open interest and is not checked.
+        assert!(check(LimitKind::Quota, &report, "m2601").is_empty());
+        assert_eq!(
+            check(LimitKind::Contract, &report, "m2601"),
+            vec![(Side::Buy, 4500)]
+        );
+        assert_eq!(
+            check(LimitKind::Variety, &report, "m"),
+            vec![(Side::Buy, 8500), (Side::Sell, 500)]
+        );
+
+        assert!(!report.is_within_limits());
+        let breaches: Vec<_> = report.breaches().map(|c| c.kind).collect();
+        assert_eq!(breaches, vec![LimitKind::Variety]);
+        assert_eq!(report.binding().unwrap().headroom(), -500.0);
+    }
+
+    #[test]
+    fn holders_have_their_own_quotas() {
+        let positions = [Position::new("m2509", Side::Sell, 6000, 3000.0)];
+        let quota = |holder| {
+            let report = checker().check(holder, &positions).unwrap();
+            let quota = report.checks.iter().find(|c| c.kind == LimitKind::Quota);
+            quota.map(LimitCheck::is_breached)
+        };
+        assert_eq!(quota(Holder::Client), Some(true));
+        assert_eq!(quota(Holder::Member), Some(false));
+    }
+
+    #[test]
+    fn options_use_their_own_or_their_underlyings_quota() {
+        let report = checker()
+            .check(
+                Holder::Client,
+                &[
+                    Position::new("m2509-C-3000", Side::Buy, 200, 120.0),
+                    Position::new("m2509-P-2900", Side::Sell, 1200, 80.0),
+                ],
+            )
+            .unwrap();
+        let limits: Vec<_> = report
+            .checks
+            .iter()
+            .map(|c| (c.subject.as_str(), c.kind, c.limit))
+            .collect();
+        assert_eq!(
+            limits,
+            vec![
+                ("m2509-C-3000", LimitKind::Quota, 300.0),
+                ("m2509-P-2900", LimitKind::Quota, 1000.0),
+            ]
+        );
+        assert_eq!(report.breaches().count(), 1);
+    }
+
+    #[test]
+    fn invalid_positions_are_rejected() {
+        let err = checker()
+            .check(
+                Holder::Client,
+                &[Position::new("m2509", Side::Buy, -1, 3000.0)],
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation { .. }), "{err}");
+
+        let err = checker()
+            .check(
+                Holder::Client,
+                &[Position::new("y2509", Side::Buy, 1, 8000.0)],
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::MissingData { .. }), "{err}");
+    }
+
+    #[test]
+    fn empty_positions_are_within_limits() {
+        let report = checker().check(Holder::Member, &[]).unwrap();
+        assert!(report.is_within_limits());
+        assert_eq!(report.binding(), None);
+    }
+}
@@ -0,0 +1,191 @@
+//! Grouping and ordering of quote lists.
+//!
+//! The API returns quotes in whatever order the exchange lists them.
+//! [`QuoteListExt`] groups and sorts them by the parsed [`ContractId`] so
+//! reports come out in a deterministic order.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BTreeMap;
+
+use crate::continuous::RollBy;
+use crate::contract_id::ContractId;
+use crate::models::Quote;
+
+/// Grouping and sorting helpers for quote lists.
+///
+/// ```
+/// use dceapi_rs::{Quote, QuoteListExt};
+///
+/// let quotes: Vec<Quote> = serde_json::from_str(
+///     r#"[{"contractId": "m2509"}, {"contractId": "a2505"}, {"contractId": "m2505"}]"#,
+/// )
+/// .unwrap();
+/// let ids: Vec<&str> = quotes
+///     .sorted_by_expiry()
+///     .iter()
+///     .map(|q| q.contract_id.as_str())
+///     .collect();
+/// assert_eq!(ids, ["a2505", "m2505", "m2509"]);
+/// assert_eq!(quotes.group_by_variety()["m"].len(), 2);
+/// ```
+pub trait QuoteListExt {
+    /// Group contract rows by variety code ("m"), taken from the contract ID.
+    ///
+    /// Rows without a parseable contract ID (subtotals) are skipped. Each
+    /// group is sorted by expiry.
+    fn group_by_variety(&self) -> BTreeMap<String, Vec<&Quote>>;
+
+    /// Group rows by series: the underlying futures contract for options
+    /// ("m2505" for "m2505-C-3000"), or the contract itself for futures.
+    ///
+    /// Uses `series_id` for series-level option rows. Rows without a series
+    /// are skipped. Each group is sorted by expiry.
+    fn group_by_series(&self) -> BTreeMap<String, Vec<&Quote>>;
+
+    /// Rows sorted by variety, delivery month, strike and option type.
+    ///
+    /// Rows without a parseable contract ID keep their relative order at
+    /// the end.
+    fn sorted_by_expiry(&self) -> Vec<&Quote>;
+
+    /// Sort the rows in place, in the same order as [`sorted_by_expiry`](Self::sorted_by_expiry).
+    fn sort_by_expiry(&mut self);
+
+    /// The main (主力) contract: the futures row ranked first by
+    /// `roll_by`, as [`ContinuousSeries`](crate::ContinuousSeries) picks
+    /// its dominant contract.
+    ///
+    /// Remaining ties go to the nearer expiry. Option and subtotal rows,
+    /// and rows with neither volume nor open interest, are ignored, so
+    /// this is `None` if no contract traded.
+    fn main_contract(&self, roll_by: RollBy) -> Option<&Quote>;
+}
+
+impl QuoteListExt for [Quote] {
+    fn group_by_variety(&self) -> BTreeMap<String, Vec<&Quote>> {
+        let mut groups: BTreeMap<String, Vec<&Quote>> = BTreeMap::new();
+        for quote in self.sorted_by_expiry() {
+            if let Some(id) = quote.parsed_contract_id() {
+                groups.entry(id.variety).or_default().push(quote);
+            }
+        }
+        groups
+    }
+
+    fn group_by_series(&self) -> BTreeMap<String, Vec<&Quote>> {
+        let mut groups: BTreeMap<String, Vec<&Quote>> = BTreeMap::new();
+        for quote in self.sorted_by_expiry() {
+            let series = match quote.parsed_contract_id() {
+                Some(id) => id.underlying().to_string(),
+                None if !quote.series_id.is_empty() => quote.series_id.clone(),
+                None => continue,
+            };
+            groups.entry(series).or_default().push(quote);
+        }
+        groups
+    }
+
+    fn sorted_by_expiry(&self) -> Vec<&Quote> {
+        let mut keyed: Vec<(Option<ContractId>, &Quote)> = self
+            .iter()
+            .map(|quote| (quote.parsed_contract_id(), quote))
+            .collect();
+        keyed.sort_by(|a, b| expiry_order(&a.0, &b.0));
+        keyed.into_iter().map(|(_, quote)| quote).collect()
+    }
+
+    fn sort_by_expiry(&mut self) {
+        self.sort_by_cached_key(|quote| ExpiryKey(quote.parsed_contract_id()));
+    }
+
+    fn main_contract(&self, roll_by: RollBy) -> Option<&Quote> {
+        self.iter()
+            .filter(|quote| quote.open_interest != 0 || quote.volume != 0)
+            .filter_map(|quote| {
+                let id = quote.parsed_contract_id().filter(|id| !id.is_option())?;
+                Some((id, quote))
+            })
+            .max_by_key(|(id, quote)| {
+                (
+                    roll_by.key(quote.open_interest, quote.volume),
+                    Reverse(id.clone()),
+                )
+            })
+            .map(|(_, quote)| quote)
+    }
+}
+
+/// Order parsed IDs ascending, with unparsed rows last.
+fn expiry_order(a: &Option<ContractId>, b: &Option<ContractId>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Sort key applying [`expiry_order`].
+#[derive(PartialEq, Eq)]
+struct ExpiryKey(Option<ContractId>);
+
+impl PartialOrd for ExpiryKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExpiryKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        expiry_order(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quotes(rows: &[(&str, i64, i64)]) -> Vec<Quote> {
+        rows.iter()
+            .map(|(id, open_interest, volume)| {
+                serde_json::from_value(serde_json::json!({
+                    "contractId": id, "openInterest": open_interest, "volumn": volume,
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn main_id(quotes: &[Quote], roll_by: RollBy) -> Option<&str> {
+        quotes
+            .main_contract(roll_by)
+            .map(|q| q.contract_id.as_str())
+    }
+
+    #[test]
+    fn main_contract_ranks_like_roll_by() {
+        let day = quotes(&[
+            ("m2505", 100, 900),
+            ("m2509", 300, 200),
+            ("m2509-C-3000", 900, 900),
+        ]);
+        assert_eq!(main_id(&day, RollBy::OpenInterest), Some("m2509"));
+        assert_eq!(main_id(&day, RollBy::Volume), Some("m2505"));
+
+        let tied = quotes(&[
+            ("m2509", 300, 200),
+            ("m2601", 300, 200),
+            ("m2505", 300, 100),
+        ]);
+        assert_eq!(main_id(&tied, RollBy::OpenInterest), Some("m2509"));
+    }
+
+    #[test]
+    fn main_contract_is_none_when_nothing_traded() {
+        assert_eq!(
+            main_id(&quotes(&[("m2509", 0, 0)]), RollBy::default()),
+            None
+        );
+        assert_eq!(main_id(&[], RollBy::default()), None);
+    }
+}
@@ -0,0 +1,343 @@
+//! Daily data collection jobs.
+//!
+//! A [`Scheduler`] runs jobs at a fixed exchange time (UTC+8) on every
+//! trading day of a [`Calendar`], skipping weekends and the holidays the
+//! calendar knows about. Each run requests one result per key (such as a
+//! variety), paced and retried by [`DownloadOptions`], and is delivered
+//! through a [`Watch`].
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use crate::calendar::Calendar;
+use crate::days::{exchange_seconds, format_day};
+use crate::division_series::TimeOfDay;
+use crate::download::{fetch_each, BatchResult, DownloadOptions};
+use crate::error::Result;
+use crate::shutdown::Shutdown;
+use crate::watch::Watch;
+
+/// Longest single sleep, so clock changes and suspends are noticed.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Days searched for the next trading day before giving up.
+const MAX_DAYS_AHEAD: i64 = 60;
+
+/// Request function of a job: key and trade date (YYYYMMDD) to result.
+type JobFn<T> =
+    Arc<dyn Fn(String, String) -> Pin<Box<dyn Future<Output = Result<T>> + Send>> + Send + Sync>;
+
+/// Results of one run of a job.
+#[derive(Debug)]
+pub struct JobRun<T> {
+    /// Job name.
+    pub job: String,
+    /// Trade date the job ran for (YYYYMMDD).
+    pub trade_date: String,
+    /// Result per key; keys that failed after retries are in `errors`.
+    pub results: BatchResult<T>,
+}
+
+/// A job registered with a scheduler.
+struct Job<T> {
+    name: String,
+    at: TimeOfDay,
+    keys: Vec<String>,
+    fetch: JobFn<T>,
+}
+
+impl<T> Clone for Job<T> {
+    fn clone(&self) -> Self {
+        Job {
+            name: self.name.clone(),
+            at: self.at,
+            keys: self.keys.clone(),
+            fetch: Arc::clone(&self.fetch),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Job<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Job")
+            .field("name", &self.name)
+            .field("at", &self.at)
+            .field("keys", &self.keys)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs data collection jobs every trading day.
+///
+/// # Example
+///
+/// ```no_run
+/// use dceapi_rs::{
+///     Calendar, Client, DownloadOptions, Lang, Scheduler, SettleParamRequest, TimeOfDay, TradeType,
+/// };
+///
+/// # async fn run(client: Client) -> dceapi_rs::Result<()> {
+/// let settle = client.settle.clone();
+/// let mut runs = Scheduler::new(Calendar::load("calendar.json")?)
+///     .with_download_options(DownloadOptions::new().with_qps(2.0))
+///     .with_shutdown(client.shutdown_token())
+///     .with_daily_job(
+///         "settle params",
+///         TimeOfDay::parse("16:30")?,
+///         ["m", "c", "i"],
+///         move |variety, trade_date| {
+///             let settle = settle.clone();
+///             async move {
+///                 let req = SettleParamRequest {
+///                     variety_id: variety,
+///                     trade_date,
+///                     trade_type: TradeType::Futures,
+///                     lang: Lang::Zh,
+///                 };
+///                 settle.get_settle_param(&req, None).await
+///             }
+///         },
+///     )
+///     .start();
+/// while let Some(run) = runs.next().await {
+///     let run = run?;
+///     println!("{} {}: {} ok, {} failed", run.job, run.trade_date,
+///         run.results.values.len(), run.results.errors.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Scheduler<T> {
+    calendar: Calendar,
+    jobs: Vec<Job<T>>,
+    download: DownloadOptions,
+    shutdown: Shutdown,
+}
+
+impl<T: Send + 'static> Scheduler<T> {
+    /// Create a scheduler with no jobs that runs on the trading days of
+    /// `calendar`. Add known future holidays to the calendar with
+    /// [`Calendar::add_holiday`]; other weekdays are assumed to trade.
+    pub fn new(calendar: Calendar) -> Self {
+        Scheduler {
+            calendar,
+            jobs: Vec::new(),
+            download: DownloadOptions::new(),
+            shutdown: Shutdown::new(),
+        }
+    }
+
+    /// Set the concurrency, rate and retry settings of each run.
+    pub fn with_download_options(mut self, download: DownloadOptions) -> Self {
+        self.download = download;
+        self
+    }
+
+    /// Stop the scheduler when `shutdown` is triggered, for example a
+    /// client's [`shutdown_token`](crate::Client::shutdown_token).
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Add a job that runs at `at` (exchange time) on every trading day,
+    /// calling `fetch` with each key and the trade date (YYYYMMDD).
+    pub fn with_daily_job<F, Fut>(
+        mut self,
+        name: impl Into<String>,
+        at: TimeOfDay,
+        keys: impl IntoIterator<Item = impl Into<String>>,
+        fetch: F,
+    ) -> Self
+    where
+        F: Fn(String, String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        self.jobs.push(Job {
+            name: name.into(),
+            at,
+            keys: keys.into_iter().map(Into::into).collect(),
+            fetch: Arc::new(move |key, date| Box::pin(fetch(key, date))),
+        });
+        self
+    }
+
+    /// Start running jobs in a background task and deliver each run.
+    ///
+    /// Jobs due at the same time run one after another. A job whose time
+    /// has already passed today first runs on the next trading day. Must be
+    /// called within a Tokio runtime.
+    pub fn start(self) -> Watch<JobRun<T>> {
+        let Scheduler {
+            calendar,
+            jobs,
+            download,
+            shutdown,
+        } = self;
+        Watch::spawn("scheduler", &shutdown, move |tx| {
+            let calendar = calendar.clone();
+            let jobs = jobs.clone();
+            let download = download.clone();
+            async move {
+                let started = exchange_seconds();
+                // Time (exchange seconds) each job last ran for, or the
+                // start time so jobs already past today wait a day.
+                let mut last_run = vec![started; jobs.len()];
+                loop {
+                    let now = exchange_seconds();
+                    let next: Vec<Option<i64>> = jobs
+                        .iter()
+                        .zip(&last_run)
+                        .map(|(job, &after)| next_run(&calendar, job.at, after))
+                        .collect();
+                    let Some(due) = next.iter().flatten().min().copied() else {
+                        return;
+                    };
+                    if due > now {
+                        let wait = Duration::from_secs((due - now) as u64);
+                        sleep(wait.min(MAX_SLEEP)).await;
+                        continue;
+                    }
+                    for (i, job) in jobs.iter().enumerate() {
+                        if next[i] != Some(due) {
+                            continue;
+                        }
+                        last_run[i] = due;
+                        let trade_date = format_day(due.div_euclid(86_400));
+                        let fetch = Arc::clone(&job.fetch);
+                        let date = trade_date.clone();
+                        let results = fetch_each(job.keys.clone(), &download, move |key| {
+                            fetch(key, date.clone())
+                        })
+                        .await;
+                        let run = JobRun {
+                            job: job.name.clone(),
+                            trade_date,
+                            results,
+                        };
+                        if !tx.send(Ok(run)).await {
+                            return;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// First time (exchange seconds) after `after` at which a job running at
+/// `at` on trading days is due, or `None` if no trading day is near.
+fn next_run(calendar: &Calendar, at: TimeOfDay, after: i64) -> Option<i64> {
+    let first = after.div_euclid(86_400);
+    (first..first + MAX_DAYS_AHEAD)
+        .filter(|&day| calendar.is_trading(day))
+        .map(|day| day * 86_400 + i64::from(at.seconds()))
+        .find(|&time| time > after)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::days::day_number;
+    use crate::supervisor::TaskState;
+
+    fn at(date: &str, time: &str) -> i64 {
+        day_number(date).unwrap() * 86_400 + i64::from(TimeOfDay::parse(time).unwrap().seconds())
+    }
+
+    #[test]
+    fn jobs_run_on_the_next_trading_day() {
+        let mut calendar = Calendar::new();
+        for date in [
+            "20251001", "20251002", "20251003", "20251006", "20251007", "20251008",
+        ] {
+            calendar.add_holiday(date).unwrap();
+        }
+        let close = TimeOfDay::parse("16:30").unwrap();
+
+        // Later the same trading day.
+        assert_eq!(
+            next_run(&calendar, close, at("20250930", "09:00")),
+            Some(at("20250930", "16:30"))
+        );
+        // Once today's time has passed, across the holiday block.
+        assert_eq!(
+            next_run(&calendar, close, at("20250930", "16:30")),
+            Some(at("20251009", "16:30"))
+        );
+        // Over a weekend.
+        assert_eq!(
+            next_run(&calendar, close, at("20251010", "17:00")),
+            Some(at("20251013", "16:30"))
+        );
+    }
+
+    #[test]
+    fn no_trading_day_in_sight_means_no_run() {
+        let mut calendar = Calendar::new();
+        for date in calendar.unconfirmed_days("20260101", "20260331").unwrap() {
+            calendar.add_holiday(&date).unwrap();
+        }
+        let open = TimeOfDay::parse("09:00").unwrap();
+        assert_eq!(next_run(&calendar, open, at("20260101", "00:00")), None);
+    }
+
+    #[tokio::test]
+    async fn schedulers_without_jobs_finish() {
+        let mut runs = Scheduler::<()>::new(Calendar::new()).start();
+        assert!(timeout(Duration::from_secs(5), runs.next())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn shutdown_stops_a_waiting_scheduler() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&calls);
+        let shutdown = Shutdown::new();
+        let mut runs = Scheduler::new(Calendar::new())
+            .with_shutdown(shutdown.clone())
+            .with_daily_job(
+                "never due",
+                // Already past today, so the first run is a day away.
+                TimeOfDay::parse("00:00").unwrap(),
+                ["m"],
+                move |_, _| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    async { Ok(()) }
+                },
+            )
+            .start();
+        assert_eq!(runs.health().state, TaskState::Running);
+        assert_eq!(shutdown.running_tasks(), 1);
+
+        timeout(Duration::from_secs(5), shutdown.shutdown())
+            .await
+            .unwrap();
+        assert!(runs.next().await.is_none());
+        assert_eq!(runs.health().state, TaskState::Stopped);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        // A new scheduler on a fresh token runs again.
+        let runs = Scheduler::<()>::new(Calendar::new())
+            .with_daily_job(
+                "again",
+                TimeOfDay::parse("00:00").unwrap(),
+                ["m"],
+                |_, _| async { Ok(()) },
+            )
+            .start();
+        assert_eq!(runs.health().state, TaskState::Running);
+    }
+}
@@ -3,6 +3,7 @@
 use crate::error::Result;
 use crate::http::{BaseClient, RequestOptions};
 use crate::models::{TradeDate, Variety, VarietyMonthYearStat, VarietyMonthYearStatRequest};
+use crate::validation::Validate;
 
 /// API endpoint for current trade date.
 const PATH_GET_CURR_TRADE_DATE: &str = "/dceapi/forward/publicweb/maxTradeDate";
@@ -42,6 +43,22 @@ impl CommonService {
         self.client.do_get(PATH_GET_VARIETY_LIST, opts).await
     }
 
+    /// Get the varieties that trade in the night session.
+    ///
+    /// Fetches the variety list and keeps the varieties flagged by
+    /// [`Variety::has_night_session`]. Varieties missing from the built-in
+    /// table are left out.
+    ///
+    /// # Arguments
+    /// * `opts` - Optional request options
+    pub async fn get_night_varieties(&self, opts: Option<RequestOptions>) -> Result<Vec<Variety>> {
+        let varieties = self.get_variety_list(opts).await?;
+        Ok(varieties
+            .into_iter()
+            .filter(|v| v.has_night_session() == Some(true))
+            .collect())
+    }
+
     /// Get variety monthly/yearly statistics.
     ///
     /// # Arguments
@@ -52,6 +69,8 @@ impl CommonService {
         req: &VarietyMonthYearStatRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<VarietyMonthYearStat>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_VARIETY_MONTH_YEAR_STAT, req, opts)
             .await
@@ -1,14 +1,30 @@
 //! Delivery service for delivery data APIs.
 
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use crate::analytics::{diff, premium_groups, PremiumGroup, SnapshotDiff};
+use crate::calendar::Calendar;
+use crate::catalog::{normalize_date, ContractCatalog};
+use crate::contract_id::ContractId;
+use crate::days::{format_day, today};
+use crate::delivery_month::DeliveryMonth;
+use crate::download::{date_windows, fetch_windows, DownloadOptions};
 use crate::error::{Error, Result};
 use crate::http::{BaseClient, RequestOptions};
 use crate::models::{
-    BondedDelivery, BondedDeliveryRequest, DeliveryCost, DeliveryData, DeliveryDataRequest,
-    DeliveryMatch, DeliveryMatchRequest, FactorySpotAgio, FactorySpotAgioRequest,
-    PlywoodDeliveryCommodity, PlywoodDeliveryCommodityRequest, RollDeliverySellerIntention,
-    RollDeliverySellerIntentionRequest, TcCongregateDelivery, TcCongregateDeliveryRequest,
-    TdBondedDelivery, TdBondedDeliveryRequest, WarehousePremiumResponse,
+    BondedDelivery, BondedDeliveryRequest, ContractInfoRequest, DeliveryCost, DeliveryData,
+    DeliveryDataRequest, DeliveryMatch, DeliveryMatchRequest, FactorySpotAgio,
+    FactorySpotAgioRequest, Lang, PlywoodDeliveryCommodity, PlywoodDeliveryCommodityRequest,
+    RollDeliverySellerIntention, RollDeliverySellerIntentionRequest, TcCongregateDelivery,
+    TcCongregateDeliveryRequest, TdBondedDelivery, TdBondedDeliveryRequest, TradeType, VarietyType,
+    WarehousePremiumResponse,
 };
+use crate::services::TradeService;
+use crate::session::PollSchedule;
+use crate::validation::Validate;
+use crate::watch::{poll_changes, Watch};
 
 /// API endpoint for delivery data.
 const PATH_GET_DELIVERY_DATA: &str = "/dceapi/forward/publicweb/deliverystat/delivery";
@@ -48,12 +64,23 @@ const PATH_GET_PLYWOOD_DELIVERY_COMMODITY: &str =
 #[derive(Debug, Clone)]
 pub struct DeliveryService {
     client: BaseClient,
+    catalog: Option<ContractCatalog>,
 }
 
 impl DeliveryService {
     /// Create a new delivery service.
     pub fn new(client: BaseClient) -> Self {
-        DeliveryService { client }
+        DeliveryService {
+            client,
+            catalog: None,
+        }
+    }
+
+    /// Look up contract information in a shared contract catalog, such as
+    /// the [client's](crate::Client::catalog).
+    pub fn with_catalog(mut self, catalog: ContractCatalog) -> Self {
+        self.catalog = Some(catalog);
+        self
     }
 
     /// Get delivery data.
@@ -66,6 +93,8 @@ impl DeliveryService {
         req: &DeliveryDataRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<DeliveryData>> {
+        req.validate()?;
+
         self.client.do_post(PATH_GET_DELIVERY_DATA, req, opts).await
     }
 
@@ -79,6 +108,8 @@ impl DeliveryService {
         req: &DeliveryMatchRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<DeliveryMatch>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_DELIVERY_MATCH, req, opts)
             .await
@@ -88,12 +119,12 @@ impl DeliveryService {
     ///
     /// # Arguments
     /// * `variety_id` - Variety ID ("all" for all varieties)
-    /// * `variety_type` - Variety type ("0" = physical delivery, "1" = average price delivery)
+    /// * `variety_type` - Delivery mode
     /// * `opts` - Optional request options
     pub async fn get_delivery_cost(
         &self,
         variety_id: &str,
-        variety_type: &str,
+        variety_type: VarietyType,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<DeliveryCost>> {
         if variety_id.is_empty() {
@@ -104,7 +135,7 @@ impl DeliveryService {
         #[serde(rename_all = "camelCase")]
         struct Request<'a> {
             variety_id: &'a str,
-            variety_type: &'a str,
+            variety_type: VarietyType,
             lang: &'a str,
         }
 
@@ -150,6 +181,26 @@ impl DeliveryService {
             .await
     }
 
+    /// Get warehouse premiums for a variety grouped by warehouse group,
+    /// cheapest group first (see
+    /// [`premium_groups`](crate::analytics::premium_groups)).
+    ///
+    /// # Arguments
+    /// * `variety_id` - Variety ID
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    /// * `opts` - Optional request options
+    pub async fn compare_warehouse_premiums(
+        &self,
+        variety_id: &str,
+        trade_date: &str,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<PremiumGroup>> {
+        let premiums = self
+            .get_warehouse_premium(variety_id, trade_date, opts)
+            .await?;
+        Ok(premium_groups(&premiums.entity_list))
+    }
+
     /// Get TC (two-way delivery) congregate delivery statistics.
     ///
     /// Returns aggregated delivery information for varieties supporting two-way delivery.
@@ -162,6 +213,8 @@ impl DeliveryService {
         req: &TcCongregateDeliveryRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<TcCongregateDelivery>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_TC_CONGREGATE_DELIVERY, req, opts)
             .await
@@ -179,11 +232,96 @@ impl DeliveryService {
         req: &RollDeliverySellerIntentionRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<RollDeliverySellerIntention>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_ROLL_DELIVERY_SELLER_INTENTION, req, opts)
             .await
     }
 
+    /// Watch roll delivery seller intentions for changes.
+    ///
+    /// Polls the intentions of the current exchange date every `interval`
+    /// in a background task, keeps the latest list keyed by contract,
+    /// warehouse and delivery type, and yields the intentions that were
+    /// added, withdrawn or changed since the previous poll (see
+    /// [`diff`](crate::analytics::diff)). The first poll only records the
+    /// current intentions.
+    ///
+    /// Intentions are only published while a contract of the variety is in
+    /// its delivery month, up to the contract's last trade date, so polls
+    /// outside that window, and on days `calendar` does not count as
+    /// trading days, are skipped. The window is checked against the
+    /// variety's contract information once per day; for "all" every
+    /// trading day is polled. Polling once a day is usually enough. Must be
+    /// called within a Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety code ("all" for all varieties)
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `calendar` - Trading calendar
+    /// * `opts` - Optional request options
+    pub fn watch_roll_delivery_intentions(
+        &self,
+        variety: &str,
+        interval: impl Into<PollSchedule>,
+        calendar: Calendar,
+        opts: Option<RequestOptions>,
+    ) -> Watch<SnapshotDiff<(String, String, String), RollDeliverySellerIntention>> {
+        let service = self.clone();
+        let variety = variety.to_string();
+        // Delivery window check of the current date: (date, open).
+        let window: Arc<Mutex<Option<(String, bool)>>> = Arc::default();
+        let mut previous: Option<Vec<RollDeliverySellerIntention>> = None;
+        poll_changes(
+            "roll delivery intentions",
+            self.client.shutdown_token(),
+            interval.into(),
+            move || {
+                let service = service.clone();
+                let variety = variety.clone();
+                let calendar = calendar.clone();
+                let window = Arc::clone(&window);
+                let opts = opts.clone();
+                async move {
+                    let date = format_day(today());
+                    if !calendar.is_trading_day(&date)? {
+                        return Ok(None);
+                    }
+                    let checked = window
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .clone()
+                        .filter(|(day, _)| *day == date);
+                    let open = match checked {
+                        Some((_, open)) => open,
+                        None => {
+                            let open =
+                                in_delivery_window(&service, &variety, &date, opts.clone()).await?;
+                            *window.lock().unwrap_or_else(|e| e.into_inner()) =
+                                Some((date.clone(), open));
+                            open
+                        }
+                    };
+                    if !open {
+                        return Ok(None);
+                    }
+                    let req = RollDeliverySellerIntentionRequest { variety, date };
+                    service
+                        .get_roll_delivery_seller_intention(&req, opts)
+                        .await
+                        .map(Some)
+                }
+            },
+            move |intentions: Option<Vec<RollDeliverySellerIntention>>| {
+                let intentions = intentions?;
+                let changes = previous.as_deref().map(|old| diff(old, &intentions));
+                previous = Some(intentions);
+                changes.filter(|changes| !changes.is_empty())
+            },
+        )
+    }
+
     /// Get bonded delivery data.
     ///
     /// Returns delivery statistics for bonded warehouse delivery mode.
@@ -196,6 +334,8 @@ impl DeliveryService {
         req: &BondedDeliveryRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<BondedDelivery>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_BONDED_DELIVERY, req, opts)
             .await
@@ -213,11 +353,79 @@ impl DeliveryService {
         req: &TdBondedDeliveryRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<TdBondedDelivery>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_TD_BONDED_DELIVERY, req, opts)
             .await
     }
 
+    /// Get bonded delivery data for a long date range.
+    ///
+    /// Splits the range into windows of at most `window_days` days and
+    /// requests them one after another, paced and retried according to
+    /// `download`. Rows returned by more than one window are kept once.
+    /// Fails with the first error that persists after retries.
+    ///
+    /// # Arguments
+    /// * `start_date` - Start date (YYYYMMDD format)
+    /// * `end_date` - End date (YYYYMMDD format)
+    /// * `window_days` - Maximum number of days per request
+    /// * `download` - Rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn get_bonded_delivery_range(
+        &self,
+        start_date: &str,
+        end_date: &str,
+        window_days: u32,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<BondedDelivery>> {
+        let windows = date_windows(start_date, end_date, window_days)?;
+        let rows = fetch_windows(windows, download, |start_date, end_date| {
+            let req = BondedDeliveryRequest {
+                start_date,
+                end_date,
+            };
+            let opts = opts.clone();
+            async move { self.get_bonded_delivery(&req, opts).await }
+        })
+        .await?;
+        Ok(dedup_rows(rows))
+    }
+
+    /// Get TD bonded delivery settlement prices for a long date range.
+    ///
+    /// Windows, pacing and deduplication work as in
+    /// [`get_bonded_delivery_range`](Self::get_bonded_delivery_range).
+    ///
+    /// # Arguments
+    /// * `start_date` - Start date (YYYYMMDD format)
+    /// * `end_date` - End date (YYYYMMDD format)
+    /// * `window_days` - Maximum number of days per request
+    /// * `download` - Rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn get_td_bonded_delivery_range(
+        &self,
+        start_date: &str,
+        end_date: &str,
+        window_days: u32,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<TdBondedDelivery>> {
+        let windows = date_windows(start_date, end_date, window_days)?;
+        let rows = fetch_windows(windows, download, |start_date, end_date| {
+            let req = TdBondedDeliveryRequest {
+                start_date,
+                end_date,
+            };
+            let opts = opts.clone();
+            async move { self.get_td_bonded_delivery(&req, opts).await }
+        })
+        .await?;
+        Ok(dedup_rows(rows))
+    }
+
     /// Get factory spot premium (basis spread).
     ///
     /// Returns the difference between factory spot price and futures price.
@@ -230,6 +438,8 @@ impl DeliveryService {
         req: &FactorySpotAgioRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<FactorySpotAgio>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_FACTORY_SPOT_AGIO, req, opts)
             .await
@@ -247,8 +457,56 @@ impl DeliveryService {
         req: &PlywoodDeliveryCommodityRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<PlywoodDeliveryCommodity>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_PLYWOOD_DELIVERY_COMMODITY, req, opts)
             .await
     }
 }
+
+/// Remove repeated rows, keeping the first occurrence.
+fn dedup_rows<T: Eq + Hash + Clone>(rows: Vec<T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    rows.into_iter()
+        .filter(|row| seen.insert(row.clone()))
+        .collect()
+}
+
+/// Whether a variety has a futures contract in its delivery month on `date`
+/// (YYYYMMDD) that has not passed its last trade date. Always `true` for
+/// "all". Contracts are looked up in the service's catalog when it has one.
+async fn in_delivery_window(
+    service: &DeliveryService,
+    variety: &str,
+    date: &str,
+    opts: Option<RequestOptions>,
+) -> Result<bool> {
+    if variety.eq_ignore_ascii_case("all") {
+        return Ok(true);
+    }
+    let month = DeliveryMonth::parse(date.get(..6).unwrap_or(date))?;
+    let contracts = match &service.catalog {
+        Some(catalog) => catalog.contracts(variety, TradeType::Futures).await?,
+        None => {
+            let req = ContractInfoRequest {
+                variety_id: variety.to_string(),
+                trade_type: TradeType::Futures,
+                lang: Lang::Zh,
+            };
+            let trade = TradeService::new(service.client.clone());
+            Arc::new(trade.get_contract_info(&req, opts).await?)
+        }
+    };
+    Ok(contracts.iter().any(|contract| {
+        let in_month = ContractId::parse(&contract.contract_id)
+            .ok()
+            .and_then(|id| DeliveryMonth::new(id.year, id.month).ok())
+            == Some(month);
+        let expired = matches!(
+            normalize_date(&contract.end_trade_date),
+            Some(end) if end.as_str() < date
+        );
+        in_month && !expired
+    }))
+}
@@ -1,13 +1,37 @@
 //! Market service for quote and market data APIs.
 
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::analytics::{
+    iv_series, receipt_alerts, ChangeTolerance, IvSeries, QuoteChange, QuoteChangeTracker,
+    ReceiptAlert,
+};
+use crate::calendar::Calendar;
+use crate::catalog::ContractCatalog;
+use crate::continuous::{ContinuousOptions, ContinuousSeries, RollBy};
+use crate::contract_id::ArbitrageId;
+use crate::days::{format_day, today};
+#[cfg(feature = "decimal")]
+use crate::decimal::DecimalQuote;
+use crate::division_series::{DivisionSeries, TimeOfDay};
+use crate::download::{fetch_dates, fetch_each, BatchResult, DownloadOptions};
 use crate::error::Result;
 use crate::http::{BaseClient, RequestOptions};
 use crate::models::{
-    ContractMonthMaxOpeni, ContractMonthMaxPrice, ContractMonthMaxRequest,
+    ContractMonthMaxOpeni, ContractMonthMaxPrice, ContractMonthMaxRequest, ContractMonthMaxResult,
     ContractMonthMaxTurnover, ContractMonthMaxVolume, DivisionPriceInfo, DivisionPriceInfoRequest,
-    Quote, QuotesRequest, RiseFallEvent, RiseFallEventRequest,
-    WarehouseReceipt, WarehouseReceiptRequest,
+    MonthQuotesRequest, Quote, QuotesRequest, RiseFallEvent, RiseFallEventRequest, StatContent,
+    StatisticsType, TradeType, WarehouseReceipt, WarehouseReceiptRequest, WeekQuotesRequest,
 };
+use crate::option_chain::OptionChain;
+use crate::quote_list::QuoteListExt;
+use crate::session::{latest_session, LatestQuotes, PollSchedule, SessionKind};
+use crate::spread::SpreadQuote;
+use crate::validation::Validate;
+use crate::watch::{changed_by_key, poll_changes, Watch};
 
 /// API endpoint for night quotes.
 const PATH_GET_NIGHT_QUOTES: &str = "/dceapi/forward/publicweb/dailystat/tiNightQuotes";
@@ -33,16 +57,30 @@ const PATH_GET_DIVISION_PRICE_INFO: &str = "/dceapi/forward/publicweb/dailystat/
 /// API endpoint for warehouse receipt (daily report).
 const PATH_GET_WAREHOUSE_RECEIPT: &str = "/dceapi/forward/publicweb/dailystat/wbillWeeklyQuotes";
 
+/// Variety probed to confirm trading days.
+const CALENDAR_VARIETY: &str = "m";
+
 /// Market service for accessing quote and market data.
 #[derive(Debug, Clone)]
 pub struct MarketService {
     client: BaseClient,
+    catalog: Option<ContractCatalog>,
 }
 
 impl MarketService {
     /// Create a new market service.
     pub fn new(client: BaseClient) -> Self {
-        MarketService { client }
+        MarketService {
+            client,
+            catalog: None,
+        }
+    }
+
+    /// Look up contract information in a shared contract catalog, such as
+    /// the [client's](crate::Client::catalog).
+    pub fn with_catalog(mut self, catalog: ContractCatalog) -> Self {
+        self.catalog = Some(catalog);
+        self
     }
 
     /// Get night session quotes.
@@ -55,7 +93,58 @@ impl MarketService {
         req: &QuotesRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<Quote>> {
-        self.client.do_post(PATH_GET_NIGHT_QUOTES, req, opts).await
+        self.quotes(PATH_GET_NIGHT_QUOTES, req, opts, Quote::is_subtotal)
+            .await
+    }
+
+    /// Watch night session quotes of a variety.
+    ///
+    /// Polls the night quotes for the current exchange date every
+    /// `interval` in a background task and feeds them to a
+    /// [`QuoteChangeTracker`] with the given tolerance, yielding the field
+    /// changes (last price, open interest and the other quote fields) that
+    /// exceed it. The first poll only records the current quotes; polls
+    /// without such changes yield nothing. Must be called within a Tokio
+    /// runtime.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety code (e.g. "a")
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `tolerance` - Smallest changes reported ([`ChangeTolerance::new`] for every change)
+    /// * `opts` - Optional request options
+    pub fn watch_night_quotes(
+        &self,
+        variety: &str,
+        interval: impl Into<PollSchedule>,
+        tolerance: ChangeTolerance,
+        opts: Option<RequestOptions>,
+    ) -> Watch<Vec<QuoteChange>> {
+        let service = self.clone();
+        let variety = variety.to_string();
+        let mut tracker = QuoteChangeTracker::new(tolerance);
+        poll_changes(
+            "night quotes",
+            self.client.shutdown_token(),
+            interval.into(),
+            move || {
+                let service = service.clone();
+                let opts = opts.clone();
+                let req = QuotesRequest {
+                    variety_id: None,
+                    variety: Some(variety.clone()),
+                    trade_date: format_day(today()),
+                    trade_type: TradeType::Futures,
+                    lang: None,
+                    statistics_type: None,
+                };
+                async move { service.get_night_quotes(&req, opts).await }
+            },
+            move |mut quotes: Vec<Quote>| {
+                quotes.retain(|q| !q.is_subtotal());
+                let changes = tracker.update(&quotes);
+                (!changes.is_empty()).then_some(changes)
+            },
+        )
     }
 
     /// Get day session quotes.
@@ -68,7 +157,430 @@ impl MarketService {
         req: &QuotesRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<Quote>> {
-        self.client.do_post(PATH_GET_DAY_QUOTES, req, opts).await
+        self.quotes(PATH_GET_DAY_QUOTES, req, opts, Quote::is_subtotal)
+            .await
+    }
+
+    /// Get the quotes of a variety's most recent session.
+    ///
+    /// Picks the night or day endpoint and the date from the current
+    /// exchange time, the variety's session schedule and `calendar`: the
+    /// night session once it has started, otherwise the day session of the
+    /// latest trading day or the night session that followed it. Custom
+    /// varieties are treated as day-only.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `calendar` - Trading calendar
+    /// * `opts` - Optional request options
+    pub async fn get_latest_quotes(
+        &self,
+        variety: &str,
+        calendar: &Calendar,
+        opts: Option<RequestOptions>,
+    ) -> Result<LatestQuotes> {
+        let (session, trade_date) =
+            latest_session(&variety.into(), calendar, today(), TimeOfDay::now())?;
+        let quotes = match session {
+            SessionKind::Night => {
+                let req = QuotesRequest {
+                    variety_id: None,
+                    variety: Some(variety.to_string()),
+                    trade_date: trade_date.clone(),
+                    trade_type: TradeType::Futures,
+                    lang: None,
+                    statistics_type: None,
+                };
+                self.get_night_quotes(&req, opts).await?
+            }
+            SessionKind::Day => {
+                let req = QuotesRequest {
+                    variety_id: Some(variety.to_string()),
+                    variety: None,
+                    trade_date: trade_date.clone(),
+                    trade_type: TradeType::Futures,
+                    lang: None,
+                    statistics_type: None,
+                };
+                self.get_day_quotes(&req, opts).await?
+            }
+        };
+        Ok(LatestQuotes {
+            session,
+            trade_date,
+            quotes,
+        })
+    }
+
+    /// Get day session quotes for every trading day in a date range.
+    ///
+    /// Requests each trading day from `start_date` to `end_date`
+    /// (inclusive), per the calendar in `download` or every weekday without
+    /// one, bounded by its concurrency and rate limits and retrying
+    /// transient errors. Days without quotes are left out of the result.
+    /// Fails with the first error that persists after retries.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `start_date` - First trade date (YYYYMMDD format)
+    /// * `end_date` - Last trade date (YYYYMMDD format)
+    /// * `download` - Concurrency, rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn get_day_quotes_range(
+        &self,
+        variety: &str,
+        start_date: &str,
+        end_date: &str,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<BTreeMap<String, Vec<Quote>>> {
+        let dates = download.trading_days(start_date, end_date)?;
+        let service = self.clone();
+        let variety = variety.to_string();
+        fetch_dates(
+            dates,
+            download,
+            |quotes: &Vec<Quote>| !quotes.is_empty(),
+            move |trade_date| {
+                let service = service.clone();
+                let opts = opts.clone();
+                let req = QuotesRequest {
+                    variety_id: Some(variety.clone()),
+                    variety: None,
+                    trade_date,
+                    trade_type: TradeType::default(),
+                    lang: None,
+                    statistics_type: None,
+                };
+                async move { service.get_day_quotes(&req, opts).await }
+            },
+        )
+        .await
+    }
+
+    /// Confirm the trading days of a date range in a calendar.
+    ///
+    /// Requests day quotes of soybean meal ("m"), which trades on every
+    /// trading day, for each weekday in the range that the calendar has not
+    /// confirmed yet and that lies before the current exchange date.
+    /// Weekdays with quotes are marked as trading days, the others as
+    /// holidays. Fails with the first error that persists after retries,
+    /// leaving the calendar unchanged. Pass the calendar to
+    /// [`DownloadOptions::with_calendar`] so range downloads skip its
+    /// holidays.
+    ///
+    /// # Arguments
+    /// * `calendar` - Calendar to update
+    /// * `start_date` - First date (YYYYMMDD format)
+    /// * `end_date` - Last date (YYYYMMDD format)
+    /// * `download` - Concurrency, rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn update_calendar(
+        &self,
+        calendar: &mut Calendar,
+        start_date: &str,
+        end_date: &str,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<()> {
+        let today = format_day(today());
+        let dates: Vec<String> = calendar
+            .unconfirmed_days(start_date, end_date)?
+            .into_iter()
+            .filter(|date| *date < today)
+            .collect();
+        if dates.is_empty() {
+            return Ok(());
+        }
+
+        let service = self.clone();
+        let traded = fetch_dates(
+            dates.clone(),
+            download,
+            |quotes: &Vec<Quote>| !quotes.is_empty(),
+            move |trade_date| {
+                let service = service.clone();
+                let opts = opts.clone();
+                let req = QuotesRequest {
+                    variety_id: Some(CALENDAR_VARIETY.to_string()),
+                    variety: None,
+                    trade_date,
+                    trade_type: TradeType::Futures,
+                    lang: None,
+                    statistics_type: None,
+                };
+                async move { service.get_day_quotes(&req, opts).await }
+            },
+        )
+        .await?;
+        for date in &dates {
+            if traded.contains_key(date) {
+                calendar.add_trading_day(date)?;
+            } else {
+                calendar.add_holiday(date)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get day session quotes of several varieties for one trade date.
+    ///
+    /// Requests each variety concurrently, bounded by the concurrency and
+    /// rate limits in `download`, retrying transient errors. A failed
+    /// variety does not fail the others; its error is returned in
+    /// [`BatchResult::errors`].
+    ///
+    /// # Arguments
+    /// * `varieties` - Variety IDs (e.g. ["m", "y", "p"])
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    /// * `download` - Concurrency, rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn get_day_quotes_many<S: AsRef<str>>(
+        &self,
+        varieties: &[S],
+        trade_date: &str,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> BatchResult<Vec<Quote>> {
+        let varieties = varieties.iter().map(|v| v.as_ref().to_string()).collect();
+        let service = self.clone();
+        let trade_date = trade_date.to_string();
+        fetch_each(varieties, download, move |variety| {
+            let service = service.clone();
+            let opts = opts.clone();
+            let req = QuotesRequest {
+                variety_id: Some(variety),
+                variety: None,
+                trade_date: trade_date.clone(),
+                trade_type: TradeType::default(),
+                lang: None,
+                statistics_type: None,
+            };
+            async move { service.get_day_quotes(&req, opts).await }
+        })
+        .await
+    }
+
+    /// Get spread quotes of arbitrage contracts for one trade date.
+    ///
+    /// Fetches the day quotes of every leg variety once with
+    /// [`get_day_quotes_many`](Self::get_day_quotes_many) and pairs up the
+    /// legs of each contract. Contracts with an unquoted leg are left out.
+    /// Fails if a contract ID cannot be parsed or a variety cannot be
+    /// fetched.
+    ///
+    /// # Arguments
+    /// * `arbi_contract_ids` - Arbitrage contract IDs (e.g. ["SP m2505&m2509"])
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    /// * `download` - Concurrency, rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn get_spread_quotes<S: AsRef<str>>(
+        &self,
+        arbi_contract_ids: &[S],
+        trade_date: &str,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<SpreadQuote>> {
+        let ids = arbi_contract_ids
+            .iter()
+            .map(|id| ArbitrageId::parse(id.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        let varieties: BTreeSet<&str> = ids
+            .iter()
+            .flat_map(|id| id.legs.iter().map(|leg| leg.variety.as_str()))
+            .collect();
+        let varieties: Vec<&str> = varieties.into_iter().collect();
+
+        let batch = self
+            .get_day_quotes_many(&varieties, trade_date, download, opts)
+            .await;
+        if let Some((_, err)) = batch.errors.into_iter().min_by(|a, b| a.0.cmp(&b.0)) {
+            return Err(err);
+        }
+        let quotes: Vec<Quote> = batch.values.into_values().flatten().collect();
+        Ok(ids
+            .iter()
+            .filter_map(|id| SpreadQuote::from_quotes(id, &quotes))
+            .collect())
+    }
+
+    /// Get the main (主力) contract of a variety on a trade date.
+    ///
+    /// Fetches the day quotes and returns the futures contract ranked first
+    /// by `roll_by`, or `None` if no contract traded (see
+    /// [`QuoteListExt::main_contract`]).
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    /// * `roll_by` - How contracts are ranked
+    /// * `opts` - Optional request options
+    pub async fn get_main_contract(
+        &self,
+        variety: &str,
+        trade_date: &str,
+        roll_by: RollBy,
+        opts: Option<RequestOptions>,
+    ) -> Result<Option<Quote>> {
+        let req = QuotesRequest {
+            variety_id: Some(variety.to_string()),
+            variety: None,
+            trade_date: trade_date.to_string(),
+            trade_type: TradeType::Futures,
+            lang: None,
+            statistics_type: None,
+        };
+        let opts = opts.unwrap_or_default().with_trade_type(TradeType::Futures);
+        let quotes = self.get_day_quotes(&req, Some(opts)).await?;
+        Ok(quotes.main_contract(roll_by).cloned())
+    }
+
+    /// Get the continuous main-contract series of a variety.
+    ///
+    /// Downloads day quotes for the range with
+    /// [`get_day_quotes_range`](Self::get_day_quotes_range) and stitches the
+    /// dominant contract of each day into one candle series.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `start_date` - First trade date (YYYYMMDD format)
+    /// * `end_date` - Last trade date (YYYYMMDD format)
+    /// * `options` - Roll and price adjustment settings
+    /// * `download` - Concurrency, rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn get_continuous_series(
+        &self,
+        variety: &str,
+        start_date: &str,
+        end_date: &str,
+        options: &ContinuousOptions,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<ContinuousSeries> {
+        let days = self
+            .get_day_quotes_range(variety, start_date, end_date, download, opts)
+            .await?;
+        Ok(ContinuousSeries::build(variety, &days, options))
+    }
+
+    /// Get the option chain of a variety.
+    ///
+    /// Fetches per-contract option day quotes and groups them by series and
+    /// strike. With a [catalog](Self::with_catalog), the variety's options
+    /// contracts are looked up concurrently to set each series' last trade
+    /// date.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `trade_date` - Trade date (YYYYMMDD format)
+    /// * `opts` - Optional request options
+    pub async fn get_option_chain(
+        &self,
+        variety: &str,
+        trade_date: &str,
+        opts: Option<RequestOptions>,
+    ) -> Result<OptionChain> {
+        let req = QuotesRequest {
+            variety_id: Some(variety.to_string()),
+            variety: None,
+            trade_date: trade_date.to_string(),
+            trade_type: TradeType::Options,
+            lang: None,
+            statistics_type: Some(StatisticsType::Contract),
+        };
+        let opts = opts.unwrap_or_default().with_trade_type(TradeType::Options);
+        let Some(catalog) = &self.catalog else {
+            let quotes = self.get_day_quotes(&req, Some(opts)).await?;
+            return Ok(OptionChain::from_quotes(variety, trade_date, &quotes));
+        };
+        let (quotes, contracts) = tokio::try_join!(
+            self.get_day_quotes(&req, Some(opts)),
+            catalog.contracts(variety, TradeType::Options),
+        )?;
+        Ok(OptionChain::from_quotes(variety, trade_date, &quotes).with_contracts(&contracts))
+    }
+
+    /// Get the daily implied volatility of an option series over a date
+    /// range.
+    ///
+    /// Requests series-level option day quotes for each trading day from
+    /// `start_date` to `end_date` (inclusive), per the calendar in
+    /// `download` or every weekday without one, bounded by its concurrency
+    /// and rate limits and retrying transient errors. Every trading day
+    /// gets a point; days without a value for the series are listed by
+    /// [`IvSeries::missing_dates`]. Fails with the first error that persists
+    /// after retries.
+    ///
+    /// # Arguments
+    /// * `variety` - Variety ID (e.g. "m")
+    /// * `series_id` - Option series ID (e.g. "m2505")
+    /// * `start_date` - First trade date (YYYYMMDD format)
+    /// * `end_date` - Last trade date (YYYYMMDD format)
+    /// * `download` - Concurrency, rate, retry and calendar settings
+    /// * `opts` - Optional request options
+    pub async fn get_iv_series(
+        &self,
+        variety: &str,
+        series_id: &str,
+        start_date: &str,
+        end_date: &str,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<IvSeries> {
+        let dates = download.trading_days(start_date, end_date)?;
+        let service = self.clone();
+        let variety = variety.to_string();
+        let opts = opts.unwrap_or_default().with_trade_type(TradeType::Options);
+        let quotes = fetch_dates(
+            dates.clone(),
+            download,
+            |quotes: &Vec<Quote>| !quotes.is_empty(),
+            move |trade_date| {
+                let service = service.clone();
+                let opts = opts.clone();
+                let req = QuotesRequest {
+                    variety_id: Some(variety.clone()),
+                    variety: None,
+                    trade_date,
+                    trade_type: TradeType::Options,
+                    lang: None,
+                    statistics_type: Some(StatisticsType::Series),
+                };
+                async move { service.get_day_quotes(&req, Some(opts)).await }
+            },
+        )
+        .await?;
+        Ok(iv_series(&quotes, series_id, &dates))
+    }
+
+    /// Get night session quotes with decimal prices.
+    ///
+    /// # Arguments
+    /// * `req` - Request with variety and trade date
+    /// * `opts` - Optional request options
+    #[cfg(feature = "decimal")]
+    pub async fn get_night_quotes_decimal(
+        &self,
+        req: &QuotesRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<DecimalQuote>> {
+        self.quotes(PATH_GET_NIGHT_QUOTES, req, opts, DecimalQuote::is_subtotal)
+            .await
+    }
+
+    /// Get day session quotes with decimal prices.
+    ///
+    /// # Arguments
+    /// * `req` - Request with variety and trade date
+    /// * `opts` - Optional request options
+    #[cfg(feature = "decimal")]
+    pub async fn get_day_quotes_decimal(
+        &self,
+        req: &QuotesRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<DecimalQuote>> {
+        self.quotes(PATH_GET_DAY_QUOTES, req, opts, DecimalQuote::is_subtotal)
+            .await
     }
 
     /// Get weekly quotes.
@@ -81,7 +593,22 @@ impl MarketService {
         req: &QuotesRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<Quote>> {
-        self.client.do_post(PATH_GET_WEEK_QUOTES, req, opts).await
+        self.quotes(PATH_GET_WEEK_QUOTES, req, opts, Quote::is_subtotal)
+            .await
+    }
+
+    /// Get weekly quotes for a year and week number.
+    ///
+    /// # Arguments
+    /// * `req` - Request with variety code, year and week
+    /// * `opts` - Optional request options
+    pub async fn get_week_quotes_by_week(
+        &self,
+        req: &WeekQuotesRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<Quote>> {
+        self.quotes(PATH_GET_WEEK_QUOTES, req, opts, Quote::is_subtotal)
+            .await
     }
 
     /// Get monthly quotes.
@@ -94,67 +621,113 @@ impl MarketService {
         req: &QuotesRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<Quote>> {
-        self.client.do_post(PATH_GET_MONTH_QUOTES, req, opts).await
+        self.quotes(PATH_GET_MONTH_QUOTES, req, opts, Quote::is_subtotal)
+            .await
+    }
+
+    /// Get monthly quotes for a year and month.
+    ///
+    /// # Arguments
+    /// * `req` - Request with variety code, year and month
+    /// * `opts` - Optional request options
+    pub async fn get_month_quotes_by_month(
+        &self,
+        req: &MonthQuotesRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<Quote>> {
+        self.quotes(PATH_GET_MONTH_QUOTES, req, opts, Quote::is_subtotal)
+            .await
+    }
+
+    /// Get contract monthly max statistics for the requested statistics content.
+    ///
+    /// The result variant always matches `req.stat_content`.
+    ///
+    /// # Arguments
+    /// * `req` - Request with month range and statistics content
+    /// * `opts` - Optional request options
+    pub async fn get_contract_month_max(
+        &self,
+        req: &ContractMonthMaxRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<ContractMonthMaxResult> {
+        Ok(match req.stat_content {
+            StatContent::Volume => {
+                ContractMonthMaxResult::Volume(self.get_contract_month_max_volume(req, opts).await?)
+            }
+            StatContent::Turnover => ContractMonthMaxResult::Turnover(
+                self.get_contract_month_max_turnover(req, opts).await?,
+            ),
+            StatContent::OpenInterest => ContractMonthMaxResult::OpenInterest(
+                self.get_contract_month_max_openi(req, opts).await?,
+            ),
+            StatContent::Price => {
+                ContractMonthMaxResult::Price(self.get_contract_month_max_price(req, opts).await?)
+            }
+        })
     }
 
     /// Get contract monthly max statistics (volume).
     ///
+    /// `req.stat_content` is ignored; volume statistics are always requested.
+    ///
     /// # Arguments
-    /// * `req` - Request with stat_content="0" for volume statistics
+    /// * `req` - Request with month range
     /// * `opts` - Optional request options
     pub async fn get_contract_month_max_volume(
         &self,
         req: &ContractMonthMaxRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<ContractMonthMaxVolume>> {
-        self.client
-            .do_post(PATH_GET_CONTRACT_MONTH_MAX, req, opts)
+        self.contract_month_max(req, StatContent::Volume, opts)
             .await
     }
 
     /// Get contract monthly max statistics (turnover).
     ///
+    /// `req.stat_content` is ignored; turnover statistics are always requested.
+    ///
     /// # Arguments
-    /// * `req` - Request with stat_content="1" for turnover statistics
+    /// * `req` - Request with month range
     /// * `opts` - Optional request options
     pub async fn get_contract_month_max_turnover(
         &self,
         req: &ContractMonthMaxRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<ContractMonthMaxTurnover>> {
-        self.client
-            .do_post(PATH_GET_CONTRACT_MONTH_MAX, req, opts)
+        self.contract_month_max(req, StatContent::Turnover, opts)
             .await
     }
 
     /// Get contract monthly max statistics (open interest).
     ///
+    /// `req.stat_content` is ignored; open interest statistics are always requested.
+    ///
     /// # Arguments
-    /// * `req` - Request with stat_content="2" for open interest statistics
+    /// * `req` - Request with month range
     /// * `opts` - Optional request options
     pub async fn get_contract_month_max_openi(
         &self,
         req: &ContractMonthMaxRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<ContractMonthMaxOpeni>> {
-        self.client
-            .do_post(PATH_GET_CONTRACT_MONTH_MAX, req, opts)
+        self.contract_month_max(req, StatContent::OpenInterest, opts)
             .await
     }
 
     /// Get contract monthly max statistics (price).
     ///
+    /// `req.stat_content` is ignored; price statistics are always requested.
+    ///
     /// # Arguments
-    /// * `req` - Request with stat_content="3" for price statistics
+    /// * `req` - Request with month range
     /// * `opts` - Optional request options
     pub async fn get_contract_month_max_price(
         &self,
         req: &ContractMonthMaxRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<ContractMonthMaxPrice>> {
-        self.client
-            .do_post(PATH_GET_CONTRACT_MONTH_MAX, req, opts)
-            .await
+        self.contract_month_max(req, StatContent::Price, opts).await
     }
 
     /// Get rise/fall events (trading limit occurrences).
@@ -167,11 +740,65 @@ impl MarketService {
         req: &RiseFallEventRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<RiseFallEvent>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_RISE_FALL_EVENT, req, opts)
             .await
     }
 
+    /// Watch limit-up/limit-down events of the current exchange date.
+    ///
+    /// Polls the rise/fall events every `interval` in a background task and
+    /// yields the events that are new since the previous poll: a contract
+    /// and direction not seen before, or one whose limit count increased.
+    /// Polls without new events yield nothing. Must be called within a
+    /// Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `variety_id` - Variety ID ("all" for all varieties)
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `opts` - Optional request options
+    pub fn watch_rise_fall_events(
+        &self,
+        variety_id: &str,
+        interval: impl Into<PollSchedule>,
+        opts: Option<RequestOptions>,
+    ) -> Watch<Vec<RiseFallEvent>> {
+        let service = self.clone();
+        let variety_id = variety_id.to_string();
+        // Same language as the request headers: the options, else the config.
+        let lang = opts
+            .as_ref()
+            .and_then(|o| o.lang)
+            .unwrap_or(self.client.config().lang);
+        poll_changes(
+            "rise/fall events",
+            self.client.shutdown_token(),
+            interval.into(),
+            move || {
+                let service = service.clone();
+                let opts = opts.clone();
+                let date = format_day(today());
+                let req = RiseFallEventRequest {
+                    start_date: date.clone(),
+                    end_date: date,
+                    variety_id: variety_id.clone(),
+                    lang,
+                };
+                async move { service.get_rise_fall_event(&req, opts).await }
+            },
+            changed_by_key(|e: &RiseFallEvent| {
+                let key = (
+                    e.trade_date.clone(),
+                    e.contract_id.clone(),
+                    e.direction.clone(),
+                );
+                Some((key, e.times))
+            }),
+        )
+    }
+
     /// Get division price information (settlement reference price by time).
     ///
     /// # Arguments
@@ -182,11 +809,62 @@ impl MarketService {
         req: &DivisionPriceInfoRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<DivisionPriceInfo>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_DIVISION_PRICE_INFO, req, opts)
             .await
     }
 
+    /// Get division price information as per-contract intraday series.
+    ///
+    /// # Arguments
+    /// * `req` - Request with variety, trade date, and trade type
+    /// * `opts` - Optional request options
+    pub async fn get_division_series(
+        &self,
+        req: &DivisionPriceInfoRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<DivisionSeries> {
+        let rows = self.get_division_price_info(req, opts).await?;
+        Ok(DivisionSeries::from_rows(&rows))
+    }
+
+    /// Watch division price information during the settlement window.
+    ///
+    /// Polls every `interval` in a background task and yields the rows for
+    /// contract and calculation time pairs not seen in earlier polls.
+    /// Polls without new rows yield nothing. Drop the handle once the
+    /// window closes. Must be called within a Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `req` - Request with variety, trade date, and trade type
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `opts` - Optional request options
+    pub fn watch_division_price_info(
+        &self,
+        req: &DivisionPriceInfoRequest,
+        interval: impl Into<PollSchedule>,
+        opts: Option<RequestOptions>,
+    ) -> Watch<Vec<DivisionPriceInfo>> {
+        let service = self.clone();
+        let req = req.clone();
+        poll_changes(
+            "division price info",
+            self.client.shutdown_token(),
+            interval.into(),
+            move || {
+                let service = service.clone();
+                let req = req.clone();
+                let opts = opts.clone();
+                async move { service.get_division_price_info(&req, opts).await }
+            },
+            changed_by_key(|row: &DivisionPriceInfo| {
+                Some(((row.contract_id.clone(), row.calculate_time.clone()), ()))
+            }),
+        )
+    }
+
     /// Get warehouse receipt daily report.
     ///
     /// # Arguments
@@ -197,8 +875,141 @@ impl MarketService {
         req: &WarehouseReceiptRequest,
         opts: Option<RequestOptions>,
     ) -> Result<WarehouseReceipt> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_WAREHOUSE_RECEIPT, req, opts)
             .await
     }
+
+    /// Get warehouse receipt daily reports for every trading day in a date
+    /// range.
+    ///
+    /// Requests each trading day from `start_date` to `end_date`
+    /// (inclusive), per the calendar in `download` or every weekday without
+    /// one, bounded by its concurrency and rate limits and retrying
+    /// transient errors. Days without receipt rows are left out of the
+    /// result.
+    ///
+    /// # Arguments
+    /// * `variety_id` - Variety ID ("all" for all varieties)
+    /// * `start_date` - First trade date (YYYYMMDD format)
+    /// * `end_date` - Last trade date (YYYYMMDD format)
+    /// * `download` - Concurrency, rate and retry settings
+    /// * `opts` - Optional request options
+    pub async fn get_warehouse_receipt_history(
+        &self,
+        variety_id: &str,
+        start_date: &str,
+        end_date: &str,
+        download: &DownloadOptions,
+        opts: Option<RequestOptions>,
+    ) -> Result<BTreeMap<String, WarehouseReceipt>> {
+        let dates = download.trading_days(start_date, end_date)?;
+        let service = self.clone();
+        let variety_id = variety_id.to_string();
+        fetch_dates(
+            dates,
+            download,
+            |receipt: &WarehouseReceipt| !receipt.entity_list.is_empty(),
+            move |trade_date| {
+                let service = service.clone();
+                let opts = opts.clone();
+                let req = WarehouseReceiptRequest {
+                    variety_id: variety_id.clone(),
+                    trade_date,
+                };
+                async move { service.get_warehouse_receipt(&req, opts).await }
+            },
+        )
+        .await
+    }
+
+    /// Watch the warehouse receipt report of the current exchange date.
+    ///
+    /// Polls the report every `interval` in a background task and yields
+    /// the varieties whose total receipts moved by at least `threshold` lots
+    /// since the previous trade date (see
+    /// [`receipt_alerts`](crate::analytics::receipt_alerts)). Each variety
+    /// is reported again only if its total changes. Must be called within
+    /// a Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `variety_id` - Variety ID ("all" for all varieties)
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `threshold` - Minimum move of a variety's total, in lots
+    /// * `opts` - Optional request options
+    pub fn watch_warehouse_receipts(
+        &self,
+        variety_id: &str,
+        interval: impl Into<PollSchedule>,
+        threshold: i64,
+        opts: Option<RequestOptions>,
+    ) -> Watch<Vec<ReceiptAlert>> {
+        let service = self.clone();
+        let variety_id = variety_id.to_string();
+        poll_changes(
+            "warehouse receipts",
+            self.client.shutdown_token(),
+            interval.into(),
+            move || {
+                let service = service.clone();
+                let opts = opts.clone();
+                let req = WarehouseReceiptRequest {
+                    variety_id: variety_id.clone(),
+                    trade_date: format_day(today()),
+                };
+                async move {
+                    let receipt = service.get_warehouse_receipt(&req, opts).await?;
+                    Ok(receipt_alerts(&receipt, threshold))
+                }
+            },
+            changed_by_key(|a: &ReceiptAlert| {
+                Some(((a.trade_date.clone(), a.variety.clone()), a.total))
+            }),
+        )
+    }
+
+    /// Request quotes, removing subtotal rows if configured.
+    async fn quotes<Q, R>(
+        &self,
+        path: &str,
+        req: &Q,
+        opts: Option<RequestOptions>,
+        is_subtotal: fn(&R) -> bool,
+    ) -> Result<Vec<R>>
+    where
+        Q: Serialize + Validate,
+        R: DeserializeOwned,
+    {
+        req.validate()?;
+
+        let drop_subtotals = self.client.drop_subtotals(opts.as_ref());
+        let mut quotes: Vec<R> = self.client.do_post(path, req, opts).await?;
+        if drop_subtotals {
+            quotes.retain(|q| !is_subtotal(q));
+        }
+        Ok(quotes)
+    }
+
+    /// Request contract monthly max statistics with the given statistics content.
+    async fn contract_month_max<R>(
+        &self,
+        req: &ContractMonthMaxRequest,
+        stat_content: StatContent,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<R>>
+    where
+        R: DeserializeOwned,
+    {
+        let req = ContractMonthMaxRequest {
+            stat_content,
+            ..req.clone()
+        };
+        req.validate()?;
+
+        self.client
+            .do_post(PATH_GET_CONTRACT_MONTH_MAX, &req, opts)
+            .await
+    }
 }
@@ -3,6 +3,7 @@
 use crate::error::Result;
 use crate::http::{BaseClient, RequestOptions};
 use crate::models::{DailyRankingRequest, DailyRankingResponse, PhaseRanking, PhaseRankingRequest};
+use crate::validation::Validate;
 
 /// API endpoint for daily ranking.
 const PATH_GET_DAILY_RANKING: &str = "/dceapi/forward/publicweb/dailystat/memberDealPosi";
@@ -34,6 +35,8 @@ impl MemberService {
         req: &DailyRankingRequest,
         opts: Option<RequestOptions>,
     ) -> Result<DailyRankingResponse> {
+        req.validate()?;
+
         self.client.do_post(PATH_GET_DAILY_RANKING, req, opts).await
     }
 
@@ -49,6 +52,8 @@ impl MemberService {
         req: &PhaseRankingRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<PhaseRanking>> {
+        req.validate()?;
+
         self.client.do_post(PATH_GET_PHASE_RANKING, req, opts).await
     }
 }
@@ -1,36 +1,44 @@
 //! News service for article and announcement APIs.
 
 use std::collections::HashSet;
-use std::sync::LazyLock;
+use std::sync::Arc;
 
-use crate::error::{Error, Result};
+use tokio::io::AsyncWrite;
+use tokio::time::sleep;
+
+use crate::config::DCE_SITE_URL;
+use crate::error::{Error, ErrorCode, Result, ValidationErrors};
+#[cfg(feature = "feed")]
+use crate::export::{ArticleFeed, FeedFormat};
 use crate::http::{BaseClient, RequestOptions};
-use crate::models::{GetArticleByPageRequest, GetArticleByPageResponse};
+use crate::models::{Article, ColumnId, GetArticleByPageRequest, GetArticleByPageResponse, Lang};
+use crate::news_sync::{MemorySyncStore, NewsSync, SyncStore};
+use crate::session::PollSchedule;
+use crate::validation::Validate;
+use crate::watch::{jittered, Watch, MAX_WATCH_BACKOFF};
 
 /// API endpoint for paginated article list.
 const PATH_GET_ARTICLE_BY_PAGE: &str = "/dceapi/cms/info/articleByPage";
 
-/// Valid column IDs for articles.
-/// - 244: 业务公告与通知
-/// - 245: 活动公告与通知
-/// - 246: 交易所新闻-文媒
-/// - 248: 媒体看大商所-文媒
-/// - 1076: 今日提示
-/// - 242: 新闻发布
-static VALID_COLUMN_IDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
-    let mut set = HashSet::new();
-    set.insert("244"); // 业务公告与通知
-    set.insert("245"); // 活动公告与通知
-    set.insert("246"); // 交易所新闻-文媒
-    set.insert("248"); // 媒体看大商所-文媒
-    set.insert("1076"); // 今日提示
-    set.insert("242"); // 新闻发布
-    set
-});
-
-/// Check if a column ID is valid.
+/// Default site ID for article requests.
+const DEFAULT_SITE_ID: i32 = 5;
+
+/// Page size used when walking article pages by date.
+const DATE_WINDOW_PAGE_SIZE: i32 = 50;
+
+/// Number of newest articles included in a feed.
+#[cfg(feature = "feed")]
+const FEED_PAGE_SIZE: i32 = 20;
+
+/// Check if a column ID is one of the documented columns.
 pub fn is_valid_column_id(column_id: &str) -> bool {
-    VALID_COLUMN_IDS.contains(column_id)
+    ColumnId::from(column_id).is_known()
+}
+
+/// Display name of a documented column ID in the given language, or `None`
+/// if the ID is not a documented column.
+pub fn column_name(column_id: &str, lang: Lang) -> Option<&'static str> {
+    ColumnId::from(column_id).label(lang)
 }
 
 /// News service for accessing articles and announcements.
@@ -51,33 +59,460 @@ impl NewsService {
     /// * `req` - Request parameters including column_id, page_no, page_size
     /// * `opts` - Optional request options
     ///
-    /// # Valid Column IDs (官方文档定义)
-    /// * `244` - 业务公告与通知
-    /// * `245` - 活动公告与通知
-    /// * `246` - 交易所新闻-文媒
-    /// * `248` - 媒体看大商所-文媒
-    /// * `1076` - 今日提示
-    /// * `242` - 新闻发布
+    /// # Column IDs (官方文档定义)
+    /// * `244` - 业务公告与通知 ([`ColumnId::BusinessNotices`])
+    /// * `245` - 活动公告与通知 ([`ColumnId::ActivityNotices`])
+    /// * `246` - 交易所新闻-文媒 ([`ColumnId::ExchangeNews`])
+    /// * `248` - 媒体看大商所-文媒 ([`ColumnId::MediaCoverage`])
+    /// * `1076` - 今日提示 ([`ColumnId::DailyTips`])
+    /// * `242` - 新闻发布 ([`ColumnId::PressReleases`])
+    ///
+    /// Other columns can be requested with [`ColumnId::Custom`], which must be numeric.
     pub async fn get_article_by_page(
         &self,
         mut req: GetArticleByPageRequest,
         opts: Option<RequestOptions>,
     ) -> Result<GetArticleByPageResponse> {
-        // Validate column_id
-        if !is_valid_column_id(&req.column_id) {
-            return Err(Error::validation(
-                "column_id",
-                "invalid column_id, must be one of: 244, 245, 246, 248, 1076, 242",
-            ));
-        }
+        req.validate()?;
 
         // Apply default site_id if not set
         if req.site_id == 0 {
-            req.site_id = 5;
+            req.site_id = DEFAULT_SITE_ID;
         }
 
         self.client
             .do_post(PATH_GET_ARTICLE_BY_PAGE, &req, opts)
             .await
     }
+
+    /// Get all articles in a column published between two dates (inclusive).
+    ///
+    /// Pages through the column from the newest article until a page contains
+    /// only articles older than `start_date`. Articles are matched on
+    /// `show_date` (falling back to `release_date`), de-duplicated by ID and
+    /// returned in chronological order.
+    ///
+    /// # Arguments
+    /// * `column_id` - Column (a [`ColumnId`] or its ID string)
+    /// * `start_date` - First date to include (YYYYMMDD format)
+    /// * `end_date` - Last date to include (YYYYMMDD format)
+    /// * `opts` - Optional request options
+    pub async fn get_articles_between(
+        &self,
+        column_id: impl Into<ColumnId>,
+        start_date: &str,
+        end_date: &str,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<Article>> {
+        let mut errors = ValidationErrors::new();
+        validate_date(&mut errors, "start_date", start_date);
+        validate_date(&mut errors, "end_date", end_date);
+        if errors.is_empty() && start_date > end_date {
+            errors.add("end_date", "end_date must not be before start_date");
+        }
+        errors.into_result()?;
+
+        let column_id = column_id.into();
+        let mut seen = HashSet::new();
+        let mut articles = Vec::new();
+        let mut page_no = 1;
+
+        loop {
+            let req = GetArticleByPageRequest {
+                column_id: column_id.clone(),
+                page_no,
+                page_size: DATE_WINDOW_PAGE_SIZE,
+                site_id: DEFAULT_SITE_ID,
+            };
+            let resp = self.get_article_by_page(req, opts.clone()).await?;
+            let page_len = resp.result_list.len();
+
+            let mut reached_start = page_len > 0;
+            for article in resp.result_list {
+                let Some(date) = article_date(&article) else {
+                    reached_start = false;
+                    continue;
+                };
+                if date.as_str() >= start_date {
+                    reached_start = false;
+                }
+                if date.as_str() >= start_date
+                    && date.as_str() <= end_date
+                    && seen.insert(article.id.clone())
+                {
+                    articles.push((date, article));
+                }
+            }
+
+            let fetched = (page_no as i64) * (DATE_WINDOW_PAGE_SIZE as i64);
+            if reached_start
+                || page_len < DATE_WINDOW_PAGE_SIZE as usize
+                || fetched >= resp.total_count as i64
+            {
+                break;
+            }
+            page_no += 1;
+        }
+
+        articles.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(articles.into_iter().map(|(_, article)| article).collect())
+    }
+
+    /// Render the newest articles of a column as a feed document.
+    ///
+    /// Fetches the first page of the column and renders it newest first.
+    ///
+    /// # Arguments
+    /// * `column_id` - Column (a [`ColumnId`] or its ID string)
+    /// * `feed` - Feed title, link and description
+    /// * `format` - RSS or Atom
+    /// * `opts` - Optional request options
+    #[cfg(feature = "feed")]
+    pub async fn get_feed(
+        &self,
+        column_id: impl Into<ColumnId>,
+        feed: &ArticleFeed,
+        format: FeedFormat,
+        opts: Option<RequestOptions>,
+    ) -> Result<String> {
+        let req = GetArticleByPageRequest {
+            column_id: column_id.into(),
+            page_no: 1,
+            page_size: FEED_PAGE_SIZE,
+            site_id: DEFAULT_SITE_ID,
+        };
+        let resp = self.get_article_by_page(req, opts).await?;
+        Ok(feed.render(format, &resp.result_list))
+    }
+
+    /// Watch columns for newly published articles.
+    ///
+    /// Polls every column about every `interval` (plus up to 10% random
+    /// jitter) in a background task and yields the articles published since
+    /// the previous poll, in chronological order; see [`NewsSync`]. The
+    /// first successful poll of a column only records what is already
+    /// published. When the API reports rate limiting, the wait doubles, up
+    /// to eight times `interval`, until a poll succeeds. Must be called
+    /// within a Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `columns` - Columns to watch (each a [`ColumnId`] or its ID string)
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `opts` - Optional request options
+    pub fn watch(
+        &self,
+        columns: impl IntoIterator<Item = impl Into<ColumnId>>,
+        interval: impl Into<PollSchedule>,
+        opts: Option<RequestOptions>,
+    ) -> Watch<Vec<Article>> {
+        let columns: Vec<ColumnId> = columns.into_iter().map(Into::into).collect();
+        let schedule: PollSchedule = interval.into();
+        // Created once so a restarted poller resumes from what was seen.
+        let sync = Arc::new(NewsSync::new(self.clone(), MemorySyncStore::new()));
+        Watch::spawn("news", self.client.shutdown_token(), move |tx| {
+            let sync = Arc::clone(&sync);
+            let columns = columns.clone();
+            let opts = opts.clone();
+            async move {
+                let mut backoff = 1;
+                loop {
+                    let mut articles = Vec::new();
+                    let mut rate_limited = false;
+                    for column_id in &columns {
+                        let primed = matches!(sync.store().load(column_id), Ok(Some(_)));
+                        match sync.sync(column_id.clone(), opts.clone()).await {
+                            Ok(fresh) => {
+                                if primed {
+                                    articles.extend(fresh);
+                                }
+                            }
+                            Err(err) => {
+                                rate_limited |= err.error_code() == Some(ErrorCode::RateLimit);
+                                if !tx.send(Err(err)).await {
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    if !articles.is_empty() && !tx.send(Ok(articles)).await {
+                        return;
+                    }
+                    backoff = if rate_limited {
+                        (backoff * 2).min(MAX_WATCH_BACKOFF)
+                    } else {
+                        1
+                    };
+                    sleep(jittered(schedule.interval_now() * backoff)).await;
+                }
+            }
+        })
+    }
+
+    /// Search a column for articles published between two dates (inclusive).
+    ///
+    /// The endpoint has no search parameters, so the date range is fetched
+    /// with [`get_articles_between`](Self::get_articles_between) and filtered
+    /// client-side: every whitespace-separated term of `query` must appear,
+    /// case-insensitively, in the title, subtitle, summary or keywords. An
+    /// empty query matches every article. Matches are returned in
+    /// chronological order.
+    ///
+    /// # Arguments
+    /// * `column_id` - Column (a [`ColumnId`] or its ID string)
+    /// * `query` - Search terms (e.g. "交割 豆粕")
+    /// * `start_date` - First date to include (YYYYMMDD format)
+    /// * `end_date` - Last date to include (YYYYMMDD format)
+    /// * `opts` - Optional request options
+    pub async fn search_articles(
+        &self,
+        column_id: impl Into<ColumnId>,
+        query: &str,
+        start_date: &str,
+        end_date: &str,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<Article>> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let articles = self
+            .get_articles_between(column_id, start_date, end_date, opts)
+            .await?;
+        Ok(articles
+            .into_iter()
+            .filter(|article| {
+                let text = [
+                    &article.title,
+                    &article.sub_title,
+                    &article.summary,
+                    &article.keywords,
+                ]
+                .map(|field| field.to_lowercase())
+                .join("\n");
+                terms.iter().all(|term| text.contains(term.as_str()))
+            })
+            .collect())
+    }
+
+    /// Download an article's title image and stream it to a writer,
+    /// returning the number of bytes written.
+    ///
+    /// # Arguments
+    /// * `article` - Article with a `title_image_url`
+    /// * `writer` - Destination of the image bytes
+    /// * `opts` - Optional request options
+    pub async fn download_title_image<W>(
+        &self,
+        article: &Article,
+        writer: &mut W,
+        opts: Option<RequestOptions>,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        if article.title_image_url.trim().is_empty() {
+            return Err(Error::missing_data(format!(
+                "article {} has no title image",
+                article.id
+            )));
+        }
+        self.download(&article.title_image_url, writer, opts).await
+    }
+
+    /// Download a file linked from an article (such as an attachment in its
+    /// content) and stream it to a writer, returning the number of bytes
+    /// written.
+    ///
+    /// Relative URLs are resolved against the configured base URL, and
+    /// requests to it are authenticated like API calls.
+    ///
+    /// # Arguments
+    /// * `url` - Absolute or site-relative file URL
+    /// * `writer` - Destination of the file bytes
+    /// * `opts` - Optional request options
+    pub async fn download<W>(
+        &self,
+        url: &str,
+        writer: &mut W,
+        opts: Option<RequestOptions>,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        self.client.download(url, writer, opts).await
+    }
+
+    /// Fill in an article's content from its published page when the API
+    /// returned none, returning whether the content was filled.
+    ///
+    /// List responses sometimes omit the body. This fetches the article's
+    /// static URL (falling back to the dynamic URL), resolving relative URLs
+    /// against [`DCE_SITE_URL`], and stores the markup of the page's article
+    /// container as the content, or of its `<body>` when no container is
+    /// found. Articles that already have content, or have no URL, are left
+    /// unchanged.
+    ///
+    /// # Arguments
+    /// * `article` - Article to fill in
+    /// * `opts` - Optional request options
+    pub async fn fill_content(
+        &self,
+        article: &mut Article,
+        opts: Option<RequestOptions>,
+    ) -> Result<bool> {
+        if !article.content.trim().is_empty() {
+            return Ok(false);
+        }
+        let Some(url) = [&article.article_static_url, &article.article_dynamic_url]
+            .into_iter()
+            .map(|url| url.trim())
+            .find(|url| !url.is_empty())
+        else {
+            return Ok(false);
+        };
+
+        let url = if url.starts_with("http://") || url.starts_with("https://") {
+            url.to_string()
+        } else {
+            format!("{}/{}", DCE_SITE_URL, url.trim_start_matches('/'))
+        };
+
+        let mut page = Vec::new();
+        self.client.download(&url, &mut page, opts).await?;
+        let page = String::from_utf8_lossy(&page);
+        let content = article_container(&page)
+            .unwrap_or_else(|| page_body(&page))
+            .trim();
+        if content.is_empty() {
+            return Ok(false);
+        }
+        article.content = content.to_string();
+        Ok(true)
+    }
+}
+
+/// Class or ID fragments that mark the element holding an article's text
+/// on the exchange's pages, in order of preference.
+const ARTICLE_CONTAINERS: [&str; 4] = ["trs_editor", "detail_content", "article_content", "zoom"];
+
+/// Inner markup of a page's article container, if it has one.
+fn article_container(page: &str) -> Option<&str> {
+    let lower = page.to_ascii_lowercase();
+    ARTICLE_CONTAINERS.iter().find_map(|marker| {
+        let mut from = 0;
+        while let Some(found) = lower[from..].find(marker).map(|i| from + i) {
+            from = found + marker.len();
+            // The marker must sit inside an opening tag's attributes.
+            let Some(open) = lower[..found].rfind('<') else {
+                continue;
+            };
+            if lower[open..found].contains('>') {
+                continue;
+            }
+            let name: String = lower[open + 1..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            let Some(start) = lower[found..].find('>').map(|i| found + i + 1) else {
+                continue;
+            };
+            if name.is_empty() {
+                continue;
+            }
+            if let Some(end) = closing_tag(&lower, &name, start) {
+                return Some(&page[start..end]);
+            }
+        }
+        None
+    })
+}
+
+/// Position of the tag closing an element named `name` whose content starts
+/// at `start`, skipping nested elements of the same name.
+fn closing_tag(lower: &str, name: &str, start: usize) -> Option<usize> {
+    let (open, close) = (format!("<{}", name), format!("</{}", name));
+    let mut depth = 1;
+    let mut at = start;
+    loop {
+        let next_close = lower[at..].find(&close).map(|i| at + i)?;
+        let next_open = lower[at..next_close]
+            .match_indices(&open)
+            .map(|(i, _)| at + i)
+            .find(|&i| {
+                lower[i + open.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c == '>' || c.is_ascii_whitespace())
+            });
+        match next_open {
+            Some(i) => {
+                depth += 1;
+                at = i + open.len();
+            }
+            None => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(next_close);
+                }
+                at = next_close + close.len();
+            }
+        }
+    }
+}
+
+/// Inner markup of a page's `<body>` element, or the whole page if it has
+/// none.
+fn page_body(page: &str) -> &str {
+    let lower = page.to_ascii_lowercase();
+    let Some(open) = lower.find("<body") else {
+        return page;
+    };
+    let Some(start) = lower[open..].find('>').map(|i| open + i + 1) else {
+        return page;
+    };
+    let end = lower.rfind("</body").filter(|&end| end >= start);
+    &page[start..end.unwrap_or(page.len())]
+}
+
+/// Extract an article's publication date as YYYYMMDD.
+///
+/// Uses `show_date`, falling back to `release_date`. Accepts both
+/// "2025-09-30" and "20250930" style values, with or without a time part.
+pub(crate) fn article_date(article: &Article) -> Option<String> {
+    [&article.show_date, &article.release_date]
+        .into_iter()
+        .find_map(|raw| {
+            let digits: String = raw
+                .chars()
+                .filter(|c| c.is_ascii_digit())
+                .take(8)
+                .collect();
+            (digits.len() == 8).then_some(digits)
+        })
+}
+
+/// Validate that a date is in YYYYMMDD format.
+fn validate_date(errors: &mut ValidationErrors, field: &str, date: &str) {
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        errors.add(field, format!("expected YYYYMMDD, got {:?}", date));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_the_article_container() {
+        let page = r#"<html><body><div class="nav">menu</div>
+            <div class="detail_content"><p>Notice</p><div class="TRS_Editor"><div>text</div></div></div>
+            <div class="footer">footer</div></body></html>"#;
+        assert_eq!(article_container(page), Some("<div>text</div>"));
+        let page = r#"<body><DIV id="zoom"><div>a</div><p>b</p></DIV><p>after</p></body>"#;
+        assert_eq!(article_container(page), Some("<div>a</div><p>b</p>"));
+    }
+
+    #[test]
+    fn falls_back_to_the_body() {
+        let page = "<html><body><p>zoom in</p></body></html>";
+        assert_eq!(article_container(page), None);
+        assert_eq!(page_body(page), "<p>zoom in</p>");
+    }
 }
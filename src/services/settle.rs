@@ -1,8 +1,11 @@
 //! Settlement service for settlement parameter APIs.
 
+#[cfg(feature = "decimal")]
+use crate::decimal::DecimalSettleParam;
 use crate::error::Result;
 use crate::http::{BaseClient, RequestOptions};
 use crate::models::{SettleParam, SettleParamRequest};
+use crate::validation::Validate;
 
 /// API endpoint for settlement parameters.
 const PATH_GET_SETTLE_PARAM: &str = "/dceapi/forward/publicweb/tradepara/futAndOptSettle";
@@ -31,6 +34,24 @@ impl SettleService {
         req: &SettleParamRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<SettleParam>> {
+        req.validate()?;
+
+        self.client.do_post(PATH_GET_SETTLE_PARAM, req, opts).await
+    }
+
+    /// Get settlement parameters with decimal prices, fees and margin rates.
+    ///
+    /// # Arguments
+    /// * `req` - Request with variety_id, trade_date, trade_type, and lang
+    /// * `opts` - Optional request options
+    #[cfg(feature = "decimal")]
+    pub async fn get_settle_param_decimal(
+        &self,
+        req: &SettleParamRequest,
+        opts: Option<RequestOptions>,
+    ) -> Result<Vec<DecimalSettleParam>> {
+        req.validate()?;
+
         self.client.do_post(PATH_GET_SETTLE_PARAM, req, opts).await
     }
 }
@@ -2,14 +2,19 @@
 
 use std::collections::HashMap;
 
+use crate::analytics::{margin_changes, MarginChange};
+use crate::days::{format_day, today};
 use crate::error::Result;
 use crate::http::{BaseClient, RequestOptions};
 use crate::models::{
     ArbitrageContract, ArbitrageContractRequest, ContractInfo, ContractInfoRequest,
-    DayTradeParamRequest, MainSeriesInfo, MainSeriesInfoRequest, MarginArbiPerfPara,
-    MarginArbiPerfParaRequest, NewContractInfo, NewContractInfoRequest, TradeParam, TradingParam,
-    TradingParamRequest,
+    DayTradeParamRequest, Lang, MainSeriesInfo, MainSeriesInfoRequest, MarginArbiPerfPara,
+    MarginArbiPerfParaRequest, NewContractInfo, NewContractInfoRequest, TradeParam, TradeType,
+    TradingParam, TradingParamRequest,
 };
+use crate::session::PollSchedule;
+use crate::validation::Validate;
+use crate::watch::{changed_by_key, poll_changes, Watch};
 
 /// API endpoint for day trade parameters.
 const PATH_GET_DAY_TRADE_PARAM: &str = "/dceapi/forward/publicweb/tradepara/dayTradPara";
@@ -60,6 +65,8 @@ impl TradeService {
         req: &DayTradeParamRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<TradeParam>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_DAY_TRADE_PARAM, req, opts)
             .await
@@ -93,6 +100,8 @@ impl TradeService {
         req: &ContractInfoRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<ContractInfo>> {
+        req.validate()?;
+
         self.client.do_post(PATH_GET_CONTRACT_INFO, req, opts).await
     }
 
@@ -101,15 +110,15 @@ impl TradeService {
     /// Returns available spread/arbitrage trading contracts.
     ///
     /// # Arguments
-    /// * `lang` - Language ("zh" or "en"), defaults to "zh"
+    /// * `lang` - Language, defaults to [`Lang::Zh`]
     /// * `opts` - Optional request options
     pub async fn get_arbitrage_contract(
         &self,
-        lang: Option<&str>,
+        lang: Option<Lang>,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<ArbitrageContract>> {
         let req = ArbitrageContractRequest {
-            lang: lang.unwrap_or("zh").to_string(),
+            lang: lang.unwrap_or_default(),
         };
         self.client
             .do_post(PATH_GET_ARBITRAGE_CONTRACT, &req, opts)
@@ -121,15 +130,15 @@ impl TradeService {
     /// Returns comprehensive trading parameters including margins, fees, limits for all varieties.
     ///
     /// # Arguments
-    /// * `lang` - Language ("zh" or "en"), defaults to "zh"
+    /// * `lang` - Language, defaults to [`Lang::Zh`]
     /// * `opts` - Optional request options
     pub async fn get_trading_param(
         &self,
-        lang: Option<&str>,
+        lang: Option<Lang>,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<TradingParam>> {
         let req = TradingParamRequest {
-            lang: lang.unwrap_or("zh").to_string(),
+            lang: lang.unwrap_or_default(),
         };
         self.client
             .do_post(PATH_GET_TRADING_PARAM, &req, opts)
@@ -148,6 +157,8 @@ impl TradeService {
         req: &MarginArbiPerfParaRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<MarginArbiPerfPara>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_MARGIN_ARBI_PERF_PARA, req, opts)
             .await
@@ -165,6 +176,8 @@ impl TradeService {
         req: &NewContractInfoRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<NewContractInfo>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_NEW_CONTRACT_INFO, req, opts)
             .await
@@ -182,8 +195,82 @@ impl TradeService {
         req: &MainSeriesInfoRequest,
         opts: Option<RequestOptions>,
     ) -> Result<Vec<MainSeriesInfo>> {
+        req.validate()?;
+
         self.client
             .do_post(PATH_GET_MAIN_SERIES_INFO, req, opts)
             .await
     }
+
+    /// Watch contracts listed on the current exchange date.
+    ///
+    /// Polls the new contract information every `interval` in a background
+    /// task and yields the contracts not seen in an earlier poll; the first
+    /// poll yields every contract listed today. Polls without new contracts
+    /// yield nothing. Must be called within a Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `trade_type` - Futures or options
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `opts` - Optional request options
+    pub fn watch_new_contracts(
+        &self,
+        trade_type: TradeType,
+        interval: impl Into<PollSchedule>,
+        opts: Option<RequestOptions>,
+    ) -> Watch<Vec<NewContractInfo>> {
+        let service = self.clone();
+        poll_changes(
+            "new contracts",
+            self.client.shutdown_token(),
+            interval.into(),
+            move || {
+                let service = service.clone();
+                let opts = opts.clone();
+                let req = NewContractInfoRequest {
+                    trade_date: format_day(today()),
+                    trade_type,
+                    lang: None,
+                };
+                async move { service.get_new_contract_info(&req, opts).await }
+            },
+            changed_by_key(|c: &NewContractInfo| Some((c.contract_id.clone(), ()))),
+        )
+    }
+
+    /// Watch trading margin rates for changes.
+    ///
+    /// Polls the trading parameters every `interval` in a background task
+    /// and yields the varieties whose speculation or hedging margin rate
+    /// changed since the previous poll (see
+    /// [`margin_changes`](crate::analytics::margin_changes)). The first
+    /// poll only records the current rates. Must be called within a Tokio
+    /// runtime.
+    ///
+    /// # Arguments
+    /// * `interval` - Time between polls, or a [`PollSchedule`] adapting it to the sessions
+    /// * `opts` - Optional request options
+    pub fn watch_margin_changes(
+        &self,
+        interval: impl Into<PollSchedule>,
+        opts: Option<RequestOptions>,
+    ) -> Watch<Vec<MarginChange>> {
+        let service = self.clone();
+        let mut previous: Option<Vec<TradingParam>> = None;
+        poll_changes(
+            "margin changes",
+            self.client.shutdown_token(),
+            interval.into(),
+            move || {
+                let service = service.clone();
+                let opts = opts.clone();
+                async move { service.get_trading_param(None, opts).await }
+            },
+            move |params: Vec<TradingParam>| {
+                let changes = previous.as_deref().map(|old| margin_changes(old, &params));
+                previous = Some(params);
+                changes.filter(|changes| !changes.is_empty())
+            },
+        )
+    }
 }
@@ -0,0 +1,305 @@
+//! Trading session schedule.
+//!
+//! The API does not publish session times, so they come from a static
+//! table: every variety trades the same three day session blocks, and
+//! varieties with night trading also trade 21:00-23:00 on the evening
+//! before. Times are exchange time (UTC+8). [`PollSchedule`] uses the same
+//! table to poll faster around session opens, closes and settlement and
+//! slower when nothing trades.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::calendar::Calendar;
+use crate::days::{day_number, exchange_seconds, format_day, is_weekday};
+use crate::division_series::TimeOfDay;
+use crate::error::Result;
+use crate::models::Quote;
+use crate::variety::VarietyCode;
+
+/// Day session blocks (start, end) as (hour, minute).
+const DAY_BLOCKS: [((u32, u32), (u32, u32)); 3] = [
+    ((9, 0), (10, 15)),
+    ((10, 30), (11, 30)),
+    ((13, 30), (15, 0)),
+];
+
+/// Night session block (start, end) as (hour, minute).
+const NIGHT_BLOCK: ((u32, u32), (u32, u32)) = ((21, 0), (23, 0));
+
+/// Settlement window after the day close (start, end) as (hour, minute).
+const SETTLEMENT_WINDOW: ((u32, u32), (u32, u32)) = ((15, 0), (16, 0));
+
+/// Seconds either side of a block's start or end polled at the active rate.
+const ACTIVE_MARGIN_SECS: u32 = 5 * 60;
+
+/// Day or night session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SessionKind {
+    /// Night session (夜盘), counted towards the next trade date.
+    Night,
+    /// Day session (日盘).
+    Day,
+}
+
+impl fmt::Display for SessionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SessionKind::Night => "night",
+            SessionKind::Day => "day",
+        })
+    }
+}
+
+/// One continuous trading block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Session {
+    /// Day or night session.
+    pub kind: SessionKind,
+    /// Start time (inclusive).
+    pub start: TimeOfDay,
+    /// End time (exclusive).
+    pub end: TimeOfDay,
+}
+
+impl Session {
+    /// Trading blocks of a variety in trade date order: the night block
+    /// (if the variety trades at night) followed by the day blocks.
+    ///
+    /// Custom varieties are assumed to trade day sessions only.
+    pub fn schedule(variety: &VarietyCode) -> Vec<Session> {
+        let night = variety
+            .has_night_session()
+            .unwrap_or(false)
+            .then(|| block(SessionKind::Night, NIGHT_BLOCK));
+        night
+            .into_iter()
+            .chain(DAY_BLOCKS.iter().map(|b| block(SessionKind::Day, *b)))
+            .collect()
+    }
+
+    /// Block of a variety that is trading at `now`, or `None` between
+    /// blocks. Does not take weekends or holidays into account.
+    pub fn current(variety: &VarietyCode, now: TimeOfDay) -> Option<Session> {
+        Session::schedule(variety)
+            .into_iter()
+            .find(|s| s.contains(now))
+    }
+
+    /// Next block of a variety to start after `now`, wrapping to the first
+    /// block of the schedule after the last one ends.
+    pub fn next(variety: &VarietyCode, now: TimeOfDay) -> Session {
+        let mut schedule = Session::schedule(variety);
+        schedule.sort_by_key(|s| s.start);
+        *schedule
+            .iter()
+            .find(|s| s.start > now)
+            .unwrap_or(&schedule[0])
+    }
+
+    /// Whether a time falls within the block.
+    pub fn contains(&self, time: TimeOfDay) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+/// Build a session block from (hour, minute) bounds.
+fn block(
+    kind: SessionKind,
+    ((start_h, start_m), (end_h, end_m)): ((u32, u32), (u32, u32)),
+) -> Session {
+    Session {
+        kind,
+        start: TimeOfDay::from_seconds(start_h * 3600 + start_m * 60),
+        end: TimeOfDay::from_seconds(end_h * 3600 + end_m * 60),
+    }
+}
+
+/// How often a watcher polls, by time of day.
+///
+/// A fixed schedule polls at one interval. An adaptive schedule polls at
+/// its active interval within five minutes of any session's open or close
+/// and during the settlement hour after the day close (15:00-16:00), at
+/// its trading interval during the rest of the sessions, and at its idle
+/// interval otherwise: in the breaks, overnight and at weekends. Holidays
+/// are not known to the schedule and are polled like trading days. A
+/// [`Duration`] converts into a fixed schedule, so watchers accept either.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use dceapi_rs::PollSchedule;
+///
+/// let schedule = PollSchedule::adaptive(
+///     Duration::from_secs(5),
+///     Duration::from_secs(30),
+///     Duration::from_secs(600),
+/// );
+/// assert!(schedule.interval_now() >= Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PollSchedule {
+    active: Duration,
+    trading: Duration,
+    idle: Duration,
+}
+
+impl PollSchedule {
+    /// Poll every `interval` at any time.
+    pub fn fixed(interval: Duration) -> Self {
+        PollSchedule {
+            active: interval,
+            trading: interval,
+            idle: interval,
+        }
+    }
+
+    /// Poll every `active` around opens, closes and settlement, every
+    /// `trading` during the rest of the sessions, and every `idle` when no
+    /// session is trading.
+    pub fn adaptive(active: Duration, trading: Duration, idle: Duration) -> Self {
+        PollSchedule {
+            active,
+            trading,
+            idle,
+        }
+    }
+
+    /// Interval to wait after a poll made now, in exchange time.
+    pub fn interval_now(&self) -> Duration {
+        let secs = exchange_seconds();
+        self.interval_at(
+            is_weekday(secs.div_euclid(86_400)),
+            TimeOfDay::from_seconds(secs.rem_euclid(86_400) as u32),
+        )
+    }
+
+    /// Interval at a time of day on a weekday or weekend day.
+    fn interval_at(&self, weekday: bool, now: TimeOfDay) -> Duration {
+        if !weekday {
+            return self.idle;
+        }
+        let blocks: Vec<Session> = DAY_BLOCKS
+            .iter()
+            .chain([&NIGHT_BLOCK])
+            .map(|b| block(SessionKind::Day, *b))
+            .collect();
+        let near = |edge: TimeOfDay| edge.seconds().abs_diff(now.seconds()) <= ACTIVE_MARGIN_SECS;
+        if blocks.iter().any(|s| near(s.start) || near(s.end))
+            || block(SessionKind::Day, SETTLEMENT_WINDOW).contains(now)
+        {
+            self.active
+        } else if blocks.iter().any(|s| s.contains(now)) {
+            self.trading
+        } else {
+            self.idle
+        }
+    }
+}
+
+impl From<Duration> for PollSchedule {
+    fn from(interval: Duration) -> Self {
+        PollSchedule::fixed(interval)
+    }
+}
+
+/// Quotes of the most recent session, as returned by
+/// [`MarketService::get_latest_quotes`](crate::MarketService::get_latest_quotes).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatestQuotes {
+    /// Session the quotes come from.
+    pub session: SessionKind,
+    /// Date requested (YYYYMMDD): the trade date for day quotes, the
+    /// evening the session ran for night quotes.
+    pub trade_date: String,
+    /// Quotes of the session.
+    pub quotes: Vec<Quote>,
+}
+
+/// Most recent session of a variety that has started by `now` on `today`
+/// (a day number), with the date to request it for.
+///
+/// Tonight's night session wins once it has started; otherwise the latest
+/// trading day's day session, or the night session that followed it. A
+/// night session only runs after a trading day whose next trading day is
+/// the next weekday, so there is none before a holiday.
+pub(crate) fn latest_session(
+    variety: &VarietyCode,
+    calendar: &Calendar,
+    today: i64,
+    now: TimeOfDay,
+) -> Result<(SessionKind, String)> {
+    let night = variety.has_night_session().unwrap_or(false);
+    let night_after = |date: &str| -> Result<bool> {
+        let Some(day) = day_number(date).filter(|_| night) else {
+            return Ok(false);
+        };
+        let next_weekday = (day + 1..).find(|&d| is_weekday(d)).map(format_day);
+        Ok(Some(calendar.next_trading_day(date)?) == next_weekday)
+    };
+    let today = format_day(today);
+    let trading_today = calendar.is_trading_day(&today)?;
+    let night_started = now >= block(SessionKind::Night, NIGHT_BLOCK).start;
+    let day_started = now >= block(SessionKind::Day, DAY_BLOCKS[0]).start;
+
+    if trading_today && night_started && night_after(&today)? {
+        return Ok((SessionKind::Night, today));
+    }
+    if trading_today && day_started {
+        return Ok((SessionKind::Day, today));
+    }
+    let previous = calendar.prev_trading_day(&today)?;
+    let kind = if night_after(&previous)? {
+        SessionKind::Night
+    } else {
+        SessionKind::Day
+    };
+    Ok((kind, previous))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> TimeOfDay {
+        TimeOfDay::from_seconds(hour * 3600 + minute * 60)
+    }
+
+    #[test]
+    fn adaptive_schedule_follows_sessions() {
+        let schedule = PollSchedule::adaptive(
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            Duration::from_secs(100),
+        );
+        let interval = |hour, minute| schedule.interval_at(true, at(hour, minute)).as_secs();
+        // Around the open and close of blocks.
+        assert_eq!(interval(8, 55), 1);
+        assert_eq!(interval(9, 5), 1);
+        assert_eq!(interval(11, 30), 1);
+        assert_eq!(interval(22, 58), 1);
+        // Settlement hour after the day close.
+        assert_eq!(interval(15, 45), 1);
+        // Inside sessions.
+        assert_eq!(interval(9, 30), 10);
+        assert_eq!(interval(14, 0), 10);
+        assert_eq!(interval(21, 30), 10);
+        // Lunch break, short break, evening and overnight.
+        assert_eq!(interval(12, 30), 100);
+        assert_eq!(interval(10, 22), 100);
+        assert_eq!(interval(18, 0), 100);
+        assert_eq!(interval(3, 0), 100);
+        // Weekends.
+        assert_eq!(schedule.interval_at(false, at(9, 30)).as_secs(), 100);
+    }
+
+    #[test]
+    fn fixed_schedule_ignores_the_time() {
+        let schedule = PollSchedule::from(Duration::from_secs(7));
+        for (hour, minute) in [(9, 0), (12, 30), (15, 30), (3, 0)] {
+            assert_eq!(schedule.interval_at(true, at(hour, minute)).as_secs(), 7);
+        }
+        assert_eq!(schedule.interval_now().as_secs(), 7);
+    }
+}
@@ -0,0 +1,171 @@
+//! Cooperative shutdown of background tasks.
+//!
+//! Every watcher and supervised task started from a [`Client`] observes
+//! the client's [`Shutdown`] token. Triggering it stops polling at the next
+//! await point and waits for the tasks to exit.
+//!
+//! No task buffers state between polls, so there is nothing to flush on
+//! shutdown. Watchers keep what they have already yielded in memory only:
+//! a watcher started after a restart begins from the current data (for
+//! example, [`NewsService::watch`] only records the articles already
+//! published on its first poll). To resume across restarts, use a
+//! [`NewsSync`] with a [`FileSyncStore`], which saves a column's state as
+//! soon as its new articles are fetched, or a [`Backfill`], which writes
+//! its checkpoint after every group of dates.
+//!
+//! [`Backfill`]: crate::Backfill
+//! [`Client`]: crate::Client
+//! [`FileSyncStore`]: crate::FileSyncStore
+//! [`NewsService::watch`]: crate::NewsService::watch
+//! [`NewsSync`]: crate::NewsSync
+
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Cancellation token shared by background tasks.
+///
+/// Cloning yields a handle to the same token.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    /// Whether shutdown was triggered.
+    triggered: watch::Sender<bool>,
+    /// Number of running tasks.
+    running: watch::Sender<usize>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    /// Create a token that has not been triggered.
+    pub fn new() -> Self {
+        Shutdown {
+            inner: Arc::new(Inner {
+                triggered: watch::Sender::new(false),
+                running: watch::Sender::new(0),
+            }),
+        }
+    }
+
+    /// Ask every task observing the token to stop. Returns immediately;
+    /// see [`shutdown`](Self::shutdown) to wait for the tasks.
+    pub fn trigger(&self) {
+        self.inner.triggered.send_replace(true);
+    }
+
+    /// Whether shutdown was triggered.
+    pub fn is_triggered(&self) -> bool {
+        *self.inner.triggered.borrow()
+    }
+
+    /// Wait until shutdown is triggered.
+    pub async fn wait(&self) {
+        let mut rx = self.inner.triggered.subscribe();
+        // The sender lives as long as `self`, so this cannot fail.
+        let _ = rx.wait_for(|triggered| *triggered).await;
+    }
+
+    /// Trigger shutdown and wait until every task observing the token has
+    /// exited.
+    pub async fn shutdown(&self) {
+        self.trigger();
+        let mut rx = self.inner.running.subscribe();
+        let _ = rx.wait_for(|running| *running == 0).await;
+    }
+
+    /// Number of tasks observing the token that are still running.
+    pub fn running_tasks(&self) -> usize {
+        *self.inner.running.borrow()
+    }
+
+    /// Count a task as running until the returned guard is dropped.
+    pub(crate) fn register(&self) -> TaskGuard {
+        self.inner.running.send_modify(|running| *running += 1);
+        TaskGuard {
+            shutdown: self.clone(),
+        }
+    }
+}
+
+/// Marks a task as running for [`Shutdown::shutdown`].
+#[derive(Debug)]
+pub(crate) struct TaskGuard {
+    shutdown: Shutdown,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.shutdown
+            .inner
+            .running
+            .send_modify(|running| *running -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn clones_share_one_trigger() {
+        let shutdown = Shutdown::new();
+        let clone = shutdown.clone();
+        assert!(!clone.is_triggered());
+
+        let waiter = tokio::spawn(async move { clone.wait().await });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        shutdown.trigger();
+        timeout(Duration::from_secs(5), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(shutdown.is_triggered());
+        // Waiting after the trigger returns at once.
+        timeout(Duration::from_secs(5), shutdown.wait())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_registered_tasks() {
+        let shutdown = Shutdown::new();
+        let guard = shutdown.register();
+        let second = shutdown.register();
+        assert_eq!(shutdown.running_tasks(), 2);
+        drop(second);
+        assert_eq!(shutdown.running_tasks(), 1);
+
+        let token = shutdown.clone();
+        tokio::spawn(async move {
+            token.wait().await;
+            // Work still in flight when shutdown is triggered finishes first.
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(guard);
+        });
+
+        timeout(Duration::from_secs(5), shutdown.shutdown())
+            .await
+            .unwrap();
+        assert_eq!(shutdown.running_tasks(), 0);
+
+        // With nothing running, shutdown returns at once.
+        timeout(Duration::from_secs(5), Shutdown::new().shutdown())
+            .await
+            .unwrap();
+    }
+}
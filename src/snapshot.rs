@@ -0,0 +1,38 @@
+//! Combined market data of one variety for one trade date.
+
+use crate::models::{Quote, SettleParam, TradeParam, WarehouseReceipt};
+
+/// Day quotes, trading and settlement parameters and warehouse receipts of
+/// one variety, as returned by [`Client::snapshot`](crate::Client::snapshot).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarietySnapshot {
+    /// Variety ID (e.g. "m").
+    pub variety: String,
+    /// Trade date (YYYYMMDD).
+    pub trade_date: String,
+    /// Day session quotes.
+    pub quotes: Vec<Quote>,
+    /// Current trading parameters (margins, price limits). The exchange
+    /// publishes these for the current trade date only.
+    pub trade_params: Vec<TradeParam>,
+    /// Settlement parameters.
+    pub settle_params: Vec<SettleParam>,
+    /// Warehouse receipt daily report.
+    pub warehouse_receipt: WarehouseReceipt,
+}
+
+impl VarietySnapshot {
+    /// Day quote of one contract.
+    pub fn quote(&self, contract_id: &str) -> Option<&Quote> {
+        self.quotes
+            .iter()
+            .find(|q| q.contract_id.eq_ignore_ascii_case(contract_id))
+    }
+
+    /// Settlement parameters of one contract.
+    pub fn settle_param(&self, contract_id: &str) -> Option<&SettleParam> {
+        self.settle_params
+            .iter()
+            .find(|p| p.contract_id.eq_ignore_ascii_case(contract_id))
+    }
+}
@@ -0,0 +1,68 @@
+//! Spread quotes of arbitrage contracts.
+//!
+//! The exchange publishes no quotes for arbitrage contracts themselves, so a
+//! [`SpreadQuote`] is assembled from the day quotes of its two legs, with
+//! prices taken as first leg minus second leg.
+
+use crate::contract_id::ArbitrageId;
+use crate::models::Quote;
+
+/// Quotes of the two legs of an arbitrage contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadQuote {
+    /// Arbitrage contract (e.g. "SP m2505&m2509").
+    pub arbitrage: ArbitrageId,
+    /// Quote of the first leg.
+    pub first: Quote,
+    /// Quote of the second leg.
+    pub second: Quote,
+}
+
+impl SpreadQuote {
+    /// Pick the legs of `arbitrage` out of day quotes.
+    ///
+    /// `quotes` may mix several varieties, as for inter-commodity spreads.
+    /// Returns `None` unless the contract has exactly two legs and both are
+    /// quoted.
+    pub fn from_quotes(arbitrage: &ArbitrageId, quotes: &[Quote]) -> Option<SpreadQuote> {
+        let [first, second] = arbitrage.legs.as_slice() else {
+            return None;
+        };
+        let find = |leg| {
+            quotes
+                .iter()
+                .find(|q| q.parsed_contract_id().as_ref() == Some(leg))
+                .cloned()
+        };
+        Some(SpreadQuote {
+            arbitrage: arbitrage.clone(),
+            first: find(first)?,
+            second: find(second)?,
+        })
+    }
+
+    /// Close price spread.
+    pub fn close(&self) -> Option<f64> {
+        spread(self.first.close, self.second.close)
+    }
+
+    /// Settlement price spread.
+    pub fn clear_price(&self) -> Option<f64> {
+        spread(self.first.clear_price, self.second.clear_price)
+    }
+
+    /// Previous settlement price spread.
+    pub fn last_clear(&self) -> Option<f64> {
+        spread(self.first.last_clear, self.second.last_clear)
+    }
+
+    /// Change of the spread: close spread minus previous settlement spread.
+    pub fn change(&self) -> Option<f64> {
+        Some(self.close()? - self.last_clear()?)
+    }
+}
+
+/// Difference of two leg prices, if both are present.
+fn spread(first: Option<f64>, second: Option<f64>) -> Option<f64> {
+    Some(first? - second?)
+}
@@ -0,0 +1,584 @@
+//! Local SQLite storage.
+//!
+//! [`SqliteStore`] keeps quotes, settlement parameters, member rankings,
+//! warehouse receipts and articles in a single database file. Every table
+//! has the columns needed to look rows up (trade date, variety, contract)
+//! plus the full record as JSON, so queries return the same models the API
+//! does. Quote and receipt tables also carry the main numeric values as
+//! columns for use in plain SQL. Backfill partition keys can be recorded
+//! alongside, so re-run jobs skip dates already saved.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::models::{
+    is_subtotal_label, parse_optional_f64, Article, DailyRankingResponse, Quote, SettleParam,
+    WarehouseReceipt, WarehouseReceiptDetail,
+};
+
+/// Tables created when a store is opened.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS quotes (
+    trade_date    TEXT NOT NULL,
+    variety       TEXT NOT NULL,
+    contract_id   TEXT NOT NULL,
+    open          REAL,
+    high          REAL,
+    low           REAL,
+    close         REAL,
+    clear_price   REAL,
+    volume        INTEGER NOT NULL,
+    open_interest INTEGER NOT NULL,
+    data          TEXT NOT NULL,
+    PRIMARY KEY (trade_date, contract_id)
+);
+CREATE INDEX IF NOT EXISTS quotes_contract ON quotes (contract_id, trade_date);
+CREATE TABLE IF NOT EXISTS settle_params (
+    trade_date  TEXT NOT NULL,
+    variety     TEXT NOT NULL,
+    contract_id TEXT NOT NULL,
+    clear_price REAL,
+    data        TEXT NOT NULL,
+    PRIMARY KEY (trade_date, contract_id)
+);
+CREATE TABLE IF NOT EXISTS rankings (
+    trade_date  TEXT NOT NULL,
+    contract_id TEXT NOT NULL,
+    data        TEXT NOT NULL,
+    PRIMARY KEY (trade_date, contract_id)
+);
+CREATE TABLE IF NOT EXISTS warehouse_receipts (
+    trade_date TEXT NOT NULL,
+    variety    TEXT NOT NULL,
+    wh_abbr    TEXT NOT NULL,
+    wbill_qty  INTEGER NOT NULL,
+    data       TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS warehouse_receipts_date ON warehouse_receipts (trade_date, variety);
+CREATE TABLE IF NOT EXISTS articles (
+    id       TEXT PRIMARY KEY,
+    show_day TEXT NOT NULL,
+    title    TEXT NOT NULL,
+    data     TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS articles_day ON articles (show_day);
+CREATE TABLE IF NOT EXISTS partitions (
+    key TEXT PRIMARY KEY
+);
+";
+
+/// SQLite database of downloaded data.
+///
+/// Saving a row replaces any row with the same key (trade date and
+/// contract, or article ID), so re-downloading a day is safe. Calls are
+/// blocking; from async code, keep them short or move them to
+/// `tokio::task::spawn_blocking`.
+///
+/// # Example
+///
+/// ```no_run
+/// use dceapi_rs::SqliteStore;
+///
+/// # fn example(quotes: &[dceapi_rs::Quote]) -> dceapi_rs::Result<()> {
+/// let mut store = SqliteStore::open("dce.db")?;
+/// store.save_quotes("20250930", quotes)?;
+/// let history = store.quote_history("m2601", "20250901", "20250930")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Open (or create) a database file and create any missing tables.
+    pub fn open(path: impl AsRef<Path>) -> Result<SqliteStore> {
+        SqliteStore::init(Connection::open(path).map_err(sqlite_error)?)
+    }
+
+    /// Open a database held in memory, for tests and scratch work.
+    pub fn open_in_memory() -> Result<SqliteStore> {
+        SqliteStore::init(Connection::open_in_memory().map_err(sqlite_error)?)
+    }
+
+    /// Create the schema on a connection.
+    fn init(conn: Connection) -> Result<SqliteStore> {
+        conn.execute_batch(SCHEMA).map_err(sqlite_error)?;
+        Ok(SqliteStore { conn })
+    }
+
+    /// The underlying connection, for queries not covered here.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Save a trade date's quotes and return the number of rows written.
+    /// Subtotal rows are skipped.
+    pub fn save_quotes(&mut self, trade_date: &str, quotes: &[Quote]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(sqlite_error)?;
+        let mut saved = 0;
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT OR REPLACE INTO quotes (trade_date, variety, contract_id, open, high, \
+                     low, close, clear_price, volume, open_interest, data) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                )
+                .map_err(sqlite_error)?;
+            for quote in quotes.iter().filter(|q| !q.is_subtotal()) {
+                stmt.execute(params![
+                    trade_date,
+                    quote.variety,
+                    quote.contract_id,
+                    quote.open,
+                    quote.high,
+                    quote.low,
+                    quote.close,
+                    quote.clear_price,
+                    quote.volume,
+                    quote.open_interest,
+                    to_json(quote)?,
+                ])
+                .map_err(sqlite_error)?;
+                saved += 1;
+            }
+        }
+        tx.commit().map_err(sqlite_error)?;
+        Ok(saved)
+    }
+
+    /// Quotes of a trade date, optionally for one variety, by contract.
+    pub fn quotes(&self, trade_date: &str, variety: Option<&str>) -> Result<Vec<Quote>> {
+        self.query(
+            "SELECT data FROM quotes WHERE trade_date = ?1 AND (?2 IS NULL OR variety = ?2) \
+             ORDER BY contract_id",
+            params![trade_date, variety],
+        )
+    }
+
+    /// Quotes of a contract from `start_date` to `end_date` (inclusive),
+    /// with their trade dates, in date order.
+    pub fn quote_history(
+        &self,
+        contract_id: &str,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Vec<(String, Quote)>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT trade_date, data FROM quotes \
+                 WHERE contract_id = ?1 AND trade_date BETWEEN ?2 AND ?3 ORDER BY trade_date",
+            )
+            .map_err(sqlite_error)?;
+        let rows = stmt
+            .query_map(params![contract_id, start_date, end_date], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(sqlite_error)?;
+        rows.map(|row| {
+            let (date, json) = row.map_err(sqlite_error)?;
+            Ok((date, from_json(&json)?))
+        })
+        .collect()
+    }
+
+    /// Save a trade date's settlement parameters and return the number of
+    /// rows written.
+    pub fn save_settle_params(
+        &mut self,
+        trade_date: &str,
+        params: &[SettleParam],
+    ) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(sqlite_error)?;
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT OR REPLACE INTO settle_params (trade_date, variety, contract_id, \
+                     clear_price, data) VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .map_err(sqlite_error)?;
+            for param in params {
+                stmt.execute(params![
+                    trade_date,
+                    param.variety,
+                    param.contract_id,
+                    number(&param.clear_price),
+                    to_json(param)?,
+                ])
+                .map_err(sqlite_error)?;
+            }
+        }
+        tx.commit().map_err(sqlite_error)?;
+        Ok(params.len())
+    }
+
+    /// Settlement parameters of a trade date, optionally for one variety,
+    /// by contract.
+    pub fn settle_params(
+        &self,
+        trade_date: &str,
+        variety: Option<&str>,
+    ) -> Result<Vec<SettleParam>> {
+        self.query(
+            "SELECT data FROM settle_params WHERE trade_date = ?1 AND (?2 IS NULL OR variety = ?2) \
+             ORDER BY contract_id",
+            params![trade_date, variety],
+        )
+    }
+
+    /// Save a contract's member rankings for a trade date.
+    pub fn save_rankings(
+        &mut self,
+        trade_date: &str,
+        ranking: &DailyRankingResponse,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO rankings (trade_date, contract_id, data) \
+                 VALUES (?1, ?2, ?3)",
+                params![trade_date, ranking.contract_id, to_json(ranking)?],
+            )
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    /// Member rankings of a contract on a trade date, if saved.
+    pub fn rankings(
+        &self,
+        trade_date: &str,
+        contract_id: &str,
+    ) -> Result<Option<DailyRankingResponse>> {
+        let json: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT data FROM rankings WHERE trade_date = ?1 AND contract_id = ?2",
+                params![trade_date, contract_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sqlite_error)?;
+        json.as_deref().map(from_json).transpose()
+    }
+
+    /// Save a trade date's warehouse receipts and return the number of rows
+    /// written.
+    ///
+    /// Rows already saved for the date and the report's varieties are
+    /// replaced. Subtotal rows are skipped.
+    pub fn save_warehouse_receipts(
+        &mut self,
+        trade_date: &str,
+        receipt: &WarehouseReceipt,
+    ) -> Result<usize> {
+        let details: Vec<&WarehouseReceiptDetail> = receipt
+            .entity_list
+            .iter()
+            .filter(|d| {
+                !d.variety.is_empty()
+                    && !d.wh_abbr.is_empty()
+                    && !is_subtotal_label(&d.variety)
+                    && !is_subtotal_label(&d.wh_abbr)
+            })
+            .collect();
+        let tx = self.conn.transaction().map_err(sqlite_error)?;
+        {
+            let mut delete = tx
+                .prepare("DELETE FROM warehouse_receipts WHERE trade_date = ?1 AND variety = ?2")
+                .map_err(sqlite_error)?;
+            let mut insert = tx
+                .prepare(
+                    "INSERT INTO warehouse_receipts (trade_date, variety, wh_abbr, wbill_qty, data) \
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .map_err(sqlite_error)?;
+            let mut varieties: Vec<&str> = details.iter().map(|d| d.variety.as_str()).collect();
+            varieties.sort_unstable();
+            varieties.dedup();
+            for variety in varieties {
+                delete
+                    .execute(params![trade_date, variety])
+                    .map_err(sqlite_error)?;
+            }
+            for detail in &details {
+                insert
+                    .execute(params![
+                        trade_date,
+                        detail.variety,
+                        detail.wh_abbr,
+                        detail.wbill_qty,
+                        to_json(detail)?,
+                    ])
+                    .map_err(sqlite_error)?;
+            }
+        }
+        tx.commit().map_err(sqlite_error)?;
+        Ok(details.len())
+    }
+
+    /// Warehouse receipt rows of a trade date, optionally for one variety,
+    /// by variety and warehouse.
+    pub fn warehouse_receipts(
+        &self,
+        trade_date: &str,
+        variety: Option<&str>,
+    ) -> Result<Vec<WarehouseReceiptDetail>> {
+        self.query(
+            "SELECT data FROM warehouse_receipts \
+             WHERE trade_date = ?1 AND (?2 IS NULL OR variety = ?2) ORDER BY variety, wh_abbr",
+            params![trade_date, variety],
+        )
+    }
+
+    /// Save articles and return the number of rows written.
+    pub fn save_articles(&mut self, articles: &[Article]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(sqlite_error)?;
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT OR REPLACE INTO articles (id, show_day, title, data) \
+                     VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(sqlite_error)?;
+            for article in articles {
+                stmt.execute(params![
+                    article.id,
+                    show_day(article),
+                    article.title,
+                    to_json(article)?,
+                ])
+                .map_err(sqlite_error)?;
+            }
+        }
+        tx.commit().map_err(sqlite_error)?;
+        Ok(articles.len())
+    }
+
+    /// An article by ID, if saved.
+    pub fn article(&self, id: &str) -> Result<Option<Article>> {
+        let json: Option<String> = self
+            .conn
+            .query_row("SELECT data FROM articles WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .optional()
+            .map_err(sqlite_error)?;
+        json.as_deref().map(from_json).transpose()
+    }
+
+    /// Articles shown from `start_date` to `end_date` (inclusive, YYYYMMDD),
+    /// newest first.
+    pub fn articles(&self, start_date: &str, end_date: &str) -> Result<Vec<Article>> {
+        self.query(
+            "SELECT data FROM articles WHERE show_day BETWEEN ?1 AND ?2 \
+             ORDER BY show_day DESC, id DESC",
+            params![start_date, end_date],
+        )
+    }
+
+    /// Whether a backfill partition key has been recorded (see
+    /// [`BackfillPartition`](crate::BackfillPartition)).
+    pub fn has_partition(&self, key: &str) -> Result<bool> {
+        self.conn
+            .query_row("SELECT 1 FROM partitions WHERE key = ?1", [key], |_| Ok(()))
+            .optional()
+            .map(|row| row.is_some())
+            .map_err(sqlite_error)
+    }
+
+    /// Record a backfill partition key once its data is saved, so a
+    /// re-run job can skip it with [`has_partition`](Self::has_partition).
+    pub fn record_partition(&mut self, key: &str) -> Result<()> {
+        self.conn
+            .execute("INSERT OR IGNORE INTO partitions (key) VALUES (?1)", [key])
+            .map(|_| ())
+            .map_err(sqlite_error)
+    }
+
+    /// Run a query whose single column is a JSON record.
+    fn query<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<T>> {
+        let mut stmt = self.conn.prepare(sql).map_err(sqlite_error)?;
+        let rows = stmt
+            .query_map(params, |row| row.get::<_, String>(0))
+            .map_err(sqlite_error)?;
+        rows.map(|json| from_json(&json.map_err(sqlite_error)?))
+            .collect()
+    }
+}
+
+/// Numeric value of a string field, or `None` (NULL) if it does not parse.
+fn number(value: &str) -> Option<f64> {
+    parse_optional_f64(value)
+}
+
+/// Date an article is shown (YYYYMMDD), from the digits of `show_date`
+/// falling back to `release_date`.
+fn show_day(article: &Article) -> String {
+    [&article.show_date, &article.release_date]
+        .into_iter()
+        .map(|raw| {
+            raw.chars()
+                .filter(char::is_ascii_digit)
+                .take(8)
+                .collect::<String>()
+        })
+        .find(|day| day.len() == 8)
+        .unwrap_or_default()
+}
+
+/// Serialize a record for the `data` column.
+fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value)
+        .map_err(|e| Error::export(format!("failed to serialize record: {}", e)))
+}
+
+/// Deserialize a record from the `data` column.
+fn from_json<T: DeserializeOwned>(json: &str) -> Result<T> {
+    serde_json::from_str(json).map_err(|e| Error::parse(json, e.to_string()))
+}
+
+/// Convert a SQLite error into a crate error.
+fn sqlite_error(err: rusqlite::Error) -> Error {
+    Error::export(format!("sqlite error: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn quote(variety: &str, contract_id: &str, close: f64, volume: i64) -> Quote {
+        serde_json::from_value(json!({
+            "variety": variety,
+            "contractId": contract_id,
+            "close": close.to_string(),
+            "clearPrice": "-",
+            "volumn": volume,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn quotes_are_replaced_per_day_and_read_back() {
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        let day = [
+            quote("豆粕", "m2601", 2900.0, 10),
+            quote("豆油", "y2601", 8000.0, 20),
+            quote("豆粕小计", "", 0.0, 10),
+        ];
+        assert_eq!(store.save_quotes("20250930", &day).unwrap(), 2);
+        assert_eq!(
+            store
+                .save_quotes("20250930", &[quote("豆粕", "m2601", 2910.0, 11)])
+                .unwrap(),
+            1
+        );
+        store
+            .save_quotes("20250929", &[quote("豆粕", "m2601", 2880.0, 9)])
+            .unwrap();
+
+        let soymeal = store.quotes("20250930", Some("豆粕")).unwrap();
+        assert_eq!(soymeal.len(), 1);
+        assert_eq!(soymeal[0].close, Some(2910.0));
+        assert_eq!(store.quotes("20250930", None).unwrap().len(), 2);
+
+        let history = store
+            .quote_history("m2601", "20250901", "20250930")
+            .unwrap();
+        let closes: Vec<_> = history
+            .iter()
+            .map(|(date, q)| (date.as_str(), q.close))
+            .collect();
+        assert_eq!(
+            closes,
+            [("20250929", Some(2880.0)), ("20250930", Some(2910.0))]
+        );
+
+        let (volume, clear): (i64, Option<f64>) = store
+            .connection()
+            .query_row(
+                "SELECT volume, clear_price FROM quotes WHERE trade_date = '20250930' \
+                 AND contract_id = 'm2601'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((volume, clear), (11, None));
+    }
+
+    #[test]
+    fn warehouse_receipts_replace_the_reported_varieties() {
+        let receipt = |rows: serde_json::Value| -> WarehouseReceipt {
+            serde_json::from_value(json!({ "entityList": rows })).unwrap()
+        };
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        let first = receipt(json!([
+            { "variety": "豆粕", "whAbbr": "A库", "wbillQty": 100 },
+            { "variety": "豆粕", "whAbbr": "B库", "wbillQty": 50 },
+            { "variety": "豆油", "whAbbr": "C库", "wbillQty": 30 },
+            { "variety": "豆粕小计", "whAbbr": "", "wbillQty": 150 },
+        ]));
+        assert_eq!(
+            store.save_warehouse_receipts("20250930", &first).unwrap(),
+            3
+        );
+
+        let update = receipt(json!([{ "variety": "豆粕", "whAbbr": "A库", "wbillQty": 80 }]));
+        assert_eq!(
+            store.save_warehouse_receipts("20250930", &update).unwrap(),
+            1
+        );
+
+        let rows = store.warehouse_receipts("20250930", None).unwrap();
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|d| (d.variety.as_str(), d.wh_abbr.as_str(), d.wbill_qty))
+            .collect();
+        assert_eq!(rows, [("豆油", "C库", 30), ("豆粕", "A库", 80)]);
+    }
+
+    #[test]
+    fn articles_rankings_and_partitions_round_trip() {
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        let article = |id: &str, show_date: &str| -> Article {
+            serde_json::from_value(json!({ "id": id, "title": id, "showDate": show_date })).unwrap()
+        };
+        store
+            .save_articles(&[
+                article("1", "2025-09-29 10:00"),
+                article("2", "2025-09-30"),
+                article("3", "2025-10-09"),
+            ])
+            .unwrap();
+        let ids: Vec<_> = store
+            .articles("20250901", "20250930")
+            .unwrap()
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, ["2", "1"]);
+        assert_eq!(store.article("3").unwrap().unwrap().title, "3");
+        assert!(store.article("4").unwrap().is_none());
+
+        let ranking: DailyRankingResponse =
+            serde_json::from_value(json!({ "contractId": "m2601" })).unwrap();
+        store.save_rankings("20250930", &ranking).unwrap();
+        assert_eq!(store.rankings("20250930", "m2601").unwrap(), Some(ranking));
+        assert_eq!(store.rankings("20250929", "m2601").unwrap(), None);
+
+        assert!(!store.has_partition("quotes/20250930").unwrap());
+        store.record_partition("quotes/20250930").unwrap();
+        store.record_partition("quotes/20250930").unwrap();
+        assert!(store.has_partition("quotes/20250930").unwrap());
+    }
+}
@@ -0,0 +1,402 @@
+//! Supervision of background tasks.
+//!
+//! Watchers run under supervision: a task that panics or returns an error
+//! is restarted after a delay that doubles with each consecutive failure,
+//! and its health can be inspected while it runs. [`Supervisor`] offers the
+//! same to an application's own background tasks.
+
+use std::any::Any;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::sleep;
+
+use crate::error::Result;
+use crate::shutdown::Shutdown;
+use crate::watch::jittered;
+
+/// Delay before the first restart after a failure.
+const MIN_RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay between restarts. A task that ran at least this long
+/// before failing starts again from [`MIN_RESTART_DELAY`].
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// Lifecycle state of a supervised task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskState {
+    /// The task is running.
+    Running,
+    /// The task failed and is waiting to be restarted.
+    Restarting,
+    /// The task returned successfully and will not be restarted.
+    Finished,
+    /// The task was stopped, or exited on shutdown.
+    Stopped,
+}
+
+/// Health of a supervised task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskHealth {
+    /// Task name.
+    pub name: String,
+    /// Current state.
+    pub state: TaskState,
+    /// Number of times the task was restarted.
+    pub restarts: u32,
+    /// Error or panic message of the most recent failure.
+    pub last_failure: Option<String>,
+}
+
+impl TaskHealth {
+    /// Whether the task is running or finished normally.
+    pub fn is_healthy(&self) -> bool {
+        matches!(self.state, TaskState::Running | TaskState::Finished)
+    }
+}
+
+/// A task restarted on failure, aborted when dropped.
+#[derive(Debug)]
+pub(crate) struct SupervisedTask {
+    health: Arc<Mutex<TaskHealth>>,
+    handle: JoinHandle<()>,
+}
+
+impl SupervisedTask {
+    /// Spawn a task that exits when `shutdown` is triggered. `factory`
+    /// creates the task's future, again on every restart; state the future
+    /// owns starts over.
+    pub(crate) fn spawn<F, Fut>(
+        name: impl Into<String>,
+        shutdown: &Shutdown,
+        mut factory: F,
+    ) -> SupervisedTask
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let health = Arc::new(Mutex::new(TaskHealth {
+            name: name.into(),
+            state: TaskState::Running,
+            restarts: 0,
+            last_failure: None,
+        }));
+        let shared = Arc::clone(&health);
+        let shutdown = shutdown.clone();
+        let guard = shutdown.register();
+        let handle = tokio::spawn(async move {
+            let _guard = guard;
+            let update = |f: &dyn Fn(&mut TaskHealth)| {
+                f(&mut shared.lock().unwrap_or_else(|e| e.into_inner()));
+            };
+            let mut delay = MIN_RESTART_DELAY;
+            loop {
+                update(&|h| h.state = TaskState::Running);
+                let started = Instant::now();
+                // Run in a set so aborting the supervisor aborts the task.
+                let mut run = JoinSet::new();
+                run.spawn(factory());
+                let result = tokio::select! {
+                    result = run.join_next() => result,
+                    _ = shutdown.wait() => None,
+                };
+                let failure = match result {
+                    Some(Ok(Ok(()))) if !shutdown.is_triggered() => {
+                        update(&|h| h.state = TaskState::Finished);
+                        return;
+                    }
+                    Some(Ok(Err(err))) => err.to_string(),
+                    Some(Err(err)) if err.is_panic() => panic_message(err.into_panic()),
+                    _ => {
+                        update(&|h| h.state = TaskState::Stopped);
+                        return;
+                    }
+                };
+                if started.elapsed() >= MAX_RESTART_DELAY {
+                    delay = MIN_RESTART_DELAY;
+                }
+                update(&|h| {
+                    h.state = TaskState::Restarting;
+                    h.restarts += 1;
+                    h.last_failure = Some(failure.clone());
+                });
+                tokio::select! {
+                    _ = sleep(jittered(delay)) => {}
+                    _ = shutdown.wait() => {
+                        update(&|h| h.state = TaskState::Stopped);
+                        return;
+                    }
+                }
+                delay = (delay * 2).min(MAX_RESTART_DELAY);
+            }
+        });
+        SupervisedTask { health, handle }
+    }
+
+    /// Current health.
+    pub(crate) fn health(&self) -> TaskHealth {
+        self.health
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Stop the task without restarting it.
+    pub(crate) fn stop(&self) {
+        self.handle.abort();
+        let mut health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        if health.state != TaskState::Finished {
+            health.state = TaskState::Stopped;
+        }
+    }
+}
+
+impl Drop for SupervisedTask {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Owner of supervised background tasks.
+///
+/// Tasks are restarted after a panic or an error with exponential backoff
+/// (1 second doubling to 1 minute) and stopped when the supervisor is
+/// dropped or its [`Shutdown`] token is triggered.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use dceapi_rs::{Client, Supervisor};
+///
+/// # async fn run(client: Client) {
+/// let mut supervisor = Supervisor::new().with_shutdown(client.shutdown_token());
+/// let catalog = client.catalog().clone();
+/// supervisor.spawn("catalog refresh", move || {
+///     let catalog = catalog.clone();
+///     async move {
+///         loop {
+///             catalog.refresh_all().await?;
+///             tokio::time::sleep(Duration::from_secs(3600)).await;
+///         }
+///     }
+/// });
+/// for task in supervisor.health() {
+///     println!("{}: {:?} ({} restarts)", task.name, task.state, task.restarts);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Supervisor {
+    tasks: Vec<SupervisedTask>,
+    shutdown: Shutdown,
+}
+
+impl Supervisor {
+    /// Create a supervisor with no tasks and its own shutdown token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop tasks spawned from now on when `shutdown` is triggered, for
+    /// example a client's [`shutdown_token`](crate::Client::shutdown_token).
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Spawn a supervised task. `factory` creates the task's future, again
+    /// on every restart; a task that returns `Ok` is not restarted. State
+    /// the future owns starts over on a restart, so state that must survive
+    /// one, such as what has already been processed, belongs in something
+    /// `factory` shares with each future (e.g. an `Arc`). Must be called
+    /// within a Tokio runtime.
+    pub fn spawn<F, Fut>(&mut self, name: impl Into<String>, factory: F)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.tasks
+            .push(SupervisedTask::spawn(name, &self.shutdown, factory));
+    }
+
+    /// Health of every task, in the order they were spawned.
+    pub fn health(&self) -> Vec<TaskHealth> {
+        self.tasks.iter().map(SupervisedTask::health).collect()
+    }
+
+    /// Whether every task is running or finished normally.
+    pub fn is_healthy(&self) -> bool {
+        self.tasks.iter().all(|t| t.health().is_healthy())
+    }
+
+    /// Stop every task.
+    pub fn stop(&self) {
+        for task in &self.tasks {
+            task.stop();
+        }
+    }
+}
+
+/// Message of a panic payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => format!("panicked: {}", message),
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => format!("panicked: {}", message),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    use super::*;
+    use crate::error::Error;
+
+    /// Sets a flag when the future owning it is dropped.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Wait up to five seconds for `done` to hold.
+    async fn eventually(done: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() {
+            assert!(Instant::now() < deadline, "condition not reached in time");
+            sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    /// A task that runs until cancelled, flagging when its future is dropped.
+    fn pending_task(
+        runs: &Arc<AtomicU32>,
+        dropped: &Arc<AtomicBool>,
+    ) -> impl FnMut() -> std::pin::Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+        let runs = Arc::clone(runs);
+        let dropped = Arc::clone(dropped);
+        move || {
+            runs.fetch_add(1, Ordering::SeqCst);
+            let flag = DropFlag(Arc::clone(&dropped));
+            Box::pin(async move {
+                let _flag = flag;
+                std::future::pending::<()>().await;
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_tasks_restart_and_keep_shared_state() {
+        // What the factory shares with each run survives the restart.
+        let mut supervisor = Supervisor::new();
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        let shared = Arc::clone(&processed);
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&runs);
+        supervisor.spawn("flaky", move || {
+            let processed = Arc::clone(&shared);
+            let run = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                processed.lock().unwrap().push(run);
+                match run {
+                    0 => Err(Error::validation("task", "first run fails")),
+                    _ => Ok(()),
+                }
+            }
+        });
+
+        eventually(|| supervisor.health()[0].state == TaskState::Finished).await;
+        let health = &supervisor.health()[0];
+        assert_eq!(health.name, "flaky");
+        assert_eq!(health.restarts, 1);
+        assert!(health
+            .last_failure
+            .as_deref()
+            .unwrap()
+            .contains("first run fails"));
+        assert!(supervisor.is_healthy());
+        assert_eq!(*processed.lock().unwrap(), [0, 1]);
+    }
+
+    #[tokio::test]
+    async fn panics_are_reported_and_restarted() {
+        let mut supervisor = Supervisor::new();
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&runs);
+        supervisor.spawn("panicky", move || {
+            let run = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if run == 0 {
+                    panic!("boom");
+                }
+                std::future::pending::<()>().await;
+                Ok(())
+            }
+        });
+
+        eventually(|| supervisor.health()[0].state == TaskState::Restarting).await;
+        assert!(!supervisor.is_healthy());
+        assert_eq!(
+            supervisor.health()[0].last_failure.as_deref(),
+            Some("panicked: boom")
+        );
+        eventually(|| runs.load(Ordering::SeqCst) == 2).await;
+        eventually(|| supervisor.health()[0].state == TaskState::Running).await;
+        supervisor.stop();
+    }
+
+    #[tokio::test]
+    async fn stop_cancels_in_flight_work_and_spawning_starts_again() {
+        let mut supervisor = Supervisor::new();
+        let runs = Arc::new(AtomicU32::new(0));
+        let dropped = Arc::new(AtomicBool::new(false));
+        supervisor.spawn("first", pending_task(&runs, &dropped));
+        eventually(|| runs.load(Ordering::SeqCst) == 1).await;
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        supervisor.stop();
+        assert_eq!(supervisor.health()[0].state, TaskState::Stopped);
+        eventually(|| dropped.load(Ordering::SeqCst)).await;
+
+        // A task spawned after a stop runs; the stopped one stays stopped.
+        let again = Arc::new(AtomicBool::new(false));
+        supervisor.spawn("second", pending_task(&runs, &again));
+        eventually(|| runs.load(Ordering::SeqCst) == 2).await;
+        let states: Vec<_> = supervisor.health().iter().map(|h| h.state).collect();
+        assert_eq!(states, [TaskState::Stopped, TaskState::Running]);
+        assert_eq!(supervisor.health()[0].restarts, 0);
+        drop(supervisor);
+        eventually(|| again.load(Ordering::SeqCst)).await;
+    }
+
+    #[tokio::test]
+    async fn shutdown_stops_tasks_and_waits_for_them() {
+        let shutdown = Shutdown::new();
+        let mut supervisor = Supervisor::new().with_shutdown(shutdown.clone());
+        let runs = Arc::new(AtomicU32::new(0));
+        let dropped = Arc::new(AtomicBool::new(false));
+        supervisor.spawn("watcher", pending_task(&runs, &dropped));
+        eventually(|| runs.load(Ordering::SeqCst) == 1).await;
+        assert_eq!(shutdown.running_tasks(), 1);
+
+        shutdown.shutdown().await;
+        assert_eq!(shutdown.running_tasks(), 0);
+        assert_eq!(supervisor.health()[0].state, TaskState::Stopped);
+        eventually(|| dropped.load(Ordering::SeqCst)).await;
+
+        // Tasks spawned on a triggered token stop without restarting.
+        supervisor.spawn("late", pending_task(&runs, &dropped));
+        eventually(|| supervisor.health()[1].state == TaskState::Stopped).await;
+        assert_eq!(supervisor.health()[1].restarts, 0);
+    }
+}
@@ -9,6 +9,7 @@ use reqwest::Client as HttpClient;
 use serde::Serialize;
 use tokio::sync::RwLock;
 
+use crate::auth::{ApiKeyAuth, AuthContext, AuthScheme};
 use crate::error::{Error, ErrorCode, Result};
 use crate::models::{ApiResponse, TokenResponse};
 
@@ -50,6 +51,7 @@ pub struct TokenManager {
     secret: String,
     base_url: String,
     http_client: HttpClient,
+    auth_scheme: Arc<dyn AuthScheme>,
     state: Arc<RwLock<TokenState>>,
 }
 
@@ -66,10 +68,17 @@ impl TokenManager {
             secret: secret.into(),
             base_url: base_url.into(),
             http_client,
+            auth_scheme: Arc::new(ApiKeyAuth),
             state: Arc::new(RwLock::new(TokenState::default())),
         }
     }
 
+    /// Set the authentication scheme used for token requests.
+    pub fn with_auth_scheme(mut self, auth_scheme: Arc<dyn AuthScheme>) -> Self {
+        self.auth_scheme = auth_scheme;
+        self
+    }
+
     /// Get a valid access token.
     ///
     /// Returns a cached token if still valid, otherwise acquires a new one.
@@ -113,12 +122,25 @@ impl TokenManager {
             secret: self.secret.clone(),
         };
 
-        let response = self
+        let body = serde_json::to_vec(&req_body)
+            .map_err(|e| Error::auth(format!("failed to encode auth request: {}", e)))?;
+
+        let request = self
             .http_client
             .post(&auth_url)
-            .header("Content-Type", "application/json")
-            .header("apikey", &self.api_key)
-            .json(&req_body)
+            .header("Content-Type", "application/json");
+        let ctx = AuthContext {
+            api_key: &self.api_key,
+            token: None,
+            method: &reqwest::Method::POST,
+            url: &auth_url,
+            body: &body,
+        };
+
+        let response = self
+            .auth_scheme
+            .apply(request, &ctx)?
+            .body(body)
             .send()
             .await
             .map_err(|e| Error::auth(format!("failed to send auth request: {}", e)))?;
@@ -0,0 +1,180 @@
+//! Parsing of Chinese-formatted amounts.
+//!
+//! Turnover and amount fields arrive as "1,234.5", "1234.5万" or in 万元
+//! units without any marker. [`Amount`] keeps the parsed value together
+//! with its [`AmountUnit`], so values can be converted to one unit before
+//! they are summed or compared.
+
+use std::fmt;
+
+use crate::models::parse_optional_f64;
+
+/// Unit of an amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AmountUnit {
+    /// Plain units (元, lots).
+    #[default]
+    One,
+    /// Ten thousand (万).
+    TenThousand,
+    /// One hundred million (亿).
+    HundredMillion,
+}
+
+impl AmountUnit {
+    /// Multiplier from this unit to plain units.
+    pub fn factor(&self) -> f64 {
+        match self {
+            AmountUnit::One => 1.0,
+            AmountUnit::TenThousand => 1e4,
+            AmountUnit::HundredMillion => 1e8,
+        }
+    }
+
+    /// Chinese suffix of the unit ("", "万" or "亿").
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            AmountUnit::One => "",
+            AmountUnit::TenThousand => "万",
+            AmountUnit::HundredMillion => "亿",
+        }
+    }
+}
+
+impl fmt::Display for AmountUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.suffix())
+    }
+}
+
+/// A numeric amount with an explicit unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Amount {
+    /// Value as written, in `unit`.
+    pub value: f64,
+    /// Unit of `value`.
+    pub unit: AmountUnit,
+}
+
+impl Amount {
+    /// Create an amount.
+    pub fn new(value: f64, unit: AmountUnit) -> Self {
+        Amount { value, unit }
+    }
+
+    /// Parse an amount such as "1,234.5", "3.2万", "1.05亿元" or "5,000 元".
+    ///
+    /// A 万/亿 suffix sets the unit; otherwise `default_unit` applies (use
+    /// [`AmountUnit::TenThousand`] for fields published in 万元). Thousands
+    /// separators (including full-width "，") are ignored. Returns `None` for
+    /// empty strings, "-" placeholders and non-numeric text.
+    ///
+    /// ```
+    /// use dceapi_rs::{Amount, AmountUnit};
+    ///
+    /// let amount = Amount::parse("1.05亿元", AmountUnit::One).unwrap();
+    /// assert_eq!(amount.unit, AmountUnit::HundredMillion);
+    /// assert_eq!(amount.in_unit(AmountUnit::TenThousand), 10500.0);
+    ///
+    /// let amount = Amount::parse("1,234.5", AmountUnit::TenThousand).unwrap();
+    /// assert_eq!(amount.base_value(), 12_345_000.0);
+    /// assert_eq!(Amount::parse("-", AmountUnit::One), None);
+    /// ```
+    pub fn parse(raw: &str, default_unit: AmountUnit) -> Option<Amount> {
+        let mut text = raw.trim().trim_end_matches('元').trim_end();
+        let mut unit = default_unit;
+        for candidate in [AmountUnit::TenThousand, AmountUnit::HundredMillion] {
+            if let Some(stripped) = text.strip_suffix(candidate.suffix()) {
+                text = stripped.trim_end();
+                unit = candidate;
+                break;
+            }
+        }
+        let cleaned: String = text
+            .chars()
+            .filter(|c| !matches!(c, ',' | '，' | ' '))
+            .collect();
+        let value = parse_optional_f64(&cleaned)?;
+        Some(Amount { value, unit })
+    }
+
+    /// Value in plain units (e.g. 元).
+    pub fn base_value(&self) -> f64 {
+        self.value * self.unit.factor()
+    }
+
+    /// Value expressed in another unit.
+    pub fn in_unit(&self, unit: AmountUnit) -> f64 {
+        self.base_value() / unit.factor()
+    }
+
+    /// Convert to another unit.
+    pub fn to_unit(&self, unit: AmountUnit) -> Amount {
+        Amount {
+            value: self.in_unit(unit),
+            unit,
+        }
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suffixes_and_separators_are_parsed() {
+        let parse = |raw: &str| Amount::parse(raw, AmountUnit::One);
+        assert_eq!(
+            parse("3.2万"),
+            Some(Amount::new(3.2, AmountUnit::TenThousand))
+        );
+        assert_eq!(
+            parse(" 1.05 亿元 "),
+            Some(Amount::new(1.05, AmountUnit::HundredMillion))
+        );
+        assert_eq!(
+            parse("5,000 元"),
+            Some(Amount::new(5000.0, AmountUnit::One))
+        );
+        assert_eq!(
+            parse("1，234，567.5"),
+            Some(Amount::new(1_234_567.5, AmountUnit::One))
+        );
+        assert_eq!(
+            parse("-12.5万"),
+            Some(Amount::new(-12.5, AmountUnit::TenThousand))
+        );
+        assert_eq!(
+            Amount::parse("42", AmountUnit::TenThousand),
+            Some(Amount::new(42.0, AmountUnit::TenThousand))
+        );
+    }
+
+    #[test]
+    fn placeholders_and_text_do_not_parse() {
+        for raw in ["", "  ", "-", "--", "万", "元", "abc", "1.2.3万"] {
+            assert_eq!(Amount::parse(raw, AmountUnit::One), None, "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn amounts_convert_between_units() {
+        let amount = Amount::new(2.5, AmountUnit::HundredMillion);
+        assert_eq!(amount.base_value(), 250_000_000.0);
+        assert_eq!(amount.in_unit(AmountUnit::TenThousand), 25_000.0);
+        assert_eq!(
+            amount.to_unit(AmountUnit::TenThousand),
+            Amount::new(25_000.0, AmountUnit::TenThousand)
+        );
+        assert_eq!(Amount::new(0.0, AmountUnit::TenThousand).base_value(), 0.0);
+        assert_eq!(amount.to_string(), "2.5亿");
+        assert_eq!(Amount::new(7.0, AmountUnit::One).to_string(), "7");
+        assert_eq!(AmountUnit::default().factor(), 1.0);
+    }
+}
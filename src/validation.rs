@@ -0,0 +1,562 @@
+//! Client-side validation of request models.
+//!
+//! Services validate every request before it is sent, so malformed dates,
+//! reversed ranges or missing variety IDs fail locally with
+//! [`Error::Validation`] instead of an opaque 400 from the API.
+
+use crate::days::day_number;
+use crate::delivery_month::DeliveryMonth;
+use crate::error::{Result, ValidationErrors};
+use crate::models::*;
+
+/// Request models that can be checked before they are sent.
+pub trait Validate {
+    /// Check the request.
+    ///
+    /// Returns [`Error::Validation`](crate::Error::Validation) for a single
+    /// problem and [`Error::ValidationErrors`](crate::Error::ValidationErrors)
+    /// for several.
+    fn validate(&self) -> Result<()>;
+}
+
+/// Record an error if a required value is blank.
+fn require(errors: &mut ValidationErrors, field: &str, value: &str) -> bool {
+    if value.trim().is_empty() {
+        errors.add(field, format!("{} is required", field));
+        return false;
+    }
+    true
+}
+
+/// Check a date in YYYYMMDD format.
+fn date(errors: &mut ValidationErrors, field: &str, value: &str) -> bool {
+    if !require(errors, field, value) {
+        return false;
+    }
+    let valid = value.len() == 8
+        && value.bytes().all(|b| b.is_ascii_digit())
+        && day_number(value).is_some();
+    if !valid {
+        errors.add(field, format!("expected a YYYYMMDD date, got {:?}", value));
+    }
+    valid
+}
+
+/// Check a month in YYYYMM format.
+fn month(errors: &mut ValidationErrors, field: &str, value: &str) -> bool {
+    if !require(errors, field, value) {
+        return false;
+    }
+    let valid = value.len() == 6
+        && value.bytes().all(|b| b.is_ascii_digit())
+        && matches!(value[4..].parse::<u32>(), Ok(1..=12));
+    if !valid {
+        errors.add(field, format!("expected a YYYYMM month, got {:?}", value));
+    }
+    valid
+}
+
+/// Check that a start/end pair is not reversed.
+///
+/// Both values must already be valid and of the same format.
+fn range(errors: &mut ValidationErrors, start: (&str, &str), end: (&str, &str)) {
+    if start.1 > end.1 {
+        errors.add(
+            end.0,
+            format!("{} ({}) is before {} ({})", end.0, end.1, start.0, start.1),
+        );
+    }
+}
+
+/// Check a pair of YYYYMMDD dates and their order.
+fn date_range(errors: &mut ValidationErrors, start: (&str, &str), end: (&str, &str)) {
+    let start_ok = date(errors, start.0, start.1);
+    let end_ok = date(errors, end.0, end.1);
+    if start_ok && end_ok {
+        range(errors, start, end);
+    }
+}
+
+/// Check that a delivery month range is not reversed.
+fn delivery_month_range(
+    errors: &mut ValidationErrors,
+    start: (&str, DeliveryMonth),
+    end: (&str, DeliveryMonth),
+) {
+    if start.1 > end.1 {
+        errors.add(
+            end.0,
+            format!("{} ({}) is before {} ({})", end.0, end.1, start.0, start.1),
+        );
+    }
+}
+
+/// Check a pair of YYYYMM months and their order.
+fn month_range(errors: &mut ValidationErrors, start: (&str, &str), end: (&str, &str)) {
+    let start_ok = month(errors, start.0, start.1);
+    let end_ok = month(errors, end.0, end.1);
+    if start_ok && end_ok {
+        range(errors, start, end);
+    }
+}
+
+impl Validate for GetArticleByPageRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        if let ColumnId::Custom(id) = &self.column_id {
+            if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+                errors.add(
+                    "column_id",
+                    format!("invalid column_id {:?}, expected a numeric column ID", id),
+                );
+            }
+        }
+        if self.page_no < 1 {
+            errors.add("page_no", "page_no must be at least 1");
+        }
+        if self.page_size < 1 {
+            errors.add("page_size", "page_size must be at least 1");
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for QuotesRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        match (&self.variety_id, &self.variety) {
+            (Some(_), Some(_)) => {
+                errors.add("variety", "set either variety or variety_id, not both");
+            }
+            (Some(variety_id), None) => {
+                require(&mut errors, "variety_id", variety_id);
+            }
+            (None, Some(variety)) => {
+                require(&mut errors, "variety", variety);
+            }
+            (None, None) => {
+                errors.add("variety_id", "variety_id or variety is required");
+            }
+        }
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for WeekQuotesRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_code", &self.variety_code);
+        if self.year < 1 {
+            errors.add("year", format!("invalid year {}", self.year));
+        }
+        if !(1..=53).contains(&self.week) {
+            errors.add(
+                "week",
+                format!("week must be between 1 and 53, got {}", self.week),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for MonthQuotesRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_code", &self.variety_code);
+        if self.year < 1 {
+            errors.add("year", format!("invalid year {}", self.year));
+        }
+        if !(1..=12).contains(&self.month) {
+            errors.add(
+                "month",
+                format!("month must be between 1 and 12, got {}", self.month),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for DeliveryDataRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        delivery_month_range(
+            &mut errors,
+            ("start_month", self.start_month),
+            ("end_month", self.end_month),
+        );
+        errors.into_result()
+    }
+}
+
+impl Validate for DeliveryMatchRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        require(&mut errors, "contract_id", &self.contract_id);
+        delivery_month_range(
+            &mut errors,
+            ("start_month", self.start_month),
+            ("end_month", self.end_month),
+        );
+        errors.into_result()
+    }
+}
+
+impl Validate for WarehouseReceiptRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for DailyRankingRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        require(&mut errors, "contract_id", &self.contract_id);
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for PhaseRankingRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety", &self.variety);
+        month_range(
+            &mut errors,
+            ("start_month", &self.start_month),
+            ("end_month", &self.end_month),
+        );
+        errors.into_result()
+    }
+}
+
+impl Validate for DayTradeParamRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        errors.into_result()
+    }
+}
+
+impl Validate for ContractInfoRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        errors.into_result()
+    }
+}
+
+impl Validate for ArbitrageContractRequest {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Validate for SettleParamRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for VarietyMonthYearStatRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        month(&mut errors, "trade_month", &self.trade_month);
+        errors.into_result()
+    }
+}
+
+impl Validate for ContractMonthMaxRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        delivery_month_range(
+            &mut errors,
+            ("start_month", self.start_month),
+            ("end_month", self.end_month),
+        );
+        errors.into_result()
+    }
+}
+
+impl Validate for RiseFallEventRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        date_range(
+            &mut errors,
+            ("start_date", &self.start_date),
+            ("end_date", &self.end_date),
+        );
+        require(&mut errors, "variety_id", &self.variety_id);
+        errors.into_result()
+    }
+}
+
+impl Validate for DivisionPriceInfoRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for TradingParamRequest {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Validate for MarginArbiPerfParaRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        errors.into_result()
+    }
+}
+
+impl Validate for NewContractInfoRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for MainSeriesInfoRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for TcCongregateDeliveryRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety", &self.variety);
+        month(&mut errors, "contract_month", &self.contract_month);
+        errors.into_result()
+    }
+}
+
+impl Validate for RollDeliverySellerIntentionRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety", &self.variety);
+        date(&mut errors, "date", &self.date);
+        errors.into_result()
+    }
+}
+
+impl Validate for BondedDeliveryRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        date_range(
+            &mut errors,
+            ("start_date", &self.start_date),
+            ("end_date", &self.end_date),
+        );
+        errors.into_result()
+    }
+}
+
+impl Validate for TdBondedDeliveryRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        date_range(
+            &mut errors,
+            ("start_date", &self.start_date),
+            ("end_date", &self.end_date),
+        );
+        errors.into_result()
+    }
+}
+
+impl Validate for FactorySpotAgioRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        date(&mut errors, "trade_date", &self.trade_date);
+        errors.into_result()
+    }
+}
+
+impl Validate for PlywoodDeliveryCommodityRequest {
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        require(&mut errors, "variety_id", &self.variety_id);
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    /// Fields of the errors a validation reported.
+    fn fields(result: Result<()>) -> Vec<String> {
+        result
+            .unwrap_err()
+            .field_errors()
+            .into_iter()
+            .map(|e| e.field)
+            .collect()
+    }
+
+    fn settle_param(variety_id: &str, trade_date: &str) -> SettleParamRequest {
+        SettleParamRequest {
+            variety_id: variety_id.to_string(),
+            trade_date: trade_date.to_string(),
+            trade_type: TradeType::Futures,
+            lang: Lang::Zh,
+        }
+    }
+
+    #[test]
+    fn dates_must_be_real_yyyymmdd_days() {
+        for trade_date in ["20250930", "20240229", "20000229"] {
+            assert!(
+                settle_param("m", trade_date).validate().is_ok(),
+                "{trade_date}"
+            );
+        }
+        for trade_date in [
+            "20250230",
+            "19000229",
+            "20251301",
+            "20250900",
+            "2025093",
+            "2025-09-30",
+            "２0250930",
+        ] {
+            let err = settle_param("m", trade_date).validate().unwrap_err();
+            assert!(matches!(err, Error::Validation { ref field, .. } if field == "trade_date"));
+        }
+    }
+
+    #[test]
+    fn blank_required_fields_are_reported_together() {
+        let err = settle_param(" ", "").validate().unwrap_err();
+        assert!(matches!(err, Error::ValidationErrors(ref errors) if errors.len() == 2));
+        assert_eq!(fields(Err(err)), ["variety_id", "trade_date"]);
+
+        let req = DailyRankingRequest {
+            variety_id: "m".to_string(),
+            contract_id: String::new(),
+            trade_date: "20250930".to_string(),
+            trade_type: TradeType::Futures,
+        };
+        assert_eq!(fields(req.validate()), ["contract_id"]);
+    }
+
+    #[test]
+    fn quotes_need_exactly_one_variety_field() {
+        let req = |variety_id: Option<&str>, variety: Option<&str>| QuotesRequest {
+            variety_id: variety_id.map(str::to_string),
+            variety: variety.map(str::to_string),
+            trade_date: "20250930".to_string(),
+            trade_type: TradeType::Futures,
+            lang: None,
+            statistics_type: None,
+        };
+        assert!(req(Some("m"), None).validate().is_ok());
+        assert!(req(None, Some("豆粕")).validate().is_ok());
+        assert_eq!(fields(req(Some("m"), Some("豆粕")).validate()), ["variety"]);
+        assert_eq!(fields(req(None, None).validate()), ["variety_id"]);
+        assert_eq!(fields(req(Some(""), None).validate()), ["variety_id"]);
+    }
+
+    #[test]
+    fn out_of_range_numbers_are_rejected() {
+        let week = |year, week| WeekQuotesRequest {
+            variety_code: "m".to_string(),
+            year,
+            week,
+        };
+        assert!(week(2025, 1).validate().is_ok());
+        assert!(week(2020, 53).validate().is_ok());
+        assert_eq!(fields(week(2025, 0).validate()), ["week"]);
+        assert_eq!(fields(week(0, 54).validate()), ["year", "week"]);
+
+        let month = |month| MonthQuotesRequest {
+            variety_code: "m".to_string(),
+            year: 2025,
+            month,
+        };
+        assert!(month(12).validate().is_ok());
+        assert_eq!(fields(month(13).validate()), ["month"]);
+        assert_eq!(fields(month(0).validate()), ["month"]);
+
+        let page = |column_id, page_no, page_size| GetArticleByPageRequest {
+            column_id,
+            page_no,
+            page_size,
+            site_id: 5,
+        };
+        assert!(page(ColumnId::DailyTips, 1, 10).validate().is_ok());
+        assert_eq!(
+            fields(page(ColumnId::DailyTips, 0, 0).validate()),
+            ["page_no", "page_size"]
+        );
+        assert_eq!(
+            fields(page(ColumnId::Custom("12a".to_string()), 1, 10).validate()),
+            ["column_id"]
+        );
+    }
+
+    #[test]
+    fn reversed_ranges_are_rejected() {
+        let events = |start_date: &str, end_date: &str| RiseFallEventRequest {
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            variety_id: "m".to_string(),
+            lang: Lang::Zh,
+        };
+        assert!(events("20250901", "20250901").validate().is_ok());
+        assert_eq!(
+            fields(events("20250902", "20250901").validate()),
+            ["end_date"]
+        );
+        // An invalid end date is reported once, without a range error.
+        assert_eq!(
+            fields(events("20250902", "20250931").validate()),
+            ["end_date"]
+        );
+
+        let ranking = |start_month: &str, end_month: &str| PhaseRankingRequest {
+            variety: "m".to_string(),
+            start_month: start_month.to_string(),
+            end_month: end_month.to_string(),
+            trade_type: TradeType::Futures,
+        };
+        assert!(ranking("202501", "202509").validate().is_ok());
+        assert_eq!(
+            fields(ranking("202509", "202501").validate()),
+            ["end_month"]
+        );
+        assert_eq!(
+            fields(ranking("202500", "2025-09").validate()),
+            ["start_month", "end_month"]
+        );
+
+        let month = |raw| DeliveryMonth::parse(raw).unwrap();
+        let delivery = DeliveryDataRequest {
+            variety_id: "m".to_string(),
+            start_month: month("202509"),
+            end_month: month("202501"),
+            variety_type: VarietyType::default(),
+        };
+        assert_eq!(fields(delivery.validate()), ["end_month"]);
+    }
+}
@@ -0,0 +1,207 @@
+//! Listed DCE varieties and their contract specifications.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::models::{Lang, Variety};
+
+/// Contract specification of a listed variety.
+struct Spec {
+    code: &'static str,
+    name_zh: &'static str,
+    name_en: &'static str,
+    contract_size: f64,
+    unit_zh: &'static str,
+    unit_en: &'static str,
+    tick_size: f64,
+    night_session: bool,
+}
+
+/// Define [`VarietyCode`] and its specification table.
+macro_rules! varieties {
+    ($(
+        $variant:ident => $code:literal, $name_zh:literal, $name_en:literal,
+            $size:literal $unit_zh:literal $unit_en:literal, tick $tick:literal,
+            night $night:literal;
+    )*) => {
+        /// Variety code (品种代码).
+        ///
+        /// Covers the varieties listed on the exchange; use
+        /// [`VarietyCode::Custom`] for new listings. Serialized as the code
+        /// ("m").
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum VarietyCode {
+            $(
+                #[doc = concat!("`", $code, "` - ", $name_zh, " (", $name_en, ").")]
+                $variant,
+            )*
+            /// Any other variety code.
+            Custom(String),
+        }
+
+        impl VarietyCode {
+            /// All listed varieties.
+            pub const ALL: &'static [VarietyCode] = &[$(VarietyCode::$variant),*];
+
+            /// Specification of a listed variety.
+            fn spec(&self) -> Option<&'static Spec> {
+                match self {
+                    $(
+                        VarietyCode::$variant => Some(&Spec {
+                            code: $code,
+                            name_zh: $name_zh,
+                            name_en: $name_en,
+                            contract_size: $size,
+                            unit_zh: $unit_zh,
+                            unit_en: $unit_en,
+                            tick_size: $tick,
+                            night_session: $night,
+                        }),
+                    )*
+                    VarietyCode::Custom(_) => None,
+                }
+            }
+        }
+    };
+}
+
+varieties! {
+    SoybeanNo1 => "a", "黄大豆1号", "No.1 Soybean", 10.0 "吨" "t", tick 1.0, night true;
+    SoybeanNo2 => "b", "黄大豆2号", "No.2 Soybean", 10.0 "吨" "t", tick 1.0, night true;
+    SoybeanMeal => "m", "豆粕", "Soybean Meal", 10.0 "吨" "t", tick 1.0, night true;
+    SoybeanOil => "y", "豆油", "Soybean Oil", 10.0 "吨" "t", tick 2.0, night true;
+    PalmOil => "p", "棕榈油", "RBD Palm Olein", 10.0 "吨" "t", tick 2.0, night true;
+    Corn => "c", "玉米", "Corn", 10.0 "吨" "t", tick 1.0, night true;
+    CornStarch => "cs", "玉米淀粉", "Corn Starch", 10.0 "吨" "t", tick 1.0, night true;
+    Egg => "jd", "鸡蛋", "Egg", 10.0 "吨" "t", tick 1.0, night false;
+    JaponicaRice => "rr", "粳米", "Japonica Rice", 10.0 "吨" "t", tick 1.0, night true;
+    LiveHog => "lh", "生猪", "Live Hog", 16.0 "吨" "t", tick 5.0, night false;
+    Fiberboard => "fb", "纤维板", "Fiberboard", 10.0 "立方米" "m³", tick 0.5, night false;
+    Blockboard => "bb", "胶合板", "Blockboard", 500.0 "张" "sheets", tick 0.05, night false;
+    Log => "lg", "原木", "Log", 90.0 "立方米" "m³", tick 0.5, night false;
+    Lldpe => "l", "聚乙烯", "LLDPE", 5.0 "吨" "t", tick 1.0, night true;
+    Pvc => "v", "聚氯乙烯", "PVC", 5.0 "吨" "t", tick 1.0, night true;
+    Polypropylene => "pp", "聚丙烯", "Polypropylene", 5.0 "吨" "t", tick 1.0, night true;
+    EthyleneGlycol => "eg", "乙二醇", "Ethylene Glycol", 10.0 "吨" "t", tick 1.0, night true;
+    Styrene => "eb", "苯乙烯", "Styrene", 5.0 "吨" "t", tick 1.0, night true;
+    Lpg => "pg", "液化石油气", "LPG", 20.0 "吨" "t", tick 1.0, night true;
+    PureBenzene => "bz", "纯苯", "Pure Benzene", 30.0 "吨" "t", tick 1.0, night true;
+    Coke => "j", "焦炭", "Coke", 100.0 "吨" "t", tick 0.5, night true;
+    CokingCoal => "jm", "焦煤", "Coking Coal", 60.0 "吨" "t", tick 0.5, night true;
+    IronOre => "i", "铁矿石", "Iron Ore", 100.0 "吨" "t", tick 0.5, night true;
+}
+
+impl VarietyCode {
+    /// Get the variety code (e.g. "m").
+    pub fn code(&self) -> &str {
+        match self {
+            VarietyCode::Custom(code) => code,
+            known => known.spec().map_or("", |s| s.code),
+        }
+    }
+
+    /// Get the variety name in the given language.
+    ///
+    /// Custom varieties return their code.
+    pub fn name(&self, lang: Lang) -> &str {
+        match (self.spec(), lang) {
+            (Some(spec), Lang::Zh) => spec.name_zh,
+            (Some(spec), Lang::En) => spec.name_en,
+            (None, _) => self.code(),
+        }
+    }
+
+    /// Contract size in [`unit`](Self::unit)s per lot.
+    pub fn contract_size(&self) -> Option<f64> {
+        self.spec().map(|s| s.contract_size)
+    }
+
+    /// Unit of the contract size (吨/t, 立方米/m³, 张/sheets).
+    pub fn unit(&self, lang: Lang) -> Option<&'static str> {
+        self.spec().map(|s| match lang {
+            Lang::Zh => s.unit_zh,
+            Lang::En => s.unit_en,
+        })
+    }
+
+    /// Minimum price fluctuation, in yuan per quoting unit.
+    ///
+    /// Prices are quoted per unit of the contract size, except eggs, which
+    /// are quoted per 500 kg.
+    pub fn tick_size(&self) -> Option<f64> {
+        self.spec().map(|s| s.tick_size)
+    }
+
+    /// Whether the variety trades in the night session (21:00-23:00).
+    ///
+    /// `None` for custom varieties, whose schedule is not known.
+    pub fn has_night_session(&self) -> Option<bool> {
+        self.spec().map(|s| s.night_session)
+    }
+
+    /// Whether this is one of the listed varieties.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, VarietyCode::Custom(_))
+    }
+}
+
+impl Variety {
+    /// Variety as a [`VarietyCode`].
+    pub fn variety_code(&self) -> VarietyCode {
+        VarietyCode::from(self.code.as_str())
+    }
+
+    /// Whether the variety trades in the night session; see
+    /// [`VarietyCode::has_night_session`].
+    pub fn has_night_session(&self) -> Option<bool> {
+        self.variety_code().has_night_session()
+    }
+}
+
+impl fmt::Display for VarietyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Maps listed codes (case-insensitive) to their variant; anything else
+/// becomes [`VarietyCode::Custom`].
+impl From<&str> for VarietyCode {
+    fn from(value: &str) -> Self {
+        let value = value.trim().to_lowercase();
+        VarietyCode::ALL
+            .iter()
+            .find(|v| v.code() == value)
+            .cloned()
+            .unwrap_or(VarietyCode::Custom(value))
+    }
+}
+
+impl From<String> for VarietyCode {
+    fn from(value: String) -> Self {
+        VarietyCode::from(value.as_str())
+    }
+}
+
+impl FromStr for VarietyCode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(VarietyCode::from(s))
+    }
+}
+
+impl Serialize for VarietyCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for VarietyCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(VarietyCode::from)
+    }
+}
@@ -0,0 +1,314 @@
+//! Polling watchers.
+//!
+//! The API has no push feed, so watchers poll an endpoint on an interval in
+//! a background task and deliver what changed through a [`Watch`], which
+//! can be awaited item by item or consumed as a [`Stream`]. The task is
+//! supervised: a poller that panics is restarted with backoff. The wait
+//! between polls follows a [`PollSchedule`], is spread with up to 10%
+//! random jitter, and grows while the API reports rate limiting.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures_core::Stream;
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+use crate::error::{ErrorCode, Result};
+use crate::session::PollSchedule;
+use crate::shutdown::Shutdown;
+use crate::supervisor::{SupervisedTask, TaskHealth};
+
+/// Number of undelivered items buffered before the poller waits.
+const WATCH_BUFFER: usize = 16;
+
+/// Largest random delay added to a poll period, as a fraction of it.
+const MAX_JITTER: f64 = 0.1;
+
+/// Largest multiple of the poll interval waited after rate limiting.
+pub(crate) const MAX_WATCH_BACKOFF: u32 = 8;
+
+/// Handle to a background poller.
+///
+/// Yields `Ok` items as changes are detected and `Err` for failed polls;
+/// polling continues after errors. Dropping the handle, or shutting down
+/// the client it came from, stops the poller.
+#[derive(Debug)]
+pub struct Watch<T> {
+    rx: mpsc::Receiver<Result<T>>,
+    task: SupervisedTask,
+}
+
+impl<T: Send + 'static> Watch<T> {
+    /// Spawn a supervised poller. `run` receives the sending half and
+    /// returns when sending fails (the handle was dropped) or the watch is
+    /// finished. It is called again if the poller panics; state owned by
+    /// the future it returns starts over, so what has already been yielded
+    /// must be tracked outside it (as [`poll_changes`] does) for a restart
+    /// not to yield it again. The poller exits when `shutdown` is triggered.
+    pub(crate) fn spawn<F, Fut>(name: &str, shutdown: &Shutdown, mut run: F) -> Watch<T>
+    where
+        F: FnMut(WatchSender<T>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(WATCH_BUFFER);
+        let tx = WatchSender { tx };
+        let task = SupervisedTask::spawn(name, shutdown, move || {
+            let poll = run(tx.clone());
+            async move {
+                poll.await;
+                Ok(())
+            }
+        });
+        Watch { rx, task }
+    }
+}
+
+impl<T> Watch<T> {
+    /// Wait for the next item; `None` once the poller has stopped.
+    pub async fn next(&mut self) -> Option<Result<T>> {
+        self.rx.recv().await
+    }
+
+    /// Pass every item to `f` until the poller stops.
+    pub async fn for_each(mut self, mut f: impl FnMut(Result<T>)) {
+        while let Some(item) = self.next().await {
+            f(item);
+        }
+    }
+
+    /// Stop polling. Items already delivered can still be received.
+    pub fn stop(&mut self) {
+        self.task.stop();
+    }
+
+    /// Health of the poller task.
+    pub fn health(&self) -> TaskHealth {
+        self.task.health()
+    }
+}
+
+impl<T> Stream for Watch<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// Sending half given to a poller.
+#[derive(Debug)]
+pub(crate) struct WatchSender<T> {
+    tx: mpsc::Sender<Result<T>>,
+}
+
+impl<T> Clone for WatchSender<T> {
+    fn clone(&self) -> Self {
+        WatchSender {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> WatchSender<T> {
+    /// Deliver an item; returns `false` if the watch was dropped.
+    pub(crate) async fn send(&self, item: Result<T>) -> bool {
+        self.tx.send(item).await.is_ok()
+    }
+}
+
+/// `period` plus a random delay of up to 10%, so pollers started together
+/// do not hit the API at the same moment.
+pub(crate) fn jittered(period: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    // Scramble the clock's low bits into a fraction in [0, 1).
+    let fraction = (nanos.wrapping_mul(2_654_435_761) >> 8) as f64 / (1u32 << 24) as f64;
+    period + period.mul_f64(fraction * MAX_JITTER)
+}
+
+/// Spawn a supervised poller that calls `fetch` right away and then after
+/// each interval of `schedule` (at least 1 ms, plus up to 10% jitter), and
+/// yields what `changes` reports for each successful result.
+///
+/// Results `changes` maps to `None` yield nothing; failed polls yield their
+/// error. When the API reports rate limiting the wait doubles, up to eight
+/// times the interval, until a poll succeeds. `changes` is kept when the
+/// poller is restarted after a panic, so a restart does not yield changes
+/// already reported.
+pub(crate) fn poll_changes<R, U, F, Fut, C>(
+    name: &str,
+    shutdown: &Shutdown,
+    schedule: PollSchedule,
+    fetch: F,
+    changes: C,
+) -> Watch<U>
+where
+    R: Send + 'static,
+    U: Send + 'static,
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<R>> + Send + 'static,
+    C: FnMut(R) -> Option<U> + Send + 'static,
+{
+    let fetch = Arc::new(fetch);
+    let changes = Arc::new(Mutex::new(changes));
+    Watch::spawn(name, shutdown, move |tx| {
+        let fetch = Arc::clone(&fetch);
+        let changes = Arc::clone(&changes);
+        async move {
+            let mut backoff = 1;
+            loop {
+                let result = fetch().await;
+                let rate_limited = matches!(
+                    &result,
+                    Err(err) if err.error_code() == Some(ErrorCode::RateLimit)
+                );
+                let item = match result {
+                    Ok(value) => detect(&changes, value).map(Ok),
+                    Err(err) => Some(Err(err)),
+                };
+                if let Some(item) = item {
+                    if !tx.send(item).await {
+                        return;
+                    }
+                }
+                backoff = if rate_limited {
+                    (backoff * 2).min(MAX_WATCH_BACKOFF)
+                } else {
+                    1
+                };
+                let interval = schedule.interval_now().max(Duration::from_millis(1));
+                sleep(jittered(interval * backoff)).await;
+            }
+        }
+    })
+}
+
+/// Run a change detector on a poll result.
+fn detect<R, U>(changes: &Mutex<impl FnMut(R) -> Option<U>>, value: R) -> Option<U> {
+    let mut changes = changes.lock().unwrap_or_else(|e| e.into_inner());
+    (*changes)(value)
+}
+
+/// Change detector for [`poll_changes`] yielding the items whose key was not
+/// seen in an earlier poll, or whose value differs from the last one seen
+/// under that key. Items `key` maps to `None` are skipped. Only the keys of
+/// the latest poll are remembered, so an item that drops out and comes back
+/// is reported again.
+pub(crate) fn changed_by_key<T, K, V>(
+    key: impl Fn(&T) -> Option<(K, V)> + Send + 'static,
+) -> impl FnMut(Vec<T>) -> Option<Vec<T>> + Send + 'static
+where
+    T: Send + 'static,
+    K: Eq + Hash + Send + 'static,
+    V: PartialEq + Send + 'static,
+{
+    let mut seen: HashMap<K, V> = HashMap::new();
+    move |items| {
+        let previous = std::mem::take(&mut seen);
+        let changed: Vec<T> = items
+            .into_iter()
+            .filter(|item| {
+                let Some((key, value)) = key(item) else {
+                    return false;
+                };
+                let last = seen.get(&key).or_else(|| previous.get(&key));
+                let changed = last != Some(&value);
+                seen.insert(key, value);
+                changed
+            })
+            .collect();
+        (!changed.is_empty()).then_some(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn changed_by_key_reports_new_and_changed_items() {
+        let mut changes = changed_by_key(|item: &(&str, i32)| Some((item.0, item.1)));
+        assert_eq!(
+            changes(vec![("a", 1), ("b", 2)]),
+            Some(vec![("a", 1), ("b", 2)])
+        );
+        assert_eq!(changes(vec![("a", 1), ("b", 2)]), None);
+        assert_eq!(
+            changes(vec![("a", 1), ("b", 3), ("c", 1)]),
+            Some(vec![("b", 3), ("c", 1)])
+        );
+    }
+
+    #[test]
+    fn changed_by_key_forgets_items_missing_from_a_poll() {
+        let mut changes = changed_by_key(|item: &(&str, i32)| Some((item.0, item.1)));
+        assert!(changes(vec![("a", 1), ("b", 2)]).is_some());
+        assert_eq!(changes(vec![("b", 2)]), None);
+        assert_eq!(changes(vec![("a", 1), ("b", 2)]), Some(vec![("a", 1)]));
+        assert_eq!(changes(vec![("b", 2), ("b", 2)]), None);
+    }
+
+    #[tokio::test]
+    async fn poll_changes_skips_unchanged_polls_and_yields_errors() {
+        let polls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&polls);
+        let mut watch = poll_changes(
+            "test",
+            &Shutdown::new(),
+            Duration::from_millis(1).into(),
+            move || {
+                let poll = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match poll {
+                        2 => Err(Error::validation("poll", "failed")),
+                        _ => Ok(vec![poll.min(1)]),
+                    }
+                }
+            },
+            changed_by_key(|n: &u32| Some((*n, ()))),
+        );
+
+        assert_eq!(watch.next().await.unwrap().unwrap(), vec![0]);
+        assert_eq!(watch.next().await.unwrap().unwrap(), vec![1]);
+        assert!(watch.next().await.unwrap().is_err());
+        watch.stop();
+        assert!(polls.load(Ordering::SeqCst) >= 3);
+    }
+
+    #[tokio::test]
+    async fn restarted_poller_does_not_repeat_changes() {
+        let polls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&polls);
+        let mut watch = poll_changes(
+            "test",
+            &Shutdown::new(),
+            Duration::from_millis(1).into(),
+            move || {
+                let poll = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match poll {
+                        0 | 2 => Ok(vec![1, 2]),
+                        1 => panic!("poller failed"),
+                        _ => Ok(vec![1, 2, 3]),
+                    }
+                }
+            },
+            changed_by_key(|n: &u32| Some((*n, ()))),
+        );
+
+        assert_eq!(watch.next().await.unwrap().unwrap(), vec![1, 2]);
+        // The restart polls [1, 2] again, which yields nothing.
+        assert_eq!(watch.next().await.unwrap().unwrap(), vec![3]);
+        assert_eq!(watch.health().restarts, 1);
+    }
+}
@@ -0,0 +1,809 @@
+//! 线上接口验收测试 - 针对真实 DCE API 逐一验证全部 36 个接口
+//!
+//! 与 `examples/complete.rs` 覆盖相同的接口和请求参数，但对每个返回结果做结构化断言
+//! (结果非空、关键字段已填充)，最后输出通过/失败矩阵。
+//!
+//! 运行前请设置环境变量:
+//!   export DCE_API_KEY="your-api-key"
+//!   export DCE_SECRET="your-secret"
+//!
+//! 运行命令:
+//!   cargo test --features live-tests --test live -- --nocapture
+
+use std::time::Duration;
+
+use dceapi_rs::{
+    Client, ColumnId, Config, DeliveryMonth, Lang, StatContent, StatisticsType, TradeType,
+    VarietyType,
+};
+use tokio::time::sleep;
+
+/// 接口调用间隔，避免触发限流。
+const CALL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// 单个接口的验证结果。
+struct CheckResult {
+    service: &'static str,
+    endpoint: &'static str,
+    outcome: std::result::Result<String, String>,
+}
+
+/// 收集验证结果并输出通过/失败矩阵。
+#[derive(Default)]
+struct Matrix {
+    results: Vec<CheckResult>,
+}
+
+impl Matrix {
+    /// 记录一次接口调用的结果，并对成功返回的数据执行断言。
+    ///
+    /// 返回成功的数据，便于后续接口使用 (例如取活跃合约)。
+    async fn check<T>(
+        &mut self,
+        service: &'static str,
+        endpoint: &'static str,
+        result: dceapi_rs::Result<T>,
+        verify: impl FnOnce(&T) -> std::result::Result<String, String>,
+    ) -> Option<T> {
+        let (outcome, value) = match result {
+            Ok(value) => (verify(&value), Some(value)),
+            Err(e) => (Err(format!("request failed: {}", e)), None),
+        };
+        match &outcome {
+            Ok(detail) => println!("✓ {}::{} - {}", service, endpoint, detail),
+            Err(reason) => println!("✗ {}::{} - {}", service, endpoint, reason),
+        }
+        self.results.push(CheckResult {
+            service,
+            endpoint,
+            outcome,
+        });
+        sleep(CALL_INTERVAL).await;
+        value
+    }
+
+    /// 打印矩阵并返回失败数量。
+    fn report(&self) -> usize {
+        println!("\n{}", "=".repeat(80));
+        println!("{:<18} {:<36} result", "service", "endpoint");
+        println!("{}", "-".repeat(80));
+        for r in &self.results {
+            let status = if r.outcome.is_ok() { "PASS" } else { "FAIL" };
+            println!("{:<18} {:<36} {}", r.service, r.endpoint, status);
+        }
+        println!("{}", "-".repeat(80));
+        let failed = self.results.iter().filter(|r| r.outcome.is_err()).count();
+        println!(
+            "total: {} | passed: {} | failed: {}",
+            self.results.len(),
+            self.results.len() - failed,
+            failed
+        );
+        failed
+    }
+}
+
+/// 断言条件成立。
+fn ensure(cond: bool, reason: impl Into<String>) -> std::result::Result<(), String> {
+    if cond {
+        Ok(())
+    } else {
+        Err(reason.into())
+    }
+}
+
+/// 断言列表非空，且每一项的关键字段已填充。
+fn non_empty_with<T>(
+    items: &[T],
+    what: &str,
+    field: impl Fn(&T) -> &str,
+) -> std::result::Result<String, String> {
+    ensure(!items.is_empty(), format!("no {} returned", what))?;
+    fields_populated(items, what, field)
+}
+
+/// 断言每一项的关键字段已填充 (允许列表为空)。
+fn fields_populated<T>(
+    items: &[T],
+    what: &str,
+    field: impl Fn(&T) -> &str,
+) -> std::result::Result<String, String> {
+    let missing = items.iter().filter(|i| field(i).trim().is_empty()).count();
+    ensure(
+        missing == 0,
+        format!("{} of {} {} missing key field", missing, items.len(), what),
+    )?;
+    Ok(format!("{} {}", items.len(), what))
+}
+
+/// 断言行情列表中存在带合约代码和收盘价的合约行 (跳过小计/总计行)。
+fn quotes_populated(quotes: &[dceapi_rs::Quote]) -> std::result::Result<String, String> {
+    ensure(!quotes.is_empty(), "no quotes returned")?;
+    let contracts = quotes
+        .iter()
+        .filter(|q| !q.contract_id.is_empty() || !q.deliv_month.is_empty())
+        .count();
+    ensure(contracts > 0, "no contract rows in quotes")?;
+    Ok(format!("{} rows, {} contracts", quotes.len(), contracts))
+}
+
+#[tokio::test]
+async fn live_all_endpoints() {
+    let config = Config::from_env();
+    assert!(
+        !config.api_key.is_empty() && !config.secret.is_empty(),
+        "DCE_API_KEY and DCE_SECRET must be set to run live tests"
+    );
+    let client = Client::new(config).expect("failed to create client");
+    let mut matrix = Matrix::default();
+
+    // ------------------------------------------------------------------------
+    // CommonService - 通用服务 (3 个 API)
+    // ------------------------------------------------------------------------
+    let trade_date = matrix
+        .check(
+            "common",
+            "get_curr_trade_date",
+            client.common.get_curr_trade_date(None).await,
+            |d| {
+                ensure(
+                    d.date.len() == 8 && d.date.bytes().all(|b| b.is_ascii_digit()),
+                    format!("unexpected trade date {:?}", d.date),
+                )?;
+                Ok(d.date.clone())
+            },
+        )
+        .await
+        .map(|d| d.date)
+        .expect("current trade date is required for the remaining checks");
+    let trade_month = trade_date[0..6].to_string();
+    let month_start = format!("{}01", trade_month);
+    let delivery_month: DeliveryMonth = trade_month.parse().expect("trade date has a valid month");
+    let year_start_month = DeliveryMonth::new(delivery_month.year(), 1).unwrap();
+
+    matrix
+        .check(
+            "common",
+            "get_variety_list",
+            client.common.get_variety_list(None).await,
+            |v| non_empty_with(v, "varieties", |v| &v.code),
+        )
+        .await;
+
+    matrix
+        .check(
+            "common",
+            "get_variety_month_year_stat",
+            client
+                .common
+                .get_variety_month_year_stat(
+                    &dceapi_rs::VarietyMonthYearStatRequest {
+                        trade_month: trade_month.clone(),
+                        trade_type: TradeType::Futures,
+                        lang: Lang::Zh,
+                    },
+                    None,
+                )
+                .await,
+            |s| non_empty_with(s, "variety stats", |s| &s.variety),
+        )
+        .await;
+
+    // ------------------------------------------------------------------------
+    // NewsService - 资讯服务 (1 个 API，6 个栏目)
+    // ------------------------------------------------------------------------
+    for column_id in ColumnId::ALL {
+        matrix
+            .check(
+                "news",
+                "get_article_by_page",
+                client
+                    .news
+                    .get_article_by_page(
+                        dceapi_rs::GetArticleByPageRequest {
+                            column_id: column_id.clone(),
+                            page_no: 1,
+                            page_size: 3,
+                            site_id: 5,
+                        },
+                        None,
+                    )
+                    .await,
+                |page| {
+                    ensure(
+                        page.total_count > 0,
+                        format!("column {} is empty", column_id),
+                    )?;
+                    non_empty_with(&page.result_list, "articles", |a| &a.title)
+                        .map(|d| format!("column {}: {}", column_id, d))
+                },
+            )
+            .await;
+    }
+
+    // ------------------------------------------------------------------------
+    // MarketService - 行情服务 (11 个 API)
+    // ------------------------------------------------------------------------
+    let quotes_request =
+        |variety_id: &str, trade_type: TradeType, statistics_type: Option<StatisticsType>| {
+            dceapi_rs::QuotesRequest {
+                trade_date: trade_date.clone(),
+                variety: None,
+                variety_id: Some(variety_id.to_string()),
+                trade_type,
+                lang: Some(Lang::Zh),
+                statistics_type,
+            }
+        };
+
+    matrix
+        .check(
+            "market",
+            "get_night_quotes",
+            client
+                .market
+                .get_night_quotes(
+                    &dceapi_rs::QuotesRequest {
+                        variety: Some("a".to_string()),
+                        variety_id: None,
+                        ..quotes_request("a", TradeType::Futures, None)
+                    },
+                    None,
+                )
+                .await,
+            |q| quotes_populated(q),
+        )
+        .await;
+
+    let day_quotes = matrix
+        .check(
+            "market",
+            "get_day_quotes (futures)",
+            client
+                .market
+                .get_day_quotes(&quotes_request("a", TradeType::Futures, None), None)
+                .await,
+            |q| quotes_populated(q),
+        )
+        .await
+        .unwrap_or_default();
+
+    matrix
+        .check(
+            "market",
+            "get_day_quotes (options)",
+            client
+                .market
+                .get_day_quotes(
+                    &quotes_request("a", TradeType::Options, Some(StatisticsType::Contract)),
+                    None,
+                )
+                .await,
+            |q| quotes_populated(q),
+        )
+        .await;
+
+    matrix
+        .check(
+            "market",
+            "get_week_quotes",
+            client
+                .market
+                .get_week_quotes(&quotes_request("m", TradeType::Futures, None), None)
+                .await,
+            |q| quotes_populated(q),
+        )
+        .await;
+
+    matrix
+        .check(
+            "market",
+            "get_month_quotes",
+            client
+                .market
+                .get_month_quotes(&quotes_request("c", TradeType::Futures, None), None)
+                .await,
+            |q| quotes_populated(q),
+        )
+        .await;
+
+    let month_max_request = |stat_content: StatContent| dceapi_rs::ContractMonthMaxRequest {
+        start_month: delivery_month,
+        end_month: delivery_month,
+        stat_content,
+        trade_type: TradeType::Futures,
+        lang: Lang::Zh,
+    };
+
+    matrix
+        .check(
+            "market",
+            "get_contract_month_max_volume",
+            client
+                .market
+                .get_contract_month_max_volume(&month_max_request(StatContent::Volume), None)
+                .await,
+            |s| non_empty_with(s, "volume stats", |s| &s.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "market",
+            "get_contract_month_max_turnover",
+            client
+                .market
+                .get_contract_month_max_turnover(&month_max_request(StatContent::Turnover), None)
+                .await,
+            |s| non_empty_with(s, "turnover stats", |s| &s.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "market",
+            "get_contract_month_max_openi",
+            client
+                .market
+                .get_contract_month_max_openi(&month_max_request(StatContent::OpenInterest), None)
+                .await,
+            |s| non_empty_with(s, "open interest stats", |s| &s.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "market",
+            "get_contract_month_max_price",
+            client
+                .market
+                .get_contract_month_max_price(&month_max_request(StatContent::Price), None)
+                .await,
+            |s| non_empty_with(s, "price stats", |s| &s.contract_id),
+        )
+        .await;
+
+    // 停板事件可能为空，只校验返回项字段
+    matrix
+        .check(
+            "market",
+            "get_rise_fall_event",
+            client
+                .market
+                .get_rise_fall_event(
+                    &dceapi_rs::RiseFallEventRequest {
+                        start_date: month_start.clone(),
+                        end_date: trade_date.clone(),
+                        variety_id: "all".to_string(),
+                        lang: Lang::Zh,
+                    },
+                    None,
+                )
+                .await,
+            |e| fields_populated(e, "events", |e| &e.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "market",
+            "get_division_price_info",
+            client
+                .market
+                .get_division_price_info(
+                    &dceapi_rs::DivisionPriceInfoRequest {
+                        variety_id: "m".to_string(),
+                        trade_date: trade_date.clone(),
+                        trade_type: TradeType::Futures,
+                    },
+                    None,
+                )
+                .await,
+            |p| fields_populated(p, "prices", |p| &p.contract_id),
+        )
+        .await;
+
+    // ------------------------------------------------------------------------
+    // MemberService - 会员成交持仓统计服务 (2 个 API)
+    // ------------------------------------------------------------------------
+    // 使用豆一持仓量最大的合约，避免硬编码已到期合约
+    let active_contract = day_quotes
+        .iter()
+        .filter(|q| !q.contract_id.is_empty())
+        .max_by_key(|q| q.open_interest)
+        .map(|q| q.contract_id.clone())
+        .unwrap_or_else(|| "a2505".to_string());
+
+    matrix
+        .check(
+            "member",
+            "get_daily_ranking",
+            client
+                .member
+                .get_daily_ranking(
+                    &dceapi_rs::DailyRankingRequest {
+                        variety_id: "a".to_string(),
+                        contract_id: active_contract.clone(),
+                        trade_date: trade_date.clone(),
+                        trade_type: TradeType::Futures,
+                    },
+                    None,
+                )
+                .await,
+            |r| {
+                ensure(!r.qty_future_list.is_empty(), "empty volume ranking")?;
+                ensure(!r.buy_future_list.is_empty(), "empty buy ranking")?;
+                ensure(!r.sell_future_list.is_empty(), "empty sell ranking")?;
+                fields_populated(&r.qty_future_list, "members", |m| &m.qty_abbr)
+                    .map(|d| format!("{}: {}", active_contract, d))
+            },
+        )
+        .await;
+
+    matrix
+        .check(
+            "member",
+            "get_phase_ranking",
+            client
+                .member
+                .get_phase_ranking(
+                    &dceapi_rs::PhaseRankingRequest {
+                        variety: "a".to_string(),
+                        start_month: trade_month.clone(),
+                        end_month: trade_month.clone(),
+                        trade_type: TradeType::Futures,
+                    },
+                    None,
+                )
+                .await,
+            |r| non_empty_with(r, "members", |r| &r.member_name),
+        )
+        .await;
+
+    // ------------------------------------------------------------------------
+    // TradeService - 交易参数服务 (8 个 API)
+    // ------------------------------------------------------------------------
+    matrix
+        .check(
+            "trade",
+            "get_day_trade_param",
+            client
+                .trade
+                .get_day_trade_param(
+                    &dceapi_rs::DayTradeParamRequest {
+                        variety_id: "m".to_string(),
+                        trade_type: TradeType::Futures,
+                        lang: Lang::Zh,
+                    },
+                    None,
+                )
+                .await,
+            |p| {
+                ensure(
+                    p.iter().all(|p| p.rise_limit.is_some_and(|v| v > 0.0)),
+                    "rise limit not populated",
+                )?;
+                non_empty_with(p, "params", |p| &p.contract_id)
+            },
+        )
+        .await;
+
+    matrix
+        .check(
+            "trade",
+            "get_month_trade_param",
+            client.trade.get_month_trade_param(None).await,
+            |p| {
+                ensure(!p.is_empty(), "no month trade params returned")?;
+                Ok(format!("{} keys", p.len()))
+            },
+        )
+        .await;
+
+    matrix
+        .check(
+            "trade",
+            "get_contract_info",
+            client
+                .trade
+                .get_contract_info(
+                    &dceapi_rs::ContractInfoRequest {
+                        variety_id: "m".to_string(),
+                        trade_type: TradeType::Futures,
+                        lang: Lang::Zh,
+                    },
+                    None,
+                )
+                .await,
+            |c| {
+                ensure(
+                    c.iter().all(|c| !c.end_trade_date.is_empty()),
+                    "last trade date not populated",
+                )?;
+                non_empty_with(c, "contracts", |c| &c.contract_id)
+            },
+        )
+        .await;
+
+    matrix
+        .check(
+            "trade",
+            "get_arbitrage_contract",
+            client.trade.get_arbitrage_contract(Some(Lang::Zh), None).await,
+            |c| non_empty_with(c, "arbitrage contracts", |c| &c.arbi_contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "trade",
+            "get_trading_param",
+            client.trade.get_trading_param(Some(Lang::Zh), None).await,
+            |p| non_empty_with(p, "varieties", |p| &p.variety_name),
+        )
+        .await;
+
+    matrix
+        .check(
+            "trade",
+            "get_margin_arbi_perf_para",
+            client
+                .trade
+                .get_margin_arbi_perf_para(
+                    &dceapi_rs::MarginArbiPerfParaRequest {
+                        variety_id: "m".to_string(),
+                    },
+                    None,
+                )
+                .await,
+            |p| non_empty_with(p, "strategies", |p| &p.strategy_name),
+        )
+        .await;
+
+    // 新上市合约可能为空，只校验返回项字段
+    matrix
+        .check(
+            "trade",
+            "get_new_contract_info",
+            client
+                .trade
+                .get_new_contract_info(
+                    &dceapi_rs::NewContractInfoRequest {
+                        trade_date: trade_date.clone(),
+                        trade_type: TradeType::Futures,
+                        lang: Some(Lang::Zh),
+                    },
+                    None,
+                )
+                .await,
+            |c| fields_populated(c, "new contracts", |c| &c.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "trade",
+            "get_main_series_info",
+            client
+                .trade
+                .get_main_series_info(
+                    &dceapi_rs::MainSeriesInfoRequest {
+                        variety_id: "m".to_string(),
+                        trade_date: trade_date.clone(),
+                    },
+                    None,
+                )
+                .await,
+            |s| non_empty_with(s, "series", |s| &s.contract_id),
+        )
+        .await;
+
+    // ------------------------------------------------------------------------
+    // SettleService - 结算参数服务 (1 个 API)
+    // ------------------------------------------------------------------------
+    matrix
+        .check(
+            "settle",
+            "get_settle_param",
+            client
+                .settle
+                .get_settle_param(
+                    &dceapi_rs::SettleParamRequest {
+                        variety_id: "m".to_string(),
+                        trade_date: trade_date.clone(),
+                        trade_type: TradeType::Futures,
+                        lang: Lang::Zh,
+                    },
+                    None,
+                )
+                .await,
+            |p| {
+                ensure(
+                    p.iter().all(|p| !p.clear_price.is_empty()),
+                    "settlement price not populated",
+                )?;
+                non_empty_with(p, "params", |p| &p.contract_id)
+            },
+        )
+        .await;
+
+    // ------------------------------------------------------------------------
+    // DeliveryService - 交割统计服务 (10 个 API)
+    // 交割类数据按月/按日发布，查询区间内可能为空，只校验返回项字段
+    // ------------------------------------------------------------------------
+    matrix
+        .check(
+            "delivery",
+            "get_delivery_data",
+            client
+                .delivery
+                .get_delivery_data(
+                    &dceapi_rs::DeliveryDataRequest {
+                        variety_id: "a".to_string(),
+                        start_month: year_start_month,
+                        end_month: delivery_month,
+                        variety_type: VarietyType::Physical,
+                    },
+                    None,
+                )
+                .await,
+            |d| fields_populated(d, "deliveries", |d| &d.variety),
+        )
+        .await;
+
+    // 当年 1 月合约已在查询区间内完成交割
+    let delivered_contract = format!("a{}01", &trade_date[2..4]);
+    matrix
+        .check(
+            "delivery",
+            "get_delivery_match",
+            client
+                .delivery
+                .get_delivery_match(
+                    &dceapi_rs::DeliveryMatchRequest {
+                        variety_id: "a".to_string(),
+                        contract_id: delivered_contract.clone(),
+                        start_month: year_start_month,
+                        end_month: delivery_month,
+                    },
+                    None,
+                )
+                .await,
+            |m| fields_populated(m, "matches", |m| &m.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_delivery_cost",
+            client
+                .delivery
+                .get_delivery_cost("a", VarietyType::Physical, None)
+                .await,
+            |c| non_empty_with(c, "costs", |c| &c.variety),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_warehouse_premium",
+            client
+                .delivery
+                .get_warehouse_premium("c", &trade_date, None)
+                .await,
+            |r| non_empty_with(&r.entity_list, "warehouses", |p| &p.wh_name),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_tc_congregate_delivery",
+            client
+                .delivery
+                .get_tc_congregate_delivery(
+                    &dceapi_rs::TcCongregateDeliveryRequest {
+                        variety: "a".to_string(),
+                        contract_month: trade_month.clone(),
+                    },
+                    None,
+                )
+                .await,
+            |d| fields_populated(d, "forecasts", |d| &d.variety_name),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_roll_delivery_seller_intention",
+            client
+                .delivery
+                .get_roll_delivery_seller_intention(
+                    &dceapi_rs::RollDeliverySellerIntentionRequest {
+                        variety: "a".to_string(),
+                        date: trade_date.clone(),
+                    },
+                    None,
+                )
+                .await,
+            |i| fields_populated(i, "intentions", |i| &i.variety_name),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_bonded_delivery",
+            client
+                .delivery
+                .get_bonded_delivery(
+                    &dceapi_rs::BondedDeliveryRequest {
+                        start_date: month_start.clone(),
+                        end_date: trade_date.clone(),
+                    },
+                    None,
+                )
+                .await,
+            |d| fields_populated(d, "prices", |d| &d.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_td_bonded_delivery",
+            client
+                .delivery
+                .get_td_bonded_delivery(
+                    &dceapi_rs::TdBondedDeliveryRequest {
+                        start_date: month_start.clone(),
+                        end_date: trade_date.clone(),
+                    },
+                    None,
+                )
+                .await,
+            |d| fields_populated(d, "prices", |d| &d.contract_id),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_factory_spot_agio",
+            client
+                .delivery
+                .get_factory_spot_agio(
+                    &dceapi_rs::FactorySpotAgioRequest {
+                        variety_id: "fb".to_string(),
+                        trade_date: trade_date.clone(),
+                    },
+                    None,
+                )
+                .await,
+            |a| fields_populated(a, "factories", |a| &a.wh_abbr),
+        )
+        .await;
+
+    matrix
+        .check(
+            "delivery",
+            "get_plywood_delivery_commodity",
+            client
+                .delivery
+                .get_plywood_delivery_commodity(
+                    &dceapi_rs::PlywoodDeliveryCommodityRequest {
+                        variety_id: "bb".to_string(),
+                    },
+                    None,
+                )
+                .await,
+            |c| fields_populated(c, "brands", |c| &c.wh_name),
+        )
+        .await;
+
+    let failed = matrix.report();
+    assert_eq!(failed, 0, "{} live endpoint check(s) failed", failed);
+}